@@ -1,10 +1,45 @@
 use crate::app::message::Message;
 use crate::app::state::{Overlay, boot};
 use crate::app::update::update;
+use crate::modules::backup::application as backup_application;
+use crate::modules::settings::application as settings_application;
+use crate::modules::shortcuts::application as shortcuts_application;
+use crate::modules::shortcuts::infrastructure::SharedReceiver as ShortcutReceiver;
 use crate::ui;
 use crate::ui::theme;
+use iced::futures::channel::mpsc;
+use iced::futures::{Stream, StreamExt, stream};
+use std::hash::{Hash, Hasher};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// How often settings.json is checked for edits made outside the app.
+const SETTINGS_WATCH_INTERVAL: Duration = Duration::from_secs(2);
 
 pub fn run() -> iced::Result {
+    let args = std::env::args().skip(1).collect::<Vec<_>>();
+    if let Some(result) = shortcuts_application::forward_cli_trigger(&args) {
+        if let Err(error) = result {
+            eprintln!("[openvoice][shortcuts] {error}");
+            std::process::exit(1);
+        }
+
+        return Ok(());
+    }
+    if let Some(result) = settings_application::run_cli_command(&args)
+        .or_else(|| backup_application::run_cli_command(&args))
+    {
+        match result {
+            Ok(note) => println!("{note}"),
+            Err(error) => {
+                eprintln!("[openvoice][settings] {error}");
+                std::process::exit(1);
+            }
+        }
+
+        return Ok(());
+    }
+
     iced::daemon(boot, update, ui::view)
         .title(Overlay::title)
         .theme(|state: &Overlay, _window| theme::app_theme(state))
@@ -13,15 +48,77 @@ pub fn run() -> iced::Result {
         .run()
 }
 
-fn subscription(_state: &Overlay) -> iced::Subscription<Message> {
+fn subscription(state: &Overlay) -> iced::Subscription<Message> {
+    let shortcut_triggers = state
+        .shortcut_receiver
+        .clone()
+        .map_or_else(iced::Subscription::none, |receiver| {
+            iced::Subscription::run_with(TriggerListener(receiver), trigger_stream)
+        });
+
     iced::Subscription::batch([
+        shortcut_triggers,
+        every(SETTINGS_WATCH_INTERVAL)
+            .map(|_| Message::SettingsFileChecked(settings_application::settings_modified_at())),
         iced::window::close_requests().map(Message::WindowCloseRequested),
         iced::keyboard::listen().map(Message::KeyEvent),
-        iced::event::listen_with(|event, _status, _id| match event {
+        iced::event::listen_with(|event, _status, id| match event {
             iced::Event::Window(iced::window::Event::Moved(point)) => {
                 Some(Message::WindowMoved(point))
             }
+            iced::Event::Mouse(iced::mouse::Event::ButtonPressed(_))
+            | iced::Event::Keyboard(iced::keyboard::Event::KeyPressed { .. }) => {
+                Some(Message::WindowInteracted(id))
+            }
             _ => None,
         }),
     ])
 }
+
+/// `iced::time::every` for the thread-pool executor, which has no timers:
+/// a thread of its own ticks for as long as the subscription runs.
+fn every(interval: Duration) -> iced::Subscription<Instant> {
+    iced::Subscription::run_with(interval, |interval| {
+        let interval = *interval;
+        let (sender, ticks) = mpsc::unbounded();
+        std::thread::spawn(move || {
+            loop {
+                std::thread::sleep(interval);
+                if sender.unbounded_send(Instant::now()).is_err() {
+                    return;
+                }
+            }
+        });
+        ticks
+    })
+}
+
+/// The shortcut receiver, identified by its allocation so the subscription
+/// keeps running for as long as the same listener is open.
+struct TriggerListener(ShortcutReceiver);
+
+impl Hash for TriggerListener {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        Arc::as_ptr(&self.0).hash(state);
+    }
+}
+
+/// Every shortcut trigger, then `None` once the listener closes. The
+/// blocking `recv` runs on a thread of its own instead of holding one of
+/// the executor's workers for the whole session.
+fn trigger_stream(listener: &TriggerListener) -> impl Stream<Item = Message> + use<> {
+    let receiver = listener.0.clone();
+    let (sender, triggers) = mpsc::unbounded();
+    std::thread::spawn(move || {
+        while let Some(trigger) = shortcuts_application::poll_next_trigger(receiver.clone()) {
+            if sender.unbounded_send(trigger).is_err() {
+                return;
+            }
+        }
+    });
+
+    triggers
+        .map(Some)
+        .chain(stream::once(async { None }))
+        .map(Message::ShortcutTriggerReceived)
+}
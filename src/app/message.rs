@@ -1,16 +1,27 @@
 use iced::widget::markdown;
 use iced::widget::text_editor;
 use iced::{Point, Size, keyboard, window};
+use std::path::PathBuf;
+use std::time::SystemTime;
 
+use crate::modules::audio::infrastructure::spool::SpooledAudio;
 use crate::modules::auth::domain::{OpenAiAuthSnapshot, PendingOpenAiOAuthFlow};
 use crate::modules::copilot::application::{
     ActiveCopilotStream, LoadedCopilotThread, RuntimeEvent as CopilotRuntimeEvent,
 };
 use crate::modules::copilot::domain::{CopilotMode, CopilotThreadSummary, ScreenshotAttachment};
-use crate::modules::dictation::domain::DictationOutput;
+use crate::modules::dictation::domain::{
+    DictationAutoStop, DictationOutput, HistoryEntry, LastOutput, ModelComparison, UsageStats,
+};
 use crate::modules::live_transcription::domain::RuntimeEvent;
 use crate::modules::live_transcription::infrastructure::db::SessionSummary;
-use crate::modules::settings::domain::AppSettings;
+use crate::modules::settings::domain::{
+    AppSettings, ConfigDiff, DictationModelChoice, OnboardingStep, ResetScope,
+};
+use crate::modules::shortcuts::application::ShortcutRegistration;
+use crate::modules::shortcuts::domain::{ShortcutAction, ShortcutError, ShortcutTrigger};
+use crate::support::error::OpenVoiceError;
+use crate::support::log::LogLevel;
 
 use crate::app::state::HomeTab;
 
@@ -24,6 +35,7 @@ pub enum Message {
     KeyEvent(keyboard::Event),
     StartDrag,
     WindowMoved(Point),
+    WindowInteracted(window::Id),
     // Navigation
     OpenHomeView,
     CloseHomeView,
@@ -32,8 +44,19 @@ pub enum Message {
     SwitchHomeTab(HomeTab),
     // Settings form
     SettingsApiKeyChanged(String),
+    SettingsFallbackApiKeysChanged(String),
     SettingsOpenAiRealtimeApiKeyChanged(String),
     SettingsModelChanged(String),
+    SettingsFallbackModelsChanged(String),
+    SettingsCompareModelChanged(String),
+    SettingsTemperatureChanged(String),
+    SettingsTopPChanged(String),
+    SettingsMaxTokensChanged(String),
+    SettingsRefererChanged(String),
+    SettingsAppTitleChanged(String),
+    SettingsBaseUrlChanged(String),
+    SettingsPrivacyModeChanged(bool),
+    SettingsApiKeysInKeyringChanged(bool),
     SettingsOpenAiRealtimeModelChanged(String),
     SettingsOpenAiRealtimeLanguageChanged(String),
     SettingsOpenAiRealtimeProfileChanged(String),
@@ -41,8 +64,119 @@ pub enum Message {
     SettingsCopilotDefaultModeChanged(String),
     SettingsCopilotAutoIncludeTranscriptChanged(bool),
     SettingsCopilotSaveHistoryChanged(bool),
+    SettingsDictationVadEnabledChanged(bool),
+    SettingsDictationSilenceThresholdChanged(String),
+    SettingsDictationShortcutChanged(String),
+    SettingsDictationRestartShortcutChanged(String),
+    SettingsDictationCopyLastShortcutChanged(String),
+    SettingsDictationUndoShortcutChanged(String),
+    SettingsDictationCancelShortcutChanged(String),
+    SettingsDictationStopKeyChanged(String),
+    SettingsDictationRetranscribeShortcutChanged(String),
+    SettingsDictationHistoryShortcutChanged(String),
+    SettingsDictationHoldThresholdChanged(String),
+    SettingsDictationShortcutModeChanged(String),
+    SettingsDictationResampleQualityChanged(String),
+    SettingsDictationTargetSampleRateChanged(String),
+    SettingsDictationNormalizeAudioChanged(bool),
+    SettingsDictationAudioFormatChanged(String),
+    SettingsDictationPreRollEnabledChanged(bool),
+    SettingsDictationMaxDurationChanged(String),
+    SettingsDictationRecordingsDirChanged(String),
+    SettingsDictationInputChannelChanged(String),
+    SettingsDictationCaptureSampleRateChanged(String),
+    SettingsDictationCaptureBufferFramesChanged(String),
+    SettingsDictationSoundFeedbackChanged(bool),
+    SettingsDictationCaptureModeChanged(String),
+    SettingsDictationIdleTimeoutChanged(String),
+    SettingsDictationIdleActionChanged(String),
+    SettingsDictationOutputModeChanged(String),
+    SettingsDictationTypingDelayChanged(String),
+    SettingsDictationWritePrimaryChanged(bool),
+    SettingsDictationNotificationsChanged(bool),
+    SettingsDictationAutoSubmitConfirmedChanged(bool),
+    SettingsTemplateNameChanged(usize, String),
+    SettingsTemplateBodyChanged(usize, String),
+    SettingsTemplateShortcutChanged(usize, String),
+    /// Starts a take delivered through the template at this index.
+    StartTemplateDictation(usize),
+    AddSettingsTemplate,
+    RemoveSettingsTemplate(usize),
+    SettingsModelOverrideModelChanged(usize, String),
+    SettingsModelOverridePromptChanged(usize, String),
+    SettingsModelOverrideTemperatureChanged(usize, String),
+    SettingsModelOverrideLanguageChanged(usize, String),
+    AddSettingsModelOverride,
+    RemoveSettingsModelOverride(usize),
+    /// Switches the active template from the home window; empty = none.
+    SelectDictationTemplate(String),
+    RefreshInputDevices,
+    InputDevicesLoaded(Result<Vec<String>, String>),
+    /// Switches the microphone; empty follows the system default.
+    SelectInputDevice(String),
+    SelectDictationModel(DictationModelChoice),
+    CompleteOnboardingStep(OnboardingStep),
+    OnboardingStepCompleted(Result<Box<AppSettings>, String>),
+    ProfileNameChanged(String),
+    /// Stores the current setup under the typed profile name.
+    SaveProfile,
+    ActivateProfile(String),
+    DeleteActiveProfile,
+    ProfilesPersisted(Result<Box<AppSettings>, String>),
+    DictationModelPersisted(Result<(), String>),
+    InputDevicePersisted(Result<(), String>),
+    DictationTemplatePersisted(Result<(), String>),
+    SettingsDictationHistoryAudioChanged(bool),
+    SettingsDictationHistoryAudioMaxMbChanged(String),
+    SettingsDictationHistoryAudioMaxDaysChanged(String),
+    SettingsDictationRetranscribeModelChanged(String),
+    SettingsDictationUriTemplateChanged(String),
+    SettingsDictationClipboardRestoreChanged(String),
+    SettingsDictationHookCommandChanged(String),
+    SettingsDictationHookTimeoutChanged(String),
+    SettingsDictationWebhookEnabledChanged(bool),
+    SettingsDictationWebhookUrlChanged(String),
+    SettingsDictationProviderChanged(String),
+    SettingsDictationMonthlyBudgetChanged(String),
+    SettingsDictationLanguageChanged(String),
+    SettingsDeepgramApiKeyChanged(String),
+    SettingsDeepgramModelChanged(String),
+    SettingsDictationStreamingChanged(bool),
+    SettingsDictationTimestampsChanged(bool),
+    SettingsDictationVocabularyChanged(String),
+    SettingsAssemblyAiApiKeyChanged(String),
+    SettingsDictationShowWindowChanged(bool),
+    SettingsHudClickActionChanged(String),
+    SettingsHudKeepOpenChanged(bool),
+    SettingsHudHideDelayChanged(String),
+    SettingsLaunchAtLoginChanged(bool),
     SaveSettings,
-    SettingsSaved(Result<AppSettings, String>),
+    /// Broadcast after any settings change, with the fields it touched.
+    ConfigChanged(ConfigDiff),
+    SettingsSaved(Result<Box<AppSettings>, String>),
+    AutostartApplied(Result<(), String>),
+    /// Periodic look at when settings.json was last written.
+    SettingsFileChecked(Option<SystemTime>),
+    /// settings.json changed on disk and was read again.
+    ConfigReloaded(Result<Box<AppSettings>, String>),
+    ConfigTransferPathChanged(String),
+    ConfigExportSecretsChanged(bool),
+    BackupPathChanged(String),
+    BackupIncludeSecretsChanged(bool),
+    BackupIncludeAudioChanged(bool),
+    CreateBackup,
+    BackupCreated(Result<PathBuf, String>),
+    RestoreBackup,
+    BackupRestored(Result<Box<AppSettings>, String>),
+    ExportConfig,
+    ConfigExported(Result<PathBuf, String>),
+    ImportConfig,
+    ResetConfig(ResetScope),
+    SelectLogLevel(LogLevel),
+    LogLevelPersisted(Result<(), String>),
+    LoadRecentLogs,
+    RecentLogsLoaded(Result<Vec<String>, String>),
+    CopyRecentLogs,
     // OpenAI OAuth
     StartOpenAiOAuthLogin,
     OpenAiOAuthStarted(Result<PendingOpenAiOAuthFlow, String>),
@@ -52,10 +186,79 @@ pub enum Message {
     OpenAiOAuthFinished(Result<OpenAiAuthSnapshot, String>),
     LogoutOpenAi,
     OpenAiLogoutFinished(Result<(), String>),
-    // Dictation (mic → OpenRouter)
+    // Global shortcuts
+    /// Starts (or stops) recording the next key combination into the
+    /// shortcut field of this action.
+    CaptureShortcut(ShortcutAction),
+    CancelShortcutSwapped(Result<(), String>),
+    ShortcutChordSwapped(Result<(), String>),
+    /// The window for the second key of a sequence may have run out.
+    ShortcutChordExpired,
+    /// Unbinds every global shortcut, or binds them again.
+    ToggleShortcutsPaused,
+    ShortcutsPausedPersisted(Result<(), String>),
+    ShortcutsRegistered(Result<Option<ShortcutRegistration>, ShortcutError>),
+    ShortcutTriggerReceived(Option<ShortcutTrigger>),
+    // Dictation (mic → OpenRouter / Deepgram / AssemblyAI)
     StartDictation,
     StopDictation,
-    DictationFinished(Result<DictationOutput, String>),
+    RestartDictation,
+    DictationTick,
+    DictationProgressTick,
+    CancelTranscription,
+    CancelRecording,
+    RetranscribeLast,
+    /// Result of a job the user cancelled; dropped without touching the UI.
+    DictationDiscarded,
+    DictationUsageLoaded(Result<UsageStats, String>),
+    RefreshOpenRouterModels,
+    /// Run the last take through the main and the compare model.
+    CompareModels,
+    ModelsCompared(Result<ModelComparison, OpenVoiceError>),
+    OpenRouterModelsLoaded(Result<Vec<String>, String>),
+    DictationUsageRecorded(Result<UsageStats, String>),
+    /// Month-to-date dictation cost (USD) went over the configured budget.
+    DictationBudgetExceeded(f64),
+    /// Interim text from a streaming provider while the take is recording.
+    DictationPartial(String),
+    DictationAutoStopped(DictationAutoStop),
+    DictationCaptureCombined(Result<SpooledAudio, String>),
+    DictationFinished(Result<DictationOutput, OpenVoiceError>),
+    HideFinishedHud(u64),
+    /// The typing output mode finished injecting the transcript.
+    DictationDelivered(Result<(), String>),
+    /// Puts `previous` back unless the clipboard moved on from `copied`.
+    RestoreClipboard {
+        previous: String,
+        copied: String,
+    },
+    DictationWebhookSent(Result<(), String>),
+    DictationNotified(Result<(), String>),
+    DictationHookFinished(Result<(), String>),
+    DictationRecordingSaved(Result<PathBuf, String>),
+    CopyLastRecordingPath,
+    CopyLastDictation,
+    ClipboardWriteChecked {
+        text: String,
+        attempt: u32,
+        written: bool,
+    },
+    FlushPendingOutput,
+    DiscardPendingOutput,
+    OutputRecorded(LastOutput),
+    UndoLastOutput,
+    LastOutputUndone(Result<(), String>),
+    StartDictationSession,
+    EndDictationSession,
+    DiscardDictationSession,
+    LastDictationLoaded(Result<Option<String>, String>),
+    ExportLastDictationSrt,
+    LastDictationSrtExported(Result<PathBuf, String>),
+    PlayLastRecording,
+    LastRecordingPlayed(Result<(), String>),
+    FeedbackTonePlayed(Result<(), String>),
+    ToggleDictationMonitor,
+    HudStatusClicked,
     // Realtime transcription (system audio → OpenAI Realtime API)
     StartRealtimeTranscription,
     StopRealtimeTranscription,
@@ -81,6 +284,19 @@ pub enum Message {
     CopySessionTranscript,
     DeleteSession(i64),
     SessionDeleted(Result<i64, String>),
+    // Dictation history (loaded inside Home tab)
+    HistoryLoaded(Result<Vec<HistoryEntry>, String>),
+    HistorySearchChanged(String),
+    HistoryEntrySaved(Result<HistoryEntry, String>),
+    CopyHistoryEntry(i64),
+    DeleteHistoryEntry(i64),
+    PlayHistoryEntry(i64),
+    HistoryEntryPlayed(Result<(), String>),
+    RetranscribeHistoryEntry(i64),
+    HistoryEntryRetranscribed(Result<HistoryEntry, OpenVoiceError>),
+    HistoryEntryDeleted(Result<i64, String>),
+    ClearHistory,
+    HistoryCleared(Result<(), String>),
     // Copilot
     CopilotInputEdited(text_editor::Action),
     CopilotModeChanged(CopilotMode),
@@ -1,6 +1,10 @@
 use crate::app::message::Message;
+use crate::modules::audio::domain::AudioLevel;
 use crate::modules::audio::infrastructure::{
-    microphone::Recorder as MicrophoneRecorder, system::Recorder as SystemRecorder,
+    microphone::{self, Recorder as MicrophoneRecorder},
+    monitor::Monitor as MicrophoneMonitor,
+    spool::SpooledAudio,
+    system::Recorder as SystemRecorder,
 };
 use crate::modules::auth::application as auth_application;
 use crate::modules::auth::domain::PendingOpenAiOAuthFlow;
@@ -8,16 +12,30 @@ use crate::modules::copilot::application::ActiveCopilotStream;
 use crate::modules::copilot::domain::{
     CopilotChatMessage, CopilotMode, CopilotThreadSummary, ScreenshotAttachment,
 };
+use crate::modules::dictation::application as dictation_application;
+use crate::modules::dictation::domain::{
+    CancelToken, DictationAutoStop, DictationSession, HistoryEntry, LastOutput, ModelComparison,
+    ProgressTracker, TranscriptTemplate, TranscriptionResult, TranscriptionStage,
+    TranscriptionStats, UsageStats, elapsed_label,
+};
+use crate::modules::dictation::infrastructure::deepgram::StreamingSession;
 use crate::modules::live_transcription::application::ActiveLiveTranscription;
 use crate::modules::live_transcription::infrastructure::db::SessionSummary;
 use crate::modules::settings::application as settings_application;
 use crate::modules::settings::domain::{AppSettings, SettingsForm};
+use crate::modules::shortcuts::application::{self as shortcuts_application, ShortcutRegistration};
+use crate::modules::shortcuts::domain::{
+    DoubleTapDetector, Shortcut, ShortcutAction, ShortcutError,
+};
+use crate::modules::shortcuts::infrastructure::SharedReceiver as ShortcutReceiver;
 use crate::platform::monitors;
 use crate::platform::monitors::MonitorGeometry;
 use crate::platform::window as platform_window;
 use iced::widget::text_editor;
 use iced::{Point, Task, window};
-use std::collections::HashSet;
+use std::collections::{HashSet, VecDeque};
+use std::path::PathBuf;
+use std::time::{Instant, SystemTime};
 
 pub struct Overlay {
     // Window IDs
@@ -33,16 +51,62 @@ pub struct Overlay {
     pub home_tab: HomeTab,
     pub primary_monitor: Option<MonitorGeometry>,
     pub hud_position: Option<Point>,
+    /// The user clicked or typed into the HUD since the take started, so it
+    /// is not hidden under them.
+    pub hud_interacted: bool,
+    /// Bumped per finished take; a pending auto-hide only fires for its own.
+    pub hud_hide_generation: u64,
     pub phase: OverlayPhase,
     pub hint: String,
     pub error: Option<String>,
     pub preview: Option<String>,
+    /// Quit was requested once while a take was running; the next request
+    /// saves the audio and exits.
+    pub quit_confirm: bool,
+    /// Name typed in the home window for the profile about to be saved.
+    pub profile_name: String,
+    /// Tail of the log file, loaded on demand from the settings window.
+    pub recent_logs: Vec<String>,
+    /// File the settings are exported to or imported from; empty uses the
+    /// default one in the home folder.
+    pub config_transfer_path: String,
+    pub config_export_secrets: bool,
+    pub backup_path: String,
+    pub backup_include_secrets: bool,
+    pub backup_include_audio: bool,
 
     // Settings
+    /// Settings the app runs with: `stored_settings` plus the environment
+    /// overrides. Never saved.
     pub settings: AppSettings,
+    /// Settings as in settings.json; every save and export starts from these.
+    pub stored_settings: AppSettings,
     pub settings_form: SettingsForm,
     pub is_saving_settings: bool,
     pub settings_note: Option<String>,
+    /// Last write to settings.json seen; a newer one is reloaded.
+    pub settings_modified_at: Option<SystemTime>,
+
+    // Global shortcuts
+    pub shortcut_receiver: Option<ShortcutReceiver>,
+    pub shortcut_registration: Option<ShortcutRegistration>,
+    /// Shortcut field waiting for the next key combination pressed in the
+    /// window.
+    pub shortcut_capture: Option<ShortcutAction>,
+    /// Cancel shortcut bound for the running take; `None` between takes.
+    pub bound_cancel_shortcut: Option<Shortcut>,
+    /// A swap of the cancel shortcut is running; the next one waits for it
+    /// so an unbind can never overtake the bind before it.
+    pub cancel_shortcut_swapping: bool,
+    /// Second keys of the sequences whose leader just fired, and when it
+    /// did; they are unbound again after [`CHORD_WINDOW`].
+    ///
+    /// [`CHORD_WINDOW`]: crate::modules::shortcuts::domain::CHORD_WINDOW
+    pub shortcut_chord: Option<(Instant, Vec<Shortcut>)>,
+    pub shortcut_error: Option<String>,
+    /// Another bind owns one of the configured combinations; settings asks
+    /// for a different one.
+    pub shortcut_conflict: Option<ShortcutError>,
 
     // Auth (OpenAI OAuth)
     pub is_openai_authenticating: bool,
@@ -53,6 +117,62 @@ pub struct Overlay {
 
     // Dictation (mic recording)
     pub recorder: Option<MicrophoneRecorder>,
+    /// System-audio capture running next to `recorder` when the capture
+    /// mode includes the desktop monitor.
+    pub dictation_system_recorder: Option<SystemRecorder>,
+    /// Live Deepgram session fed by `recorder` while the take runs.
+    pub dictation_stream: Option<StreamingSession>,
+    /// Set while a take is being combined or transcribed, so it can be
+    /// abandoned from the HUD, Escape or the `cancel` trigger.
+    pub dictation_cancel: Option<CancelToken>,
+    /// Written by the upload job, polled by `DictationProgressTick`.
+    pub dictation_progress: Option<ProgressTracker>,
+    /// Last stage read from `dictation_progress`, shown in the HUD and home.
+    pub dictation_stage: Option<TranscriptionStage>,
+    /// Advanced by the progress tick to animate the transcribing state.
+    pub processing_frame: usize,
+    /// Loaded when the settings tab opens and after each billed request.
+    pub dictation_usage: Option<UsageStats>,
+    /// Audio-capable OpenRouter models offered in the settings dropdown.
+    pub openrouter_models: Vec<String>,
+    pub is_loading_openrouter_models: bool,
+    pub model_comparison: Option<ModelComparison>,
+    pub is_comparing_models: bool,
+    pub dictation_pre_roll: Option<MicrophoneRecorder>,
+    /// Whether the user wants to hear the mic while recording.
+    pub dictation_monitor_enabled: bool,
+    pub dictation_monitor: Option<MicrophoneMonitor>,
+    pub dictation_auto_stop: Option<DictationAutoStop>,
+    /// Model a re-transcription asked for, so it is not reported as a
+    /// fallback.
+    pub dictation_model_override: Option<String>,
+    /// Open dictation session; finished takes are appended here instead of
+    /// being delivered.
+    pub dictation_session: Option<DictationSession>,
+    /// When the dictation shortcut started the current take, so its release
+    /// can tell a tap from a hold.
+    pub dictation_shortcut_pressed_at: Option<Instant>,
+    /// Pairs modifier taps for double-tap shortcuts.
+    pub shortcut_double_tap: DoubleTapDetector,
+    /// Template picked by a template shortcut for the current take, in
+    /// place of the one selected on the home tab.
+    pub dictation_template_override: Option<usize>,
+    /// Microphones offered by the home picker, refreshed when it opens.
+    pub input_devices: Vec<String>,
+    pub dictation_level: AudioLevel,
+    pub dictation_waveform: VecDeque<f32>,
+    pub dictation_clipping: bool,
+    pub dictation_started_at: Option<Instant>,
+    pub last_recording_path: Option<PathBuf>,
+    /// Text and timed segments of the last finished take.
+    pub last_dictation: Option<TranscriptionResult>,
+    pub last_dictation_stats: Option<TranscriptionStats>,
+    /// Text the clipboard refused, kept until a later write sticks.
+    pub pending_output: Option<String>,
+    /// What the last delivery changed, for undo.
+    pub last_output: Option<LastOutput>,
+    pub last_capture: Option<SpooledAudio>,
+    pub is_playing_last_capture: bool,
 
     // Live transcription (system audio streaming)
     pub live_transcription: Option<ActiveLiveTranscription>,
@@ -73,10 +193,21 @@ pub struct Overlay {
     pub sessions_loading: bool,
     pub sessions_error: Option<String>,
     pub sessions_search_query: String,
+    pub history_search_query: String,
     pub selected_session_id: Option<i64>,
     pub selected_session_segments: Vec<String>,
     pub selected_session_loading: bool,
 
+    // Dictation history view
+    pub history_entries: Vec<HistoryEntry>,
+    pub history_loading: bool,
+    pub history_error: Option<String>,
+    /// "Limpar" was pressed once; the next press clears everything.
+    pub history_confirm_clear: bool,
+    /// Entry whose kept audio is playing or being re-transcribed.
+    pub history_playing: Option<i64>,
+    pub history_retranscribing: Option<i64>,
+
     // Title generation circuit breaker: session IDs where generation already failed
     pub title_gen_failed_ids: HashSet<i64>,
 
@@ -97,14 +228,70 @@ pub struct Overlay {
 }
 
 impl Overlay {
+    /// Window title; while recording it carries the running time, which
+    /// the recording tick keeps fresh.
     pub fn title(&self, _window: window::Id) -> String {
-        String::from("OpenVoice")
+        match self.dictation_started_at {
+            Some(started_at) if self.is_dictation_recording() => format!(
+                "Gravando {} - OpenVoice",
+                elapsed_label(started_at.elapsed())
+            ),
+            _ if self.is_processing() => {
+                let stage = if self.is_uploading() {
+                    "Enviando"
+                } else {
+                    "Transcrevendo"
+                };
+                format!("{stage} {} - OpenVoice", self.processing_spinner())
+            }
+            _ => {
+                let mut notes = Vec::new();
+                if self.pending_count() > 0 {
+                    notes.push(format!("{} pendente", self.pending_count()));
+                }
+                if self.settings.shortcuts_paused {
+                    notes.push(String::from("atalhos pausados"));
+                }
+
+                if notes.is_empty() {
+                    String::from("OpenVoice")
+                } else {
+                    format!("OpenVoice ({})", notes.join(", "))
+                }
+            }
+        }
+    }
+
+    /// Transcriptions still waiting to reach the clipboard.
+    pub fn pending_count(&self) -> usize {
+        usize::from(self.pending_output.is_some())
+    }
+
+    pub fn processing_spinner(&self) -> &'static str {
+        PROCESSING_SPINNER[self.processing_frame % PROCESSING_SPINNER.len()]
+    }
+
+    /// The take is still on its way to the provider, as opposed to waiting
+    /// for the transcript.
+    pub fn is_uploading(&self) -> bool {
+        matches!(
+            self.dictation_stage,
+            Some(TranscriptionStage::Encoding | TranscriptionStage::Uploading { .. })
+        )
     }
 
     pub fn is_recording(&self) -> bool {
         matches!(self.phase, OverlayPhase::Recording)
     }
 
+    /// Template applied to the current take before it is delivered.
+    pub fn dictation_template(&self) -> Option<&TranscriptTemplate> {
+        match self.dictation_template_override {
+            Some(index) => self.settings.dictation_templates.get(index),
+            None => self.settings.dictation_template(),
+        }
+    }
+
     pub fn is_dictation_recording(&self) -> bool {
         self.recorder.is_some()
     }
@@ -113,6 +300,13 @@ impl Overlay {
         matches!(self.phase, OverlayPhase::Processing)
     }
 
+    /// Share of the running transcription done, when its stage can tell.
+    pub fn dictation_progress_fraction(&self) -> Option<f32> {
+        self.dictation_stage
+            .filter(|_| self.is_processing())
+            .and_then(TranscriptionStage::fraction)
+    }
+
     pub fn is_live_transcribing(&self) -> bool {
         self.live_transcription.is_some()
     }
@@ -122,7 +316,7 @@ impl Overlay {
     }
 
     pub fn can_start_dictation(&self) -> bool {
-        self.settings.has_api_key()
+        self.settings.has_dictation_credentials()
             && !self.is_processing()
             && !self.is_saving_settings
             && !self.is_live_transcribing()
@@ -147,6 +341,8 @@ pub enum OverlayPhase {
     Error,
 }
 
+const PROCESSING_SPINNER: [&str; 4] = ["◐", "◓", "◑", "◒"];
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum MainView {
     Hud,
@@ -158,6 +354,7 @@ pub enum HomeTab {
     Home,
     Copilot,
     Sessions,
+    History,
     Settings,
 }
 
@@ -183,17 +380,41 @@ impl OverlayConfig {
 pub fn boot() -> (Overlay, Task<Message>) {
     let config = OverlayConfig::from_env();
     let primary_monitor = monitors::focused_monitor_geometry();
-    let (settings, settings_error) = match settings_application::load_settings() {
+    let (stored_settings, settings_error) = match settings_application::load_settings() {
         Ok(settings) => (settings, None),
         Err(error) => (AppSettings::default(), Some(error)),
     };
+    let settings = settings_application::runtime_settings(&stored_settings);
+    settings_application::set_log_level(settings.log_level());
     let auth_snapshot = auth_application::load_auth_snapshot()
         .unwrap_or_else(|_| crate::modules::auth::domain::OpenAiAuthSnapshot::signed_out());
-    let settings_form = SettingsForm::from(&settings);
+    let settings_form = SettingsForm::from(&stored_settings);
     let copilot_mode = settings.copilot_default_mode();
     let copilot_include_transcript = settings.copilot_auto_include_transcript;
-    let missing_api_key = (!settings.has_api_key())
-        .then(|| String::from("Cadastre sua OpenRouter API key no painel de settings abaixo."));
+    let missing_api_key = (!settings.has_dictation_credentials()).then(|| {
+        format!(
+            "Cadastre sua {} API key no painel de settings abaixo.",
+            settings.dictation_provider()
+        )
+    });
+    let onboarding_hint = (!settings.onboarding_completed)
+        .then(|| settings_application::get_onboarding_state(&settings))
+        .and_then(|onboarding| onboarding.pending.first().copied())
+        .map(|step| format!("Primeiros passos: {}.", step.label().to_lowercase()));
+    let settings_modified_at = settings_application::settings_modified_at();
+    let (shortcut_receiver, shortcut_error) = match shortcuts_application::listen_for_triggers() {
+        Ok(receiver) => (Some(receiver), None),
+        Err(error) => {
+            log_warn!("[openvoice][shortcuts] trigger listener unavailable: {error}");
+            (None, Some(error))
+        }
+    };
+    let shortcut_settings = settings.clone();
+    // Rewrite the login entry on every launch so it follows the binary, and
+    // drop one left behind after the setting was turned off.
+    let launch_at_login = settings.launch_at_login;
+    let sync_autostart = launch_at_login || settings_application::get_autostart();
+    let dictation_pre_roll = dictation_application::start_pre_roll(&settings);
 
     let state = Overlay {
         main_window_id: None,
@@ -206,15 +427,26 @@ pub fn boot() -> (Overlay, Task<Message>) {
         home_tab: HomeTab::Home,
         primary_monitor,
         hud_position: None,
+        hud_interacted: false,
+        hud_hide_generation: 0,
         phase: OverlayPhase::Idle,
         hint: if config.start_with_passthrough {
             String::from("Passthrough ativo. Pressione P para interagir.")
         } else {
-            String::new()
+            onboarding_hint.unwrap_or_default()
         },
         error: settings_error.or(missing_api_key),
         preview: None,
+        quit_confirm: false,
+        profile_name: String::new(),
+        recent_logs: Vec::new(),
+        config_transfer_path: String::new(),
+        config_export_secrets: false,
+        backup_path: String::new(),
+        backup_include_secrets: false,
+        backup_include_audio: false,
         settings,
+        stored_settings,
         settings_form,
         is_saving_settings: false,
         is_openai_authenticating: false,
@@ -223,7 +455,48 @@ pub fn boot() -> (Overlay, Task<Message>) {
         has_openai_credentials: auth_snapshot.is_authenticated,
         openai_account_label: auth_snapshot.account_label,
         settings_note: None,
+        settings_modified_at,
+        shortcut_receiver,
+        shortcut_registration: None,
+        bound_cancel_shortcut: None,
+        cancel_shortcut_swapping: false,
+        shortcut_chord: None,
+        shortcut_capture: None,
+        shortcut_error,
+        shortcut_conflict: None,
         recorder: None,
+        dictation_system_recorder: None,
+        dictation_stream: None,
+        dictation_cancel: None,
+        dictation_progress: None,
+        dictation_stage: None,
+        dictation_usage: None,
+        openrouter_models: Vec::new(),
+        is_loading_openrouter_models: false,
+        model_comparison: None,
+        is_comparing_models: false,
+        dictation_pre_roll,
+        dictation_monitor_enabled: false,
+        dictation_monitor: None,
+        dictation_auto_stop: None,
+        dictation_model_override: None,
+        dictation_session: None,
+        dictation_shortcut_pressed_at: None,
+        shortcut_double_tap: DoubleTapDetector::default(),
+        dictation_template_override: None,
+        input_devices: Vec::new(),
+        dictation_level: AudioLevel::default(),
+        dictation_waveform: VecDeque::new(),
+        dictation_clipping: false,
+        dictation_started_at: None,
+        processing_frame: 0,
+        last_recording_path: None,
+        last_dictation: None,
+        last_dictation_stats: None,
+        pending_output: None,
+        last_output: None,
+        last_capture: None,
+        is_playing_last_capture: false,
         live_transcription: None,
         live_session_started_at: None,
         live_session_db_id: None,
@@ -240,9 +513,16 @@ pub fn boot() -> (Overlay, Task<Message>) {
         sessions_loading: false,
         sessions_error: None,
         sessions_search_query: String::new(),
+        history_search_query: String::new(),
         selected_session_id: None,
         selected_session_segments: Vec::new(),
         selected_session_loading: false,
+        history_entries: Vec::new(),
+        history_loading: false,
+        history_error: None,
+        history_confirm_clear: false,
+        history_playing: None,
+        history_retranscribing: None,
         title_gen_failed_ids: HashSet::new(),
         copilot_mode,
         copilot_input: text_editor::Content::new(),
@@ -261,7 +541,24 @@ pub fn boot() -> (Overlay, Task<Message>) {
 
     // With iced::daemon, we must open the initial window manually.
     let (_, open_hud) = window::open(platform_window::hud_settings());
-    let tasks = vec![open_hud.map(Message::WindowOpened)];
+    let mut tasks = vec![
+        open_hud.map(Message::WindowOpened),
+        Task::perform(
+            async move { shortcuts_application::setup_global_shortcuts(&shortcut_settings) },
+            Message::ShortcutsRegistered,
+        ),
+        Task::perform(
+            async { microphone::list_input_devices() },
+            Message::InputDevicesLoaded,
+        ),
+    ];
+
+    if sync_autostart {
+        tasks.push(Task::perform(
+            async move { settings_application::set_autostart(launch_at_login) },
+            Message::AutostartApplied,
+        ));
+    }
 
     (state, Task::batch(tasks))
 }
@@ -1,27 +1,64 @@
 use crate::app::message::Message;
 use crate::app::state::{HomeTab, MainView, Overlay, OverlayPhase};
+use crate::modules::audio::domain::{AudioLevel, FeedbackTone};
 use crate::modules::audio::infrastructure::microphone;
+use crate::modules::audio::infrastructure::playback as audio_playback;
+use crate::modules::audio::infrastructure::spool::SpooledAudio;
+use crate::modules::audio::infrastructure::storage as audio_storage;
 use crate::modules::audio::infrastructure::system as system_audio;
 use crate::modules::auth::application as auth_application;
 use crate::modules::auth::domain::CredentialStoreStrategy;
+use crate::modules::backup::application as backup_application;
+use crate::modules::backup::domain::BackupOptions;
 use crate::modules::copilot::application as copilot_application;
 use crate::modules::copilot::domain::{CopilotChatMessage, CopilotContext, CopilotRole};
 use crate::modules::copilot::infrastructure as copilot_infrastructure;
 use crate::modules::dictation::application as dictation_application;
-use crate::modules::dictation::domain::DictationConfig;
+use crate::modules::dictation::domain::{
+    CancelToken, DictationAutoStop, DictationCaptureMode, DictationConfig, DictationOutput,
+    DictationOutputMode, DictationSession, IdleStopAction, LastOutput, ProgressTracker,
+    TEMPLATE_PLACEHOLDER, TranscriptTemplate,
+};
 use crate::modules::live_transcription::application as live_transcription_application;
 use crate::modules::live_transcription::domain::RuntimeEvent;
 use crate::modules::live_transcription::infrastructure::db;
 use crate::modules::settings::application as settings_application;
-use crate::modules::settings::domain::SettingsForm;
+use crate::modules::settings::domain::{
+    AppSettings, HudClickAction, ModelOverrideForm, SettingsForm,
+};
+use crate::modules::shortcuts::application as shortcuts_application;
+use crate::modules::shortcuts::domain::{
+    CHORD_WINDOW, Shortcut, ShortcutAction, ShortcutError, ShortcutEvent, ShortcutMode,
+    ShortcutTrigger,
+};
 use crate::platform::hyprland;
 use crate::platform::screenshot as screenshot_platform;
 use crate::platform::window as app_window;
+use crate::support::error::OpenVoiceError;
+use crate::ui::history as history_ui;
 use iced::keyboard::{self, Key, key::Named};
-use iced::widget::text_editor;
+use iced::widget::{self, text_editor};
 use iced::{Point, Task, window};
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+const DICTATION_TICK_MS: u64 = 100;
+const PROGRESS_TICK_MS: u64 = 200;
+/// Log lines shown in, and copied from, the settings window.
+const RECENT_LOG_LINES: usize = 200;
+/// Waveform bars kept for the HUD (about 3 s at 25 ms per bar).
+const DICTATION_WAVEFORM_BARS: usize = 120;
+const CLIPBOARD_WRITE_ATTEMPTS: u32 = 3;
+const CLIPBOARD_RETRY_DELAY: Duration = Duration::from_millis(250);
+const DICTATION_CLIPPING_HINT: &str = "Audio saturando: reduza o ganho do microfone.";
 
 pub fn update(state: &mut Overlay, message: Message) -> Task<Message> {
+    let task = handle_message(state, message);
+
+    Task::batch([task, sync_cancel_shortcut(state)])
+}
+
+fn handle_message(state: &mut Overlay, message: Message) -> Task<Message> {
     match message {
         // ------------------------------------------------------------------ //
         // Window lifecycle
@@ -86,27 +123,73 @@ pub fn update(state: &mut Overlay, message: Message) -> Task<Message> {
             }
 
             state.hud_position = Some(position);
+            state.hud_interacted = true;
+            Task::none()
+        }
+        Message::WindowInteracted(id) => {
+            if state.main_view == MainView::Hud && state.main_window_id == Some(id) {
+                state.hud_interacted = true;
+            }
             Task::none()
         }
 
         // ------------------------------------------------------------------ //
         // Input events
         // ------------------------------------------------------------------ //
+        Message::KeyEvent(keyboard::Event::KeyPressed {
+            key,
+            physical_key,
+            location,
+            modifiers,
+            ..
+        }) if state.shortcut_capture.is_some() => {
+            let latin = key.to_latin(physical_key);
+            let Some(captured) = captured_shortcut(&key, latin, location, modifiers) else {
+                return Task::none();
+            };
+
+            match captured {
+                Ok(label) => {
+                    if let Some(action) = state.shortcut_capture.take()
+                        && let Some(field) = state.settings_form.shortcut_field_mut(action)
+                    {
+                        *field = label;
+                    }
+                    state.error = None;
+                }
+                // Keep listening so another combination can be tried.
+                Err(error) => state.error = Some(error),
+            }
+            Task::none()
+        }
         Message::KeyEvent(event) => match event {
             keyboard::Event::KeyPressed {
-                key, physical_key, ..
-            } => match key.as_ref() {
-                Key::Named(Named::Escape) if state.main_view == MainView::Home => {
+                key,
+                physical_key,
+                location,
+                modifiers,
+                ..
+            } => {
+                let latin = key.to_latin(physical_key);
+                let stop = state.settings.dictation_stop_key().is_some_and(|stop| {
+                    captured_shortcut(&key, latin, location, keyboard::Modifiers::empty())
+                        == Some(Ok(stop.label()))
+                });
+
+                if stop && modifiers.control() && state.is_dictation_recording() {
+                    Task::done(Message::CancelRecording)
+                } else if stop && state.dictation_cancel.is_some() {
+                    Task::done(Message::CancelTranscription)
+                } else if stop && state.main_view == MainView::Home {
                     Task::done(Message::CloseHomeView)
-                }
-                Key::Named(Named::Escape) => Task::done(Message::Quit),
-                _ if matches!(key.to_latin(physical_key), Some('p'))
-                    && state.main_view == MainView::Hud =>
-                {
+                } else if stop {
+                    Task::done(Message::Quit)
+                } else if latin == Some('p') && state.main_view == MainView::Hud {
                     Task::done(Message::TogglePassthrough)
+                } else {
+                    Task::none()
                 }
-                _ => Task::none(),
-            },
+            }
             _ => Task::none(),
         },
 
@@ -168,6 +251,8 @@ pub fn update(state: &mut Overlay, message: Message) -> Task<Message> {
         Message::SwitchHomeTab(tab) => {
             let reload_sessions = matches!(tab, HomeTab::Sessions);
             let reload_copilot_threads = matches!(tab, HomeTab::Copilot);
+            let reload_usage = matches!(tab, HomeTab::Settings);
+            let reload_history = matches!(tab, HomeTab::History);
 
             // Close copilot overlay windows if they are open.
             let copilot_was_open =
@@ -222,6 +307,15 @@ pub fn update(state: &mut Overlay, message: Message) -> Task<Message> {
                     ));
                 }
 
+                if reload_history {
+                    tasks.push(load_history(state));
+                }
+
+                if reload_usage {
+                    tasks.push(load_dictation_usage());
+                    tasks.push(load_openrouter_models(state, false));
+                }
+
                 Task::batch(tasks)
             } else {
                 state.home_tab = tab;
@@ -244,6 +338,15 @@ pub fn update(state: &mut Overlay, message: Message) -> Task<Message> {
                     ));
                 }
 
+                if reload_history {
+                    tasks.push(load_history(state));
+                }
+
+                if reload_usage {
+                    tasks.push(load_dictation_usage());
+                    tasks.push(load_openrouter_models(state, false));
+                }
+
                 if tasks.is_empty() {
                     Task::none()
                 } else {
@@ -259,6 +362,10 @@ pub fn update(state: &mut Overlay, message: Message) -> Task<Message> {
             state.settings_form.openrouter_api_key = value;
             Task::none()
         }
+        Message::SettingsFallbackApiKeysChanged(value) => {
+            state.settings_form.openrouter_fallback_api_keys = value;
+            Task::none()
+        }
         Message::SettingsOpenAiRealtimeApiKeyChanged(value) => {
             state.settings_form.openai_realtime_api_key = value;
             Task::none()
@@ -267,6 +374,46 @@ pub fn update(state: &mut Overlay, message: Message) -> Task<Message> {
             state.settings_form.openrouter_model = value;
             Task::none()
         }
+        Message::SettingsFallbackModelsChanged(value) => {
+            state.settings_form.openrouter_fallback_models = value;
+            Task::none()
+        }
+        Message::SettingsCompareModelChanged(value) => {
+            state.settings_form.openrouter_compare_model = value;
+            Task::none()
+        }
+        Message::SettingsTemperatureChanged(value) => {
+            state.settings_form.openrouter_temperature = value;
+            Task::none()
+        }
+        Message::SettingsTopPChanged(value) => {
+            state.settings_form.openrouter_top_p = value;
+            Task::none()
+        }
+        Message::SettingsMaxTokensChanged(value) => {
+            state.settings_form.openrouter_max_tokens = value;
+            Task::none()
+        }
+        Message::SettingsRefererChanged(value) => {
+            state.settings_form.openrouter_referer = value;
+            Task::none()
+        }
+        Message::SettingsAppTitleChanged(value) => {
+            state.settings_form.openrouter_app_title = value;
+            Task::none()
+        }
+        Message::SettingsBaseUrlChanged(value) => {
+            state.settings_form.openrouter_base_url = value;
+            Task::none()
+        }
+        Message::SettingsPrivacyModeChanged(value) => {
+            state.settings_form.openrouter_privacy_mode = value;
+            Task::none()
+        }
+        Message::SettingsApiKeysInKeyringChanged(value) => {
+            state.settings_form.api_keys_in_keyring = value;
+            Task::none()
+        }
         Message::SettingsOpenAiRealtimeModelChanged(value) => {
             state.settings_form.openai_realtime_model = value;
             Task::none()
@@ -295,100 +442,794 @@ pub fn update(state: &mut Overlay, message: Message) -> Task<Message> {
             state.settings_form.copilot_save_history = value;
             Task::none()
         }
-        Message::SaveSettings => {
-            state.is_saving_settings = true;
-            state.settings_note = Some(String::from("Salvando settings..."));
-            state.error = None;
-
-            let openrouter_api_key = state.settings_form.openrouter_api_key.clone();
-            let openai_realtime_api_key = state.settings_form.openai_realtime_api_key.clone();
-            let openrouter_model = state.settings_form.openrouter_model.clone();
-            let openai_realtime_model = state.settings_form.openai_realtime_model.clone();
-            let openai_realtime_language = state.settings_form.openai_realtime_language.clone();
-            let openai_realtime_profile = state.settings_form.openai_realtime_profile.clone();
-            let copilot_model = state.settings_form.copilot_model.clone();
-            let copilot_default_mode = state.settings_form.copilot_default_mode.clone();
-            let copilot_auto_include_transcript =
-                state.settings_form.copilot_auto_include_transcript;
-            let copilot_save_history = state.settings_form.copilot_save_history;
-
+        Message::SettingsDictationVadEnabledChanged(value) => {
+            state.settings_form.dictation_vad_enabled = value;
+            Task::none()
+        }
+        Message::SettingsDictationSilenceThresholdChanged(value) => {
+            state.settings_form.dictation_silence_threshold_ms = value;
+            Task::none()
+        }
+        Message::SettingsDictationShortcutChanged(value) => {
+            state.settings_form.dictation_shortcut = value;
+            Task::none()
+        }
+        Message::SettingsDictationRestartShortcutChanged(value) => {
+            state.settings_form.dictation_restart_shortcut = value;
+            Task::none()
+        }
+        Message::SettingsDictationCopyLastShortcutChanged(value) => {
+            state.settings_form.dictation_copy_last_shortcut = value;
+            Task::none()
+        }
+        Message::SettingsDictationUndoShortcutChanged(value) => {
+            state.settings_form.dictation_undo_shortcut = value;
+            Task::none()
+        }
+        Message::SettingsDictationCancelShortcutChanged(value) => {
+            state.settings_form.dictation_cancel_shortcut = value;
+            Task::none()
+        }
+        Message::SettingsDictationStopKeyChanged(value) => {
+            state.settings_form.dictation_stop_key = value;
+            Task::none()
+        }
+        Message::SettingsDictationRetranscribeShortcutChanged(value) => {
+            state.settings_form.dictation_retranscribe_shortcut = value;
+            Task::none()
+        }
+        Message::SettingsDictationHistoryShortcutChanged(value) => {
+            state.settings_form.dictation_history_shortcut = value;
+            Task::none()
+        }
+        Message::SettingsDictationHoldThresholdChanged(value) => {
+            state.settings_form.dictation_hold_threshold_ms = value;
+            Task::none()
+        }
+        Message::SettingsDictationShortcutModeChanged(value) => {
+            state.settings_form.dictation_shortcut_mode = value;
+            Task::none()
+        }
+        Message::SettingsDictationResampleQualityChanged(value) => {
+            state.settings_form.dictation_resample_quality = value;
+            Task::none()
+        }
+        Message::SettingsDictationTargetSampleRateChanged(value) => {
+            state.settings_form.dictation_target_sample_rate = value;
+            Task::none()
+        }
+        Message::SettingsDictationNormalizeAudioChanged(value) => {
+            state.settings_form.dictation_normalize_audio = value;
+            Task::none()
+        }
+        Message::SettingsDictationAudioFormatChanged(value) => {
+            state.settings_form.dictation_audio_format = value;
+            Task::none()
+        }
+        Message::SettingsDictationPreRollEnabledChanged(value) => {
+            state.settings_form.dictation_pre_roll_enabled = value;
+            Task::none()
+        }
+        Message::SettingsDictationMaxDurationChanged(value) => {
+            state.settings_form.dictation_max_duration_secs = value;
+            Task::none()
+        }
+        Message::SettingsDictationRecordingsDirChanged(value) => {
+            state.settings_form.dictation_recordings_dir = value;
+            Task::none()
+        }
+        Message::SettingsDictationInputChannelChanged(value) => {
+            state.settings_form.dictation_input_channel = value;
+            Task::none()
+        }
+        Message::SettingsDictationCaptureSampleRateChanged(value) => {
+            state.settings_form.dictation_capture_sample_rate = value;
+            Task::none()
+        }
+        Message::SettingsDictationCaptureBufferFramesChanged(value) => {
+            state.settings_form.dictation_capture_buffer_frames = value;
+            Task::none()
+        }
+        Message::SettingsDictationSoundFeedbackChanged(value) => {
+            state.settings_form.dictation_sound_feedback = value;
+            Task::none()
+        }
+        Message::SettingsDictationCaptureModeChanged(value) => {
+            state.settings_form.dictation_capture_mode = value;
+            Task::none()
+        }
+        Message::SettingsDictationIdleTimeoutChanged(value) => {
+            state.settings_form.dictation_idle_timeout_secs = value;
+            Task::none()
+        }
+        Message::SettingsDictationIdleActionChanged(value) => {
+            state.settings_form.dictation_idle_action = value;
+            Task::none()
+        }
+        Message::SettingsDictationOutputModeChanged(value) => {
+            state.settings_form.dictation_output_mode = value;
+            Task::none()
+        }
+        Message::SettingsDictationTypingDelayChanged(value) => {
+            state.settings_form.dictation_typing_delay_ms = value;
+            Task::none()
+        }
+        Message::SettingsDictationHistoryAudioChanged(value) => {
+            state.settings_form.dictation_history_audio = value;
+            Task::none()
+        }
+        Message::SettingsDictationHistoryAudioMaxMbChanged(value) => {
+            state.settings_form.dictation_history_audio_max_mb = value;
+            Task::none()
+        }
+        Message::SettingsDictationHistoryAudioMaxDaysChanged(value) => {
+            state.settings_form.dictation_history_audio_max_days = value;
+            Task::none()
+        }
+        Message::SettingsDictationRetranscribeModelChanged(value) => {
+            state.settings_form.dictation_retranscribe_model = value;
+            Task::none()
+        }
+        Message::SettingsTemplateNameChanged(index, value) => {
+            if let Some(template) = state.settings_form.dictation_templates.get_mut(index) {
+                template.name = value;
+            }
+            Task::none()
+        }
+        Message::SettingsTemplateBodyChanged(index, value) => {
+            if let Some(template) = state.settings_form.dictation_templates.get_mut(index) {
+                template.body = value;
+            }
+            Task::none()
+        }
+        Message::SettingsTemplateShortcutChanged(index, value) => {
+            if let Some(template) = state.settings_form.dictation_templates.get_mut(index) {
+                template.shortcut = value;
+            }
+            Task::none()
+        }
+        Message::AddSettingsTemplate => {
+            state
+                .settings_form
+                .dictation_templates
+                .push(TranscriptTemplate {
+                    name: String::new(),
+                    body: String::from(TEMPLATE_PLACEHOLDER),
+                    shortcut: String::new(),
+                });
+            Task::none()
+        }
+        Message::RemoveSettingsTemplate(index) => {
+            if index < state.settings_form.dictation_templates.len() {
+                let removed = state.settings_form.dictation_templates.remove(index);
+                if removed.name == state.settings_form.dictation_template {
+                    state.settings_form.dictation_template.clear();
+                }
+            }
+            Task::none()
+        }
+        Message::SettingsModelOverrideModelChanged(index, value) => {
+            if let Some(tuning) = state.settings_form.dictation_model_overrides.get_mut(index) {
+                tuning.model = value;
+            }
+            Task::none()
+        }
+        Message::SettingsModelOverridePromptChanged(index, value) => {
+            if let Some(tuning) = state.settings_form.dictation_model_overrides.get_mut(index) {
+                tuning.prompt = value;
+            }
+            Task::none()
+        }
+        Message::SettingsModelOverrideTemperatureChanged(index, value) => {
+            if let Some(tuning) = state.settings_form.dictation_model_overrides.get_mut(index) {
+                tuning.temperature = value;
+            }
+            Task::none()
+        }
+        Message::SettingsModelOverrideLanguageChanged(index, value) => {
+            if let Some(tuning) = state.settings_form.dictation_model_overrides.get_mut(index) {
+                tuning.language = value;
+            }
+            Task::none()
+        }
+        Message::AddSettingsModelOverride => {
+            // Starts from the model in use, the one most likely being tuned.
+            let model = state.settings.active_dictation_model().model;
+            state
+                .settings_form
+                .dictation_model_overrides
+                .push(ModelOverrideForm {
+                    model,
+                    ..ModelOverrideForm::default()
+                });
+            Task::none()
+        }
+        Message::RemoveSettingsModelOverride(index) => {
+            if index < state.settings_form.dictation_model_overrides.len() {
+                state.settings_form.dictation_model_overrides.remove(index);
+            }
+            Task::none()
+        }
+        Message::SelectDictationTemplate(name) => {
+            // Takes effect at once, without saving whatever else is being
+            // edited in the form.
+            let previous = state.settings.clone();
+            let settings = change_settings(state, |settings| {
+                settings.dictation_template = name.clone();
+            });
+            state.settings_form.dictation_template = name;
+            Task::batch([
+                config_changed(&previous, state),
+                Task::perform(
+                    async move { settings_application::persist_settings(settings).map(|_| ()) },
+                    Message::DictationTemplatePersisted,
+                ),
+            ])
+        }
+        Message::DictationTemplatePersisted(result) => {
+            if let Err(error) = result {
+                log_error!("[openvoice][settings] failed to persist template choice: {error}");
+                state.error = Some(error);
+            }
+            Task::none()
+        }
+        Message::RefreshInputDevices => Task::perform(
+            async { microphone::list_input_devices() },
+            Message::InputDevicesLoaded,
+        ),
+        Message::InputDevicesLoaded(Ok(devices)) => {
+            state.input_devices = devices;
+            Task::none()
+        }
+        Message::InputDevicesLoaded(Err(error)) => {
+            log_error!("[openvoice][audio] failed to list input devices: {error}");
+            Task::none()
+        }
+        Message::SelectInputDevice(name) => {
+            // Like the template picker, this applies without saving the
+            // rest of the form; the warm stream reopens on the new device.
+            let previous = state.settings.clone();
+            let settings = change_settings(state, |settings| {
+                settings.dictation_input_device = name.clone();
+            });
+            state.settings_form.dictation_input_device = name;
+            state.dictation_pre_roll = None;
+            sync_dictation_pre_roll(state);
+            if state.is_dictation_recording() {
+                state.hint = String::from("O novo microfone vale a partir do proximo ditado.");
+            }
+            Task::batch([
+                config_changed(&previous, state),
+                Task::perform(
+                    async move { settings_application::persist_settings(settings).map(|_| ()) },
+                    Message::InputDevicePersisted,
+                ),
+            ])
+        }
+        Message::SelectDictationModel(choice) => {
+            let previous = state.settings.clone();
+            let settings = change_settings(state, |settings| {
+                settings.select_dictation_model(&choice);
+            });
+            state.settings_form.dictation_provider = settings.dictation_provider.clone();
+            state.settings_form.openrouter_model = settings.openrouter_model.clone();
+            state.settings_form.openrouter_fallback_models =
+                settings.openrouter_fallback_models.join(", ");
+            if !state.is_recording() && !state.is_processing() {
+                state.hint = format!("Proximos ditados usam {choice}.");
+            }
+            Task::batch([
+                config_changed(&previous, state),
+                Task::perform(
+                    async move { settings_application::persist_settings(settings).map(|_| ()) },
+                    Message::DictationModelPersisted,
+                ),
+            ])
+        }
+        Message::CompleteOnboardingStep(step) => {
+            let settings = state.stored_settings.clone();
             Task::perform(
                 async move {
-                    settings_application::save_settings(
-                        openrouter_api_key,
-                        openai_realtime_api_key,
-                        openrouter_model,
-                        openai_realtime_model,
-                        openai_realtime_language,
-                        openai_realtime_profile,
-                        copilot_model,
-                        copilot_default_mode,
-                        copilot_auto_include_transcript,
-                        copilot_save_history,
-                    )
+                    settings_application::complete_onboarding_step(settings, step).map(Box::new)
                 },
-                Message::SettingsSaved,
+                Message::OnboardingStepCompleted,
             )
         }
-        Message::SettingsSaved(result) => {
-            state.is_saving_settings = false;
+        Message::OnboardingStepCompleted(Ok(settings)) => {
+            let finished = settings.onboarding_completed && !state.settings.onboarding_completed;
+            let previous = state.settings.clone();
+            change_settings(state, |stored| {
+                stored.onboarding_completed = settings.onboarding_completed;
+            });
+            state.settings_form.onboarding_completed = settings.onboarding_completed;
+            let changed = config_changed(&previous, state);
+            if !finished {
+                return changed;
+            }
 
+            log_info!("[openvoice][settings] onboarding completed");
+            state.error = None;
+            state.hint = format!(
+                "Tudo pronto. Use {} para ditar.",
+                state.settings.dictation_shortcut
+            );
+            let current = state.shortcut_registration.take();
+            let settings = state.settings.clone();
+            Task::batch([
+                changed,
+                Task::perform(
+                    async move { shortcuts_application::update_shortcut(current, &settings) },
+                    Message::ShortcutsRegistered,
+                ),
+            ])
+        }
+        Message::OnboardingStepCompleted(Err(error)) => {
+            state.error = Some(error);
+            Task::none()
+        }
+        Message::ProfileNameChanged(value) => {
+            state.profile_name = value;
+            Task::none()
+        }
+        Message::SaveProfile => {
+            let name = if state.profile_name.trim().is_empty() {
+                state.settings.active_profile.clone()
+            } else {
+                state.profile_name.clone()
+            };
+            let settings = state.stored_settings.clone();
+            Task::perform(
+                async move { settings_application::save_profile(settings, name).map(Box::new) },
+                Message::ProfilesPersisted,
+            )
+        }
+        Message::ActivateProfile(name) => {
+            log_info!("[openvoice][settings] activating profile {name}");
+            let settings = state.stored_settings.clone();
+            Task::perform(
+                async move { settings_application::activate_profile(settings, name).map(Box::new) },
+                Message::SettingsSaved,
+            )
+        }
+        Message::DeleteActiveProfile => {
+            let name = state.settings.active_profile.clone();
+            let settings = state.stored_settings.clone();
+            Task::perform(
+                async move { settings_application::delete_profile(settings, name).map(Box::new) },
+                Message::ProfilesPersisted,
+            )
+        }
+        Message::ProfilesPersisted(result) => {
             match result {
                 Ok(settings) => {
-                    state.settings = settings;
-                    state.settings_form = SettingsForm::from(&state.settings);
-                    state.copilot_mode = state.settings.copilot_default_mode();
-                    state.copilot_include_transcript =
-                        state.settings.copilot_auto_include_transcript;
-                    state.settings_note = Some(String::from("Settings salvas em disco."));
-                    state.error = None;
-
-                    if !state.is_recording() && !state.is_processing() {
-                        state.phase = OverlayPhase::Idle;
-                        state.hint = String::from(
-                            "Settings prontas. Clique no microfone ou use RT para transcricao ao vivo.",
-                        );
-                    }
-
-                    Task::none()
+                    // Only the profile list changed; edits in the settings
+                    // form are kept.
+                    let previous = state.settings.clone();
+                    change_settings(state, |stored| {
+                        stored.dictation_profiles = settings.dictation_profiles.clone();
+                        stored.active_profile = settings.active_profile.clone();
+                    });
+                    state.settings_form.dictation_profiles = settings.dictation_profiles;
+                    state.settings_form.active_profile = settings.active_profile;
+                    state.profile_name.clear();
+                    config_changed(&previous, state)
                 }
                 Err(error) => {
-                    state.settings_note = None;
+                    log_error!("[openvoice][settings] failed to persist profiles: {error}");
                     state.error = Some(error);
                     Task::none()
                 }
             }
         }
-
-        // ------------------------------------------------------------------ //
-        // OpenAI OAuth
-        // ------------------------------------------------------------------ //
-        Message::StartOpenAiOAuthLogin => {
-            state.is_openai_authenticating = true;
-            state.settings_note = Some(String::from(
-                "Abrindo navegador para login ChatGPT em localhost:1455...",
-            ));
-            state.error = None;
-            state.pending_openai_oauth = None;
-            state.openai_callback_url_input.clear();
-
-            Task::perform(
-                async move { auth_application::start_login(CredentialStoreStrategy::Auto) },
-                Message::OpenAiOAuthStarted,
-            )
+        Message::DictationModelPersisted(result) => {
+            if let Err(error) = result {
+                log_error!("[openvoice][settings] failed to persist model choice: {error}");
+                state.error = Some(error);
+            }
+            Task::none()
         }
-        Message::OpenAiOAuthStarted(result) => match result {
-            Ok(flow) => {
-                state.pending_openai_oauth = Some(flow.clone());
-                state.settings_note = Some(String::from(
-                    "Navegador aberto. Se o app nao concluir sozinho, cole a callback URL abaixo.",
-                ));
-                state.error = None;
-
-                Task::perform(
-                    async move { auth_application::wait_for_callback(flow.flow_id) },
-                    Message::OpenAiOAuthCallbackCaptured,
-                )
+        Message::InputDevicePersisted(result) => {
+            if let Err(error) = result {
+                log_error!("[openvoice][settings] failed to persist input device: {error}");
+                state.error = Some(error);
+            }
+            Task::none()
+        }
+        Message::SettingsDictationWritePrimaryChanged(value) => {
+            state.settings_form.dictation_write_primary = value;
+            Task::none()
+        }
+        Message::SettingsDictationNotificationsChanged(value) => {
+            state.settings_form.dictation_notifications = value;
+            Task::none()
+        }
+        Message::SettingsDictationAutoSubmitConfirmedChanged(value) => {
+            state.settings_form.dictation_auto_submit_confirmed = value;
+            Task::none()
+        }
+        Message::SettingsDictationUriTemplateChanged(value) => {
+            state.settings_form.dictation_uri_template = value;
+            Task::none()
+        }
+        Message::SettingsDictationClipboardRestoreChanged(value) => {
+            state.settings_form.dictation_clipboard_restore_secs = value;
+            Task::none()
+        }
+        Message::SettingsDictationHookCommandChanged(value) => {
+            state.settings_form.dictation_hook_command = value;
+            Task::none()
+        }
+        Message::SettingsDictationHookTimeoutChanged(value) => {
+            state.settings_form.dictation_hook_timeout_secs = value;
+            Task::none()
+        }
+        Message::SettingsDictationWebhookEnabledChanged(value) => {
+            state.settings_form.dictation_webhook_enabled = value;
+            Task::none()
+        }
+        Message::SettingsDictationWebhookUrlChanged(value) => {
+            state.settings_form.dictation_webhook_url = value;
+            Task::none()
+        }
+        Message::SettingsDictationProviderChanged(value) => {
+            if let Err(error) =
+                dictation_application::set_provider(&mut state.settings_form, &value)
+            {
+                log_warn!("[openvoice][settings] {error}");
+            }
+            Task::none()
+        }
+        Message::SettingsDeepgramApiKeyChanged(value) => {
+            state.settings_form.deepgram_api_key = value;
+            Task::none()
+        }
+        Message::SettingsDeepgramModelChanged(value) => {
+            state.settings_form.deepgram_model = value;
+            Task::none()
+        }
+        Message::SettingsDictationLanguageChanged(value) => {
+            state.settings_form.dictation_language = value;
+            Task::none()
+        }
+        Message::SettingsDictationStreamingChanged(value) => {
+            state.settings_form.dictation_streaming = value;
+            Task::none()
+        }
+        Message::SettingsDictationTimestampsChanged(value) => {
+            state.settings_form.dictation_timestamps = value;
+            Task::none()
+        }
+        Message::SettingsDictationVocabularyChanged(value) => {
+            state.settings_form.dictation_vocabulary = value;
+            Task::none()
+        }
+        Message::SettingsAssemblyAiApiKeyChanged(value) => {
+            state.settings_form.assemblyai_api_key = value;
+            Task::none()
+        }
+        Message::SettingsDictationMonthlyBudgetChanged(value) => {
+            state.settings_form.dictation_monthly_budget_usd = value;
+            Task::none()
+        }
+        Message::SettingsDictationShowWindowChanged(value) => {
+            state.settings_form.dictation_show_window = value;
+            Task::none()
+        }
+        Message::SettingsHudClickActionChanged(value) => {
+            state.settings_form.hud_click_action = value;
+            Task::none()
+        }
+        Message::SettingsHudKeepOpenChanged(value) => {
+            state.settings_form.hud_keep_open = value;
+            Task::none()
+        }
+        Message::SettingsHudHideDelayChanged(value) => {
+            state.settings_form.hud_hide_delay_ms = value;
+            Task::none()
+        }
+        Message::SettingsLaunchAtLoginChanged(value) => {
+            state.settings_form.launch_at_login = value;
+            Task::none()
+        }
+        Message::AutostartApplied(result) => {
+            if let Err(error) = result {
+                log_error!("[openvoice][settings] failed to update autostart: {error}");
+                state.error = Some(error);
+            }
+            Task::none()
+        }
+        Message::SettingsFileChecked(modified_at) => {
+            let changed = modified_at.is_some() && modified_at != state.settings_modified_at;
+            state.settings_modified_at = modified_at;
+            if !changed {
+                return Task::none();
+            }
+
+            Task::perform(
+                async { settings_application::load_settings().map(Box::new) },
+                Message::ConfigReloaded,
+            )
+        }
+        Message::ConfigReloaded(Ok(settings)) => {
+            // The app's own writes come back unchanged.
+            if *settings == state.stored_settings {
+                return Task::none();
+            }
+
+            log_info!("[openvoice][settings] config-reloaded from disk");
+            let task = update(state, Message::SettingsSaved(Ok(settings)));
+            state.settings_note = Some(String::from(
+                "Settings recarregadas: settings.json foi editado fora do app.",
+            ));
+            task
+        }
+        Message::ConfigReloaded(Err(error)) => {
+            // Likely a hand edit caught halfway; the next write retries.
+            log_error!("[openvoice][settings] failed to reload settings: {error}");
+            state.error = Some(error);
+            Task::none()
+        }
+        Message::ConfigTransferPathChanged(value) => {
+            state.config_transfer_path = value;
+            Task::none()
+        }
+        Message::ConfigExportSecretsChanged(value) => {
+            state.config_export_secrets = value;
+            Task::none()
+        }
+        Message::ExportConfig => {
+            let settings = state.stored_settings.clone();
+            let path = state.config_transfer_path.clone();
+            let include_secrets = state.config_export_secrets;
+            Task::perform(
+                async move { settings_application::export_config(&settings, &path, include_secrets) },
+                Message::ConfigExported,
+            )
+        }
+        Message::ConfigExported(result) => {
+            match result {
+                Ok(path) => {
+                    state.settings_note =
+                        Some(format!("Settings exportadas para {}.", path.display()));
+                    state.error = None;
+                }
+                Err(error) => {
+                    log_error!("[openvoice][settings] failed to export settings: {error}");
+                    state.error = Some(error);
+                }
+            }
+            Task::none()
+        }
+        Message::ImportConfig => {
+            let current = state.stored_settings.clone();
+            let path = state.config_transfer_path.clone();
+            Task::perform(
+                async move { settings_application::import_config(&current, &path).map(Box::new) },
+                Message::SettingsSaved,
+            )
+        }
+        Message::SelectLogLevel(level) => {
+            // Applies at once, like the microphone picker.
+            let previous = state.settings.clone();
+            let settings = change_settings(state, |settings| {
+                settings.log_level = level.code().to_owned();
+            });
+            state.settings_form.log_level = settings.log_level.clone();
+            Task::batch([
+                config_changed(&previous, state),
+                Task::perform(
+                    async move { settings_application::persist_settings(settings).map(|_| ()) },
+                    Message::LogLevelPersisted,
+                ),
+            ])
+        }
+        Message::LogLevelPersisted(result) => {
+            if let Err(error) = result {
+                log_error!("[openvoice][settings] failed to persist log level: {error}");
+                state.error = Some(error);
+            }
+            Task::none()
+        }
+        Message::LoadRecentLogs => Task::perform(
+            async { settings_application::get_recent_logs(RECENT_LOG_LINES) },
+            Message::RecentLogsLoaded,
+        ),
+        Message::RecentLogsLoaded(Ok(lines)) => {
+            state.recent_logs = lines;
+            Task::none()
+        }
+        Message::RecentLogsLoaded(Err(error)) => {
+            state.error = Some(error);
+            Task::none()
+        }
+        Message::CopyRecentLogs => {
+            state.settings_note = Some(String::from("Logs copiados para o clipboard."));
+            iced::clipboard::write(state.recent_logs.join("\n"))
+        }
+        Message::BackupPathChanged(value) => {
+            state.backup_path = value;
+            Task::none()
+        }
+        Message::BackupIncludeSecretsChanged(value) => {
+            state.backup_include_secrets = value;
+            Task::none()
+        }
+        Message::BackupIncludeAudioChanged(value) => {
+            state.backup_include_audio = value;
+            Task::none()
+        }
+        Message::CreateBackup => {
+            let settings = state.stored_settings.clone();
+            let path = state.backup_path.clone();
+            let options = BackupOptions {
+                include_secrets: state.backup_include_secrets,
+                include_audio: state.backup_include_audio,
+            };
+            state.settings_note = Some(String::from("Criando backup..."));
+            Task::perform(
+                async move { backup_application::create_backup(&settings, &path, options) },
+                Message::BackupCreated,
+            )
+        }
+        Message::BackupCreated(result) => {
+            match result {
+                Ok(path) => {
+                    state.settings_note = Some(format!("Backup criado em {}.", path.display()));
+                    state.error = None;
+                }
+                Err(error) => {
+                    log_error!("[openvoice][backup] failed to create backup: {error}");
+                    state.settings_note = None;
+                    state.error = Some(error);
+                }
+            }
+            Task::none()
+        }
+        Message::RestoreBackup => {
+            let current = state.stored_settings.clone();
+            let path = state.backup_path.clone();
+            state.settings_note = Some(String::from("Restaurando backup..."));
+            Task::perform(
+                async move { backup_application::restore_backup(&current, &path).map(Box::new) },
+                Message::BackupRestored,
+            )
+        }
+        Message::BackupRestored(Ok(settings)) => {
+            let task = update(state, Message::SettingsSaved(Ok(settings)));
+            state.settings_note = Some(String::from("Backup restaurado: settings e historico."));
+            Task::batch([task, load_history(state)])
+        }
+        Message::BackupRestored(Err(error)) => {
+            log_error!("[openvoice][backup] failed to restore backup: {error}");
+            state.settings_note = None;
+            state.error = Some(error);
+            Task::none()
+        }
+        Message::ResetConfig(scope) => {
+            let current = state.stored_settings.clone();
+            Task::perform(
+                async move { settings_application::reset_config(&current, scope).map(Box::new) },
+                Message::SettingsSaved,
+            )
+        }
+        Message::SaveSettings => {
+            state.is_saving_settings = true;
+            state.settings_note = Some(String::from("Salvando settings..."));
+            state.error = None;
+
+            let form = state.settings_form.clone();
+
+            Task::perform(
+                async move { settings_application::save_settings(form).map(Box::new) },
+                Message::SettingsSaved,
+            )
+        }
+        Message::ConfigChanged(diff) => {
+            log_info!(
+                "[openvoice][settings] config-changed: {}",
+                diff.fields.join(", ")
+            );
+            // The copilot windows only pick up their defaults on a change,
+            // so a mode switched by hand survives unrelated edits.
+            if diff.contains("copilot_default_mode") {
+                state.copilot_mode = state.settings.copilot_default_mode();
+            }
+            if diff.contains("copilot_auto_include_transcript") {
+                state.copilot_include_transcript = state.settings.copilot_auto_include_transcript;
+            }
+            if diff.contains("log_level") {
+                settings_application::set_log_level(state.settings.log_level());
+            }
+            Task::none()
+        }
+        Message::SettingsSaved(result) => {
+            state.is_saving_settings = false;
+
+            match result {
+                Ok(stored) => {
+                    let settings = settings_application::runtime_settings(&stored);
+                    let shortcut_changed = settings.shortcut_binds()
+                        != state.settings.shortcut_binds()
+                        || settings.dictation_shortcut_mode
+                            != state.settings.dictation_shortcut_mode;
+                    let autostart_changed =
+                        settings.launch_at_login != state.settings.launch_at_login;
+                    if settings.dictation_capture_overrides()
+                        != state.settings.dictation_capture_overrides()
+                    {
+                        // Reopen the warm stream with the new device config.
+                        state.dictation_pre_roll = None;
+                    }
+                    let previous = std::mem::replace(&mut state.settings, settings);
+                    state.stored_settings = *stored;
+                    state.settings_form = SettingsForm::from(&state.stored_settings);
+                    sync_dictation_pre_roll(state);
+                    state.settings_note = Some(String::from("Settings salvas em disco."));
+                    state.error = None;
+
+                    if !state.is_recording() && !state.is_processing() {
+                        state.phase = OverlayPhase::Idle;
+                        state.hint = String::from(
+                            "Settings prontas. Clique no microfone ou use RT para transcricao ao vivo.",
+                        );
+                    }
+
+                    let mut tasks = vec![config_changed(&previous, state)];
+                    if autostart_changed {
+                        let enabled = state.settings.launch_at_login;
+                        tasks.push(Task::perform(
+                            async move { settings_application::set_autostart(enabled) },
+                            Message::AutostartApplied,
+                        ));
+                    }
+                    if shortcut_changed {
+                        let current = state.shortcut_registration.take();
+                        let settings = state.settings.clone();
+                        tasks.push(Task::perform(
+                            async move { shortcuts_application::update_shortcut(current, &settings) },
+                            Message::ShortcutsRegistered,
+                        ));
+                    }
+
+                    Task::batch(tasks)
+                }
+                Err(error) => {
+                    state.settings_note = None;
+                    state.error = Some(error);
+                    Task::none()
+                }
+            }
+        }
+
+        // ------------------------------------------------------------------ //
+        // OpenAI OAuth
+        // ------------------------------------------------------------------ //
+        Message::StartOpenAiOAuthLogin => {
+            state.is_openai_authenticating = true;
+            state.settings_note = Some(String::from(
+                "Abrindo navegador para login ChatGPT em localhost:1455...",
+            ));
+            state.error = None;
+            state.pending_openai_oauth = None;
+            state.openai_callback_url_input.clear();
+
+            Task::perform(
+                async move { auth_application::start_login(CredentialStoreStrategy::Auto) },
+                Message::OpenAiOAuthStarted,
+            )
+        }
+        Message::OpenAiOAuthStarted(result) => match result {
+            Ok(flow) => {
+                state.pending_openai_oauth = Some(flow.clone());
+                state.settings_note = Some(String::from(
+                    "Navegador aberto. Se o app nao concluir sozinho, cole a callback URL abaixo.",
+                ));
+                state.error = None;
+
+                Task::perform(
+                    async move { auth_application::wait_for_callback(flow.flow_id) },
+                    Message::OpenAiOAuthCallbackCaptured,
+                )
             }
             Err(error) => {
                 state.is_openai_authenticating = false;
@@ -411,214 +1252,1224 @@ pub fn update(state: &mut Overlay, message: Message) -> Task<Message> {
 
                 Task::none()
             }
-            Err(error) => {
-                state.is_openai_authenticating = false;
-                state.settings_note = Some(String::from(
-                    "Callback automatico nao concluiu. Cole a callback URL manualmente.",
-                ));
-                state.error = Some(error);
+            Err(error) => {
+                state.is_openai_authenticating = false;
+                state.settings_note = Some(String::from(
+                    "Callback automatico nao concluiu. Cole a callback URL manualmente.",
+                ));
+                state.error = Some(error);
+                Task::none()
+            }
+        },
+        Message::OpenAiOAuthCallbackUrlChanged(value) => {
+            state.openai_callback_url_input = value;
+            Task::none()
+        }
+        Message::SubmitOpenAiOAuthCallback => {
+            let Some(flow) = state.pending_openai_oauth.as_ref() else {
+                state.error = Some(String::from(
+                    "Nao existe login OAuth pendente para concluir manualmente.",
+                ));
+                return Task::none();
+            };
+
+            let callback_url = state.openai_callback_url_input.trim().to_owned();
+            if callback_url.is_empty() {
+                state.error = Some(String::from("Cole a callback URL antes de continuar."));
+                return Task::none();
+            }
+
+            state.is_openai_authenticating = true;
+            state.settings_note = Some(String::from(
+                "Finalizando login ChatGPT a partir da callback URL...",
+            ));
+            state.error = None;
+
+            let flow_id = flow.flow_id.clone();
+            Task::perform(
+                async move { auth_application::complete_login(flow_id, callback_url) },
+                Message::OpenAiOAuthFinished,
+            )
+        }
+        Message::OpenAiOAuthFinished(result) => {
+            state.is_openai_authenticating = false;
+
+            match result {
+                Ok(snapshot) => {
+                    state.pending_openai_oauth = None;
+                    state.openai_callback_url_input.clear();
+                    state.has_openai_credentials = snapshot.is_authenticated;
+                    state.openai_account_label = snapshot.account_label;
+                    state.settings_note = Some(String::from(
+                        "Login ChatGPT concluido para modelos OAuth futuros.",
+                    ));
+                    state.error = None;
+                    Task::none()
+                }
+                Err(error) => {
+                    state.settings_note = None;
+                    state.error = Some(error);
+                    Task::none()
+                }
+            }
+        }
+        Message::LogoutOpenAi => {
+            state.is_openai_authenticating = true;
+            state.pending_openai_oauth = None;
+            state.openai_callback_url_input.clear();
+            state.settings_note = Some(String::from("Removendo sessao ChatGPT do OpenVoice..."));
+            state.error = None;
+
+            Task::perform(
+                async move { auth_application::logout(CredentialStoreStrategy::Auto) },
+                Message::OpenAiLogoutFinished,
+            )
+        }
+        Message::OpenAiLogoutFinished(result) => {
+            state.is_openai_authenticating = false;
+
+            match result {
+                Ok(()) => {
+                    state.has_openai_credentials = false;
+                    state.openai_account_label = None;
+                    state.settings_note =
+                        Some(String::from("Sessao ChatGPT removida do OpenVoice."));
+                    state.error = None;
+                    Task::none()
+                }
+                Err(error) => {
+                    state.settings_note = None;
+                    state.error = Some(error);
+                    Task::none()
+                }
+            }
+        }
+
+        // ------------------------------------------------------------------ //
+        // Global shortcuts
+        // ------------------------------------------------------------------ //
+        Message::CaptureShortcut(action) => {
+            state.shortcut_capture = (state.shortcut_capture != Some(action)).then_some(action);
+            Task::none()
+        }
+        Message::ToggleShortcutsPaused => {
+            let previous = state.settings.clone();
+            let paused = !state.settings.shortcuts_paused;
+            let persisted = change_settings(state, |settings| settings.shortcuts_paused = paused);
+            state.settings_form.shortcuts_paused = paused;
+            state.hint = String::from(if paused {
+                "Atalhos globais pausados."
+            } else {
+                "Atalhos globais reativados."
+            });
+            log_info!(
+                "[openvoice][shortcuts] shortcuts {}",
+                if paused { "paused" } else { "resumed" }
+            );
+
+            let current = state.shortcut_registration.take();
+            let settings = state.settings.clone();
+            Task::batch([
+                config_changed(&previous, state),
+                Task::perform(
+                    async move { shortcuts_application::update_shortcut(current, &settings) },
+                    Message::ShortcutsRegistered,
+                ),
+                Task::perform(
+                    async move { settings_application::persist_settings(persisted).map(|_| ()) },
+                    Message::ShortcutsPausedPersisted,
+                ),
+            ])
+        }
+        Message::ShortcutsPausedPersisted(result) => {
+            if let Err(error) = result {
+                log_error!("[openvoice][settings] failed to persist shortcut pause: {error}");
+                state.error = Some(error);
+            }
+            Task::none()
+        }
+        Message::ShortcutChordSwapped(Ok(())) => Task::none(),
+        Message::ShortcutChordSwapped(Err(error)) => {
+            log_error!("[openvoice][shortcuts] failed to swap the sequence keys: {error}");
+            Task::none()
+        }
+        Message::ShortcutChordExpired => match state.shortcut_chord {
+            Some((armed_at, _)) if armed_at.elapsed() >= CHORD_WINDOW => end_shortcut_chord(state),
+            _ => Task::none(),
+        },
+        Message::CancelShortcutSwapped(result) => {
+            state.cancel_shortcut_swapping = false;
+            if let Err(error) = result {
+                log_error!("[openvoice][shortcuts] failed to swap the cancel shortcut: {error}");
+            }
+            // `update` re-syncs next, catching a take that started or ended
+            // while the swap ran.
+            Task::none()
+        }
+        Message::ShortcutsRegistered(result) => {
+            match result {
+                Ok(registration) => {
+                    state.shortcut_registration = registration;
+                    state.shortcut_error = None;
+                    state.shortcut_conflict = None;
+                }
+                Err(error) => {
+                    log_error!("[openvoice][shortcuts] registration failed: {error}");
+                    state.shortcut_registration = None;
+                    state.shortcut_error = Some(error.to_string());
+                    state.shortcut_conflict =
+                        matches!(error, ShortcutError::Conflict { .. }).then_some(error);
+                }
+            }
+
+            Task::none()
+        }
+        Message::ShortcutTriggerReceived(trigger) => {
+            let Some(trigger) = trigger else {
+                log_info!("[openvoice][shortcuts] trigger listener closed");
+                state.shortcut_receiver = None;
+                return Task::none();
+            };
+
+            // Any other shortcut, the second key included, ends a chord.
+            let chord = if trigger.event == ShortcutEvent::Leader {
+                Task::none()
+            } else {
+                end_shortcut_chord(state)
+            };
+            Task::batch([chord, on_shortcut(state, trigger)])
+        }
+
+        // ------------------------------------------------------------------ //
+        // Dictation (mic → OpenRouter / Deepgram / AssemblyAI)
+        // ------------------------------------------------------------------ //
+        Message::StartTemplateDictation(index) => {
+            let task = update(state, Message::StartDictation);
+            if state.is_dictation_recording() {
+                state.dictation_template_override = Some(index);
+            }
+            task
+        }
+        Message::StartDictation => {
+            state.dictation_template_override = None;
+            state.quit_confirm = false;
+            if !state.can_start_dictation() {
+                state.phase = OverlayPhase::Error;
+                state.error = Some(format!(
+                    "Cadastre e salve sua {} API key antes de gravar.",
+                    state.settings.dictation_provider()
+                ));
+                return Task::none();
+            }
+
+            // Auto-close Home → HUD before starting dictation, unless the
+            // take should run without touching the window (and its focus).
+            let show_window = state.settings.dictation_show_window;
+            state.hud_interacted = false;
+            let mut morph_tasks = if show_window {
+                prepare_capture_ui(state)
+            } else {
+                Vec::new()
+            };
+
+            // A warm pre-roll stream already holds the last moments of audio.
+            let recorder = match state.dictation_pre_roll.take() {
+                Some(recorder) => recorder
+                    .begin()
+                    .map(|()| recorder)
+                    .map_err(OpenVoiceError::DeviceUnavailable),
+                None => microphone::start_default_recording(
+                    state.settings.dictation_capture_overrides(),
+                ),
+            };
+
+            match recorder {
+                Ok(recorder) => {
+                    let device_name = recorder
+                        .device_name()
+                        .unwrap_or("microfone padrao")
+                        .to_owned();
+                    let mut sources = String::from("MIC");
+
+                    if state
+                        .settings
+                        .dictation_capture_mode()
+                        .includes_system_audio()
+                    {
+                        match system_audio::start_default_recording() {
+                            Ok(system_recorder) => {
+                                state.dictation_system_recorder = Some(system_recorder);
+                                sources.push_str(" + SISTEMA");
+                            }
+                            Err(error) => log_warn!(
+                                "[openvoice][dictation] system audio unavailable, recording mic only: {error}"
+                            ),
+                        }
+                    }
+
+                    // Streaming hears only the microphone, so takes mixed
+                    // with system audio are uploaded once they finish.
+                    if state.settings.dictation_capture_mode() == DictationCaptureMode::Microphone
+                        && let Ok(config) = DictationConfig::from_settings(&state.settings)
+                    {
+                        state.dictation_stream = dictation_application::start_streaming(
+                            &config,
+                            recorder.sample_rate(),
+                            recorder.channels(),
+                        );
+                        if let Some(stream) = state.dictation_stream.as_ref() {
+                            recorder.stream_to(stream.sender());
+                            sources.push_str(" + STREAM");
+                        }
+                    }
+
+                    state.recorder = Some(recorder);
+                    state.dictation_auto_stop = None;
+                    state.dictation_level = AudioLevel::default();
+                    state.dictation_waveform.clear();
+                    state.dictation_clipping = false;
+                    state.dictation_started_at = Some(Instant::now());
+                    state.phase = OverlayPhase::Recording;
+                    state.hint = format!(
+                        "REC {sources} ativo em {device_name}. Clique no microfone para parar."
+                    );
+                    state.error = None;
+                    state.preview = None;
+
+                    if state.dictation_monitor_enabled {
+                        start_dictation_monitor(state);
+                    }
+
+                    morph_tasks.push(schedule_dictation_tick());
+                    morph_tasks.push(play_feedback_tone(state, FeedbackTone::Start));
+
+                    if show_window && state.passthrough_enabled {
+                        state.passthrough_enabled = false;
+
+                        if let Some(window_id) = state.main_window_id {
+                            morph_tasks.push(window::disable_mouse_passthrough(window_id));
+                            morph_tasks
+                                .push(window::set_level(window_id, window::Level::AlwaysOnTop));
+                        }
+                    }
+
+                    if morph_tasks.is_empty() {
+                        Task::none()
+                    } else {
+                        Task::batch(morph_tasks)
+                    }
+                }
+                Err(error) => {
+                    state.phase = OverlayPhase::Error;
+                    state.hint = String::from("Nao consegui iniciar a captura do microfone.");
+                    if let Some(advice) = error.advice() {
+                        state.hint = format!("{} {advice}", state.hint);
+                    }
+                    state.error = Some(error.to_string());
+                    if morph_tasks.is_empty() {
+                        Task::none()
+                    } else {
+                        Task::batch(morph_tasks)
+                    }
+                }
+            }
+        }
+        Message::StopDictation => {
+            let Some(recorder) = state.recorder.take() else {
+                return Task::none();
+            };
+            let system_recorder = state.dictation_system_recorder.take();
+            state.dictation_monitor = None;
+            state.dictation_level = AudioLevel::default();
+            state.dictation_waveform.clear();
+            state.dictation_started_at = None;
+            state.dictation_clipping = recorder.has_clipped();
+            let finished = recorder.finish();
+            sync_dictation_pre_roll(state);
+            let stop_tone = play_feedback_tone(state, FeedbackTone::Stop);
+
+            // Always stop parec, even when the microphone take is lost.
+            let system_track = system_recorder.and_then(|recorder| match recorder.finish() {
+                Ok(track) => Some(track),
+                Err(error) => {
+                    log_warn!("[openvoice][dictation] system audio dropped from take: {error}");
+                    None
+                }
+            });
+
+            let capture_track = match finished {
+                Ok(capture_track) => capture_track,
+                Err(error) => {
+                    state.phase = OverlayPhase::Error;
+                    state.hint =
+                        String::from("A captura do microfone foi interrompida antes do envio.");
+                    state.error = Some(error);
+                    return stop_tone;
+                }
+            };
+
+            let Some(system_track) = system_track else {
+                return Task::batch([stop_tone, submit_dictation(state, capture_track.audio)]);
+            };
+
+            state.phase = OverlayPhase::Processing;
+            state.hint = String::from("Juntando microfone e audio do sistema...");
+            state.error = None;
+            let mode = state.settings.dictation_capture_mode();
+            let quality = state.settings.dictation_resample_quality();
+            let cancel = CancelToken::default();
+            state.dictation_cancel = Some(cancel.clone());
+
+            Task::batch([
+                stop_tone,
+                Task::perform(
+                    async move {
+                        dictation_application::combine_capture(
+                            &capture_track.audio,
+                            &system_track.audio,
+                            mode,
+                            quality,
+                        )
+                    },
+                    move |result| {
+                        if cancel.is_cancelled() {
+                            Message::DictationDiscarded
+                        } else {
+                            Message::DictationCaptureCombined(result)
+                        }
+                    },
+                ),
+            ])
+        }
+        Message::CancelRecording => {
+            if !drop_dictation_take(state) {
+                return Task::none();
+            }
+
+            log_info!("[openvoice][dictation] recording cancelled by the user");
+            state.dictation_shortcut_pressed_at = None;
+            state.dictation_clipping = false;
+            state.phase = OverlayPhase::Idle;
+            state.hint = String::from("Gravacao cancelada; nada foi enviado.");
+            state.error = None;
+            state.preview = None;
+            Task::none()
+        }
+        Message::RestartDictation => {
+            if state.is_processing() {
+                return Task::none();
+            }
+
+            if state.is_dictation_recording() {
+                log_info!("[openvoice][dictation] take discarded, recording again");
+                drop_dictation_take(state);
+            }
+
+            Task::done(Message::StartDictation)
+        }
+        Message::ToggleDictationMonitor => {
+            state.dictation_monitor_enabled = !state.dictation_monitor_enabled;
+
+            if state.dictation_monitor_enabled {
+                start_dictation_monitor(state);
+            } else {
+                state.dictation_monitor = None;
+            }
+
+            Task::none()
+        }
+        Message::HudStatusClicked => match state.settings.hud_click_action() {
+            HudClickAction::ToggleDictation if state.is_dictation_recording() => {
+                Task::done(Message::StopDictation)
+            }
+            HudClickAction::ToggleDictation if state.can_start_dictation() => {
+                Task::done(Message::StartDictation)
+            }
+            HudClickAction::ToggleDictation => Task::none(),
+            HudClickAction::OpenSettings => Task::done(Message::SwitchHomeTab(HomeTab::Settings)),
+            HudClickAction::OpenHistory => Task::done(Message::SwitchHomeTab(HomeTab::History)),
+        },
+        Message::DictationCaptureCombined(Ok(audio)) => submit_dictation(state, audio),
+        Message::DictationCaptureCombined(Err(error)) => {
+            state.dictation_cancel = None;
+            state.phase = OverlayPhase::Error;
+            state.hint = String::from("Nao consegui juntar o microfone e o audio do sistema.");
+            state.error = Some(error);
+            Task::none()
+        }
+        Message::DictationTick => {
+            let Some(recorder) = state.recorder.as_ref() else {
+                return Task::none();
+            };
+
+            state.dictation_level = recorder.take_level();
+            state.dictation_waveform.extend(recorder.take_waveform());
+            let overflow = state
+                .dictation_waveform
+                .len()
+                .saturating_sub(DICTATION_WAVEFORM_BARS);
+            state.dictation_waveform.drain(..overflow);
+
+            if recorder.take_clip_events() > 0 && !state.dictation_clipping {
+                log_warn!("[openvoice][dictation] microphone input is clipping");
+                state.dictation_clipping = true;
+                state.hint = String::from(DICTATION_CLIPPING_HINT);
+            }
+
+            let max_duration =
+                Duration::from_secs(u64::from(state.settings.dictation_max_duration_secs));
+            if state
+                .dictation_started_at
+                .is_some_and(|started_at| started_at.elapsed() >= max_duration)
+            {
+                return Task::done(Message::DictationAutoStopped(
+                    DictationAutoStop::MaxDuration,
+                ));
+            }
+
+            if state.settings.dictation_vad_enabled {
+                let threshold =
+                    Duration::from_millis(u64::from(state.settings.dictation_silence_threshold_ms));
+
+                if recorder
+                    .silence_since_last_voice()
+                    .is_some_and(|silence| silence >= threshold)
+                {
+                    return Task::done(Message::DictationAutoStopped(DictationAutoStop::Silence));
+                }
+            }
+
+            // Unlike VAD this also counts a take where nobody ever spoke.
+            if let Some(timeout) = state.settings.dictation_idle_timeout()
+                && recorder
+                    .silence_since_last_voice()
+                    .or_else(|| state.dictation_started_at.map(|started| started.elapsed()))
+                    .is_some_and(|silence| silence >= timeout)
+            {
+                return Task::done(Message::DictationAutoStopped(DictationAutoStop::Idle));
+            }
+
+            match state
+                .dictation_stream
+                .as_ref()
+                .and_then(|stream| stream.take_partial())
+            {
+                Some(partial) => Task::batch([
+                    Task::done(Message::DictationPartial(partial)),
+                    schedule_dictation_tick(),
+                ]),
+                None => schedule_dictation_tick(),
+            }
+        }
+        Message::DictationPartial(partial) => {
+            // A late partial must not overwrite the final transcript.
+            if state.is_dictation_recording() {
+                state.preview = Some(partial);
+            }
+            Task::none()
+        }
+        Message::DictationAutoStopped(reason) => {
+            if state.recorder.is_none() {
+                return Task::none();
+            }
+
+            log_info!("[openvoice][dictation] auto-stop triggered: {reason:?}");
+            if reason == DictationAutoStop::Idle
+                && state.settings.dictation_idle_action() == IdleStopAction::Discard
+            {
+                return discard_dictation(state, reason);
+            }
+
+            state.dictation_auto_stop = Some(reason);
+            Task::done(Message::StopDictation)
+        }
+        Message::DictationProgressTick => {
+            let Some(progress) = state.dictation_progress.as_ref() else {
+                return Task::none();
+            };
+
+            state.processing_frame = state.processing_frame.wrapping_add(1);
+            let stage = progress.stage();
+            if state.dictation_stage != Some(stage) {
+                state.dictation_stage = Some(stage);
+                state.hint = stage.label();
+            }
+
+            schedule_progress_tick()
+        }
+        Message::CancelTranscription => {
+            let Some(cancel) = state.dictation_cancel.take() else {
+                return Task::none();
+            };
+            state.dictation_progress = None;
+            state.dictation_stage = None;
+
+            state.dictation_model_override = None;
+
+            log_info!("[openvoice][dictation] transcription cancelled by the user");
+            cancel.cancel();
+            state.phase = OverlayPhase::Idle;
+            state.hint = OpenVoiceError::Cancelled.to_string();
+            state.error = None;
+            state.preview = None;
+            state.dictation_auto_stop = None;
+            Task::none()
+        }
+        Message::RetranscribeLast => {
+            if state.is_dictation_recording() || state.is_processing() {
+                return Task::none();
+            }
+            let Some(audio) = state.last_capture.clone() else {
+                state.error = Some(String::from("Grave um ditado antes de retranscrever."));
+                return Task::none();
+            };
+            let mut config = match DictationConfig::from_settings(&state.settings) {
+                Ok(config) => config,
+                Err(error) => {
+                    state.phase = OverlayPhase::Error;
+                    state.error = Some(error);
+                    return Task::none();
+                }
+            };
+            let model = state
+                .settings
+                .dictation_retranscribe_model()
+                .map(str::to_owned);
+
+            state.phase = OverlayPhase::Processing;
+            state.hint = match &model {
+                Some(model) => format!("Retranscrevendo a ultima captura com {model}..."),
+                None => String::from("Retranscrevendo a ultima captura..."),
+            };
+            state.error = None;
+            state.preview = None;
+            state.dictation_auto_stop = None;
+            state.dictation_model_override = model.clone();
+
+            let cancel = CancelToken::default();
+            let progress = ProgressTracker::default();
+            config.cancel = cancel.clone();
+            config.progress = progress.clone();
+            state.dictation_cancel = Some(cancel.clone());
+            state.dictation_progress = Some(progress);
+            state.dictation_stage = None;
+
+            Task::batch([
+                schedule_progress_tick(),
+                Task::perform(
+                    async move {
+                        let started = Instant::now();
+                        dictation_application::retranscribe_last(config, audio, model.as_deref())
+                            .map(|output| DictationOutput {
+                                latency_ms: Some(started.elapsed().as_millis() as u64),
+                                ..output
+                            })
+                    },
+                    move |result| {
+                        if cancel.is_cancelled() {
+                            Message::DictationDiscarded
+                        } else {
+                            Message::DictationFinished(result)
+                        }
+                    },
+                ),
+            ])
+        }
+        Message::DictationUsageLoaded(result) => {
+            match result {
+                Ok(stats) => state.dictation_usage = Some(stats),
+                Err(error) => log_warn!("[openvoice][dictation] usage unavailable: {error}"),
+            }
+            Task::none()
+        }
+        Message::RefreshOpenRouterModels => load_openrouter_models(state, true),
+        Message::CompareModels => {
+            if state.is_comparing_models {
+                return Task::none();
+            }
+            let Some(audio) = state.last_capture.clone() else {
+                state.error = Some(String::from("Grave um ditado antes de comparar modelos."));
+                return Task::none();
+            };
+            let compare_model = state.settings.openrouter_compare_model.trim().to_owned();
+            if compare_model.is_empty() {
+                state.error = Some(String::from(
+                    "Salve um modelo para comparar antes de rodar a comparacao.",
+                ));
+                return Task::none();
+            }
+            let config = match DictationConfig::from_settings(&state.settings) {
+                Ok(config) => config,
+                Err(error) => {
+                    state.error = Some(error);
+                    return Task::none();
+                }
+            };
+
+            state.is_comparing_models = true;
+            state.model_comparison = None;
+            let models = vec![state.settings.openrouter_model.clone(), compare_model];
+
+            Task::perform(
+                async move { dictation_application::compare_models(config, audio, models) },
+                Message::ModelsCompared,
+            )
+        }
+        Message::ModelsCompared(result) => {
+            state.is_comparing_models = false;
+            match result {
+                Ok(comparison) => state.model_comparison = Some(comparison),
+                Err(error) => state.error = Some(error.to_string()),
+            }
+            Task::none()
+        }
+        Message::OpenRouterModelsLoaded(result) => {
+            state.is_loading_openrouter_models = false;
+            match result {
+                Ok(models) => state.openrouter_models = models,
+                Err(error) => log_warn!("[openvoice][dictation] models unavailable: {error}"),
+            }
+            Task::none()
+        }
+        Message::DictationUsageRecorded(result) => {
+            let stats = match result {
+                Ok(stats) => stats,
+                Err(error) => {
+                    log_error!("[openvoice][dictation] failed to record usage: {error}");
+                    return Task::none();
+                }
+            };
+
+            let spent = stats.month.cost_usd;
+            state.dictation_usage = Some(stats);
+
+            match state.settings.dictation_monthly_budget() {
+                Some(budget) if spent > budget => {
+                    Task::done(Message::DictationBudgetExceeded(spent))
+                }
+                _ => Task::none(),
+            }
+        }
+        Message::DictationBudgetExceeded(spent) => {
+            let budget = state
+                .settings
+                .dictation_monthly_budget()
+                .unwrap_or_default();
+            log_warn!(
+                "[openvoice][dictation] monthly budget exceeded: US$ {spent:.2} of US$ {budget:.2}"
+            );
+
+            // Leave a take that already started alone; the next one warns.
+            if !state.is_dictation_recording() && !state.is_processing() {
+                state.hint = format!(
+                    "{} Orcamento mensal do ditado excedido: US$ {spent:.2} de US$ {budget:.2}.",
+                    state.hint
+                );
+            }
+            Task::none()
+        }
+        Message::DictationDiscarded => {
+            log_warn!("[openvoice][dictation] dropped the result of a cancelled transcription");
+            Task::none()
+        }
+        Message::HideFinishedHud(generation) => {
+            if generation != state.hud_hide_generation
+                || state.hud_interacted
+                || state.phase != OverlayPhase::Success
+                || state.main_view != MainView::Hud
+            {
+                return Task::none();
+            }
+
+            state.main_window_id.map_or_else(Task::none, |main_id| {
+                window::set_mode(main_id, window::Mode::Hidden)
+            })
+        }
+        Message::DictationFinished(result) => {
+            state.dictation_cancel = None;
+            state.dictation_progress = None;
+            state.dictation_stage = None;
+            let requested_model = state
+                .dictation_model_override
+                .take()
+                .unwrap_or_else(|| state.settings.openrouter_model.clone());
+
+            match result {
+                Ok(output) => {
+                    let output_mode = state.settings.dictation_output_mode();
+                    let session_takes = state.dictation_session.as_mut().map(|session| {
+                        session.push(&output.transcript);
+                        session.len()
+                    });
+                    state.phase = OverlayPhase::Success;
+                    state.hint = format!(
+                        "{:.1}s de audio do microfone transcritos e {}.",
+                        output.duration_seconds,
+                        match (session_takes, output_mode) {
+                            (Some(takes), _) => format!("adicionados a sessao ({takes} trecho(s))"),
+                            (None, DictationOutputMode::Clipboard) => {
+                                String::from("enviados para o clipboard")
+                            }
+                            (None, DictationOutputMode::Type) => {
+                                String::from("digitados no app em foco")
+                            }
+                            (None, DictationOutputMode::Uri) => {
+                                String::from("enviados para a URI configurada")
+                            }
+                            (None, DictationOutputMode::Submit) => {
+                                String::from("colados e enviados no app em foco")
+                            }
+                        }
+                    );
+                    if let Some(reason) = state.dictation_auto_stop.take() {
+                        state.hint = format!("{} {}", reason.hint(), state.hint);
+                    }
+                    if let Some(model) = output
+                        .model
+                        .as_deref()
+                        .filter(|model| *model != requested_model)
+                    {
+                        state.hint = format!("{} (modelo de fallback: {model})", state.hint);
+                    }
+                    state.error = None;
+                    state.preview = Some(output.preview());
+                    state.last_dictation = Some(output.result());
+                    state.last_dictation_stats = Some(output.stats());
+                    if output.clipped {
+                        state.hint = format!("{} {DICTATION_CLIPPING_HINT}", state.hint);
+                        state.preview = state
+                            .preview
+                            .take()
+                            .map(|preview| format!("[audio saturado] {preview}"));
+                    }
+
+                    let save_history = {
+                        let output = output.clone();
+                        let provider = state.settings.dictation_provider();
+                        let audio = state
+                            .settings
+                            .dictation_history_audio_retention()
+                            .zip(state.last_capture.clone())
+                            .zip(DictationConfig::from_settings(&state.settings).ok())
+                            .map(|((retention, audio), config)| {
+                                dictation_application::HistoryAudio {
+                                    audio,
+                                    config,
+                                    retention,
+                                }
+                            });
+                        Task::perform(
+                            async move { dictation_application::save_history(&output, provider, audio) },
+                            Message::HistoryEntrySaved,
+                        )
+                    };
+                    let notify_webhook = match state.settings.dictation_webhook_url() {
+                        Some(url) => {
+                            let (url, output) = (url.to_owned(), output.clone());
+                            Task::perform(
+                                async move { dictation_application::notify_webhook(&url, &output) },
+                                Message::DictationWebhookSent,
+                            )
+                        }
+                        None => Task::none(),
+                    };
+                    let run_hook = match state.settings.dictation_hook_command() {
+                        Some(command) => {
+                            let (command, output) = (command.to_owned(), output.clone());
+                            let timeout_secs = state.settings.dictation_hook_timeout_secs;
+                            Task::perform(
+                                async move {
+                                    dictation_application::run_hook(&command, timeout_secs, &output)
+                                },
+                                Message::DictationHookFinished,
+                            )
+                        }
+                        None => Task::none(),
+                    };
+                    let record_usage = output.usage.map_or_else(Task::none, |usage| {
+                        Task::perform(
+                            async move { dictation_application::record_usage(&usage) },
+                            Message::DictationUsageRecorded,
+                        )
+                    });
+
+                    let notify = if state.settings.dictation_notifications {
+                        let text = output.transcript.clone();
+                        let title = match session_takes {
+                            Some(_) => "Adicionado a sessao",
+                            None => output_mode.notification_title(),
+                        };
+                        Task::perform(
+                            async move { dictation_application::notify_finished(title, &text) },
+                            Message::DictationNotified,
+                        )
+                    } else {
+                        Task::none()
+                    };
+
+                    let text = match state.dictation_template() {
+                        Some(template) => template.apply(&output.transcript),
+                        None => output.transcript,
+                    };
+                    if session_takes.is_none() {
+                        // Only clipboard copies and typing can be undone;
+                        // the copy records itself once it knows what it
+                        // replaced.
+                        state.last_output =
+                            (output_mode == DictationOutputMode::Type).then(|| LastOutput::Typed {
+                                chars: text.chars().count(),
+                            });
+                    }
+                    let deliver = match output_mode {
+                        // The session document is delivered when it ends.
+                        _ if session_takes.is_some() => Task::none(),
+                        DictationOutputMode::Clipboard => copy_transcript(state, text),
+                        DictationOutputMode::Type => {
+                            let delay_ms = state.settings.dictation_typing_delay_ms;
+                            Task::perform(
+                                async move { dictation_application::type_transcript(&text, delay_ms) },
+                                Message::DictationDelivered,
+                            )
+                        }
+                        DictationOutputMode::Uri => {
+                            let template = state.settings.dictation_uri_template.clone();
+                            Task::perform(
+                                async move {
+                                    dictation_application::open_transcript_uri(&template, &text)
+                                },
+                                Message::DictationDelivered,
+                            )
+                        }
+                        DictationOutputMode::Submit => submit_transcript(state, text),
+                    };
+
+                    Task::batch([
+                        deliver,
+                        notify,
+                        save_history,
+                        run_hook,
+                        notify_webhook,
+                        record_usage,
+                        schedule_hud_hide(state),
+                    ])
+                }
+                Err(error) => {
+                    state.phase = OverlayPhase::Error;
+                    state.hint = format!(
+                        "A transcricao via {} falhou.",
+                        state.settings.dictation_provider()
+                    );
+                    if let Some(advice) = error.advice() {
+                        state.hint = format!("{} {advice}", state.hint);
+                    }
+                    state.error = Some(error.to_string());
+                    state.dictation_auto_stop = None;
+                    if state.settings.dictation_notifications {
+                        let error = error.to_string();
+                        Task::perform(
+                            async move { dictation_application::notify_failed(&error) },
+                            Message::DictationNotified,
+                        )
+                    } else {
+                        Task::none()
+                    }
+                }
+            }
+        }
+
+        Message::DictationHookFinished(Ok(())) => Task::none(),
+        Message::DictationHookFinished(Err(error)) => {
+            log_error!("[openvoice][dictation] hook failed: {error}");
+            if !state.is_dictation_recording() && !state.is_processing() {
+                state.hint = format!("{} {error}", state.hint);
+            }
+            Task::none()
+        }
+        Message::DictationNotified(Ok(())) => Task::none(),
+        Message::DictationNotified(Err(error)) => {
+            log_error!("[openvoice][dictation] notification failed: {error}");
+            Task::none()
+        }
+        Message::DictationWebhookSent(Ok(())) => Task::none(),
+        Message::DictationWebhookSent(Err(error)) => {
+            log_error!("[openvoice][dictation] webhook failed: {error}");
+            if !state.is_dictation_recording() && !state.is_processing() {
+                state.hint = format!("{} Webhook falhou: {error}", state.hint);
+            }
+            Task::none()
+        }
+        Message::DictationDelivered(Ok(())) => Task::none(),
+        Message::DictationDelivered(Err(error)) => {
+            log_error!("[openvoice][dictation] delivery failed: {error}");
+            let Some(text) =
+                state
+                    .last_dictation
+                    .as_ref()
+                    .map(|result| match state.dictation_template() {
+                        Some(template) => template.apply(&result.text),
+                        None => result.text.clone(),
+                    })
+            else {
+                return Task::none();
+            };
+
+            // Never lose the take: hand it to the clipboard instead.
+            state.phase = OverlayPhase::Error;
+            state.hint = match state.settings.dictation_output_mode() {
+                DictationOutputMode::Uri => String::from(
+                    "Nao consegui abrir a URI de saida; a transcricao foi copiada para o clipboard.",
+                ),
+                DictationOutputMode::Submit => {
+                    String::from("Nao consegui colar e enviar; a transcricao esta no clipboard.")
+                }
+                _ => String::from(
+                    "Nao consegui digitar a transcricao; ela foi copiada para o clipboard.",
+                ),
+            };
+            state.error = Some(error);
+            copy_transcript(state, text)
+        }
+        Message::ClipboardWriteChecked {
+            text,
+            attempt,
+            written,
+        } => {
+            if written {
+                if state.pending_output.as_deref() == Some(text.as_str()) {
+                    state.pending_output = None;
+                }
+                return Task::none();
+            }
+
+            let write_primary = state.settings.dictation_write_primary;
+            if attempt < CLIPBOARD_WRITE_ATTEMPTS {
+                log_warn!("[openvoice][clipboard] write attempt {attempt} did not stick; retrying");
+                return Task::perform(
+                    async move { std::thread::sleep(CLIPBOARD_RETRY_DELAY * attempt) },
+                    |_| (),
+                )
+                .then(move |_| verified_clipboard_write(text.clone(), write_primary, attempt + 1));
+            }
+
+            log_warn!(
+                "[openvoice][clipboard] write failed after {attempt} attempts; keeping the text pending"
+            );
+            state.pending_output = Some(text);
+            if !state.is_dictation_recording() && !state.is_processing() {
+                state.phase = OverlayPhase::Error;
+                state.hint = String::from(
+                    "O clipboard recusou a transcricao; ela ficou pendente. Use \"Copiar pendente\" ou openvoice --trigger flush-pending.",
+                );
+                state.error = Some(String::from(
+                    "Falha ao escrever no clipboard (comum no Wayland sem janela em foco).",
+                ));
+            }
+            Task::none()
+        }
+        Message::FlushPendingOutput => match state.pending_output.clone() {
+            Some(text) => {
+                state.hint = String::from("Copiando a transcricao pendente...");
+                write_clipboard(state, text)
+            }
+            None => {
+                state.hint = String::from("Nenhuma transcricao pendente.");
+                Task::none()
+            }
+        },
+        Message::DiscardPendingOutput => {
+            if state.pending_output.take().is_some() {
+                log_info!("[openvoice][clipboard] pending transcription discarded");
+                state.hint =
+                    String::from("Transcricao pendente descartada; ela segue no historico.");
+            }
+            Task::none()
+        }
+        Message::OutputRecorded(output) => {
+            state.last_output = Some(output);
+            Task::none()
+        }
+        Message::UndoLastOutput => match state.last_output.take() {
+            Some(LastOutput::Clipboard { previous, copied }) => {
+                iced::clipboard::read().then(move |current| {
+                    // The user copied something else since; leave it alone.
+                    if current.as_deref() != Some(copied.as_str()) {
+                        return Task::done(Message::LastOutputUndone(Err(String::from(
+                            "O clipboard mudou desde o ditado; nada foi desfeito.",
+                        ))));
+                    }
+                    iced::clipboard::write(previous.clone().unwrap_or_default())
+                        .chain(Task::done(Message::LastOutputUndone(Ok(()))))
+                })
+            }
+            Some(LastOutput::Typed { chars }) => Task::perform(
+                async move { dictation_application::erase_typed(chars) },
+                Message::LastOutputUndone,
+            ),
+            None => {
+                state.hint = String::from("Nada para desfazer.");
                 Task::none()
             }
         },
-        Message::OpenAiOAuthCallbackUrlChanged(value) => {
-            state.openai_callback_url_input = value;
+        Message::LastOutputUndone(result) => {
+            match result {
+                Ok(()) => state.hint = String::from("Ultimo ditado desfeito."),
+                Err(error) => {
+                    log_error!("[openvoice][dictation] undo failed: {error}");
+                    state.hint = error;
+                }
+            }
             Task::none()
         }
-        Message::SubmitOpenAiOAuthCallback => {
-            let Some(flow) = state.pending_openai_oauth.as_ref() else {
-                state.error = Some(String::from(
-                    "Nao existe login OAuth pendente para concluir manualmente.",
-                ));
+        Message::StartDictationSession => {
+            if state.dictation_session.is_none() {
+                state.dictation_session = Some(DictationSession::default());
+                state.hint = String::from(
+                    "Sessao de ditado iniciada: cada ditado sera somado ao documento ate encerrar.",
+                );
+            }
+            Task::none()
+        }
+        Message::EndDictationSession => {
+            let Some(session) = state.dictation_session.take() else {
                 return Task::none();
             };
-
-            let callback_url = state.openai_callback_url_input.trim().to_owned();
-            if callback_url.is_empty() {
-                state.error = Some(String::from("Cole a callback URL antes de continuar."));
+            if session.is_empty() {
+                state.hint = String::from("Sessao encerrada sem nenhum ditado.");
                 return Task::none();
             }
 
-            state.is_openai_authenticating = true;
-            state.settings_note = Some(String::from(
-                "Finalizando login ChatGPT a partir da callback URL...",
-            ));
-            state.error = None;
-
-            let flow_id = flow.flow_id.clone();
-            Task::perform(
-                async move { auth_application::complete_login(flow_id, callback_url) },
-                Message::OpenAiOAuthFinished,
-            )
+            state.hint = format!(
+                "Sessao encerrada: {} trecho(s) copiados para o clipboard.",
+                session.len()
+            );
+            let text = match state.settings.dictation_template() {
+                Some(template) => template.apply(&session.text()),
+                None => session.text(),
+            };
+            copy_transcript(state, text)
         }
-        Message::OpenAiOAuthFinished(result) => {
-            state.is_openai_authenticating = false;
-
+        Message::DiscardDictationSession => {
+            if state.dictation_session.take().is_some() {
+                state.hint = String::from("Sessao de ditado descartada.");
+            }
+            Task::none()
+        }
+        Message::RestoreClipboard { previous, copied } => {
+            iced::clipboard::read().then(move |current| {
+                // The user copied something else meanwhile; leave it alone.
+                if current.as_deref() != Some(copied.as_str()) {
+                    return Task::none();
+                }
+                log_info!("[openvoice][dictation] restoring previous clipboard");
+                iced::clipboard::write(previous.clone())
+            })
+        }
+        Message::DictationRecordingSaved(result) => {
             match result {
-                Ok(snapshot) => {
-                    state.pending_openai_oauth = None;
-                    state.openai_callback_url_input.clear();
-                    state.has_openai_credentials = snapshot.is_authenticated;
-                    state.openai_account_label = snapshot.account_label;
-                    state.settings_note = Some(String::from(
-                        "Login ChatGPT concluido para modelos OAuth futuros.",
-                    ));
-                    state.error = None;
-                    Task::none()
+                Ok(path) => {
+                    log_info!(
+                        "[openvoice][dictation] recording saved to {}",
+                        path.display()
+                    );
+                    state.last_recording_path = Some(path);
                 }
                 Err(error) => {
-                    state.settings_note = None;
-                    state.error = Some(error);
-                    Task::none()
+                    log_error!("[openvoice][dictation] failed to save recording: {error}");
                 }
             }
+
+            Task::none()
         }
-        Message::LogoutOpenAi => {
-            state.is_openai_authenticating = true;
-            state.pending_openai_oauth = None;
-            state.openai_callback_url_input.clear();
-            state.settings_note = Some(String::from("Removendo sessao ChatGPT do OpenVoice..."));
-            state.error = None;
+        Message::ExportLastDictationSrt => {
+            let Some(result) = state.last_dictation.clone() else {
+                return Task::none();
+            };
+            let recordings_dir = state.settings.dictation_recordings_dir.clone();
 
             Task::perform(
-                async move { auth_application::logout(CredentialStoreStrategy::Auto) },
-                Message::OpenAiLogoutFinished,
+                async move { dictation_application::export_srt(&result, &recordings_dir) },
+                Message::LastDictationSrtExported,
             )
         }
-        Message::OpenAiLogoutFinished(result) => {
-            state.is_openai_authenticating = false;
-
+        Message::LastDictationSrtExported(result) => {
             match result {
-                Ok(()) => {
-                    state.has_openai_credentials = false;
-                    state.openai_account_label = None;
-                    state.settings_note =
-                        Some(String::from("Sessao ChatGPT removida do OpenVoice."));
+                Ok(path) => {
+                    state.hint = format!("Legenda salva em {}.", path.display());
                     state.error = None;
-                    Task::none()
+                    return iced::clipboard::write(path.display().to_string());
                 }
                 Err(error) => {
-                    state.settings_note = None;
+                    log_error!("[openvoice][dictation] failed to export srt: {error}");
                     state.error = Some(error);
-                    Task::none()
                 }
             }
-        }
 
-        // ------------------------------------------------------------------ //
-        // Dictation (mic → OpenRouter)
-        // ------------------------------------------------------------------ //
-        Message::StartDictation => {
-            if !state.can_start_dictation() {
-                state.phase = OverlayPhase::Error;
-                state.error = Some(String::from(
-                    "Cadastre e salve sua OpenRouter API key antes de gravar.",
-                ));
-                return Task::none();
+            Task::none()
+        }
+        Message::CopyLastDictation => match &state.last_dictation {
+            Some(result) => {
+                state.hint = String::from("Ultimo ditado copiado de novo.");
+                write_clipboard(state, result.text.clone())
             }
-
-            // Auto-close Home → HUD before starting dictation
-            let mut morph_tasks = prepare_capture_ui(state);
-
-            match microphone::start_default_recording() {
-                Ok(recorder) => {
-                    let device_name = recorder
-                        .device_name()
-                        .unwrap_or("microfone padrao")
-                        .to_owned();
-
-                    state.recorder = Some(recorder);
-                    state.phase = OverlayPhase::Recording;
-                    state.hint =
-                        format!("REC MIC ativo em {device_name}. Clique no microfone para parar.");
-                    state.error = None;
-                    state.preview = None;
-
-                    if state.passthrough_enabled {
-                        state.passthrough_enabled = false;
-
-                        if let Some(window_id) = state.main_window_id {
-                            morph_tasks.push(window::disable_mouse_passthrough(window_id));
-                            morph_tasks
-                                .push(window::set_level(window_id, window::Level::AlwaysOnTop));
-                        }
-                    }
-
-                    if morph_tasks.is_empty() {
-                        Task::none()
-                    } else {
-                        Task::batch(morph_tasks)
-                    }
-                }
-                Err(error) => {
-                    state.phase = OverlayPhase::Error;
-                    state.hint = String::from("Nao consegui iniciar a captura do microfone.");
-                    state.error = Some(error);
-                    if morph_tasks.is_empty() {
-                        Task::none()
-                    } else {
-                        Task::batch(morph_tasks)
-                    }
-                }
+            // Nothing dictated since launch; fall back to the history.
+            None => Task::perform(
+                async { dictation_application::last_transcription() },
+                Message::LastDictationLoaded,
+            ),
+        },
+        Message::LastDictationLoaded(result) => match result {
+            Ok(Some(text)) => {
+                state.hint = String::from("Ultimo ditado copiado de novo.");
+                write_clipboard(state, text)
             }
-        }
-        Message::StopDictation => {
-            let Some(recorder) = state.recorder.take() else {
+            Ok(None) => {
+                state.hint = String::from("Nenhum ditado para copiar ainda.");
+                Task::none()
+            }
+            Err(error) => {
+                log_warn!("[openvoice][dictation] last transcription unavailable: {error}");
+                state.error = Some(error);
+                Task::none()
+            }
+        },
+        Message::CopyLastRecordingPath => {
+            let Some(path) = state.last_recording_path.as_ref() else {
                 return Task::none();
             };
 
-            match recorder.finish() {
-                Ok(capture_track) => {
-                    let Ok(config) = DictationConfig::from_settings(&state.settings) else {
-                        state.phase = OverlayPhase::Error;
-                        state.hint = String::from("OpenRouter nao configurado.");
-                        state.error = Some(String::from(
-                            "Cadastre e salve a OpenRouter API key antes de gravar.",
-                        ));
-                        return Task::none();
-                    };
-
-                    state.phase = OverlayPhase::Processing;
-                    state.hint = String::from("Enviando audio do microfone para o OpenRouter...");
-                    state.error = None;
+            iced::clipboard::write(path.display().to_string())
+        }
+        Message::PlayLastRecording => {
+            if state.is_playing_last_capture {
+                return Task::none();
+            }
+            let Some(audio) = state.last_capture.clone() else {
+                return Task::none();
+            };
 
-                    Task::perform(
-                        async move {
-                            dictation_application::transcribe_capture(config, capture_track.audio)
-                        },
-                        Message::DictationFinished,
-                    )
-                }
-                Err(error) => {
-                    state.phase = OverlayPhase::Error;
-                    state.hint =
-                        String::from("A captura do microfone foi interrompida antes do envio.");
-                    state.error = Some(error);
-                    Task::none()
-                }
+            state.is_playing_last_capture = true;
+            Task::perform(
+                async move { audio.load().and_then(audio_playback::play) },
+                Message::LastRecordingPlayed,
+            )
+        }
+        Message::FeedbackTonePlayed(result) => {
+            if let Err(error) = result {
+                log_error!("[openvoice][dictation] failed to play feedback tone: {error}");
             }
+
+            Task::none()
         }
-        Message::DictationFinished(result) => match result {
-            Ok(output) => {
-                state.phase = OverlayPhase::Success;
-                state.hint = format!(
-                    "{:.1}s de audio do microfone transcritos e enviados para o clipboard.",
-                    output.duration_seconds
-                );
-                state.error = None;
-                state.preview = Some(output.preview());
+        Message::LastRecordingPlayed(result) => {
+            state.is_playing_last_capture = false;
 
-                Task::batch([
-                    iced::clipboard::write(output.transcript.clone()),
-                    iced::clipboard::write_primary(output.transcript),
-                ])
-            }
-            Err(error) => {
-                state.phase = OverlayPhase::Error;
-                state.hint = String::from("A transcricao via OpenRouter falhou.");
+            if let Err(error) = result {
+                log_error!("[openvoice][dictation] failed to play last recording: {error}");
                 state.error = Some(error);
-                Task::none()
             }
-        },
+
+            Task::none()
+        }
 
         // ------------------------------------------------------------------ //
         // Realtime transcription (system audio → OpenAI Realtime API)
@@ -781,7 +2632,7 @@ pub fn update(state: &mut Overlay, message: Message) -> Task<Message> {
                     let message = format!(
                         "Falha ao registrar no_screen_share da janela {role} no Hyprland: {error}"
                     );
-                    eprintln!("[openvoice][hyprland] {message}");
+                    log_error!("[openvoice][hyprland] {message}");
 
                     if state.error.is_none() {
                         state.error = Some(message);
@@ -984,7 +2835,7 @@ pub fn update(state: &mut Overlay, message: Message) -> Task<Message> {
                     if let Some(session_id) = finalized_session_id {
                         if state.has_openai_credentials {
                             state.title_gen_failed_ids.insert(session_id);
-                            eprintln!(
+                            log_debug!(
                                 "[openvoice][title] dispatching title generation for session_id={session_id}"
                             );
                             return Task::perform(
@@ -996,12 +2847,12 @@ pub fn update(state: &mut Overlay, message: Message) -> Task<Message> {
                                 Message::LiveSessionTitleGenerated,
                             );
                         } else {
-                            eprintln!(
+                            log_warn!(
                                 "[openvoice][title] skipped: no OAuth credentials (has_openai_credentials=false)"
                             );
                         }
                     } else {
-                        eprintln!("[openvoice][title] skipped: finalized_session_id was None");
+                        log_warn!("[openvoice][title] skipped: finalized_session_id was None");
                     }
 
                     Task::none()
@@ -1016,7 +2867,7 @@ pub fn update(state: &mut Overlay, message: Message) -> Task<Message> {
         Message::LiveSessionTitleGenerated(result) => {
             match result {
                 Ok((session_id, title)) => {
-                    eprintln!(
+                    log_info!(
                         "[openvoice][title] title generated for session {session_id}: {title}"
                     );
                     // Update the title in our cached sessions list if present
@@ -1030,7 +2881,7 @@ pub fn update(state: &mut Overlay, message: Message) -> Task<Message> {
                     state.hint = format!("Titulo gerado: {title}");
                 }
                 Err(err) => {
-                    eprintln!("[openvoice][title] title generation failed: {err}");
+                    log_error!("[openvoice][title] title generation failed: {err}");
                     // Title generation is best-effort; don't block on errors.
                     // NOTE: We cannot extract session_id from the error string alone,
                     // so the circuit breaker is applied before dispatching (see below).
@@ -1048,7 +2899,7 @@ pub fn update(state: &mut Overlay, message: Message) -> Task<Message> {
                     let session_id = session.id;
                     // Mark as attempted so we don't retry on failure
                     state.title_gen_failed_ids.insert(session_id);
-                    eprintln!("[openvoice][title] chaining title gen for session_id={session_id}");
+                    log_debug!("[openvoice][title] chaining title gen for session_id={session_id}");
                     return Task::perform(
                         async move {
                             live_transcription_application::generate_session_title(session_id)
@@ -1086,7 +2937,7 @@ pub fn update(state: &mut Overlay, message: Message) -> Task<Message> {
                 {
                     let session_id = session.id;
                     state.title_gen_failed_ids.insert(session_id);
-                    eprintln!(
+                    log_debug!(
                         "[openvoice][title] retroactive title gen for session_id={session_id}"
                     );
                     return Task::perform(
@@ -1106,6 +2957,11 @@ pub fn update(state: &mut Overlay, message: Message) -> Task<Message> {
             Task::none()
         }
 
+        Message::HistorySearchChanged(query) => {
+            state.history_search_query = query;
+            Task::none()
+        }
+
         Message::SessionSelected(id) => {
             // id == 0 is a sentinel for "deselect"
             if id == 0 || state.selected_session_id == Some(id) {
@@ -1180,17 +3036,139 @@ pub fn update(state: &mut Overlay, message: Message) -> Task<Message> {
                 Message::SessionDeleted,
             )
         }
-
-        Message::SessionDeleted(result) => match result {
-            Ok(session_id) => {
-                state.sessions_list.retain(|s| s.id != session_id);
-                Task::none()
-            }
-            Err(err) => {
-                state.error = Some(format!("Erro ao remover sessao: {err}"));
-                Task::none()
+
+        Message::SessionDeleted(result) => match result {
+            Ok(session_id) => {
+                state.sessions_list.retain(|s| s.id != session_id);
+                Task::none()
+            }
+            Err(err) => {
+                state.error = Some(format!("Erro ao remover sessao: {err}"));
+                Task::none()
+            }
+        },
+
+        // ------------------------------------------------------------------ //
+        // Dictation history
+        // ------------------------------------------------------------------ //
+        Message::HistoryLoaded(result) => {
+            state.history_loading = false;
+            match result {
+                Ok(entries) => {
+                    state.history_entries = entries;
+                    state.history_error = None;
+                }
+                Err(error) => state.history_error = Some(error),
+            }
+            Task::none()
+        }
+        Message::HistoryEntrySaved(result) => {
+            match result {
+                Ok(entry) => state.history_entries.insert(0, entry),
+                Err(error) => log_warn!("[openvoice][dictation] history not saved: {error}"),
+            }
+            Task::none()
+        }
+        Message::CopyHistoryEntry(id) => {
+            let Some(entry) = state.history_entries.iter().find(|entry| entry.id == id) else {
+                return Task::none();
+            };
+            write_clipboard(state, entry.text.clone())
+        }
+        Message::PlayHistoryEntry(id) => {
+            if state.history_playing.is_some() {
+                return Task::none();
+            }
+
+            state.history_playing = Some(id);
+            Task::perform(
+                async move { dictation_application::play_history_entry(id) },
+                Message::HistoryEntryPlayed,
+            )
+        }
+        Message::HistoryEntryPlayed(result) => {
+            state.history_playing = None;
+            if let Err(error) = result {
+                log_error!("[openvoice][dictation] failed to play history audio: {error}");
+                state.history_error = Some(error);
+            }
+            Task::none()
+        }
+        Message::RetranscribeHistoryEntry(id) => {
+            if state.history_retranscribing.is_some() {
+                return Task::none();
+            }
+            let config = match DictationConfig::from_settings(&state.settings) {
+                Ok(config) => config,
+                Err(error) => {
+                    state.history_error = Some(error);
+                    return Task::none();
+                }
+            };
+            let model = state
+                .settings
+                .dictation_retranscribe_model()
+                .map(str::to_owned);
+
+            state.history_retranscribing = Some(id);
+            state.history_error = None;
+            Task::perform(
+                async move { dictation_application::retranscribe_entry(config, id, model.as_deref()) },
+                Message::HistoryEntryRetranscribed,
+            )
+        }
+        Message::HistoryEntryRetranscribed(result) => {
+            state.history_retranscribing = None;
+            match result {
+                Ok(entry) => {
+                    if let Some(slot) = state
+                        .history_entries
+                        .iter_mut()
+                        .find(|slot| slot.id == entry.id)
+                    {
+                        *slot = entry;
+                    }
+                }
+                Err(error) => {
+                    log_error!("[openvoice][dictation] re-transcription failed: {error}");
+                    state.history_error = Some(match error.advice() {
+                        Some(advice) => format!("{error} {advice}"),
+                        None => error.to_string(),
+                    });
+                }
+            }
+            Task::none()
+        }
+        Message::DeleteHistoryEntry(id) => Task::perform(
+            async move { dictation_application::delete_history_entry(id) },
+            Message::HistoryEntryDeleted,
+        ),
+        Message::HistoryEntryDeleted(result) => {
+            match result {
+                Ok(id) => state.history_entries.retain(|entry| entry.id != id),
+                Err(error) => state.history_error = Some(error),
+            }
+            Task::none()
+        }
+        Message::ClearHistory => {
+            if !state.history_confirm_clear {
+                state.history_confirm_clear = true;
+                return Task::none();
+            }
+
+            state.history_confirm_clear = false;
+            Task::perform(
+                async { dictation_application::clear_history() },
+                Message::HistoryCleared,
+            )
+        }
+        Message::HistoryCleared(result) => {
+            match result {
+                Ok(()) => state.history_entries.clear(),
+                Err(error) => state.history_error = Some(error),
             }
-        },
+            Task::none()
+        }
 
         // ------------------------------------------------------------------ //
         // Copilot
@@ -1248,6 +3226,7 @@ pub fn update(state: &mut Overlay, message: Message) -> Task<Message> {
                         async move {
                             dictation_application::transcribe_capture(config, track.audio)
                                 .map(|output| output.transcript)
+                                .map_err(String::from)
                         },
                         Message::CopilotListenTranscribed,
                     )
@@ -1453,149 +3432,733 @@ pub fn update(state: &mut Overlay, message: Message) -> Task<Message> {
             state.copilot_stream = None;
             state.copilot_error = None;
 
-            Task::perform(
-                async move { copilot_application::load_saved_thread(thread_id) },
-                Message::CopilotThreadLoaded,
-            )
-        }
-        Message::CopilotThreadLoaded(result) => match result {
-            Ok(loaded) => {
-                state.copilot_thread_id = Some(loaded.summary.id);
-                state.selected_copilot_thread_id = Some(loaded.summary.id);
-                state.copilot_mode = loaded.summary.mode;
-                state.copilot_messages = loaded.messages;
-                state.copilot_error = None;
-                Task::none()
+            Task::perform(
+                async move { copilot_application::load_saved_thread(thread_id) },
+                Message::CopilotThreadLoaded,
+            )
+        }
+        Message::CopilotThreadLoaded(result) => match result {
+            Ok(loaded) => {
+                state.copilot_thread_id = Some(loaded.summary.id);
+                state.selected_copilot_thread_id = Some(loaded.summary.id);
+                state.copilot_mode = loaded.summary.mode;
+                state.copilot_messages = loaded.messages;
+                state.copilot_error = None;
+                Task::none()
+            }
+            Err(error) => {
+                state.copilot_error = Some(error);
+                Task::none()
+            }
+        },
+        Message::OpenCopilotThreadInOverlay(thread_id) => {
+            state.copilot_thread_id = Some(thread_id);
+            state.selected_copilot_thread_id = Some(thread_id);
+            state.copilot_busy = false;
+            state.copilot_stream = None;
+            state.copilot_error = None;
+
+            let load_task = Task::perform(
+                async move { copilot_application::load_saved_thread(thread_id) },
+                Message::CopilotThreadLoaded,
+            );
+
+            let open_task = open_copilot_view(state);
+            Task::batch([load_task, open_task])
+        }
+
+        Message::NewCopilotThread => {
+            state.copilot_thread_id = None;
+            state.selected_copilot_thread_id = None;
+            state.copilot_stream = None;
+            state.copilot_busy = false;
+            state.copilot_error = None;
+            state.copilot_messages.clear();
+            state.copilot_input = text_editor::Content::new();
+            state.copilot_screenshot = None;
+            Task::none()
+        }
+        Message::CopilotMarkdownLinkClicked(_uri) => Task::none(),
+        Message::CopyCopilotAnswer => {
+            let Some(answer) = state
+                .copilot_messages
+                .iter()
+                .rev()
+                .find(|message| message.role == CopilotRole::Assistant)
+                .map(|message| message.content.clone())
+            else {
+                return Task::none();
+            };
+
+            Task::batch([
+                iced::clipboard::write(answer.clone()),
+                iced::clipboard::write_primary(answer),
+            ])
+        }
+
+        Message::DeleteCopilotThread(thread_id) => {
+            // If currently viewing this thread, clear it
+            if state.copilot_thread_id == Some(thread_id) {
+                state.copilot_thread_id = None;
+                state.selected_copilot_thread_id = None;
+                state.copilot_messages.clear();
+                state.copilot_error = None;
+            } else if state.selected_copilot_thread_id == Some(thread_id) {
+                state.selected_copilot_thread_id = None;
+            }
+
+            Task::perform(
+                async move {
+                    copilot_infrastructure::delete_thread(thread_id)?;
+                    Ok(thread_id)
+                },
+                Message::CopilotThreadDeleted,
+            )
+        }
+
+        Message::CopilotThreadDeleted(result) => match result {
+            Ok(thread_id) => {
+                state.copilot_threads.retain(|t| t.id != thread_id);
+                Task::none()
+            }
+            Err(err) => {
+                state.copilot_error = Some(format!("Erro ao remover thread: {err}"));
+                Task::none()
+            }
+        },
+
+        // ------------------------------------------------------------------ //
+        // Window behavior
+        // ------------------------------------------------------------------ //
+        Message::TogglePassthrough => {
+            if state.main_view == MainView::Home {
+                return Task::none();
+            }
+
+            if !state.passthrough_enabled
+                && (state.is_recording() || state.is_processing() || state.is_live_transcribing())
+            {
+                state.error = Some(String::from(
+                    "Finalize a captura atual antes de habilitar passthrough.",
+                ));
+                return Task::none();
+            }
+
+            state.passthrough_enabled = !state.passthrough_enabled;
+            state.hint = if state.passthrough_enabled {
+                String::from("Passthrough ativo. Pressione P para voltar ao modo interativo.")
+            } else {
+                String::from("Modo interativo ativo. O HUD pode receber cliques novamente.")
+            };
+
+            state.main_window_id.map_or_else(Task::none, |window_id| {
+                let passthrough_task = if state.passthrough_enabled {
+                    window::enable_mouse_passthrough(window_id)
+                } else {
+                    window::disable_mouse_passthrough(window_id)
+                };
+
+                Task::batch([
+                    passthrough_task,
+                    window::set_level(window_id, window::Level::AlwaysOnTop),
+                ])
+            })
+        }
+
+        Message::Quit => {
+            if (state.is_dictation_recording() || state.is_processing()) && !state.quit_confirm {
+                state.quit_confirm = true;
+                state.error = Some(String::from(
+                    "Ha um ditado em andamento. Saia de novo para fechar; o audio fica salvo em disco.",
+                ));
+                return Task::none();
+            }
+
+            match save_unsent_take(state) {
+                Some(Ok(path)) => {
+                    log_info!(
+                        "[openvoice][dictation] unsent take saved to {}",
+                        path.display()
+                    );
+                }
+                Some(Err(error)) => {
+                    log_error!("[openvoice][dictation] failed to save unsent take: {error}");
+                }
+                None => {}
+            }
+
+            if let Some(session) = state.live_transcription.take() {
+                session.stop();
+            }
+
+            state.copilot_listen_recorder = None;
+            state.dictation_pre_roll = None;
+
+            if let Some(registration) = state.shortcut_registration.take() {
+                shortcuts_application::release_shortcut(&registration);
+            }
+            if let Err(error) = shortcuts_application::swap_cancel_shortcut(
+                state.bound_cancel_shortcut.take(),
+                None,
+            ) {
+                log_error!("[openvoice][shortcuts] failed to unbind cancel: {error}");
+            }
+            if let Some((_, keys)) = state.shortcut_chord.take()
+                && let Err(error) = shortcuts_application::swap_chord(keys, Vec::new())
+            {
+                log_error!("[openvoice][shortcuts] failed to unbind the sequence keys: {error}");
+            }
+
+            iced::exit()
+        }
+    }
+}
+
+/// Maps a global shortcut edge to dictation start/stop. Toggle mode only
+/// reacts to key-down; hold mode records while the combination is held;
+/// tap-or-hold toggles on a tap and stops on the release of a long press.
+fn on_shortcut(state: &mut Overlay, mut trigger: ShortcutTrigger) -> Task<Message> {
+    if trigger.event == ShortcutEvent::Tap {
+        if !state
+            .shortcut_double_tap
+            .tap(trigger.action, Instant::now())
+        {
+            return Task::none();
+        }
+        trigger.event = ShortcutEvent::Pressed;
+    }
+    if trigger.event == ShortcutEvent::Leader {
+        return start_shortcut_chord(state, trigger.action);
+    }
+
+    match trigger.action {
+        ShortcutAction::Dictation => {
+            let recording = state.is_dictation_recording();
+            let mode = state.settings.dictation_shortcut_mode();
+
+            match trigger.event {
+                ShortcutEvent::Pressed if recording && mode != ShortcutMode::Hold => {
+                    state.dictation_shortcut_pressed_at = None;
+                    Task::done(Message::StopDictation)
+                }
+                ShortcutEvent::Pressed if !recording && !state.is_processing() => {
+                    state.dictation_shortcut_pressed_at = Some(Instant::now());
+                    Task::done(Message::StartDictation)
+                }
+                ShortcutEvent::Released if recording => {
+                    // A take the shortcut didn't start counts as a tap.
+                    let held = state
+                        .dictation_shortcut_pressed_at
+                        .take()
+                        .map(|pressed_at| pressed_at.elapsed())
+                        .unwrap_or_default();
+                    if mode.stops_on_release(held, state.settings.dictation_hold_threshold()) {
+                        Task::done(Message::StopDictation)
+                    } else {
+                        Task::none()
+                    }
+                }
+                _ => Task::none(),
+            }
+        }
+        ShortcutAction::Restart => match trigger.event {
+            ShortcutEvent::Pressed => Task::done(Message::RestartDictation),
+            ShortcutEvent::Released | ShortcutEvent::Tap | ShortcutEvent::Leader => Task::none(),
+        },
+        ShortcutAction::Monitor => match trigger.event {
+            ShortcutEvent::Pressed => Task::done(Message::ToggleDictationMonitor),
+            ShortcutEvent::Released | ShortcutEvent::Tap | ShortcutEvent::Leader => Task::none(),
+        },
+        ShortcutAction::Cancel => match trigger.event {
+            ShortcutEvent::Pressed if state.is_dictation_recording() => {
+                Task::done(Message::CancelRecording)
+            }
+            ShortcutEvent::Pressed => Task::done(Message::CancelTranscription),
+            ShortcutEvent::Released | ShortcutEvent::Tap | ShortcutEvent::Leader => Task::none(),
+        },
+        ShortcutAction::Retranscribe => match trigger.event {
+            ShortcutEvent::Pressed => Task::done(Message::RetranscribeLast),
+            ShortcutEvent::Released | ShortcutEvent::Tap | ShortcutEvent::Leader => Task::none(),
+        },
+        ShortcutAction::CopyLast => match trigger.event {
+            ShortcutEvent::Pressed => Task::done(Message::CopyLastDictation),
+            ShortcutEvent::Released | ShortcutEvent::Tap | ShortcutEvent::Leader => Task::none(),
+        },
+        ShortcutAction::FlushPending => match trigger.event {
+            ShortcutEvent::Pressed => Task::done(Message::FlushPendingOutput),
+            ShortcutEvent::Released | ShortcutEvent::Tap | ShortcutEvent::Leader => Task::none(),
+        },
+        ShortcutAction::History => match trigger.event {
+            ShortcutEvent::Pressed => {
+                state.history_search_query.clear();
+                Task::done(Message::SwitchHomeTab(HomeTab::History))
+                    .chain(widget::operation::focus(history_ui::SEARCH_INPUT))
+            }
+            ShortcutEvent::Released | ShortcutEvent::Tap | ShortcutEvent::Leader => Task::none(),
+        },
+        ShortcutAction::Undo => match trigger.event {
+            ShortcutEvent::Pressed => Task::done(Message::UndoLastOutput),
+            ShortcutEvent::Released | ShortcutEvent::Tap | ShortcutEvent::Leader => Task::none(),
+        },
+        ShortcutAction::Template(index) => match trigger.event {
+            ShortcutEvent::Pressed if state.is_dictation_recording() => {
+                Task::done(Message::StopDictation)
+            }
+            ShortcutEvent::Pressed if !state.is_processing() => {
+                Task::done(Message::StartTemplateDictation(index))
+            }
+            _ => Task::none(),
+        },
+        ShortcutAction::Session => match trigger.event {
+            ShortcutEvent::Pressed if state.dictation_session.is_some() => {
+                Task::done(Message::EndDictationSession)
+            }
+            ShortcutEvent::Pressed => Task::done(Message::StartDictationSession),
+            ShortcutEvent::Released | ShortcutEvent::Tap | ShortcutEvent::Leader => Task::none(),
+        },
+        ShortcutAction::PauseShortcuts => match trigger.event {
+            ShortcutEvent::Pressed => Task::done(Message::ToggleShortcutsPaused),
+            ShortcutEvent::Released | ShortcutEvent::Tap | ShortcutEvent::Leader => Task::none(),
+        },
+    }
+}
+
+/// Keeps the pre-roll stream in line with settings while no take is active.
+fn sync_dictation_pre_roll(state: &mut Overlay) {
+    if !state.settings.dictation_pre_roll_enabled {
+        state.dictation_pre_roll = None;
+        return;
+    }
+
+    if state.dictation_pre_roll.is_none() && state.recorder.is_none() {
+        state.dictation_pre_roll = dictation_application::start_pre_roll(&state.settings);
+    }
+}
+
+/// Opens the monitor output for the running take, if there is one.
+fn start_dictation_monitor(state: &mut Overlay) {
+    let Some(recorder) = state.recorder.as_ref() else {
+        return;
+    };
+
+    if state.dictation_monitor.is_some() {
+        return;
+    }
+
+    match recorder.start_monitor() {
+        Ok(monitor) => state.dictation_monitor = Some(monitor),
+        Err(error) => {
+            log_warn!("[openvoice][dictation] monitor unavailable: {error}");
+            state.dictation_monitor_enabled = false;
+            state.hint = format!("Retorno do microfone indisponivel: {error}");
+        }
+    }
+}
+
+/// Stops the take without transcribing or keeping it.
+fn discard_dictation(state: &mut Overlay, reason: DictationAutoStop) -> Task<Message> {
+    if !drop_dictation_take(state) {
+        return Task::none();
+    }
+
+    state.phase = OverlayPhase::Idle;
+    state.hint = format!("{} Gravacao descartada.", reason.hint());
+    state.error = None;
+    state.preview = None;
+
+    play_feedback_tone(state, FeedbackTone::Stop)
+}
+
+/// Shortcut label for a key pressed while a shortcut field is recording;
+/// `None` while only modifiers are down.
+fn captured_shortcut(
+    key: &Key,
+    latin: Option<char>,
+    location: keyboard::Location,
+    modifiers: keyboard::Modifiers,
+) -> Option<Result<String, String>> {
+    let key = match key.as_ref() {
+        Key::Named(
+            Named::Control
+            | Named::Shift
+            | Named::Alt
+            | Named::AltGraph
+            | Named::Super
+            | Named::Meta
+            | Named::Hyper,
+        ) => return None,
+        Key::Named(Named::Enter) if location == keyboard::Location::Numpad => {
+            String::from("NumpadEnter")
+        }
+        // Named keys debug-print as their W3C name, which the parser knows.
+        Key::Named(named) => format!("{named:?}"),
+        Key::Character(digit)
+            if location == keyboard::Location::Numpad
+                && digit.len() == 1
+                && digit.chars().all(|ch| ch.is_ascii_digit()) =>
+        {
+            format!("Numpad{digit}")
+        }
+        Key::Character(value) => latin.map_or_else(|| value.to_owned(), String::from),
+        Key::Unidentified => return Some(Err(String::from("Tecla nao reconhecida."))),
+    };
+    let held = [
+        (modifiers.logo(), "Super"),
+        (modifiers.control(), "Ctrl"),
+        (modifiers.alt(), "Alt"),
+        (modifiers.shift(), "Shift"),
+    ];
+    let combination = held
+        .iter()
+        .filter(|(down, _)| *down)
+        .map(|(_, label)| *label)
+        .chain(std::iter::once(key.as_str()))
+        .collect::<Vec<_>>()
+        .join("+");
+
+    Some(Shortcut::parse(&combination).map(|shortcut| shortcut.label()))
+}
+
+/// Binds the cancel shortcut while a take is recording or transcribing and
+/// frees the key again once it is over. Swaps run one at a time: a change
+/// made while one runs is picked up when its `CancelShortcutSwapped` lands.
+fn sync_cancel_shortcut(state: &mut Overlay) -> Task<Message> {
+    if state.cancel_shortcut_swapping {
+        return Task::none();
+    }
+
+    let active = state.is_dictation_recording() || state.dictation_cancel.is_some();
+    let wanted = state
+        .shortcut_registration
+        .as_ref()
+        .filter(|_| active)
+        .and_then(|registration| registration.shortcut_for(ShortcutAction::Cancel))
+        .cloned();
+    if wanted == state.bound_cancel_shortcut {
+        return Task::none();
+    }
+
+    let previous = std::mem::replace(&mut state.bound_cancel_shortcut, wanted.clone());
+    state.cancel_shortcut_swapping = true;
+    Task::perform(
+        async move { shortcuts_application::swap_cancel_shortcut(previous, wanted) },
+        Message::CancelShortcutSwapped,
+    )
+}
+
+/// Binds the second keys of every sequence led by `action`'s leader for
+/// [`CHORD_WINDOW`], replacing those of a chord still open.
+fn start_shortcut_chord(state: &mut Overlay, action: ShortcutAction) -> Task<Message> {
+    let follows = state
+        .shortcut_registration
+        .as_ref()
+        .map(|registration| registration.chord_follows(action))
+        .unwrap_or_default();
+    if follows.is_empty() {
+        return Task::none();
+    }
+
+    let keys = follows
+        .iter()
+        .map(|(shortcut, _)| shortcut.clone())
+        .collect();
+    let previous = state
+        .shortcut_chord
+        .replace((Instant::now(), keys))
+        .map(|(_, previous)| previous)
+        .unwrap_or_default();
+
+    Task::batch([
+        Task::perform(
+            async move { shortcuts_application::swap_chord(previous, follows) },
+            Message::ShortcutChordSwapped,
+        ),
+        Task::perform(async { std::thread::sleep(CHORD_WINDOW) }, |_| {
+            Message::ShortcutChordExpired
+        }),
+    ])
+}
+
+/// Frees the second keys of an open chord.
+fn end_shortcut_chord(state: &mut Overlay) -> Task<Message> {
+    let Some((_, previous)) = state.shortcut_chord.take() else {
+        return Task::none();
+    };
+
+    Task::perform(
+        async move { shortcuts_application::swap_chord(previous, Vec::new()) },
+        Message::ShortcutChordSwapped,
+    )
+}
+
+/// Closes the running take's streams and throws the audio away. Returns
+/// `false` when nothing was recording.
+fn drop_dictation_take(state: &mut Overlay) -> bool {
+    let Some(recorder) = state.recorder.take() else {
+        return false;
+    };
+
+    state.dictation_monitor = None;
+    state.dictation_stream = None;
+    if let Err(error) = recorder.finish() {
+        log_error!("[openvoice][dictation] discarded take failed to close: {error}");
+    }
+    if let Some(system_recorder) = state.dictation_system_recorder.take()
+        && let Err(error) = system_recorder.finish()
+    {
+        log_error!("[openvoice][dictation] discarded system audio failed to close: {error}");
+    }
+
+    state.dictation_level = AudioLevel::default();
+    state.dictation_waveform.clear();
+    state.dictation_started_at = None;
+    state.dictation_auto_stop = None;
+    sync_dictation_pre_roll(state);
+    true
+}
+
+/// Writes the take being recorded, or the one still being transcribed, to
+/// disk so quitting does not lose it. Uses the recordings folder, or
+/// `recordings` in the data dir when none is configured; a take in flight
+/// is skipped when the folder already got its copy on submit.
+fn save_unsent_take(state: &mut Overlay) -> Option<Result<PathBuf, String>> {
+    let recordings_dir = audio_storage::resolve_user_dir(&state.settings.dictation_recordings_dir);
+    let audio = match state.recorder.take() {
+        Some(recorder) => {
+            if let Some(system_recorder) = state.dictation_system_recorder.take() {
+                let _ = system_recorder.finish();
             }
-            Err(error) => {
-                state.copilot_error = Some(error);
-                Task::none()
+            match recorder.finish() {
+                Ok(track) => track.audio,
+                Err(error) => return Some(Err(error)),
             }
+        }
+        None if state.is_processing() && recordings_dir.is_none() => state.last_capture.clone()?,
+        None => return None,
+    };
+
+    let dir = match recordings_dir {
+        Some(dir) => dir,
+        None => match audio_storage::data_dir() {
+            Ok(dir) => dir.join("recordings"),
+            Err(error) => return Some(Err(error)),
         },
-        Message::OpenCopilotThreadInOverlay(thread_id) => {
-            state.copilot_thread_id = Some(thread_id);
-            state.selected_copilot_thread_id = Some(thread_id);
-            state.copilot_busy = false;
-            state.copilot_stream = None;
-            state.copilot_error = None;
+    };
 
-            let load_task = Task::perform(
-                async move { copilot_application::load_saved_thread(thread_id) },
-                Message::CopilotThreadLoaded,
-            );
+    Some(audio_storage::save_recording(&audio, &dir))
+}
 
-            let open_task = open_copilot_view(state);
-            Task::batch([load_task, open_task])
-        }
+/// Sends a finished take for transcription, keeping it for playback and
+/// saving a copy when a recordings folder is configured.
+fn submit_dictation(state: &mut Overlay, audio: SpooledAudio) -> Task<Message> {
+    let stream = state.dictation_stream.take();
+    let provider = state.settings.dictation_provider();
+    let Ok(mut config) = DictationConfig::from_settings(&state.settings) else {
+        state.phase = OverlayPhase::Error;
+        state.hint = format!("{provider} nao configurado.");
+        state.error = Some(format!(
+            "Cadastre e salve a {provider} API key antes de gravar."
+        ));
+        return Task::none();
+    };
 
-        Message::NewCopilotThread => {
-            state.copilot_thread_id = None;
-            state.selected_copilot_thread_id = None;
-            state.copilot_stream = None;
-            state.copilot_busy = false;
-            state.copilot_error = None;
-            state.copilot_messages.clear();
-            state.copilot_input = text_editor::Content::new();
-            state.copilot_screenshot = None;
-            Task::none()
-        }
-        Message::CopilotMarkdownLinkClicked(_uri) => Task::none(),
-        Message::CopyCopilotAnswer => {
-            let Some(answer) = state
-                .copilot_messages
-                .iter()
-                .rev()
-                .find(|message| message.role == CopilotRole::Assistant)
-                .map(|message| message.content.clone())
-            else {
-                return Task::none();
-            };
+    let sending = match stream {
+        Some(_) => format!("Finalizando a transcricao ao vivo do {provider}..."),
+        None => format!("Enviando audio do microfone para o {provider}..."),
+    };
+    state.phase = OverlayPhase::Processing;
+    state.hint = match state.dictation_auto_stop {
+        Some(reason) => format!("{} {sending}", reason.hint()),
+        None => sending,
+    };
+    state.error = None;
 
-            Task::batch([
-                iced::clipboard::write(answer.clone()),
-                iced::clipboard::write_primary(answer),
-            ])
-        }
+    state.last_capture = Some(audio.clone());
+    let clipped = state.dictation_clipping;
+    let cancel = CancelToken::default();
+    config.cancel = cancel.clone();
+    state.dictation_cancel = Some(cancel.clone());
+    let mut tasks = Vec::new();
 
-        Message::DeleteCopilotThread(thread_id) => {
-            // If currently viewing this thread, clear it
-            if state.copilot_thread_id == Some(thread_id) {
-                state.copilot_thread_id = None;
-                state.selected_copilot_thread_id = None;
-                state.copilot_messages.clear();
-                state.copilot_error = None;
-            } else if state.selected_copilot_thread_id == Some(thread_id) {
-                state.selected_copilot_thread_id = None;
-            }
+    // A live session already has the text; only uploads have stages worth
+    // showing.
+    if stream.is_none() {
+        let progress = ProgressTracker::default();
+        config.progress = progress.clone();
+        state.dictation_progress = Some(progress);
+        state.dictation_stage = None;
+        tasks.push(schedule_progress_tick());
+    }
 
-            Task::perform(
-                async move {
-                    copilot_infrastructure::delete_thread(thread_id)?;
-                    Ok(thread_id)
-                },
-                Message::CopilotThreadDeleted,
-            )
-        }
+    if let Some(dir) = audio_storage::resolve_user_dir(&state.settings.dictation_recordings_dir) {
+        let audio = audio.clone();
+        tasks.push(Task::perform(
+            async move { audio_storage::save_recording(&audio, &dir) },
+            Message::DictationRecordingSaved,
+        ));
+    }
 
-        Message::CopilotThreadDeleted(result) => match result {
-            Ok(thread_id) => {
-                state.copilot_threads.retain(|t| t.id != thread_id);
-                Task::none()
+    tasks.push(Task::perform(
+        async move {
+            let started = Instant::now();
+            match stream {
+                Some(stream) => dictation_application::finish_streaming(config, stream, audio),
+                None => dictation_application::transcribe_spooled(config, audio),
             }
-            Err(err) => {
-                state.copilot_error = Some(format!("Erro ao remover thread: {err}"));
-                Task::none()
+            .map(|output| DictationOutput {
+                clipped,
+                latency_ms: Some(started.elapsed().as_millis() as u64),
+                ..output
+            })
+        },
+        move |result| {
+            if cancel.is_cancelled() {
+                Message::DictationDiscarded
+            } else {
+                Message::DictationFinished(result)
             }
         },
+    ));
 
-        // ------------------------------------------------------------------ //
-        // Window behavior
-        // ------------------------------------------------------------------ //
-        Message::TogglePassthrough => {
-            if state.main_view == MainView::Home {
-                return Task::none();
-            }
+    Task::batch(tasks)
+}
 
-            if !state.passthrough_enabled
-                && (state.is_recording() || state.is_processing() || state.is_live_transcribing())
-            {
-                state.error = Some(String::from(
-                    "Finalize a captura atual antes de habilitar passthrough.",
-                ));
-                return Task::none();
-            }
+fn load_history(state: &mut Overlay) -> Task<Message> {
+    state.history_loading = true;
+    state.history_confirm_clear = false;
+    Task::perform(
+        async { dictation_application::get_history() },
+        Message::HistoryLoaded,
+    )
+}
 
-            state.passthrough_enabled = !state.passthrough_enabled;
-            state.hint = if state.passthrough_enabled {
-                String::from("Passthrough ativo. Pressione P para voltar ao modo interativo.")
-            } else {
-                String::from("Modo interativo ativo. O HUD pode receber cliques novamente.")
-            };
+fn load_dictation_usage() -> Task<Message> {
+    Task::perform(
+        async { dictation_application::get_usage_stats() },
+        Message::DictationUsageLoaded,
+    )
+}
 
-            state.main_window_id.map_or_else(Task::none, |window_id| {
-                let passthrough_task = if state.passthrough_enabled {
-                    window::enable_mouse_passthrough(window_id)
-                } else {
-                    window::disable_mouse_passthrough(window_id)
-                };
+fn load_openrouter_models(state: &mut Overlay, refresh: bool) -> Task<Message> {
+    if state.is_loading_openrouter_models {
+        return Task::none();
+    }
 
-                Task::batch([
-                    passthrough_task,
-                    window::set_level(window_id, window::Level::AlwaysOnTop),
-                ])
-            })
-        }
+    state.is_loading_openrouter_models = true;
+    let api_key = state.settings.openrouter_api_key.clone();
+    let base_url = state.settings.openrouter_base_url.clone();
 
-        Message::Quit => {
-            if let Some(session) = state.live_transcription.take() {
-                session.stop();
-            }
+    Task::perform(
+        async move { dictation_application::list_models(&api_key, &base_url, refresh) },
+        Message::OpenRouterModelsLoaded,
+    )
+}
 
-            state.copilot_listen_recorder = None;
+fn play_feedback_tone(state: &Overlay, tone: FeedbackTone) -> Task<Message> {
+    if !state.settings.dictation_sound_feedback {
+        return Task::none();
+    }
 
-            iced::exit()
+    Task::perform(
+        async move { audio_playback::play(tone.render()) },
+        Message::FeedbackTonePlayed,
+    )
+}
+
+/// Copies a transcription to the clipboard and, when enabled, to the
+/// primary selection.
+fn write_clipboard(state: &Overlay, text: String) -> Task<Message> {
+    verified_clipboard_write(text, state.settings.dictation_write_primary, 1)
+}
+
+/// Writes `text` and reads the clipboard back, since a refused write is
+/// otherwise silent; the result decides between done, retry and pending.
+fn verified_clipboard_write(text: String, write_primary: bool, attempt: u32) -> Task<Message> {
+    let mut copy = iced::clipboard::write(text.clone());
+    if write_primary {
+        copy = Task::batch([copy, iced::clipboard::write_primary(text.clone())]);
+    }
+
+    copy.chain(
+        iced::clipboard::read().map(move |current| Message::ClipboardWriteChecked {
+            written: text.is_empty() || current.as_deref() == Some(text.as_str()),
+            text: text.clone(),
+            attempt,
+        }),
+    )
+}
+
+/// Copies a transcription, first snapshotting the clipboard so the copy can
+/// be undone and, when the user wants it, restored after a while.
+fn copy_transcript(state: &Overlay, text: String) -> Task<Message> {
+    let delay = state.settings.dictation_clipboard_restore_delay();
+    let write_primary = state.settings.dictation_write_primary;
+
+    iced::clipboard::read().then(move |previous| {
+        let copy = Task::batch([
+            Task::done(Message::OutputRecorded(LastOutput::Clipboard {
+                previous: previous.clone(),
+                copied: text.clone(),
+            })),
+            verified_clipboard_write(text.clone(), write_primary, 1),
+        ]);
+        match (delay, previous.filter(|previous| *previous != text)) {
+            (Some(delay), Some(previous)) => {
+                let copied = text.clone();
+                copy.chain(Task::perform(
+                    async move { std::thread::sleep(delay) },
+                    move |_| Message::RestoreClipboard {
+                        previous: previous.clone(),
+                        copied: copied.clone(),
+                    },
+                ))
+            }
+            _ => copy,
         }
+    })
+}
+
+/// Copies a transcription and, only once the clipboard is confirmed to hold
+/// it, pastes and presses Enter; a refused write takes the retry path
+/// instead of sending whatever was on the clipboard before.
+fn submit_transcript(state: &Overlay, text: String) -> Task<Message> {
+    let mut copy = iced::clipboard::write(text.clone());
+    if state.settings.dictation_write_primary {
+        copy = Task::batch([copy, iced::clipboard::write_primary(text.clone())]);
     }
+
+    copy.chain(iced::clipboard::read().then(move |current| {
+        if current.as_deref() == Some(text.as_str()) {
+            Task::perform(
+                async { dictation_application::submit_transcript() },
+                Message::DictationDelivered,
+            )
+        } else {
+            Task::done(Message::ClipboardWriteChecked {
+                text: text.clone(),
+                attempt: 1,
+                written: false,
+            })
+        }
+    }))
+}
+
+fn schedule_progress_tick() -> Task<Message> {
+    Task::perform(
+        async {
+            std::thread::sleep(Duration::from_millis(PROGRESS_TICK_MS));
+        },
+        |_| Message::DictationProgressTick,
+    )
+}
+
+fn schedule_dictation_tick() -> Task<Message> {
+    Task::perform(
+        async {
+            std::thread::sleep(Duration::from_millis(DICTATION_TICK_MS));
+        },
+        |_| Message::DictationTick,
+    )
 }
 
 fn push_live_delta(target: &mut String, delta: &str) {
@@ -1795,6 +4358,43 @@ fn close_copilot_view(state: &mut Overlay) -> Task<Message> {
     }
 }
 
+/// Applies `change` to the stored settings and rebuilds the runtime copy
+/// from them. Returns the stored settings, the ones to persist.
+fn change_settings(state: &mut Overlay, change: impl FnOnce(&mut AppSettings)) -> AppSettings {
+    change(&mut state.stored_settings);
+    state.settings = settings_application::runtime_settings(&state.stored_settings);
+    state.stored_settings.clone()
+}
+
+/// Announces the settings fields that changed since `previous`, so every
+/// window reacts to one `ConfigChanged` instead of reloading settings.json.
+fn config_changed(previous: &AppSettings, state: &Overlay) -> Task<Message> {
+    let diff = previous.diff(&state.settings);
+    if diff.is_empty() {
+        Task::none()
+    } else {
+        Task::done(Message::ConfigChanged(diff))
+    }
+}
+
+/// Hides the HUD a while after a take it was raised for, unless it should
+/// stay open until dismissed.
+fn schedule_hud_hide(state: &mut Overlay) -> Task<Message> {
+    state.hud_hide_generation += 1;
+    let Some(delay) = state
+        .settings
+        .hud_hide_delay()
+        .filter(|_| state.settings.dictation_show_window)
+    else {
+        return Task::none();
+    };
+
+    let generation = state.hud_hide_generation;
+    Task::perform(async move { std::thread::sleep(delay) }, move |_| {
+        Message::HideFinishedHud(generation)
+    })
+}
+
 fn prepare_capture_ui(state: &mut Overlay) -> Vec<Task<Message>> {
     let mut tasks = Vec::new();
     state.copilot_listen_recorder = None;
@@ -1996,9 +4596,59 @@ fn clear_pending_copilot_placeholder(state: &mut Overlay) {
 
 #[cfg(test)]
 mod tests {
-    use super::{build_copilot_context, push_live_delta, resolve_completed_transcript};
-    use crate::app::state::boot;
+    use super::{
+        build_copilot_context, captured_shortcut, change_settings, push_live_delta,
+        resolve_completed_transcript, schedule_hud_hide, sync_cancel_shortcut, update,
+    };
+    use crate::app::message::Message;
+    use crate::app::state::{OverlayPhase, boot};
     use crate::modules::copilot::domain::CopilotMode;
+    use crate::modules::dictation::domain::CancelToken;
+    use crate::modules::shortcuts::application::ShortcutRegistration;
+    use crate::modules::shortcuts::domain::{Shortcut, ShortcutAction, ShortcutMode};
+    use iced::keyboard::{Key, Location, Modifiers, key::Named};
+
+    #[test]
+    fn captures_key_combinations_as_shortcut_labels() {
+        let ctrl_shift = Modifiers::CTRL | Modifiers::SHIFT;
+
+        assert_eq!(
+            captured_shortcut(
+                &Key::Character("D".into()),
+                Some('d'),
+                Location::Standard,
+                ctrl_shift
+            ),
+            Some(Ok(String::from("Ctrl+Shift+D")))
+        );
+        assert_eq!(
+            captured_shortcut(
+                &Key::Named(Named::ArrowUp),
+                None,
+                Location::Standard,
+                Modifiers::LOGO
+            ),
+            Some(Ok(String::from("Super+Up")))
+        );
+        assert_eq!(
+            captured_shortcut(
+                &Key::Character("7".into()),
+                None,
+                Location::Numpad,
+                Modifiers::empty()
+            ),
+            Some(Ok(String::from("Numpad7")))
+        );
+        assert_eq!(
+            captured_shortcut(
+                &Key::Named(Named::Shift),
+                None,
+                Location::Left,
+                Modifiers::SHIFT
+            ),
+            None
+        );
+    }
 
     #[test]
     fn appends_delta_without_double_leading_space() {
@@ -2014,6 +4664,33 @@ mod tests {
         assert_eq!(transcript, "partial text");
     }
 
+    #[test]
+    fn quit_asks_again_while_transcribing() {
+        let (mut state, _task) = boot();
+        state.phase = OverlayPhase::Processing;
+
+        let _ = update(&mut state, Message::Quit);
+
+        assert!(state.quit_confirm);
+        assert!(state.error.is_some());
+    }
+
+    #[test]
+    fn reloads_settings_edited_outside_the_app() {
+        let (mut state, _task) = boot();
+        let unchanged = Box::new(state.stored_settings.clone());
+        let _ = update(&mut state, Message::ConfigReloaded(Ok(unchanged)));
+        assert_eq!(state.settings_note, None);
+
+        let mut edited = state.stored_settings.clone();
+        edited.dictation_language = String::from("en");
+        let _ = update(&mut state, Message::ConfigReloaded(Ok(Box::new(edited))));
+
+        assert_eq!(state.settings.dictation_language, "en");
+        assert_eq!(state.settings_form.dictation_language, "en");
+        assert!(state.settings_note.is_some());
+    }
+
     #[test]
     fn copilot_context_prefers_live_segments() {
         let (mut state, _task) = boot();
@@ -2030,4 +4707,91 @@ mod tests {
             vec![String::from("recent segment")]
         );
     }
+
+    #[test]
+    fn keeps_the_hud_up_once_the_user_touches_it() {
+        let (mut state, _task) = boot();
+        let main_id = iced::window::Id::unique();
+        state.main_window_id = Some(main_id);
+        state.settings.hud_keep_open = false;
+
+        let _ = update(
+            &mut state,
+            Message::WindowInteracted(iced::window::Id::unique()),
+        );
+        assert!(!state.hud_interacted);
+
+        let _ = update(&mut state, Message::WindowInteracted(main_id));
+        assert!(state.hud_interacted);
+
+        let _ = schedule_hud_hide(&mut state);
+        let _ = schedule_hud_hide(&mut state);
+        assert_eq!(state.hud_hide_generation, 2);
+    }
+
+    #[test]
+    fn applies_config_changes_to_every_window() {
+        let (mut state, _task) = boot();
+        state.copilot_mode = CopilotMode::Interview;
+
+        let mut next = state.settings.clone();
+        next.dictation_language = String::from("en");
+        let diff = state.settings.diff(&next);
+        assert_eq!(diff.fields, vec![String::from("dictation_language")]);
+        state.settings = next;
+        let _ = update(&mut state, Message::ConfigChanged(diff));
+        assert_eq!(state.copilot_mode, CopilotMode::Interview);
+
+        let mut next = state.settings.clone();
+        next.copilot_default_mode = String::from("meeting");
+        let diff = state.settings.diff(&next);
+        state.settings = next;
+        let _ = update(&mut state, Message::ConfigChanged(diff));
+        assert_eq!(state.copilot_mode, CopilotMode::Meeting);
+    }
+
+    #[test]
+    fn swaps_the_cancel_shortcut_one_at_a_time() {
+        let (mut state, _task) = boot();
+        let escape = Shortcut::parse("Escape").expect("shortcut");
+        state.shortcut_registration = Some(ShortcutRegistration {
+            mode: ShortcutMode::Toggle,
+            binds: vec![(escape.clone(), ShortcutAction::Cancel)],
+        });
+
+        state.dictation_cancel = Some(CancelToken::default());
+        let _ = sync_cancel_shortcut(&mut state);
+        assert_eq!(state.bound_cancel_shortcut, Some(escape.clone()));
+        assert!(state.cancel_shortcut_swapping);
+
+        // The take ends before the bind lands: the unbind waits for it.
+        state.dictation_cancel = None;
+        let _ = sync_cancel_shortcut(&mut state);
+        assert_eq!(state.bound_cancel_shortcut, Some(escape));
+
+        let _ = update(&mut state, Message::CancelShortcutSwapped(Ok(())));
+        assert_eq!(state.bound_cancel_shortcut, None);
+        assert!(state.cancel_shortcut_swapping);
+    }
+
+    #[test]
+    fn saves_the_stored_settings_without_env_overrides() {
+        let (mut state, _task) = boot();
+        state.stored_settings.openrouter_api_key = String::from("sk-or-stored");
+        state.settings = state.stored_settings.clone();
+        state
+            .settings
+            .apply_env_overrides(|name| {
+                (name == "OPENVOICE_API_KEY").then(|| String::from("sk-or-env"))
+            })
+            .expect("applied");
+        assert_eq!(state.settings.openrouter_api_key, "sk-or-env");
+
+        let persisted = change_settings(&mut state, |settings| {
+            settings.log_level = String::from("debug");
+        });
+        assert_eq!(persisted.openrouter_api_key, "sk-or-stored");
+        assert_eq!(persisted.log_level, "debug");
+        assert_eq!(state.settings.log_level, "debug");
+    }
 }
@@ -1,7 +1,9 @@
+#[macro_use]
+mod support;
+
 mod app;
 mod modules;
 mod platform;
-mod support;
 mod ui;
 
 fn main() -> iced::Result {
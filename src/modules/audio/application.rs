@@ -1,6 +1,6 @@
 #![allow(dead_code)]
 
-use crate::modules::audio::domain::CaptureSession;
+use crate::modules::audio::domain::{CaptureOverrides, CaptureSession};
 use crate::modules::audio::infrastructure::{microphone, storage, system};
 
 pub struct ActiveCaptureSession {
@@ -19,7 +19,7 @@ impl ActiveCaptureSession {
 pub fn start_capture_session() -> Result<ActiveCaptureSession, String> {
     let session_id = storage::generate_session_id();
     let started_at_unix_ms = storage::unix_timestamp_ms();
-    let microphone = microphone::start_default_recording()?;
+    let microphone = microphone::start_default_recording(CaptureOverrides::default())?;
 
     let system = match system::start_default_recording() {
         Ok(system) => system,
@@ -44,7 +44,7 @@ pub fn finish_capture_session(session: ActiveCaptureSession) -> Result<CaptureSe
         microphone,
         system,
     } = session;
-    let microphone_track = microphone.finish()?;
+    let microphone_track = microphone.finish()?.load()?;
     let system_track = system.finish()?;
     let finished_at_unix_ms = storage::unix_timestamp_ms();
 
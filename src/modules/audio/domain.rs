@@ -1,8 +1,31 @@
 #![allow(dead_code)]
 
 use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::ops::Range;
 use std::path::PathBuf;
 
+/// RMS level above which a microphone buffer is treated as speech.
+pub const VOICE_ACTIVITY_RMS_THRESHOLD: f32 = 0.02;
+/// Quietest level shown by the input meter; anything below reads as empty.
+pub const METER_FLOOR_DB: f32 = -60.0;
+/// Milliseconds of input folded into one bar of the live waveform.
+pub const WAVEFORM_BUCKET_MS: u32 = 25;
+/// Bars a waveform tap holds between reads, so an undrained tap (e.g. the
+/// pre-roll stream) stays bounded.
+const WAVEFORM_PENDING_LIMIT: usize = 256;
+/// Magnitude treated as full scale when looking for clipping.
+pub const CLIP_THRESHOLD: f32 = 0.999;
+/// Consecutive full-scale frames that count as one clipping event; single
+/// peaks touching the limit are normal and not reported.
+pub const CLIP_RUN_FRAMES: usize = 3;
+/// Window the silence splitter measures loudness over.
+const SILENCE_SPLIT_WINDOW_MS: u32 = 50;
+const FEEDBACK_TONE_SAMPLE_RATE: u32 = 48_000;
+const FEEDBACK_NOTE_MS: u32 = 70;
+const FEEDBACK_FADE_MS: u32 = 5;
+const FEEDBACK_TONE_AMPLITUDE: f32 = 0.2;
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum AudioSourceKind {
     Microphone,
@@ -36,6 +59,294 @@ impl CapturedAudio {
     }
 }
 
+pub fn rms_level(samples: &[f32]) -> f32 {
+    if samples.is_empty() {
+        return 0.0;
+    }
+
+    let energy = samples.iter().map(|sample| sample * sample).sum::<f32>();
+    (energy / samples.len() as f32).sqrt()
+}
+
+pub fn is_voice_activity(samples: &[f32]) -> bool {
+    rms_level(samples) >= VOICE_ACTIVITY_RMS_THRESHOLD
+}
+
+/// Cuts mono samples into consecutive ranges of `min_secs..=max_secs`,
+/// each ending at the quietest window in that span so words are not split
+/// between chunks. The last range holds whatever is left.
+pub fn split_on_silence(
+    samples: &[f32],
+    sample_rate: u32,
+    min_secs: u32,
+    max_secs: u32,
+) -> Vec<Range<usize>> {
+    let rate = sample_rate.max(1) as usize;
+    let window = (rate * SILENCE_SPLIT_WINDOW_MS as usize / 1_000).max(1);
+    let min_len = (rate * min_secs as usize).max(window);
+    let max_len = (rate * max_secs as usize).max(min_len);
+    let mut ranges = Vec::new();
+    let mut start = 0;
+
+    while samples.len() - start > max_len {
+        let end = (start + min_len..=start + max_len - window)
+            .step_by(window)
+            .min_by(|a, b| {
+                rms_level(&samples[*a..*a + window])
+                    .total_cmp(&rms_level(&samples[*b..*b + window]))
+            })
+            .map_or(start + max_len, |quietest| quietest + window / 2);
+
+        ranges.push(start..end);
+        start = end;
+    }
+
+    if start < samples.len() {
+        ranges.push(start..samples.len());
+    }
+
+    ranges
+}
+
+/// Input level over a short window, both in linear amplitude (0.0..=1.0).
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct AudioLevel {
+    pub rms: f32,
+    pub peak: f32,
+}
+
+impl AudioLevel {
+    /// RMS mapped onto a dBFS scale between `METER_FLOOR_DB` and 0 dB,
+    /// which tracks perceived loudness better than the linear value.
+    pub fn meter_fraction(self) -> f32 {
+        db_fraction(self.rms)
+    }
+}
+
+/// Linear amplitude mapped onto `METER_FLOOR_DB..=0` dBFS as 0.0..=1.0.
+pub fn db_fraction(amplitude: f32) -> f32 {
+    if amplitude <= 0.0 {
+        return 0.0;
+    }
+
+    let db = 20.0 * amplitude.log10();
+    ((db - METER_FLOOR_DB) / -METER_FLOOR_DB).clamp(0.0, 1.0)
+}
+
+/// Accumulates levels across audio callbacks until the meter reads them.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LevelWindow {
+    energy: f64,
+    sample_count: usize,
+    peak: f32,
+}
+
+impl LevelWindow {
+    pub fn push(&mut self, samples: &[f32]) {
+        for sample in samples {
+            self.energy += f64::from(sample * sample);
+            self.peak = self.peak.max(sample.abs());
+        }
+        self.sample_count += samples.len();
+    }
+
+    pub fn take(&mut self) -> AudioLevel {
+        let window = std::mem::take(self);
+
+        if window.sample_count == 0 {
+            return AudioLevel::default();
+        }
+
+        AudioLevel {
+            rms: (window.energy / window.sample_count as f64).sqrt() as f32,
+            peak: window.peak,
+        }
+    }
+}
+
+/// Folds interleaved input into one peak per `WAVEFORM_BUCKET_MS`, for the
+/// scrolling waveform drawn while recording.
+#[derive(Debug, Clone, Default)]
+pub struct WaveformTap {
+    bucket_len: usize,
+    bucket_peak: f32,
+    bucket_filled: usize,
+    peaks: VecDeque<f32>,
+}
+
+impl WaveformTap {
+    pub fn new(sample_rate: u32, channels: u16) -> Self {
+        let frames = (u64::from(sample_rate) * u64::from(WAVEFORM_BUCKET_MS) / 1_000) as usize;
+
+        Self {
+            bucket_len: (frames * usize::from(channels.max(1))).max(1),
+            ..Self::default()
+        }
+    }
+
+    pub fn push(&mut self, samples: &[f32]) {
+        if self.bucket_len == 0 {
+            return;
+        }
+
+        for sample in samples {
+            self.bucket_peak = self.bucket_peak.max(sample.abs());
+            self.bucket_filled += 1;
+
+            if self.bucket_filled == self.bucket_len {
+                self.peaks.push_back(self.bucket_peak);
+                self.bucket_peak = 0.0;
+                self.bucket_filled = 0;
+            }
+        }
+
+        let overflow = self.peaks.len().saturating_sub(WAVEFORM_PENDING_LIMIT);
+        self.peaks.drain(..overflow);
+    }
+
+    /// Completed bars since the previous call, oldest first.
+    pub fn take(&mut self) -> Vec<f32> {
+        self.peaks.drain(..).collect()
+    }
+}
+
+/// Counts sustained runs of full-scale frames, a sign that the input gain
+/// is too high for the speaker.
+#[derive(Debug, Clone, Default)]
+pub struct ClipDetector {
+    channels: usize,
+    run: usize,
+    pending: u32,
+    total: u32,
+}
+
+impl ClipDetector {
+    pub fn new(channels: u16) -> Self {
+        Self {
+            channels: usize::from(channels.max(1)),
+            ..Self::default()
+        }
+    }
+
+    pub fn push(&mut self, samples: &[f32]) {
+        for frame in samples.chunks(self.channels.max(1)) {
+            if frame.iter().any(|sample| sample.abs() >= CLIP_THRESHOLD) {
+                self.run += 1;
+
+                if self.run == CLIP_RUN_FRAMES {
+                    self.pending += 1;
+                    self.total += 1;
+                }
+            } else {
+                self.run = 0;
+            }
+        }
+    }
+
+    /// Clipping events since the previous call.
+    pub fn take(&mut self) -> u32 {
+        std::mem::take(&mut self.pending)
+    }
+
+    pub fn total(&self) -> u32 {
+        self.total
+    }
+
+    pub fn reset(&mut self) {
+        *self = Self {
+            channels: self.channels,
+            ..Self::default()
+        };
+    }
+}
+
+/// Short two-note cue confirming that dictation started or stopped, for
+/// when the HUD is not on screen.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FeedbackTone {
+    Start,
+    Stop,
+}
+
+impl FeedbackTone {
+    fn notes(self) -> [f64; 2] {
+        match self {
+            Self::Start => [660.0, 880.0],
+            Self::Stop => [880.0, 660.0],
+        }
+    }
+
+    /// Renders the cue as mono audio, fading every note in and out so the
+    /// speaker does not click.
+    pub fn render(self) -> CapturedAudio {
+        let rate = FEEDBACK_TONE_SAMPLE_RATE;
+        let note_len = (rate * FEEDBACK_NOTE_MS / 1_000) as usize;
+        let fade_len = (rate * FEEDBACK_FADE_MS / 1_000) as usize;
+        let mut samples = Vec::with_capacity(note_len * 2);
+
+        for frequency in self.notes() {
+            samples.extend((0..note_len).map(|index| {
+                let t = index as f64 / f64::from(rate);
+                let edge = index.min(note_len - 1 - index);
+                let envelope = (edge as f32 / fade_len as f32).min(1.0);
+
+                (2.0 * std::f64::consts::PI * frequency * t).sin() as f32
+                    * FEEDBACK_TONE_AMPLITUDE
+                    * envelope
+            }));
+        }
+
+        CapturedAudio {
+            samples,
+            sample_rate: rate,
+            channels: 1,
+        }
+    }
+}
+
+/// Optional replacements for the system default input device and its
+/// stream config. `None` keeps whatever the system picks.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CaptureOverrides {
+    /// Input device name, as listed by [`list_input_devices`].
+    ///
+    /// [`list_input_devices`]: crate::modules::audio::infrastructure::microphone::list_input_devices
+    pub device: Option<String>,
+    pub sample_rate: Option<u32>,
+    pub buffer_frames: Option<u32>,
+}
+
+/// Fixed-size history of the most recent interleaved samples. Capacity is
+/// rounded to whole frames so draining never splits a frame across channels.
+#[derive(Debug, Clone, Default)]
+pub struct PreRollBuffer {
+    samples: VecDeque<f32>,
+    capacity: usize,
+}
+
+impl PreRollBuffer {
+    pub fn new(sample_rate: u32, channels: u16, duration_ms: u32) -> Self {
+        let frames = (u64::from(sample_rate) * u64::from(duration_ms) / 1_000) as usize;
+        let capacity = frames * usize::from(channels.max(1));
+
+        Self {
+            samples: VecDeque::with_capacity(capacity),
+            capacity,
+        }
+    }
+
+    pub fn push(&mut self, input: &[f32]) {
+        self.samples.extend(input.iter().copied());
+
+        let overflow = self.samples.len().saturating_sub(self.capacity);
+        self.samples.drain(..overflow);
+    }
+
+    pub fn drain(&mut self) -> Vec<f32> {
+        self.samples.drain(..).collect()
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct CapturedTrack {
     pub source: AudioSourceKind,
@@ -139,7 +450,11 @@ impl SessionMetadata {
 
 #[cfg(test)]
 mod tests {
-    use super::{AudioSourceKind, CaptureFormat, CapturedAudio, TrackArtifact};
+    use super::{
+        AudioLevel, AudioSourceKind, CaptureFormat, CapturedAudio, ClipDetector, FeedbackTone,
+        LevelWindow, PreRollBuffer, TrackArtifact, WaveformTap, is_voice_activity, rms_level,
+        split_on_silence,
+    };
     use std::path::PathBuf;
 
     #[test]
@@ -153,6 +468,125 @@ mod tests {
         assert_eq!(capture.duration_seconds(), 1.0);
     }
 
+    #[test]
+    fn computes_rms_level() {
+        assert_eq!(rms_level(&[]), 0.0);
+        assert!((rms_level(&[0.5, -0.5, 0.5, -0.5]) - 0.5).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn separates_voice_from_background_noise() {
+        assert!(!is_voice_activity(&[0.001, -0.002, 0.001, 0.0]));
+        assert!(is_voice_activity(&[0.2, -0.3, 0.25, -0.1]));
+    }
+
+    #[test]
+    fn splits_long_audio_at_the_quietest_gap() {
+        // 10 Hz "audio": loud everywhere except a pause at 2.5s..2.8s.
+        let samples: Vec<f32> = (0..60)
+            .map(|index| if (25..28).contains(&index) { 0.0 } else { 0.5 })
+            .collect();
+
+        let ranges = split_on_silence(&samples, 10, 2, 4);
+
+        assert_eq!(ranges.first().map(|range| range.start), Some(0));
+        assert_eq!(ranges.last().map(|range| range.end), Some(60));
+        assert!((25..28).contains(&ranges[0].end));
+        assert!(ranges.windows(2).all(|pair| pair[0].end == pair[1].start));
+        assert!(ranges.iter().all(|range| range.len() <= 40));
+    }
+
+    #[test]
+    fn accumulates_level_window_until_taken() {
+        let mut window = LevelWindow::default();
+        window.push(&[0.5, -0.5]);
+        window.push(&[0.5, -0.8]);
+
+        let level = window.take();
+
+        assert!((level.peak - 0.8).abs() < f32::EPSILON);
+        assert!(level.rms > 0.5 && level.rms < 0.8);
+        assert_eq!(window.take(), AudioLevel::default());
+    }
+
+    #[test]
+    fn maps_level_to_meter_fraction() {
+        let silent = AudioLevel::default();
+        let full_scale = AudioLevel {
+            rms: 1.0,
+            peak: 1.0,
+        };
+        let minus_thirty_db = AudioLevel {
+            rms: 10_f32.powf(-1.5),
+            peak: 0.1,
+        };
+
+        assert_eq!(silent.meter_fraction(), 0.0);
+        assert_eq!(full_scale.meter_fraction(), 1.0);
+        assert!((minus_thirty_db.meter_fraction() - 0.5).abs() < 1e-4);
+    }
+
+    #[test]
+    fn pre_roll_keeps_only_latest_whole_frames() {
+        // 1 kHz stereo, 2 ms => 2 frames => 4 samples.
+        let mut pre_roll = PreRollBuffer::new(1_000, 2, 2);
+
+        pre_roll.push(&[0.1, 0.1, 0.2, 0.2]);
+        pre_roll.push(&[0.3, 0.3]);
+
+        assert_eq!(pre_roll.drain(), vec![0.2, 0.2, 0.3, 0.3]);
+        assert!(pre_roll.drain().is_empty());
+    }
+
+    #[test]
+    fn reports_sustained_clipping_once_per_run() {
+        let mut detector = ClipDetector::new(2);
+
+        // A lone full-scale peak is not clipping.
+        detector.push(&[1.0, 0.1, 0.2, 0.1]);
+        assert_eq!(detector.take(), 0);
+
+        // Left channel pinned for four frames: one event.
+        detector.push(&[1.0, 0.3, -1.0, 0.3, 1.0, 0.3, 1.0, 0.3, 0.5, 0.5]);
+        detector.push(&[-1.0, -1.0, -1.0, -1.0, -1.0, -1.0]);
+
+        assert_eq!(detector.take(), 2);
+        assert_eq!(detector.take(), 0);
+        assert_eq!(detector.total(), 2);
+    }
+
+    #[test]
+    fn renders_feedback_tone_with_silent_edges() {
+        let tone = FeedbackTone::Start.render();
+
+        assert_eq!(tone.channels, 1);
+        assert!((tone.duration_seconds() - 0.14).abs() < 1e-3);
+        assert_eq!(tone.samples.first().copied(), Some(0.0));
+        assert!(
+            tone.samples
+                .last()
+                .is_some_and(|sample| sample.abs() < 1e-3)
+        );
+        assert!(
+            tone.samples
+                .iter()
+                .all(|sample| sample.abs() <= 0.2 + f32::EPSILON)
+        );
+    }
+
+    #[test]
+    fn folds_input_into_waveform_peaks() {
+        // 25 ms at 1 kHz stereo = 50 samples per bar.
+        let mut tap = WaveformTap::new(1_000, 2);
+
+        tap.push(&[0.1; 30]);
+        assert!(tap.take().is_empty());
+
+        tap.push(&[-0.4; 20]);
+        tap.push(&[0.2; 60]);
+        assert_eq!(tap.take(), vec![0.4, 0.2]);
+    }
+
     #[test]
     fn preserves_track_artifact_frame_count() {
         let artifact = TrackArtifact {
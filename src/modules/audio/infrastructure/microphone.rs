@@ -1,35 +1,171 @@
-use crate::modules::audio::domain::{AudioSourceKind, CapturedAudio, CapturedTrack};
+use crate::modules::audio::domain::{
+    AudioLevel, AudioSourceKind, CaptureOverrides, ClipDetector, LevelWindow, PreRollBuffer,
+    WaveformTap, is_voice_activity,
+};
+use crate::modules::audio::infrastructure::monitor::{self, Monitor, MonitorSource, MonitorTap};
+use crate::modules::audio::infrastructure::ring::{self, Consumer, Producer};
+use crate::modules::audio::infrastructure::spool::{SpoolWriter, SpooledTrack};
+use crate::support::error::OpenVoiceError;
 use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
-use cpal::{FromSample, Sample, SupportedStreamConfig};
+use cpal::{FromSample, SizedSample, SupportedStreamConfig};
+use std::sync::mpsc::Sender;
 use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
 
-type SharedSamples = Arc<Mutex<Vec<f32>>>;
+type SharedSamples = Arc<Mutex<CaptureBuffer>>;
 type SharedError = Arc<Mutex<Option<String>>>;
+type SharedActivity = Arc<Mutex<InputActivity>>;
+
+/// Audio the callback can queue before the drain thread must catch up.
+const RING_CAPACITY_MS: u64 = 2_000;
+const DRAIN_INTERVAL_MS: u64 = 10;
 
 pub struct Recorder {
     config: SupportedStreamConfig,
     stream: cpal::Stream,
     samples: SharedSamples,
     last_error: SharedError,
+    activity: SharedActivity,
+    drain_worker: JoinHandle<()>,
+    monitor: MonitorSource,
     device_name: String,
 }
 
+/// Where captured samples go. While `pre_roll` is set the recorder is only
+/// listening: audio goes to the pre-roll history until `begin` opens the spool
+/// file, which then receives everything so long takes stay off the heap.
+/// Spooled audio is also copied to `stream` when a live transcription is
+/// listening.
+#[derive(Default)]
+struct CaptureBuffer {
+    spool: Option<SpoolWriter>,
+    pre_roll: Option<PreRollBuffer>,
+    stream: Option<Sender<Vec<f32>>>,
+}
+
+#[derive(Debug, Default)]
+struct InputActivity {
+    last_voice_at: Option<Instant>,
+    level: LevelWindow,
+    waveform: WaveformTap,
+    clipping: ClipDetector,
+}
+
 impl Recorder {
     pub fn device_name(&self) -> Option<&str> {
         Some(&self.device_name)
     }
 
-    pub fn finish(self) -> Result<CapturedTrack, String> {
+    /// Starts recording for real, keeping the buffered pre-roll audio as
+    /// the beginning of the take.
+    pub fn begin(&self) -> Result<(), String> {
+        let mut buffer = self
+            .samples
+            .lock()
+            .map_err(|_| String::from("Nao foi possivel iniciar a captura de audio."))?;
+
+        if let Some(mut pre_roll) = buffer.pre_roll.take() {
+            let spool = SpoolWriter::create(self.config.sample_rate(), self.config.channels())?;
+            let history = pre_roll.drain();
+            if let Some(stream) = buffer.stream.as_ref() {
+                let _ = stream.send(history.clone());
+            }
+            spool.write(history);
+            buffer.spool = Some(spool);
+        }
+        drop(buffer);
+
+        if let Ok(mut activity) = self.activity.lock() {
+            activity.last_voice_at = None;
+            activity.level = LevelWindow::default();
+            activity.waveform.take();
+            activity.clipping.reset();
+        }
+
+        Ok(())
+    }
+
+    pub fn sample_rate(&self) -> u32 {
+        self.config.sample_rate()
+    }
+
+    pub fn channels(&self) -> u16 {
+        self.config.channels()
+    }
+
+    /// Copies the take to `sender` as it is recorded, including the pre-roll
+    /// when `begin` has not run yet. The sender is dropped with the recorder.
+    pub fn stream_to(&self, sender: Sender<Vec<f32>>) {
+        if let Ok(mut buffer) = self.samples.lock() {
+            buffer.stream = Some(sender);
+        }
+    }
+
+    /// Plays the microphone back through the default output until the
+    /// returned handle is dropped.
+    pub fn start_monitor(&self) -> Result<Monitor, String> {
+        self.monitor.start()
+    }
+
+    /// Time elapsed since the last buffer that looked like speech.
+    /// Returns `None` until the speaker has said something.
+    pub fn silence_since_last_voice(&self) -> Option<Duration> {
+        self.activity
+            .lock()
+            .ok()?
+            .last_voice_at
+            .map(|instant| instant.elapsed())
+    }
+
+    /// Waveform bars completed since the previous call.
+    pub fn take_waveform(&self) -> Vec<f32> {
+        self.activity
+            .lock()
+            .map(|mut activity| activity.waveform.take())
+            .unwrap_or_default()
+    }
+
+    /// Clipping events since the previous call.
+    pub fn take_clip_events(&self) -> u32 {
+        self.activity
+            .lock()
+            .map(|mut activity| activity.clipping.take())
+            .unwrap_or_default()
+    }
+
+    /// Whether the take clipped at any point so far.
+    pub fn has_clipped(&self) -> bool {
+        self.activity
+            .lock()
+            .is_ok_and(|activity| activity.clipping.total() > 0)
+    }
+
+    /// Input level since the previous call, for the live meter.
+    pub fn take_level(&self) -> AudioLevel {
+        self.activity
+            .lock()
+            .map(|mut activity| activity.level.take())
+            .unwrap_or_default()
+    }
+
+    pub fn finish(self) -> Result<SpooledTrack, String> {
         let Recorder {
-            config,
             stream,
             samples,
             last_error,
+            drain_worker,
             device_name,
+            ..
         } = self;
 
         let _ = stream.pause();
+        // Dropping the stream releases the ring producer, which lets the
+        // drain thread flush what is left and exit.
         drop(stream);
+        drain_worker
+            .join()
+            .map_err(|_| String::from("A captura de audio foi interrompida."))?;
 
         if let Some(error) = last_error
             .lock()
@@ -39,114 +175,326 @@ impl Recorder {
             return Err(error);
         }
 
-        let samples = samples
+        let spool = samples
             .lock()
             .map_err(|_| String::from("Nao foi possivel finalizar a captura de audio."))?
-            .clone();
+            .spool
+            .take()
+            .ok_or_else(|| String::from("A captura de audio ainda nao tinha comecado."))?;
 
-        Ok(CapturedTrack {
+        Ok(SpooledTrack {
             source: AudioSourceKind::Microphone,
             device_name,
-            audio: CapturedAudio {
-                samples,
-                sample_rate: config.sample_rate(),
-                channels: config.channels(),
-            },
+            audio: spool.finish()?,
         })
     }
 }
 
-pub fn start_default_recording() -> Result<Recorder, String> {
+pub fn start_default_recording(overrides: CaptureOverrides) -> Result<Recorder, OpenVoiceError> {
+    open_default_input(None, overrides).map_err(OpenVoiceError::DeviceUnavailable)
+}
+
+/// Opens the default microphone without recording yet, keeping the last
+/// `duration_ms` of audio so `Recorder::begin` can prepend it to the take.
+pub fn start_pre_roll(
+    duration_ms: u32,
+    overrides: CaptureOverrides,
+) -> Result<Recorder, OpenVoiceError> {
+    open_default_input(Some(duration_ms), overrides).map_err(OpenVoiceError::DeviceUnavailable)
+}
+
+fn open_default_input(
+    pre_roll_ms: Option<u32>,
+    overrides: CaptureOverrides,
+) -> Result<Recorder, String> {
     let host = cpal::default_host();
-    let device = host
-        .default_input_device()
-        .ok_or_else(|| String::from("Nenhum microfone padrao foi encontrado."))?;
+    let device = match overrides
+        .device
+        .as_deref()
+        .and_then(|name| find_input_device(&host, name))
+    {
+        Some(device) => device,
+        None => {
+            if let Some(name) = &overrides.device {
+                log_warn!("[openvoice][audio] input device {name} not found, using the default");
+            }
+            host.default_input_device()
+                .ok_or_else(|| String::from("Nenhum microfone padrao foi encontrado."))?
+        }
+    };
     let device_name = device
         .description()
         .map(|description| description.to_string())
         .unwrap_or_else(|_| String::from("microfone padrao"));
-    let config = device
+    let default_config = device
         .default_input_config()
         .map_err(|error| format!("Falha ao ler a configuracao do microfone: {error}"))?;
 
-    let samples = Arc::new(Mutex::new(Vec::new()));
+    let config = preferred_input_config(&device, &default_config, overrides.sample_rate);
+    let buffer_size = preferred_buffer_size(&config, overrides.buffer_frames);
+
+    if config != default_config || buffer_size != cpal::BufferSize::Default {
+        match open_stream(
+            &device,
+            config,
+            buffer_size,
+            pre_roll_ms,
+            device_name.clone(),
+        ) {
+            Ok(recorder) => return Ok(recorder),
+            Err(error) => log_warn!(
+                "[openvoice][audio] capture overrides rejected, using device defaults: {error}"
+            ),
+        }
+    }
+
+    open_stream(
+        &device,
+        default_config,
+        cpal::BufferSize::Default,
+        pre_roll_ms,
+        device_name,
+    )
+}
+
+/// Names of the input devices the host can open, in its own order.
+pub fn list_input_devices() -> Result<Vec<String>, String> {
+    let devices = cpal::default_host()
+        .input_devices()
+        .map_err(|error| format!("Falha ao listar os microfones: {error}"))?;
+
+    Ok(devices
+        .filter_map(|device| device.description().ok())
+        .map(|description| description.to_string())
+        .collect())
+}
+
+fn find_input_device(host: &cpal::Host, name: &str) -> Option<cpal::Device> {
+    host.input_devices().ok()?.find(|device| {
+        device
+            .description()
+            .is_ok_and(|description| description.to_string() == name)
+    })
+}
+
+fn open_stream(
+    device: &cpal::Device,
+    config: SupportedStreamConfig,
+    buffer_size: cpal::BufferSize,
+    pre_roll_ms: Option<u32>,
+    device_name: String,
+) -> Result<Recorder, String> {
+    let pre_roll = pre_roll_ms.map(|duration_ms| {
+        PreRollBuffer::new(config.sample_rate(), config.channels(), duration_ms)
+    });
+    let spool = match pre_roll {
+        Some(_) => None,
+        None => Some(SpoolWriter::create(
+            config.sample_rate(),
+            config.channels(),
+        )?),
+    };
+    let samples = Arc::new(Mutex::new(CaptureBuffer {
+        spool,
+        pre_roll,
+        stream: None,
+    }));
     let last_error = Arc::new(Mutex::new(None));
+    let activity = Arc::new(Mutex::new(InputActivity {
+        waveform: WaveformTap::new(config.sample_rate(), config.channels()),
+        clipping: ClipDetector::new(config.channels()),
+        ..InputActivity::default()
+    }));
+    let capacity = (u64::from(config.sample_rate()) * RING_CAPACITY_MS / 1_000) as usize
+        * usize::from(config.channels().max(1));
+    let (producer, consumer) = ring::channel(capacity);
+    let (monitor_tap, monitor) = monitor::tap(config.sample_rate(), config.channels());
     let stream = build_stream(
-        &device,
+        device,
         &config,
-        Arc::clone(&samples),
-        Arc::clone(&last_error),
+        buffer_size,
+        (producer, monitor_tap, Arc::clone(&last_error)),
     )?;
 
     stream
         .play()
         .map_err(|error| format!("Falha ao iniciar a captura de audio: {error}"))?;
 
+    let drain_worker = {
+        let samples = Arc::clone(&samples);
+        let activity = Arc::clone(&activity);
+        thread::spawn(move || drain_samples(consumer, &samples, &activity))
+    };
+
     Ok(Recorder {
         config,
         stream,
         samples,
         last_error,
+        activity,
+        drain_worker,
+        monitor,
         device_name,
     })
 }
 
+/// Looks for a config running at the requested rate with the default
+/// channel layout, keeping the default config when none is offered.
+fn preferred_input_config(
+    device: &cpal::Device,
+    default_config: &SupportedStreamConfig,
+    sample_rate: Option<u32>,
+) -> SupportedStreamConfig {
+    let Some(sample_rate) = sample_rate else {
+        return default_config.clone();
+    };
+
+    if sample_rate == default_config.sample_rate() {
+        return default_config.clone();
+    }
+
+    let matching = device.supported_input_configs().ok().and_then(|configs| {
+        configs
+            .filter(|range| range.channels() == default_config.channels())
+            .filter_map(|range| range.try_with_sample_rate(sample_rate))
+            .max_by_key(|config| config.sample_format() == default_config.sample_format())
+    });
+
+    matching.unwrap_or_else(|| {
+        log_warn!(
+            "[openvoice][audio] microphone does not offer {sample_rate} Hz, keeping {} Hz",
+            default_config.sample_rate()
+        );
+        default_config.clone()
+    })
+}
+
+/// Clamps the requested buffer to the range the device reports.
+fn preferred_buffer_size(
+    config: &SupportedStreamConfig,
+    buffer_frames: Option<u32>,
+) -> cpal::BufferSize {
+    let Some(frames) = buffer_frames else {
+        return cpal::BufferSize::Default;
+    };
+
+    match *config.buffer_size() {
+        cpal::SupportedBufferSize::Range { min, max } => {
+            let clamped = frames.clamp(min, max);
+            if clamped != frames {
+                log_warn!(
+                    "[openvoice][audio] buffer of {frames} frames outside {min}..={max}, using {clamped}"
+                );
+            }
+            cpal::BufferSize::Fixed(clamped)
+        }
+        cpal::SupportedBufferSize::Unknown => cpal::BufferSize::Fixed(frames),
+    }
+}
+
 fn build_stream(
     device: &cpal::Device,
     config: &SupportedStreamConfig,
-    samples: SharedSamples,
-    last_error: SharedError,
+    buffer_size: cpal::BufferSize,
+    shared: (Producer, MonitorTap, SharedError),
 ) -> Result<cpal::Stream, String> {
-    let err_fn = move |error| {
-        if let Ok(mut slot) = last_error.lock() {
-            *slot = Some(format!("O stream de audio falhou: {error}"));
-        }
-    };
+    use cpal::SampleFormat as Format;
+
+    let mut stream_config: cpal::StreamConfig = config.clone().into();
+    stream_config.buffer_size = buffer_size;
 
     match config.sample_format() {
-        cpal::SampleFormat::I8 => device
-            .build_input_stream(
-                &config.clone().into(),
-                move |input: &[i8], _| push_samples(input, &samples),
-                err_fn,
-                None,
-            )
-            .map_err(stream_error),
-        cpal::SampleFormat::I16 => device
-            .build_input_stream(
-                &config.clone().into(),
-                move |input: &[i16], _| push_samples(input, &samples),
-                err_fn,
-                None,
-            )
-            .map_err(stream_error),
-        cpal::SampleFormat::I32 => device
-            .build_input_stream(
-                &config.clone().into(),
-                move |input: &[i32], _| push_samples(input, &samples),
-                err_fn,
-                None,
-            )
-            .map_err(stream_error),
-        cpal::SampleFormat::F32 => device
-            .build_input_stream(
-                &config.clone().into(),
-                move |input: &[f32], _| push_samples(input, &samples),
-                err_fn,
-                None,
-            )
-            .map_err(stream_error),
+        Format::I8 => build_typed_stream::<i8>(device, &stream_config, shared),
+        Format::I16 => build_typed_stream::<i16>(device, &stream_config, shared),
+        Format::I24 => build_typed_stream::<cpal::I24>(device, &stream_config, shared),
+        Format::I32 => build_typed_stream::<i32>(device, &stream_config, shared),
+        Format::I64 => build_typed_stream::<i64>(device, &stream_config, shared),
+        Format::U8 => build_typed_stream::<u8>(device, &stream_config, shared),
+        Format::U16 => build_typed_stream::<u16>(device, &stream_config, shared),
+        Format::U24 => build_typed_stream::<cpal::U24>(device, &stream_config, shared),
+        Format::U32 => build_typed_stream::<u32>(device, &stream_config, shared),
+        Format::U64 => build_typed_stream::<u64>(device, &stream_config, shared),
+        Format::F32 => build_typed_stream::<f32>(device, &stream_config, shared),
+        Format::F64 => build_typed_stream::<f64>(device, &stream_config, shared),
         other => Err(format!("Formato de audio nao suportado: {other:?}")),
     }
 }
 
-fn push_samples<T>(input: &[T], samples: &SharedSamples)
+/// Builds the input stream for one native sample type. The callback only
+/// converts to `f32` and queues into the lock-free rings (recording and,
+/// when enabled, monitoring); everything else happens on the drain thread.
+fn build_typed_stream<T>(
+    device: &cpal::Device,
+    stream_config: &cpal::StreamConfig,
+    (mut producer, mut monitor_tap, last_error): (Producer, MonitorTap, SharedError),
+) -> Result<cpal::Stream, String>
 where
-    T: Sample,
+    T: SizedSample,
     f32: FromSample<T>,
 {
+    let err_fn = move |error| {
+        if let Ok(mut slot) = last_error.lock() {
+            *slot = Some(format!("O stream de audio falhou: {error}"));
+        }
+    };
+
+    device
+        .build_input_stream(
+            stream_config,
+            move |input: &[T], _| {
+                producer.push(input);
+                monitor_tap.push(input);
+            },
+            err_fn,
+            None,
+        )
+        .map_err(stream_error)
+}
+
+/// Empties the ring every few milliseconds until the stream goes away.
+fn drain_samples(mut consumer: Consumer, samples: &SharedSamples, activity: &SharedActivity) {
+    loop {
+        // Checked before draining so the final batch is never left behind.
+        let abandoned = consumer.is_abandoned();
+        let mut converted = Vec::new();
+
+        if consumer.pop_into(&mut converted) > 0 {
+            push_samples(converted, samples, activity);
+        }
+
+        let dropped = consumer.take_dropped();
+        if dropped > 0 {
+            log_warn!("[openvoice][audio] microphone ring overflowed, dropped {dropped} samples");
+        }
+
+        if abandoned {
+            return;
+        }
+
+        thread::sleep(Duration::from_millis(DRAIN_INTERVAL_MS));
+    }
+}
+
+fn push_samples(converted: Vec<f32>, samples: &SharedSamples, activity: &SharedActivity) {
+    if let Ok(mut slot) = activity.lock() {
+        slot.level.push(&converted);
+        slot.waveform.push(&converted);
+        slot.clipping.push(&converted);
+
+        if is_voice_activity(&converted) {
+            slot.last_voice_at = Some(Instant::now());
+        }
+    }
+
     if let Ok(mut buffer) = samples.lock() {
-        buffer.extend(input.iter().copied().map(f32::from_sample));
+        if let Some(pre_roll) = buffer.pre_roll.as_mut() {
+            pre_roll.push(&converted);
+        } else if let Some(spool) = buffer.spool.as_ref() {
+            if let Some(stream) = buffer.stream.as_ref() {
+                let _ = stream.send(converted.clone());
+            }
+            spool.write(converted);
+        }
     }
 }
 
@@ -1,3 +1,7 @@
 pub mod microphone;
+pub mod monitor;
+pub mod playback;
+pub mod ring;
+pub mod spool;
 pub mod storage;
 pub mod system;
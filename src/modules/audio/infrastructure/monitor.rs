@@ -0,0 +1,184 @@
+use crate::modules::audio::infrastructure::ring::{self, Consumer, Producer};
+use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
+use cpal::{FromSample, Sample, SizedSample, SupportedStreamConfig};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+
+/// Upper bound on how far the monitored audio may lag behind the mic; the
+/// ring drops input buffers rather than letting the delay grow.
+const MONITOR_RING_MS: u64 = 100;
+const MONITOR_BUFFER_FRAMES: u32 = 256;
+
+/// Input-callback side of the monitor: copies buffers into the ring only
+/// while a monitor output is running.
+pub struct MonitorTap {
+    enabled: Arc<AtomicBool>,
+    producer: Producer,
+}
+
+impl MonitorTap {
+    pub fn push<T>(&mut self, input: &[T])
+    where
+        T: Sample,
+        f32: FromSample<T>,
+    {
+        if self.enabled.load(Ordering::Relaxed) {
+            self.producer.push(input);
+        }
+    }
+}
+
+/// Recorder side of the monitor, used to open the output stream on demand.
+pub struct MonitorSource {
+    enabled: Arc<AtomicBool>,
+    consumer: Arc<Mutex<Consumer>>,
+    sample_rate: u32,
+    channels: u16,
+}
+
+/// Running monitor output. Dropping it stops the stream and the tap.
+pub struct Monitor {
+    _stream: cpal::Stream,
+    enabled: Arc<AtomicBool>,
+}
+
+impl Drop for Monitor {
+    fn drop(&mut self) {
+        self.enabled.store(false, Ordering::Relaxed);
+    }
+}
+
+pub fn tap(sample_rate: u32, channels: u16) -> (MonitorTap, MonitorSource) {
+    let capacity =
+        (u64::from(sample_rate) * MONITOR_RING_MS / 1_000) as usize * usize::from(channels.max(1));
+    let (producer, consumer) = ring::channel(capacity);
+    let enabled = Arc::new(AtomicBool::new(false));
+
+    (
+        MonitorTap {
+            enabled: Arc::clone(&enabled),
+            producer,
+        },
+        MonitorSource {
+            enabled,
+            consumer: Arc::new(Mutex::new(consumer)),
+            sample_rate,
+            channels,
+        },
+    )
+}
+
+impl MonitorSource {
+    /// Routes the microphone to the default output device. The output has to
+    /// run at the capture rate; channels are mapped like in playback.
+    pub fn start(&self) -> Result<Monitor, String> {
+        let host = cpal::default_host();
+        let device = host
+            .default_output_device()
+            .ok_or_else(|| String::from("Nenhuma saida de audio padrao foi encontrada."))?;
+        let config = self.output_config(&device)?;
+
+        let mut stream_config: cpal::StreamConfig = config.clone().into();
+        if let cpal::SupportedBufferSize::Range { min, max } = *config.buffer_size() {
+            stream_config.buffer_size =
+                cpal::BufferSize::Fixed(MONITOR_BUFFER_FRAMES.clamp(min, max));
+        }
+
+        if let Ok(mut consumer) = self.consumer.lock() {
+            consumer.clear();
+        }
+
+        let stream = self.build_stream(&device, &config, &stream_config)?;
+        self.enabled.store(true, Ordering::Relaxed);
+
+        if let Err(error) = stream.play() {
+            self.enabled.store(false, Ordering::Relaxed);
+            return Err(format!("Falha ao iniciar o retorno do microfone: {error}"));
+        }
+
+        Ok(Monitor {
+            _stream: stream,
+            enabled: Arc::clone(&self.enabled),
+        })
+    }
+
+    fn output_config(&self, device: &cpal::Device) -> Result<SupportedStreamConfig, String> {
+        let configs = device
+            .supported_output_configs()
+            .map_err(|error| format!("Falha ao ler a configuracao da saida de audio: {error}"))?;
+
+        configs
+            .filter_map(|range| range.try_with_sample_rate(self.sample_rate))
+            .max_by_key(|config| {
+                (
+                    config.channels() == self.channels,
+                    config.sample_format() == cpal::SampleFormat::F32,
+                )
+            })
+            .ok_or_else(|| {
+                format!(
+                    "A saida de audio nao aceita {} Hz para o retorno do microfone.",
+                    self.sample_rate
+                )
+            })
+    }
+
+    fn build_stream(
+        &self,
+        device: &cpal::Device,
+        config: &SupportedStreamConfig,
+        stream_config: &cpal::StreamConfig,
+    ) -> Result<cpal::Stream, String> {
+        use cpal::SampleFormat as Format;
+
+        match config.sample_format() {
+            Format::I16 => self.build_typed_stream::<i16>(device, stream_config),
+            Format::I32 => self.build_typed_stream::<i32>(device, stream_config),
+            Format::U16 => self.build_typed_stream::<u16>(device, stream_config),
+            Format::F32 => self.build_typed_stream::<f32>(device, stream_config),
+            Format::F64 => self.build_typed_stream::<f64>(device, stream_config),
+            other => Err(format!(
+                "Formato de saida de audio nao suportado para o retorno: {other:?}"
+            )),
+        }
+    }
+
+    fn build_typed_stream<T>(
+        &self,
+        device: &cpal::Device,
+        stream_config: &cpal::StreamConfig,
+    ) -> Result<cpal::Stream, String>
+    where
+        T: SizedSample + FromSample<f32>,
+    {
+        let consumer = Arc::clone(&self.consumer);
+        let output_channels = usize::from(stream_config.channels.max(1));
+        let mut frame = vec![0.0_f32; usize::from(self.channels.max(1))];
+
+        device
+            .build_output_stream(
+                stream_config,
+                move |output: &mut [T], _| {
+                    // Only this callback locks the consumer, so `try_lock`
+                    // never actually waits.
+                    let Ok(mut consumer) = consumer.try_lock() else {
+                        output.fill(T::EQUILIBRIUM);
+                        return;
+                    };
+
+                    for slots in output.chunks_mut(output_channels) {
+                        if consumer.pop_slice(&mut frame) < frame.len() {
+                            frame.fill(0.0);
+                        }
+
+                        for (channel, slot) in slots.iter_mut().enumerate() {
+                            *slot = T::from_sample(frame[channel.min(frame.len() - 1)]);
+                        }
+                    }
+                },
+                |error| log_error!("[openvoice][audio] monitor output failed: {error}"),
+                None,
+            )
+            .map_err(|error| format!("Falha ao preparar o retorno do microfone: {error}"))
+    }
+}
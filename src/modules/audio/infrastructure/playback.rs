@@ -0,0 +1,214 @@
+use crate::modules::audio::domain::CapturedAudio;
+use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
+use cpal::{FromSample, SizedSample, SupportedStreamConfig};
+use std::sync::mpsc::{self, Sender};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+type SharedError = Arc<Mutex<Option<String>>>;
+
+const PLAYBACK_GRACE_MS: u64 = 1_000;
+
+/// Plays a capture through the default output device and blocks until the
+/// last sample has been handed to the device.
+pub fn play(audio: CapturedAudio) -> Result<(), String> {
+    if audio.samples.is_empty() {
+        return Err(String::from("A ultima gravacao nao tem audio."));
+    }
+
+    let host = cpal::default_host();
+    let device = host
+        .default_output_device()
+        .ok_or_else(|| String::from("Nenhuma saida de audio padrao foi encontrada."))?;
+    let config = output_config(&device, &audio)?;
+    let timeout = Duration::from_secs_f32(audio.duration_seconds())
+        + Duration::from_millis(PLAYBACK_GRACE_MS);
+
+    let (done_tx, done_rx) = mpsc::channel();
+    let last_error = Arc::new(Mutex::new(None));
+    let cursor = PlaybackCursor::new(audio, config.sample_rate(), config.channels());
+    let stream = build_stream(&device, &config, cursor, done_tx, Arc::clone(&last_error))?;
+
+    stream
+        .play()
+        .map_err(|error| format!("Falha ao iniciar a reproducao: {error}"))?;
+
+    let _ = done_rx.recv_timeout(timeout);
+    drop(stream);
+
+    match last_error
+        .lock()
+        .map_err(|_| String::from("Nao foi possivel ler o estado da reproducao."))?
+        .take()
+    {
+        Some(error) => Err(error),
+        None => Ok(()),
+    }
+}
+
+/// Prefers an output config matching the capture so it plays untouched;
+/// otherwise falls back to the device default and converts on the fly.
+fn output_config(
+    device: &cpal::Device,
+    audio: &CapturedAudio,
+) -> Result<SupportedStreamConfig, String> {
+    let matching = device.supported_output_configs().ok().and_then(|configs| {
+        configs
+            .filter(|range| range.channels() == audio.channels)
+            .filter_map(|range| range.try_with_sample_rate(audio.sample_rate))
+            .max_by_key(|config| config.sample_format() == cpal::SampleFormat::F32)
+    });
+
+    match matching {
+        Some(config) => Ok(config),
+        None => device
+            .default_output_config()
+            .map_err(|error| format!("Falha ao ler a configuracao da saida de audio: {error}")),
+    }
+}
+
+fn build_stream(
+    device: &cpal::Device,
+    config: &SupportedStreamConfig,
+    cursor: PlaybackCursor,
+    done_tx: Sender<()>,
+    last_error: SharedError,
+) -> Result<cpal::Stream, String> {
+    match config.sample_format() {
+        cpal::SampleFormat::I8 => {
+            build_typed_stream::<i8>(device, config, cursor, done_tx, last_error)
+        }
+        cpal::SampleFormat::I16 => {
+            build_typed_stream::<i16>(device, config, cursor, done_tx, last_error)
+        }
+        cpal::SampleFormat::I32 => {
+            build_typed_stream::<i32>(device, config, cursor, done_tx, last_error)
+        }
+        cpal::SampleFormat::U8 => {
+            build_typed_stream::<u8>(device, config, cursor, done_tx, last_error)
+        }
+        cpal::SampleFormat::U16 => {
+            build_typed_stream::<u16>(device, config, cursor, done_tx, last_error)
+        }
+        cpal::SampleFormat::U32 => {
+            build_typed_stream::<u32>(device, config, cursor, done_tx, last_error)
+        }
+        cpal::SampleFormat::F32 => {
+            build_typed_stream::<f32>(device, config, cursor, done_tx, last_error)
+        }
+        cpal::SampleFormat::F64 => {
+            build_typed_stream::<f64>(device, config, cursor, done_tx, last_error)
+        }
+        other => Err(format!(
+            "Formato de saida de audio nao suportado: {other:?}"
+        )),
+    }
+}
+
+fn build_typed_stream<T>(
+    device: &cpal::Device,
+    config: &SupportedStreamConfig,
+    mut cursor: PlaybackCursor,
+    done_tx: Sender<()>,
+    last_error: SharedError,
+) -> Result<cpal::Stream, String>
+where
+    T: SizedSample + FromSample<f32>,
+{
+    let mut done_tx = Some(done_tx);
+    let err_fn = move |error| {
+        if let Ok(mut slot) = last_error.lock() {
+            *slot = Some(format!("A reproducao de audio falhou: {error}"));
+        }
+    };
+
+    device
+        .build_output_stream(
+            &config.clone().into(),
+            move |output: &mut [T], _| {
+                if !cursor.fill(output)
+                    && let Some(done_tx) = done_tx.take()
+                {
+                    let _ = done_tx.send(());
+                }
+            },
+            err_fn,
+            None,
+        )
+        .map_err(|error| format!("Falha ao preparar a saida de audio: {error}"))
+}
+
+/// Reads the capture frame by frame at the output rate. Rate conversion is
+/// nearest-neighbour, which is plenty for checking what the mic picked up.
+struct PlaybackCursor {
+    samples: Vec<f32>,
+    channels: usize,
+    output_channels: usize,
+    step: f64,
+    position: f64,
+}
+
+impl PlaybackCursor {
+    fn new(audio: CapturedAudio, output_rate: u32, output_channels: u16) -> Self {
+        Self {
+            channels: usize::from(audio.channels.max(1)),
+            output_channels: usize::from(output_channels.max(1)),
+            step: f64::from(audio.sample_rate) / f64::from(output_rate.max(1)),
+            position: 0.0,
+            samples: audio.samples,
+        }
+    }
+
+    /// Fills the device buffer, padding with silence past the end.
+    /// Returns `false` once every input frame has been played.
+    fn fill<T>(&mut self, output: &mut [T]) -> bool
+    where
+        T: SizedSample + FromSample<f32>,
+    {
+        let frames = self.samples.len() / self.channels;
+
+        for frame in output.chunks_mut(self.output_channels) {
+            let index = self.position as usize;
+
+            for (channel, slot) in frame.iter_mut().enumerate() {
+                let value = if index < frames {
+                    self.samples[index * self.channels + channel.min(self.channels - 1)]
+                } else {
+                    0.0
+                };
+                *slot = T::from_sample(value);
+            }
+
+            if index < frames {
+                self.position += self.step;
+            }
+        }
+
+        (self.position as usize) < frames
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PlaybackCursor;
+    use crate::modules::audio::domain::CapturedAudio;
+
+    #[test]
+    fn upmixes_and_resamples_capture_for_output() {
+        let audio = CapturedAudio {
+            samples: vec![0.1, 0.2, 0.3],
+            sample_rate: 8_000,
+            channels: 1,
+        };
+        let mut cursor = PlaybackCursor::new(audio, 16_000, 2);
+        let mut output = [0.0_f32; 16];
+
+        assert!(!cursor.fill(&mut output));
+        assert_eq!(
+            output,
+            [
+                0.1, 0.1, 0.1, 0.1, 0.2, 0.2, 0.2, 0.2, 0.3, 0.3, 0.3, 0.3, 0.0, 0.0, 0.0, 0.0
+            ]
+        );
+    }
+}
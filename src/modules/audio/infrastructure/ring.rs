@@ -0,0 +1,174 @@
+use cpal::{FromSample, Sample};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU32, AtomicUsize, Ordering};
+
+/// Single-producer/single-consumer sample queue between the realtime audio
+/// callback and a regular thread. Samples are stored as `f32` bits in
+/// atomics, so neither side ever locks or allocates.
+pub fn channel(capacity: usize) -> (Producer, Consumer) {
+    let shared = Arc::new(Shared {
+        slots: (0..capacity.max(1)).map(|_| AtomicU32::new(0)).collect(),
+        read: AtomicUsize::new(0),
+        write: AtomicUsize::new(0),
+        dropped: AtomicUsize::new(0),
+    });
+
+    (
+        Producer {
+            shared: Arc::clone(&shared),
+        },
+        Consumer { shared },
+    )
+}
+
+struct Shared {
+    slots: Box<[AtomicU32]>,
+    /// Total samples consumed; only the consumer stores it.
+    read: AtomicUsize,
+    /// Total samples produced; only the producer stores it.
+    write: AtomicUsize,
+    dropped: AtomicUsize,
+}
+
+pub struct Producer {
+    shared: Arc<Shared>,
+}
+
+impl Producer {
+    /// Queues a whole callback buffer, converting to `f32`. When the queue
+    /// cannot hold all of it the buffer is dropped as a unit, so interleaved
+    /// frames never get split.
+    pub fn push<T>(&mut self, input: &[T]) -> bool
+    where
+        T: Sample,
+        f32: FromSample<T>,
+    {
+        let shared = &self.shared;
+        let capacity = shared.slots.len();
+        let write = shared.write.load(Ordering::Relaxed);
+        let read = shared.read.load(Ordering::Acquire);
+
+        if capacity - write.wrapping_sub(read) < input.len() {
+            shared.dropped.fetch_add(input.len(), Ordering::Relaxed);
+            return false;
+        }
+
+        for (offset, sample) in input.iter().enumerate() {
+            let value = f32::from_sample(*sample);
+            shared.slots[write.wrapping_add(offset) % capacity]
+                .store(value.to_bits(), Ordering::Relaxed);
+        }
+
+        shared
+            .write
+            .store(write.wrapping_add(input.len()), Ordering::Release);
+        true
+    }
+}
+
+pub struct Consumer {
+    shared: Arc<Shared>,
+}
+
+impl Consumer {
+    /// Moves every queued sample into `output`. Returns how many were read.
+    pub fn pop_into(&mut self, output: &mut Vec<f32>) -> usize {
+        let shared = &self.shared;
+        let capacity = shared.slots.len();
+        let read = shared.read.load(Ordering::Relaxed);
+        let write = shared.write.load(Ordering::Acquire);
+        let available = write.wrapping_sub(read);
+
+        output.extend((0..available).map(|offset| {
+            f32::from_bits(
+                shared.slots[read.wrapping_add(offset) % capacity].load(Ordering::Relaxed),
+            )
+        }));
+
+        shared.read.store(write, Ordering::Release);
+        available
+    }
+
+    /// Fills `output` with the oldest queued samples without allocating.
+    /// Returns how many were read; fewer than requested means underrun.
+    pub fn pop_slice(&mut self, output: &mut [f32]) -> usize {
+        let shared = &self.shared;
+        let capacity = shared.slots.len();
+        let read = shared.read.load(Ordering::Relaxed);
+        let write = shared.write.load(Ordering::Acquire);
+        let count = write.wrapping_sub(read).min(output.len());
+
+        for (offset, slot) in output[..count].iter_mut().enumerate() {
+            *slot = f32::from_bits(
+                shared.slots[read.wrapping_add(offset) % capacity].load(Ordering::Relaxed),
+            );
+        }
+
+        shared
+            .read
+            .store(read.wrapping_add(count), Ordering::Release);
+        count
+    }
+
+    /// Discards everything queued so far.
+    pub fn clear(&mut self) {
+        let write = self.shared.write.load(Ordering::Acquire);
+        self.shared.read.store(write, Ordering::Release);
+    }
+
+    /// Samples dropped because the consumer fell behind, since last call.
+    pub fn take_dropped(&self) -> usize {
+        self.shared.dropped.swap(0, Ordering::Relaxed)
+    }
+
+    /// `true` once the producer is gone (the stream was dropped).
+    pub fn is_abandoned(&self) -> bool {
+        Arc::strong_count(&self.shared) == 1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::channel;
+
+    #[test]
+    fn passes_samples_through_in_order_and_wraps() {
+        let (mut producer, mut consumer) = channel(4);
+        let mut output = Vec::new();
+
+        assert!(producer.push(&[0.1_f32, 0.2, 0.3]));
+        assert_eq!(consumer.pop_into(&mut output), 3);
+        assert!(producer.push(&[0.4_f32, 0.5, 0.6]));
+        assert_eq!(consumer.pop_into(&mut output), 3);
+
+        assert_eq!(output, vec![0.1, 0.2, 0.3, 0.4, 0.5, 0.6]);
+    }
+
+    #[test]
+    fn pops_partial_slices_and_clears() {
+        let (mut producer, mut consumer) = channel(8);
+        let mut frame = [0.0_f32; 2];
+
+        assert!(producer.push(&[0.1_f32, 0.2, 0.3, 0.4]));
+        assert_eq!(consumer.pop_slice(&mut frame), 2);
+        assert_eq!(frame, [0.1, 0.2]);
+
+        consumer.clear();
+        assert_eq!(consumer.pop_slice(&mut frame), 0);
+    }
+
+    #[test]
+    fn drops_whole_buffers_when_full() {
+        let (mut producer, mut consumer) = channel(4);
+        let mut output = Vec::new();
+
+        assert!(producer.push(&[1_i16, 2, 3]));
+        assert!(!producer.push(&[4_i16, 5]));
+        assert_eq!(consumer.take_dropped(), 2);
+        assert_eq!(consumer.pop_into(&mut output), 3);
+
+        assert!(!consumer.is_abandoned());
+        drop(producer);
+        assert!(consumer.is_abandoned());
+    }
+}
@@ -0,0 +1,246 @@
+use crate::modules::audio::domain::{AudioSourceKind, CapturedAudio, CapturedTrack};
+use crate::modules::audio::infrastructure::storage;
+use hound::{SampleFormat, WavReader, WavSpec, WavWriter};
+use std::fs::{self, File};
+use std::io::BufReader;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::sync::mpsc::{self, Sender};
+use std::thread::{self, JoinHandle};
+
+/// Frames read per chunk when streaming a spooled take back from disk.
+pub const SPOOL_CHUNK_FRAMES: usize = 16_384;
+
+/// Streams captured samples to a temporary float WAV on a background
+/// thread, so a recording only keeps a few callbacks worth of audio in RAM.
+pub struct SpoolWriter {
+    sender: Sender<Vec<f32>>,
+    worker: JoinHandle<Result<u64, String>>,
+    file: Arc<SpoolFile>,
+    sample_rate: u32,
+    channels: u16,
+}
+
+impl SpoolWriter {
+    pub fn create(sample_rate: u32, channels: u16) -> Result<Self, String> {
+        let path = std::env::temp_dir().join(format!(
+            "openvoice-take-{}-{}.wav",
+            std::process::id(),
+            storage::unix_timestamp_ms()
+        ));
+        let spec = WavSpec {
+            channels,
+            sample_rate,
+            bits_per_sample: 32,
+            sample_format: SampleFormat::Float,
+        };
+        let mut writer = WavWriter::create(&path, spec).map_err(|error| {
+            format!(
+                "Falha ao criar arquivo temporario da gravacao em {}: {error}",
+                path.display()
+            )
+        })?;
+        let file = Arc::new(SpoolFile { path });
+        let (sender, receiver) = mpsc::channel::<Vec<f32>>();
+
+        let worker = thread::spawn(move || {
+            let mut samples_written = 0_u64;
+
+            for chunk in receiver {
+                for sample in &chunk {
+                    writer.write_sample(*sample).map_err(|error| {
+                        format!("Falha ao escrever a gravacao temporaria: {error}")
+                    })?;
+                }
+                samples_written += chunk.len() as u64;
+            }
+
+            writer
+                .finalize()
+                .map_err(|error| format!("Falha ao finalizar a gravacao temporaria: {error}"))?;
+
+            Ok(samples_written)
+        });
+
+        Ok(Self {
+            sender,
+            worker,
+            file,
+            sample_rate,
+            channels,
+        })
+    }
+
+    pub fn write(&self, samples: Vec<f32>) {
+        let _ = self.sender.send(samples);
+    }
+
+    /// Flushes every pending chunk and closes the file.
+    pub fn finish(self) -> Result<SpooledAudio, String> {
+        let SpoolWriter {
+            sender,
+            worker,
+            file,
+            sample_rate,
+            channels,
+        } = self;
+
+        drop(sender);
+        let samples_written = worker
+            .join()
+            .map_err(|_| String::from("A gravacao temporaria foi interrompida."))??;
+
+        Ok(SpooledAudio {
+            file,
+            sample_rate,
+            channels,
+            frames: samples_written / u64::from(channels.max(1)),
+        })
+    }
+}
+
+/// A finished take kept on disk. Clones share the file, which is removed
+/// once the last clone is dropped.
+#[derive(Debug, Clone)]
+pub struct SpooledAudio {
+    file: Arc<SpoolFile>,
+    pub sample_rate: u32,
+    pub channels: u16,
+    pub frames: u64,
+}
+
+impl SpooledAudio {
+    pub fn path(&self) -> &Path {
+        &self.file.path
+    }
+
+    pub fn duration_seconds(&self) -> f32 {
+        self.frames as f32 / self.sample_rate.max(1) as f32
+    }
+
+    /// Reads the take back in chunks of whole frames.
+    pub fn chunks(&self) -> Result<SpoolChunks, String> {
+        let reader = WavReader::open(self.path()).map_err(|error| {
+            format!(
+                "Falha ao abrir a gravacao temporaria em {}: {error}",
+                self.path().display()
+            )
+        })?;
+
+        Ok(SpoolChunks {
+            samples: reader.into_samples(),
+            chunk_len: SPOOL_CHUNK_FRAMES * usize::from(self.channels.max(1)),
+        })
+    }
+
+    /// Loads the whole take into memory.
+    pub fn load(&self) -> Result<CapturedAudio, String> {
+        let mut samples = Vec::with_capacity(self.frames as usize * usize::from(self.channels));
+
+        for chunk in self.chunks()? {
+            samples.extend(chunk?);
+        }
+
+        Ok(CapturedAudio {
+            samples,
+            sample_rate: self.sample_rate,
+            channels: self.channels,
+        })
+    }
+
+    pub fn copy_to(&self, destination: &Path) -> Result<(), String> {
+        fs::copy(self.path(), destination)
+            .map(|_| ())
+            .map_err(|error| {
+                format!(
+                    "Falha ao copiar a gravacao para {}: {error}",
+                    destination.display()
+                )
+            })
+    }
+}
+
+pub struct SpoolChunks {
+    samples: hound::WavIntoSamples<BufReader<File>, f32>,
+    chunk_len: usize,
+}
+
+impl Iterator for SpoolChunks {
+    type Item = Result<Vec<f32>, String>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut chunk = Vec::with_capacity(self.chunk_len);
+
+        for sample in self.samples.by_ref().take(self.chunk_len) {
+            match sample {
+                Ok(sample) => chunk.push(sample),
+                Err(error) => {
+                    return Some(Err(format!("Falha ao ler a gravacao temporaria: {error}")));
+                }
+            }
+        }
+
+        (!chunk.is_empty()).then_some(Ok(chunk))
+    }
+}
+
+/// Spooled recording plus the device it came from.
+#[derive(Debug, Clone)]
+pub struct SpooledTrack {
+    pub source: AudioSourceKind,
+    pub device_name: String,
+    pub audio: SpooledAudio,
+}
+
+impl SpooledTrack {
+    pub fn load(&self) -> Result<CapturedTrack, String> {
+        Ok(CapturedTrack {
+            source: self.source,
+            device_name: self.device_name.clone(),
+            audio: self.audio.load()?,
+        })
+    }
+}
+
+#[derive(Debug)]
+struct SpoolFile {
+    path: PathBuf,
+}
+
+impl Drop for SpoolFile {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.path);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{SPOOL_CHUNK_FRAMES, SpoolWriter};
+
+    #[test]
+    fn spools_samples_and_reads_them_back_in_frame_chunks() {
+        let writer = SpoolWriter::create(8_000, 2).expect("spool");
+        let samples = (0..SPOOL_CHUNK_FRAMES * 2 + 10)
+            .map(|index| (index % 100) as f32 / 100.0)
+            .collect::<Vec<_>>();
+
+        writer.write(samples[..1_000].to_vec());
+        writer.write(samples[1_000..].to_vec());
+        let spooled = writer.finish().expect("finish");
+        let path = spooled.path().to_path_buf();
+
+        let chunks = spooled
+            .chunks()
+            .expect("chunks")
+            .collect::<Result<Vec<_>, _>>()
+            .expect("read");
+
+        assert_eq!(spooled.frames as usize, SPOOL_CHUNK_FRAMES + 5);
+        assert_eq!(chunks.len(), 2);
+        assert_eq!(chunks[0].len(), SPOOL_CHUNK_FRAMES * 2);
+        assert_eq!(chunks.concat(), samples);
+
+        drop(spooled);
+        assert!(!path.exists());
+    }
+}
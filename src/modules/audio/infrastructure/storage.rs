@@ -3,9 +3,10 @@
 use crate::modules::audio::domain::{
     CaptureSession, CapturedTrack, SessionArtifacts, SessionMetadata, TrackArtifact,
 };
+use crate::modules::audio::infrastructure::spool::SpooledAudio;
 use hound::{SampleFormat, WavSpec, WavWriter};
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::time::{SystemTime, UNIX_EPOCH};
 
 pub fn generate_session_id() -> String {
@@ -97,6 +98,36 @@ pub fn data_dir() -> Result<PathBuf, String> {
     Ok(base.join("openvoice"))
 }
 
+/// Copies a raw take to `dir` as `dictation-<unix_ms>.wav`.
+pub fn save_recording(audio: &SpooledAudio, dir: &Path) -> Result<PathBuf, String> {
+    fs::create_dir_all(dir).map_err(|error| {
+        format!(
+            "Falha ao criar pasta de gravacoes {}: {error}",
+            dir.display()
+        )
+    })?;
+
+    let path = dir.join(format!("dictation-{}.wav", unix_timestamp_ms()));
+    audio.copy_to(&path)?;
+
+    Ok(path)
+}
+
+/// Resolves a user-provided directory, expanding a leading `~`.
+pub fn resolve_user_dir(value: &str) -> Option<PathBuf> {
+    let trimmed = value.trim();
+
+    if trimmed.is_empty() {
+        return None;
+    }
+
+    match trimmed.strip_prefix("~") {
+        Some(rest) if rest.is_empty() || rest.starts_with('/') => std::env::var_os("HOME")
+            .map(|home| PathBuf::from(home).join(rest.trim_start_matches('/'))),
+        _ => Some(PathBuf::from(trimmed)),
+    }
+}
+
 pub fn write_track_wav(track: &CapturedTrack, path: &PathBuf) -> Result<(), String> {
     let spec = WavSpec {
         channels: track.audio.channels,
@@ -122,13 +153,29 @@ pub fn write_track_wav(track: &CapturedTrack, path: &PathBuf) -> Result<(), Stri
 
 #[cfg(test)]
 mod tests {
-    use super::{generate_session_id, session_dir};
+    use super::{generate_session_id, resolve_user_dir, session_dir};
+    use std::path::PathBuf;
 
     #[test]
     fn generates_session_ids_with_prefix() {
         assert!(generate_session_id().starts_with("session-"));
     }
 
+    #[test]
+    fn resolves_user_dirs() {
+        assert_eq!(resolve_user_dir("  "), None);
+        assert_eq!(
+            resolve_user_dir("/tmp/openvoice"),
+            Some(PathBuf::from("/tmp/openvoice"))
+        );
+        assert!(
+            resolve_user_dir("~/Audio")
+                .expect("path")
+                .ends_with("Audio")
+        );
+        assert_eq!(resolve_user_dir("~user"), Some(PathBuf::from("~user")));
+    }
+
     #[test]
     fn builds_session_dir_path() {
         let path = session_dir("session-123").expect("path");
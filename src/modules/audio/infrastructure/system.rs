@@ -218,7 +218,7 @@ fn spawn_parec_process(monitor_source: &str) -> Result<Child, String> {
 
 fn default_monitor_source_name() -> Result<String, String> {
     if let Some(source) = monitor_source_override()? {
-        eprintln!("[openvoice][audio] using monitor source override: {source}");
+        log_debug!("[openvoice][audio] using monitor source override: {source}");
         return Ok(source);
     }
 
@@ -227,7 +227,7 @@ fn default_monitor_source_name() -> Result<String, String> {
     let available = list_monitor_sources()?;
 
     if available.iter().any(|source| source.name == preferred) {
-        eprintln!("[openvoice][audio] using default sink monitor: {preferred}");
+        log_debug!("[openvoice][audio] using default sink monitor: {preferred}");
         return Ok(preferred);
     }
 
@@ -240,7 +240,7 @@ fn default_monitor_source_name() -> Result<String, String> {
             String::from("Nao encontrei nenhum monitor source do PulseAudio/PipeWire para capturar o audio do sistema.")
         })?;
 
-    eprintln!(
+    log_warn!(
         "[openvoice][audio] default sink monitor {preferred} not found, falling back to {fallback}"
     );
     Ok(fallback)
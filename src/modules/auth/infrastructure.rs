@@ -76,16 +76,16 @@ pub fn now_unix_ms() -> u128 {
 }
 
 pub fn load_credentials() -> Result<Option<StoredOpenAiCredentials>, String> {
-    eprintln!("[openvoice][auth] loading credentials");
+    log_debug!("[openvoice][auth] loading credentials");
     if let Some(credentials) = load_from_keyring()? {
-        eprintln!("[openvoice][auth] credentials loaded from keyring");
+        log_debug!("[openvoice][auth] credentials loaded from keyring");
         return Ok(Some(StoredOpenAiCredentials {
             strategy: CredentialStoreStrategy::Keyring,
             session: credentials,
         }));
     }
 
-    eprintln!("[openvoice][auth] keyring unavailable or empty, trying auth file");
+    log_warn!("[openvoice][auth] keyring unavailable or empty, trying auth file");
     load_from_file().map(|session| {
         session.map(|session| StoredOpenAiCredentials {
             strategy: CredentialStoreStrategy::File,
@@ -116,14 +116,14 @@ pub fn save_credentials(stored: &StoredOpenAiCredentials) -> Result<(), String>
             match (keyring_result, file_result) {
                 (Ok(()), Ok(())) => Ok(()),
                 (Ok(()), Err(error)) => {
-                    eprintln!(
+                    log_warn!(
                         "[openvoice][auth] auth file fallback save failed but keyring save succeeded error={}",
                         error
                     );
                     Ok(())
                 }
                 (Err(error), Ok(())) => {
-                    eprintln!(
+                    log_warn!(
                         "[openvoice][auth] keyring save failed but auth file fallback succeeded error={}",
                         error
                     );
@@ -194,9 +194,11 @@ pub fn complete_login(
     callback_url: &str,
 ) -> Result<StoredOpenAiCredentials, String> {
     let context = get_pending_oauth_context(flow_id)?;
-    eprintln!(
+    log_debug!(
         "[openvoice][auth] completing oauth flow_id={} redirect_uri={} callback_url={}",
-        flow_id, context.redirect_uri, callback_url
+        flow_id,
+        context.redirect_uri,
+        callback_url
     );
     let callback = parse_callback_url(callback_url)?;
 
@@ -213,26 +215,29 @@ pub fn complete_login(
         ));
     }
 
-    eprintln!(
+    log_debug!(
         "[openvoice][auth] state validated flow_id={} starting token exchange",
         flow_id
     );
     let session = exchange_code_for_tokens(&code, &context.redirect_uri, &context.verifier)?;
-    eprintln!(
+    log_debug!(
         "[openvoice][auth] token exchange succeeded flow_id={} account={:?} expires_at_unix_ms={}",
-        flow_id, session.email, session.expires_at_unix_ms
+        flow_id,
+        session.email,
+        session.expires_at_unix_ms
     );
     let stored = StoredOpenAiCredentials {
         strategy: context.strategy,
         session,
     };
 
-    eprintln!(
+    log_debug!(
         "[openvoice][auth] saving credentials flow_id={} strategy={:?}",
-        flow_id, stored.strategy
+        flow_id,
+        stored.strategy
     );
     save_credentials(&stored)?;
-    eprintln!("[openvoice][auth] credentials saved flow_id={}", flow_id);
+    log_debug!("[openvoice][auth] credentials saved flow_id={}", flow_id);
     clear_pending_oauth_flow(flow_id);
     Ok(stored)
 }
@@ -247,7 +252,7 @@ fn load_from_keyring() -> Result<Option<OpenAiOAuthSession>, String> {
     match entry.get_password() {
         Ok(raw) => parse_session(&raw).map(Some),
         Err(error) if is_missing_keyring_entry(&error.to_string()) => {
-            eprintln!(
+            log_warn!(
                 "[openvoice][auth] keyring read returned empty/unavailable error={}",
                 error
             );
@@ -262,9 +267,9 @@ fn save_to_keyring(session: &OpenAiOAuthSession) -> Result<(), String> {
     let serialized = serde_json::to_string(session)
         .map_err(|error| format!("Falha ao serializar sessao OpenAI: {error}"))?;
 
-    eprintln!("[openvoice][auth] attempting keyring save");
+    log_debug!("[openvoice][auth] attempting keyring save");
     entry.set_password(&serialized).map_err(|error| {
-        eprintln!("[openvoice][auth] keyring save failed error={}", error);
+        log_error!("[openvoice][auth] keyring save failed error={}", error);
         format!("Falha ao salvar credenciais no keyring: {error}")
     })
 }
@@ -311,12 +316,12 @@ fn save_to_file(session: &OpenAiOAuthSession) -> Result<(), String> {
     let raw = serde_json::to_string_pretty(session)
         .map_err(|error| format!("Falha ao serializar auth OpenAI: {error}"))?;
 
-    eprintln!(
+    log_debug!(
         "[openvoice][auth] writing auth file path={}",
         path.display()
     );
     fs::write(&path, raw).map_err(|error| {
-        eprintln!(
+        log_error!(
             "[openvoice][auth] auth file write failed path={} error={}",
             path.display(),
             error
@@ -500,8 +505,15 @@ fn handle_callback_connection(mut stream: TcpStream) -> Result<String, String> {
         .ok_or_else(|| String::from("Recebi um callback OAuth sem path."))?;
 
     let url = format!("http://localhost:{OPENAI_OAUTH_PORT}{path}");
-    eprintln!("[openvoice][auth] oauth callback received url={}", url);
     let query = parse_callback_url(&url)?;
+    // The query carries the authorization code; the log file ends up in bug
+    // reports, so only its shape is logged.
+    log_debug!(
+        "[openvoice][auth] oauth callback received path={} code={} error={}",
+        path.split('?').next().unwrap_or_default(),
+        query.code.is_some(),
+        query.error.is_some()
+    );
 
     if let Some(error) = query.error.as_deref() {
         let description = query
@@ -590,7 +602,7 @@ fn exchange_code_for_tokens(
     verifier: &str,
 ) -> Result<OpenAiOAuthSession, String> {
     let client = oauth_http_client()?;
-    eprintln!(
+    log_debug!(
         "[openvoice][auth] posting oauth/token grant_type=authorization_code redirect_uri={}",
         redirect_uri
     );
@@ -605,7 +617,7 @@ fn exchange_code_for_tokens(
         ])
         .send()
         .map_err(|error| {
-            eprintln!(
+            log_error!(
                 "[openvoice][auth] oauth/token transport failure grant_type=authorization_code error={}",
                 error
             );
@@ -615,7 +627,7 @@ fn exchange_code_for_tokens(
     let body = response
         .text()
         .map_err(|error| {
-            eprintln!(
+            log_warn!(
                 "[openvoice][auth] oauth/token body read failure grant_type=authorization_code status={} error={}",
                 status, error
             );
@@ -623,9 +635,10 @@ fn exchange_code_for_tokens(
         })?;
 
     if !status.is_success() {
-        eprintln!(
+        log_warn!(
             "[openvoice][auth] oauth/token authorization_code failure status={} body={}",
-            status, body
+            status,
+            body
         );
         return Err(format!(
             "OpenAI recusou a troca do authorization code. Status: {}",
@@ -634,7 +647,7 @@ fn exchange_code_for_tokens(
     }
 
     let token_response = serde_json::from_str::<TokenResponse>(&body).map_err(|error| {
-        eprintln!(
+        log_warn!(
             "[openvoice][auth] oauth/token authorization_code parse failure status={} body={} error={}",
             status, body, error
         );
@@ -646,7 +659,7 @@ fn exchange_code_for_tokens(
 
 fn refresh_access_token(refresh_token: &str) -> Result<OpenAiOAuthSession, String> {
     let client = oauth_http_client()?;
-    eprintln!("[openvoice][auth] posting oauth/token grant_type=refresh_token");
+    log_debug!("[openvoice][auth] posting oauth/token grant_type=refresh_token");
     let response = client
         .post(format!("{OPENAI_OAUTH_ISSUER}/oauth/token"))
         .form(&[
@@ -656,7 +669,7 @@ fn refresh_access_token(refresh_token: &str) -> Result<OpenAiOAuthSession, Strin
         ])
         .send()
         .map_err(|error| {
-            eprintln!(
+            log_error!(
                 "[openvoice][auth] oauth/token transport failure grant_type=refresh_token error={}",
                 error
             );
@@ -666,7 +679,7 @@ fn refresh_access_token(refresh_token: &str) -> Result<OpenAiOAuthSession, Strin
     let body = response
         .text()
         .map_err(|error| {
-            eprintln!(
+            log_warn!(
                 "[openvoice][auth] oauth/token body read failure grant_type=refresh_token status={} error={}",
                 status, error
             );
@@ -674,9 +687,10 @@ fn refresh_access_token(refresh_token: &str) -> Result<OpenAiOAuthSession, Strin
         })?;
 
     if !status.is_success() {
-        eprintln!(
+        log_warn!(
             "[openvoice][auth] oauth/token refresh_token failure status={} body={}",
-            status, body
+            status,
+            body
         );
         return Err(format!(
             "OpenAI recusou o refresh da sessao. Status: {}",
@@ -685,9 +699,11 @@ fn refresh_access_token(refresh_token: &str) -> Result<OpenAiOAuthSession, Strin
     }
 
     let token_response = serde_json::from_str::<TokenResponse>(&body).map_err(|error| {
-        eprintln!(
+        log_warn!(
             "[openvoice][auth] oauth/token refresh_token parse failure status={} body={} error={}",
-            status, body, error
+            status,
+            body,
+            error
         );
         format!("Falha ao interpretar refresh OAuth: {error}")
     })?;
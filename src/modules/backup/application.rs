@@ -0,0 +1,128 @@
+use crate::modules::backup::domain::{
+    AUDIO_DIR, BackupManifest, BackupOptions, DATABASE_FILE, SETTINGS_FILE,
+};
+use crate::modules::backup::infrastructure;
+use crate::modules::live_transcription::infrastructure::db;
+use crate::modules::settings::application as settings_application;
+use crate::modules::settings::domain::AppSettings;
+use crate::modules::settings::infrastructure as settings_infrastructure;
+use crate::support::cli;
+use std::path::{Path, PathBuf};
+
+const CREATE_BACKUP_FLAG: &str = "--create-backup";
+const RESTORE_BACKUP_FLAG: &str = "--restore-backup";
+const WITH_SECRETS_FLAG: &str = "--with-secrets";
+const WITH_AUDIO_FLAG: &str = "--with-audio";
+/// Written to the home folder when no path is given.
+pub const DEFAULT_BACKUP_FILE: &str = "openvoice-backup.tar.gz";
+
+/// Packs the settings, the history database and, when asked, the kept
+/// history audio into one archive at `path`.
+pub fn create_backup(
+    settings: &AppSettings,
+    path: &str,
+    options: BackupOptions,
+) -> Result<PathBuf, String> {
+    let archive = backup_path(path)?;
+    let staging = infrastructure::staging_dir()?;
+    let result = stage_backup(settings, options, &staging)
+        .and_then(|()| infrastructure::pack(&staging, &archive));
+    infrastructure::remove_staging_dir(&staging);
+    result?;
+
+    log_info!(
+        "[openvoice][backup] created {} (audio={}, secrets={})",
+        archive.display(),
+        options.include_audio,
+        options.include_secrets
+    );
+    Ok(archive)
+}
+
+fn stage_backup(
+    settings: &AppSettings,
+    options: BackupOptions,
+    staging: &Path,
+) -> Result<(), String> {
+    let archived = if options.include_secrets {
+        settings.clone()
+    } else {
+        settings.clone().without_api_keys()
+    };
+    settings_infrastructure::write_settings_file(&staging.join(SETTINGS_FILE), &archived)?;
+    infrastructure::snapshot_database(&staging.join(DATABASE_FILE))?;
+
+    let audio_dir = infrastructure::history_audio_dir()?;
+    if options.include_audio {
+        infrastructure::copy_dir(&audio_dir, &staging.join(AUDIO_DIR))?;
+    }
+
+    infrastructure::write_manifest(
+        staging,
+        &BackupManifest::new(
+            options,
+            db::now_iso(),
+            Some(audio_dir.to_string_lossy().into_owned()),
+        ),
+    )
+}
+
+/// Replaces the settings and history with those of the backup at `path`.
+/// API keys the backup leaves out are kept from `current`.
+pub fn restore_backup(current: &AppSettings, path: &str) -> Result<AppSettings, String> {
+    let archive = backup_path(path)?;
+    let staging = infrastructure::staging_dir()?;
+    let result =
+        infrastructure::unpack(&archive, &staging).and_then(|()| restore_staged(current, &staging));
+    infrastructure::remove_staging_dir(&staging);
+
+    let settings = result?;
+    log_info!("[openvoice][backup] restored {}", archive.display());
+    Ok(settings)
+}
+
+fn restore_staged(current: &AppSettings, staging: &Path) -> Result<AppSettings, String> {
+    let manifest = infrastructure::read_manifest(staging)?;
+    manifest.check_version()?;
+
+    let mut settings = settings_infrastructure::read_settings_file(&staging.join(SETTINGS_FILE))?;
+    settings.keep_api_keys_from(current);
+
+    let database = staging.join(DATABASE_FILE);
+    if database.is_file() {
+        infrastructure::restore_database(&database)?;
+    }
+    if manifest.includes_audio {
+        let audio_dir = infrastructure::history_audio_dir()?;
+        infrastructure::copy_dir(&staging.join(AUDIO_DIR), &audio_dir)?;
+        if let Some(from) = manifest.audio_dir.as_deref() {
+            infrastructure::rebase_history_audio(from, &audio_dir.to_string_lossy())?;
+        }
+    }
+
+    settings_infrastructure::save_settings(&settings)?;
+    Ok(settings)
+}
+
+/// Handles `openvoice --create-backup [path] [--with-secrets] [--with-audio]`
+/// and `openvoice --restore-backup [path]`. Returns `None` otherwise.
+pub fn run_cli_command(args: &[String]) -> Option<Result<String, String>> {
+    let (flag, path) = cli::path_command(args, &[CREATE_BACKUP_FLAG, RESTORE_BACKUP_FLAG])?;
+
+    Some(settings_application::load_settings().and_then(|settings| {
+        if flag == CREATE_BACKUP_FLAG {
+            let options = BackupOptions {
+                include_secrets: cli::has_flag(args, WITH_SECRETS_FLAG),
+                include_audio: cli::has_flag(args, WITH_AUDIO_FLAG),
+            };
+            create_backup(&settings, path, options)
+                .map(|path| format!("Backup criado em {}.", path.display()))
+        } else {
+            restore_backup(&settings, path).map(|_| String::from("Backup restaurado."))
+        }
+    }))
+}
+
+fn backup_path(path: &str) -> Result<PathBuf, String> {
+    cli::path_or_home(path, DEFAULT_BACKUP_FILE, "Informe o arquivo do backup.")
+}
@@ -0,0 +1,113 @@
+use serde::{Deserialize, Serialize};
+
+/// Bumped whenever the archive layout changes. Archives from an older
+/// format are still restored; newer ones are refused.
+pub const BACKUP_FORMAT_VERSION: u32 = 1;
+pub const MANIFEST_FILE: &str = "manifest.json";
+pub const SETTINGS_FILE: &str = "settings.json";
+pub const DATABASE_FILE: &str = "openvoice.db";
+pub const AUDIO_DIR: &str = "history-audio";
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct BackupOptions {
+    /// Keep the API keys in the archived settings; off by default so a
+    /// backup can be shared or synced safely.
+    pub include_secrets: bool,
+    /// Also pack the audio kept for history entries, which can be large.
+    pub include_audio: bool,
+}
+
+/// Written at the root of every archive, describing what it holds.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BackupManifest {
+    pub format_version: u32,
+    pub created_at: String,
+    pub app_version: String,
+    pub includes_secrets: bool,
+    pub includes_audio: bool,
+    /// Where the history audio lived when backed up; the history keeps
+    /// absolute paths, which are moved to this machine's folder on restore.
+    #[serde(default)]
+    pub audio_dir: Option<String>,
+}
+
+impl BackupManifest {
+    pub fn new(options: BackupOptions, created_at: String, audio_dir: Option<String>) -> Self {
+        Self {
+            format_version: BACKUP_FORMAT_VERSION,
+            created_at,
+            app_version: env!("CARGO_PKG_VERSION").to_owned(),
+            includes_secrets: options.include_secrets,
+            includes_audio: options.include_audio,
+            audio_dir: audio_dir.filter(|_| options.include_audio),
+        }
+    }
+
+    pub fn check_version(&self) -> Result<(), String> {
+        if self.format_version > BACKUP_FORMAT_VERSION {
+            return Err(format!(
+                "Backup no formato {} e mais novo que o suportado ({BACKUP_FORMAT_VERSION}); atualize o OpenVoice.",
+                self.format_version
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+/// `path` moved from under `from` to under `to`, or `None` when it lies
+/// elsewhere.
+pub fn rebase_audio_path(path: &str, from: &str, to: &str) -> Option<String> {
+    let relative = path.strip_prefix(from.trim_end_matches('/'))?;
+    relative
+        .starts_with('/')
+        .then(|| format!("{}{relative}", to.trim_end_matches('/')))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{BACKUP_FORMAT_VERSION, BackupManifest, BackupOptions, rebase_audio_path};
+
+    #[test]
+    fn refuses_newer_archives_and_rebases_audio() {
+        let options = BackupOptions {
+            include_secrets: false,
+            include_audio: true,
+        };
+        let mut manifest = BackupManifest::new(
+            options,
+            String::from("2026-01-01T00:00:00Z"),
+            Some(String::from(
+                "/home/old/.local/share/openvoice/history-audio",
+            )),
+        );
+        assert!(manifest.check_version().is_ok());
+        manifest.format_version = BACKUP_FORMAT_VERSION + 1;
+        assert!(manifest.check_version().is_err());
+
+        let without_audio = BackupManifest::new(
+            BackupOptions::default(),
+            String::new(),
+            Some(String::from("/tmp")),
+        );
+        assert_eq!(without_audio.audio_dir, None);
+
+        assert_eq!(
+            rebase_audio_path(
+                "/home/old/share/history-audio/1.wav",
+                "/home/old/share/history-audio/",
+                "/home/new/share/history-audio",
+            )
+            .as_deref(),
+            Some("/home/new/share/history-audio/1.wav")
+        );
+        assert_eq!(
+            rebase_audio_path(
+                "/home/old/share/history-audio-2/1.wav",
+                "/home/old/share/history-audio",
+                "/x",
+            ),
+            None
+        );
+    }
+}
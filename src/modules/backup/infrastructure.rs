@@ -0,0 +1,163 @@
+use crate::modules::audio::infrastructure::storage as audio_storage;
+use crate::modules::backup::domain::{BackupManifest, MANIFEST_FILE, rebase_audio_path};
+use crate::modules::dictation::infrastructure::history;
+use crate::modules::live_transcription::infrastructure::db;
+use rusqlite::DatabaseName;
+use rusqlite::backup::Progress;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Empty folder the archive is assembled in or unpacked to; removed by
+/// `remove_staging_dir` once done.
+pub fn staging_dir() -> Result<PathBuf, String> {
+    let dir = std::env::temp_dir().join(format!(
+        "openvoice-backup-{}-{}",
+        std::process::id(),
+        audio_storage::unix_timestamp_ms()
+    ));
+    fs::create_dir_all(&dir)
+        .map_err(|error| format!("Falha ao criar {}: {error}", dir.display()))?;
+    Ok(dir)
+}
+
+pub fn remove_staging_dir(dir: &Path) {
+    if let Err(error) = fs::remove_dir_all(dir) {
+        log_warn!(
+            "[openvoice][backup] failed to remove {}: {error}",
+            dir.display()
+        );
+    }
+}
+
+/// Packs everything in `dir` into a gzipped tar at `archive`.
+pub fn pack(dir: &Path, archive: &Path) -> Result<(), String> {
+    if let Some(parent) = archive
+        .parent()
+        .filter(|parent| !parent.as_os_str().is_empty())
+    {
+        fs::create_dir_all(parent)
+            .map_err(|error| format!("Falha ao criar {}: {error}", parent.display()))?;
+    }
+
+    run_tar(
+        Command::new("tar")
+            .arg("-czf")
+            .arg(archive)
+            .arg("-C")
+            .arg(dir)
+            .arg("."),
+    )
+}
+
+pub fn unpack(archive: &Path, dir: &Path) -> Result<(), String> {
+    if !archive.is_file() {
+        return Err(format!("Backup nao encontrado: {}.", archive.display()));
+    }
+
+    run_tar(
+        Command::new("tar")
+            .arg("-xzf")
+            .arg(archive)
+            .arg("-C")
+            .arg(dir),
+    )
+}
+
+fn run_tar(command: &mut Command) -> Result<(), String> {
+    let output = command
+        .output()
+        .map_err(|error| format!("Nao consegui executar o tar: {error}"))?;
+
+    if output.status.success() {
+        Ok(())
+    } else {
+        Err(format!(
+            "O tar falhou: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ))
+    }
+}
+
+pub fn write_manifest(dir: &Path, manifest: &BackupManifest) -> Result<(), String> {
+    let contents = serde_json::to_string_pretty(manifest)
+        .map_err(|error| format!("Falha ao serializar o manifesto: {error}"))?;
+    fs::write(dir.join(MANIFEST_FILE), contents)
+        .map_err(|error| format!("Falha ao salvar o manifesto: {error}"))
+}
+
+pub fn read_manifest(dir: &Path) -> Result<BackupManifest, String> {
+    let contents = fs::read_to_string(dir.join(MANIFEST_FILE))
+        .map_err(|_| String::from("O arquivo nao e um backup do OpenVoice (sem manifesto)."))?;
+    serde_json::from_str(&contents)
+        .map_err(|error| format!("Falha ao interpretar o manifesto: {error}"))
+}
+
+/// Consistent copy of the history database, even while it is in use.
+pub fn snapshot_database(path: &Path) -> Result<(), String> {
+    let conn = db::open_db()?;
+    conn.execute("VACUUM INTO ?1", [path.to_string_lossy()])
+        .map(|_| ())
+        .map_err(|error| format!("Falha ao copiar o banco do historico: {error}"))
+}
+
+/// Replaces the history database with the one at `path` through SQLite's
+/// backup API, so connections the app holds see a consistent database
+/// instead of a file swapped under them.
+pub fn restore_database(path: &Path) -> Result<(), String> {
+    let mut conn = db::open_db()?;
+    conn.restore(DatabaseName::Main, path, None::<fn(Progress)>)
+        .map_err(|error| format!("Falha ao restaurar o banco do historico: {error}"))
+}
+
+pub fn history_audio_dir() -> Result<PathBuf, String> {
+    history::audio_dir()
+}
+
+/// Copies the files of `from` into `to`, one level deep like the history
+/// audio folder.
+pub fn copy_dir(from: &Path, to: &Path) -> Result<(), String> {
+    if !from.is_dir() {
+        return Ok(());
+    }
+
+    fs::create_dir_all(to).map_err(|error| format!("Falha ao criar {}: {error}", to.display()))?;
+    let entries =
+        fs::read_dir(from).map_err(|error| format!("Falha ao ler {}: {error}", from.display()))?;
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_file() {
+            fs::copy(&path, to.join(entry.file_name()))
+                .map_err(|error| format!("Falha ao copiar {}: {error}", path.display()))?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Points history entries whose audio was under `from` at `to`.
+pub fn rebase_history_audio(from: &str, to: &str) -> Result<(), String> {
+    let conn = db::open_db()?;
+    history::ensure_schema(&conn)?;
+    let rows = conn
+        .prepare("SELECT id, audio_path FROM dt_history WHERE audio_path IS NOT NULL")
+        .and_then(|mut stmt| {
+            stmt.query_map([], |row| {
+                Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?))
+            })?
+            .collect::<Result<Vec<_>, _>>()
+        })
+        .map_err(|error| format!("Falha ao ler o historico: {error}"))?;
+
+    for (id, path) in rows {
+        if let Some(rebased) = rebase_audio_path(&path, from, to) {
+            conn.execute(
+                "UPDATE dt_history SET audio_path = ?2 WHERE id = ?1",
+                rusqlite::params![id, rebased],
+            )
+            .map_err(|error| format!("Falha ao atualizar o historico: {error}"))?;
+        }
+    }
+
+    Ok(())
+}
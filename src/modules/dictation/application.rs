@@ -1,33 +1,650 @@
 #![allow(dead_code)]
 
-use crate::modules::audio::domain::CapturedAudio;
+use crate::modules::audio::domain::{CapturedAudio, rms_level, split_on_silence};
+use crate::modules::audio::infrastructure::microphone::{self, Recorder};
+use crate::modules::audio::infrastructure::playback as audio_playback;
+use crate::modules::audio::infrastructure::spool::{SpoolWriter, SpooledAudio};
+use crate::modules::audio::infrastructure::storage as audio_storage;
 use crate::modules::dictation::domain::{
-    DictationConfig, DictationOutput, DualTranscriptOutput, PreparedAudio, TARGET_SAMPLE_RATE,
-    TranscriptionJob,
+    AudioFormat, CHUNKED_TRANSCRIPTION_MIN_SECS, DictationCaptureMode, DictationConfig,
+    DictationOutput, DictationProvider, DualTranscriptOutput, HistoryAudioRetention, HistoryEntry,
+    MAX_PARALLEL_CHUNKS, ModelComparison, ModelTranscript, PRE_ROLL_MS, PreparedAudio,
+    ProgressTracker, ProviderTranscript, ResampleQuality, TRANSCRIPTION_CHUNK_MAX_SECS,
+    TRANSCRIPTION_CHUNK_MIN_SECS, TranscriptionJob, TranscriptionResult, TranscriptionStage,
+    TranscriptionUsage, UsageStats, fill_uri_template, notification_preview,
 };
 use crate::modules::dictation::infrastructure;
-use base64::Engine;
+use crate::modules::dictation::infrastructure::deepgram::StreamingSession;
+use crate::modules::dictation::infrastructure::webhook::{self, WebhookPayload};
+use crate::modules::dictation::infrastructure::{history, hook, models, providers, usage};
+use crate::modules::live_transcription::infrastructure::db;
+use crate::modules::settings::domain::{AppSettings, SettingsForm};
+use crate::platform::{desktop, keyboard};
+use crate::support::error::OpenVoiceError;
 use hound::{SampleFormat, WavSpec, WavWriter};
+use std::f64::consts::PI;
 use std::io::Cursor;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+use std::time::Duration;
+
+const TYPING_START_DELAY_MS: u64 = 250;
+/// Entries shown in the history tab.
+const HISTORY_LIMIT: usize = 500;
+/// Passband edge as a fraction of the output Nyquist frequency, leaving a
+/// small transition band so the windowed kernel can roll off before it.
+const RESAMPLE_CUTOFF: f64 = 0.95;
+/// Loudness the normalisation step aims for (about -20 dBFS RMS).
+const NORMALIZE_TARGET_RMS: f32 = 0.1;
+/// Recordings quieter than this (about -50 dBFS) are treated as noise and
+/// left untouched rather than amplified.
+const NORMALIZE_NOISE_FLOOR_RMS: f32 = 0.003;
+const NORMALIZE_MAX_GAIN: f32 = 10.0;
+const NORMALIZE_PEAK_CEILING: f32 = 0.98;
+
+/// Opens the always-on pre-roll microphone when the user opted in.
+pub fn start_pre_roll(settings: &AppSettings) -> Option<Recorder> {
+    if !settings.dictation_pre_roll_enabled {
+        return None;
+    }
+
+    match microphone::start_pre_roll(PRE_ROLL_MS, settings.dictation_capture_overrides()) {
+        Ok(recorder) => Some(recorder),
+        Err(error) => {
+            log_warn!("[openvoice][dictation] pre-roll unavailable: {error}");
+            None
+        }
+    }
+}
 
 pub fn transcribe_capture(
     config: DictationConfig,
     capture: CapturedAudio,
-) -> Result<DictationOutput, String> {
+) -> Result<DictationOutput, OpenVoiceError> {
     let duration_seconds = capture.duration_seconds();
-    let prepared = prepare_audio(capture)?;
-    let transcript = infrastructure::transcribe(&config, &prepared.wav_base64)?;
-    let transcript = transcript.trim().to_owned();
+    config.progress.set(TranscriptionStage::Encoding);
+    let (samples, sample_rate, channels) = prepare_samples(
+        std::iter::once(Ok(capture.samples)),
+        capture.sample_rate,
+        capture.channels,
+        &config,
+    )?;
 
-    if transcript.is_empty() {
+    finish_transcription(&config, &samples, sample_rate, channels, duration_seconds)
+}
+
+/// Transcribes a take that was spooled to disk, streaming it through the
+/// downmix and resampler so the raw recording is never fully in memory.
+pub fn transcribe_spooled(
+    config: DictationConfig,
+    audio: SpooledAudio,
+) -> Result<DictationOutput, OpenVoiceError> {
+    let duration_seconds = audio.duration_seconds();
+    config.progress.set(TranscriptionStage::Encoding);
+    let (samples, sample_rate, channels) =
+        prepare_samples(audio.chunks()?, audio.sample_rate, audio.channels, &config)?;
+
+    finish_transcription(&config, &samples, sample_rate, channels, duration_seconds)
+}
+
+/// Merges the microphone take with the system audio recorded next to it into
+/// a new spooled take at the system rate, mixed down or as a stereo pair.
+pub fn combine_capture(
+    microphone: &SpooledAudio,
+    system: &CapturedAudio,
+    mode: DictationCaptureMode,
+    quality: ResampleQuality,
+) -> Result<SpooledAudio, String> {
+    let sample_rate = system.sample_rate;
+    let mut resampler = Resampler::new(microphone.sample_rate, sample_rate, quality);
+
+    for chunk in microphone.chunks()? {
+        resampler.push(&downmix_to_mono(&chunk?, microphone.channels)?);
+    }
+
+    let microphone = resampler.finish();
+    let system = downmix_to_mono(&system.samples, system.channels)?;
+    let (channels, samples) = mix_tracks(&microphone, &system, mode);
+
+    let writer = SpoolWriter::create(sample_rate, channels)?;
+    writer.write(samples);
+    writer.finish()
+}
+
+/// Both captures stop at the same moment but the microphone may start
+/// earlier (pre-roll, parec start-up), so the tracks are aligned at their
+/// ends and the shorter one is padded with leading silence.
+fn mix_tracks(microphone: &[f32], system: &[f32], mode: DictationCaptureMode) -> (u16, Vec<f32>) {
+    let frames = microphone.len().max(system.len());
+    let sample_at = |track: &[f32], index: usize| {
+        (index + track.len())
+            .checked_sub(frames)
+            .map_or(0.0, |index| track[index])
+    };
+
+    match mode {
+        DictationCaptureMode::Split => (
+            2,
+            (0..frames)
+                .flat_map(|index| [sample_at(microphone, index), sample_at(system, index)])
+                .collect(),
+        ),
+        DictationCaptureMode::Microphone | DictationCaptureMode::Mixed => (
+            1,
+            (0..frames)
+                .map(|index| (sample_at(microphone, index) + sample_at(system, index)) * 0.5)
+                .collect(),
+        ),
+    }
+}
+
+fn finish_transcription(
+    config: &DictationConfig,
+    samples: &[f32],
+    sample_rate: u32,
+    channels: u16,
+    duration_seconds: f32,
+) -> Result<DictationOutput, OpenVoiceError> {
+    config.cancel.check()?;
+    let transcript = if duration_seconds > CHUNKED_TRANSCRIPTION_MIN_SECS {
+        transcribe_chunked(config, samples, sample_rate, channels)?
+    } else {
+        let prepared = encode_samples(samples, sample_rate, channels, config)?;
+        providers::get(config.provider).transcribe(config, &prepared)?
+    };
+    config.cancel.check()?;
+
+    into_output(transcript, duration_seconds)
+}
+
+/// Cuts a long take on its pauses and transcribes the pieces on a few
+/// worker threads, stitching the text back in recording order. A stereo
+/// take is cut on the pauses of its downmix, keeping whole frames.
+fn transcribe_chunked(
+    config: &DictationConfig,
+    samples: &[f32],
+    sample_rate: u32,
+    channels: u16,
+) -> Result<ProviderTranscript, OpenVoiceError> {
+    let width = usize::from(channels.max(1));
+    let ranges = split_on_silence(
+        &downmix_to_mono(samples, channels.max(1))?,
+        sample_rate,
+        TRANSCRIPTION_CHUNK_MIN_SECS,
+        TRANSCRIPTION_CHUNK_MAX_SECS,
+    );
+    let provider = providers::get(config.provider);
+    let next = AtomicUsize::new(0);
+    let done = AtomicUsize::new(0);
+    let results = Mutex::new(vec![None; ranges.len()]);
+    // Byte-level upload progress means nothing with several uploads in
+    // flight, so workers report to a private tracker and the job counts
+    // finished chunks instead.
+    let worker_config = DictationConfig {
+        progress: ProgressTracker::default(),
+        ..config.clone()
+    };
+    config.progress.set(TranscriptionStage::Chunks {
+        done: 0,
+        total: ranges.len(),
+    });
+
+    log_debug!(
+        "[openvoice][dictation] transcribing {} chunks in parallel",
+        ranges.len()
+    );
+
+    thread::scope(|scope| {
+        for _ in 0..MAX_PARALLEL_CHUNKS.min(ranges.len()) {
+            scope.spawn(|| {
+                loop {
+                    let index = next.fetch_add(1, Ordering::Relaxed);
+                    let Some(range) = ranges.get(index) else {
+                        break;
+                    };
+
+                    let offset_secs = range.start as f32 / sample_rate.max(1) as f32;
+                    let result = config.cancel.check().and_then(|()| {
+                        let prepared = encode_samples(
+                            &samples[range.start * width..range.end * width],
+                            sample_rate,
+                            channels,
+                            config,
+                        )?;
+                        provider.transcribe(&worker_config, &prepared)
+                    });
+                    let result = result.map(|part| (offset_secs, part));
+                    let failed = result.is_err();
+
+                    if let Ok(mut results) = results.lock() {
+                        results[index] = Some(result);
+                    }
+                    if failed {
+                        // Let the remaining workers stop picking up chunks.
+                        next.store(ranges.len(), Ordering::Relaxed);
+                    } else {
+                        config.progress.set(TranscriptionStage::Chunks {
+                            done: done.fetch_add(1, Ordering::Relaxed) + 1,
+                            total: ranges.len(),
+                        });
+                    }
+                }
+            });
+        }
+    });
+
+    let results = results.into_inner().map_err(|_| {
+        OpenVoiceError::Other(String::from("Falha ao juntar os trechos transcritos."))
+    })?;
+    let mut parts = Vec::with_capacity(results.len());
+
+    for (index, result) in results.into_iter().enumerate() {
+        match result {
+            Some(Ok(part)) => parts.push(part),
+            Some(Err(error)) => {
+                log_error!(
+                    "[openvoice][dictation] chunk {} of {} failed: {error}",
+                    index + 1,
+                    ranges.len()
+                );
+                return Err(error);
+            }
+            None => {
+                return Err(OpenVoiceError::Other(format!(
+                    "O trecho {} de {} nao foi transcrito.",
+                    index + 1,
+                    ranges.len()
+                )));
+            }
+        }
+    }
+
+    Ok(ProviderTranscript::stitch(parts))
+}
+
+/// Opens a live session for the take when streaming is on and the provider
+/// can do it; otherwise the take is uploaded once it finishes.
+pub fn start_streaming(
+    config: &DictationConfig,
+    sample_rate: u32,
+    channels: u16,
+) -> Option<StreamingSession> {
+    if !config.streaming {
+        return None;
+    }
+
+    providers::get(config.provider).start_streaming(config, sample_rate, channels)
+}
+
+/// Whether the provider can transcribe while the take is still recording.
+pub fn provider_supports_streaming(provider: DictationProvider) -> bool {
+    providers::get(provider).supports_streaming()
+}
+
+/// Types the transcript into the focused window. Waits a moment first so
+/// the shortcut's modifier keys are released and don't combine with it.
+pub fn type_transcript(text: &str, delay_ms: u32) -> Result<(), String> {
+    thread::sleep(Duration::from_millis(TYPING_START_DELAY_MS));
+    let injector = keyboard::type_text(text, delay_ms)?;
+    log_debug!(
+        "[openvoice][dictation] typed {} chars with {injector}",
+        text.chars().count()
+    );
+    Ok(())
+}
+
+/// Erases a transcription typed into the focused window, one Backspace
+/// per character.
+pub fn erase_typed(chars: usize) -> Result<(), String> {
+    thread::sleep(Duration::from_millis(TYPING_START_DELAY_MS));
+    let injector = keyboard::erase_text(chars)?;
+    log_debug!("[openvoice][dictation] erased {chars} typed chars with {injector}");
+    Ok(())
+}
+
+/// Pastes the clipboard into the focused window and presses Enter, after
+/// the same pause as typing so the shortcut's modifiers are released.
+pub fn submit_transcript() -> Result<(), String> {
+    thread::sleep(Duration::from_millis(TYPING_START_DELAY_MS));
+    let injector = keyboard::paste_and_submit()?;
+    log_info!("[openvoice][dictation] pasted and submitted with {injector}");
+    Ok(())
+}
+
+/// Registered transcription backends, in the order settings lists them.
+pub fn list_providers() -> Vec<DictationProvider> {
+    providers::all().map(|provider| provider.id()).collect()
+}
+
+/// Selects the dictation backend in the settings form by provider id.
+pub fn set_provider(form: &mut SettingsForm, id: &str) -> Result<(), String> {
+    let provider =
+        providers::find(id).ok_or_else(|| format!("Provedor de transcricao desconhecido: {id}"))?;
+
+    form.dictation_provider = provider.id().code().to_owned();
+    Ok(())
+}
+
+/// Saves the timed segments as SRT next to the recordings, or under the
+/// app data folder when recordings are not kept.
+pub fn export_srt(result: &TranscriptionResult, recordings_dir: &str) -> Result<PathBuf, String> {
+    if result.segments.is_empty() {
         return Err(String::from(
-            "A API respondeu sem texto. Tente falar de forma mais clara.",
+            "O ultimo ditado nao tem timestamps para exportar.",
         ));
     }
 
+    let dir = match audio_storage::resolve_user_dir(recordings_dir) {
+        Some(dir) => dir,
+        None => audio_storage::data_dir()?.join("subtitles"),
+    };
+
+    infrastructure::save_srt(result, &dir)
+}
+
+/// Opens the user's URI template with the transcription filled in.
+pub fn open_transcript_uri(template: &str, text: &str) -> Result<(), String> {
+    desktop::open_uri(&fill_uri_template(template, text))
+}
+
+/// Shows a desktop notification with the start of the transcription.
+pub fn notify_finished(title: &str, text: &str) -> Result<(), String> {
+    desktop::notify(title, &notification_preview(text))
+}
+
+/// Shows a desktop notification for a take that failed.
+pub fn notify_failed(error: &str) -> Result<(), String> {
+    desktop::notify("Falha na transcricao", &notification_preview(error))
+}
+
+/// Sends one take to every model in `models` at the same time so their
+/// transcripts can be compared side by side.
+pub fn compare_models(
+    config: DictationConfig,
+    audio: SpooledAudio,
+    models: Vec<String>,
+) -> Result<ModelComparison, OpenVoiceError> {
+    let (samples, sample_rate, channels) =
+        prepare_samples(audio.chunks()?, audio.sample_rate, audio.channels, &config)?;
+    let prepared = encode_samples(&samples, sample_rate, channels, &config)?;
+
+    let transcripts = thread::scope(|scope| {
+        let workers: Vec<_> = models
+            .into_iter()
+            .map(|model| {
+                let (config, prepared) = (&config, &prepared);
+                scope.spawn(move || {
+                    let result =
+                        infrastructure::transcribe(config, &model, &config.api_key, prepared)
+                            .map(|transcript| transcript.result.text.trim().to_owned());
+                    ModelTranscript { model, result }
+                })
+            })
+            .collect();
+
+        workers
+            .into_iter()
+            .map(|worker| {
+                worker.join().unwrap_or_else(|_| ModelTranscript {
+                    model: String::new(),
+                    result: Err(OpenVoiceError::Other(String::from(
+                        "A comparacao foi interrompida.",
+                    ))),
+                })
+            })
+            .collect()
+    });
+
+    Ok(ModelComparison { transcripts })
+}
+
+/// OpenRouter models that accept audio, for the settings dropdown. Cached
+/// for a day unless `refresh` is set.
+pub fn list_models(api_key: &str, base_url: &str, refresh: bool) -> Result<Vec<String>, String> {
+    models::list_audio_models(api_key, base_url, refresh)
+}
+
+/// Adds a finished request to the usage counters.
+pub fn record_usage(usage: &TranscriptionUsage) -> Result<UsageStats, String> {
+    usage::record_usage(usage)
+}
+
+/// Transcribes the previous take again, with `model` instead of the
+/// configured one when given.
+pub fn retranscribe_last(
+    config: DictationConfig,
+    audio: SpooledAudio,
+    model: Option<&str>,
+) -> Result<DictationOutput, OpenVoiceError> {
+    let config = match model {
+        Some(model) => config.with_model(model),
+        None => config,
+    };
+    transcribe_spooled(config, audio)
+}
+
+/// The take behind a transcription, kept with its history entry.
+pub struct HistoryAudio {
+    pub audio: SpooledAudio,
+    pub config: DictationConfig,
+    pub retention: HistoryAudioRetention,
+}
+
+/// Keeps a finished transcription in the history, with its audio when
+/// given. Failing to keep the audio never loses the text.
+pub fn save_history(
+    output: &DictationOutput,
+    provider: DictationProvider,
+    audio: Option<HistoryAudio>,
+) -> Result<HistoryEntry, String> {
+    let entry = history::add_entry(HistoryEntry {
+        id: 0,
+        created_at: db::now_iso(),
+        provider: provider.code().to_owned(),
+        model: output.model.clone(),
+        api_key: output.api_key.clone(),
+        duration_seconds: output.duration_seconds,
+        cost_usd: output.usage.map(|usage| usage.cost_usd),
+        text: output.transcript.clone(),
+        audio_path: None,
+        latency_ms: output.latency_ms,
+    })?;
+    let Some(audio) = audio else {
+        return Ok(entry);
+    };
+
+    match keep_history_audio(entry.id, &audio) {
+        Ok(path) => Ok(HistoryEntry {
+            audio_path: Some(path),
+            ..entry
+        }),
+        Err(error) => {
+            log_warn!("[openvoice][dictation] history audio not kept: {error}");
+            Ok(entry)
+        }
+    }
+}
+
+/// Stores the take as it would be uploaded, compressed to Ogg/Opus when
+/// `ffmpeg` is around, then trims older takes to the retention limits.
+fn keep_history_audio(id: i64, audio: &HistoryAudio) -> Result<PathBuf, OpenVoiceError> {
+    let (samples, sample_rate, channels) = prepare_samples(
+        audio.audio.chunks()?,
+        audio.audio.sample_rate,
+        audio.audio.channels,
+        &audio.config,
+    )?;
+    let (bytes, format) = encode_for_upload(
+        samples_to_wav(&samples, sample_rate, channels)?,
+        AudioFormat::Ogg,
+    );
+    let path = history::save_audio(id, &bytes, format)?;
+
+    if let Err(error) = history::prune_audio(audio.retention) {
+        log_warn!("[openvoice][dictation] history audio not pruned: {error}");
+    }
+
+    Ok(path)
+}
+
+/// Plays the audio kept with a history entry.
+pub fn play_history_entry(id: i64) -> Result<(), String> {
+    let path = history_audio_path(id)?;
+    infrastructure::decode_audio(&path).and_then(audio_playback::play)
+}
+
+/// Sends the audio kept with a history entry through the configured
+/// provider again, with `model` instead of the configured one when given,
+/// and replaces the entry's text with the result.
+pub fn retranscribe_entry(
+    config: DictationConfig,
+    id: i64,
+    model: Option<&str>,
+) -> Result<HistoryEntry, OpenVoiceError> {
+    let path = history_audio_path(id)?;
+    let entry = history::get_entry(id)?
+        .ok_or_else(|| OpenVoiceError::Other(String::from("A transcricao nao existe mais.")))?;
+    let config = match model {
+        Some(model) => config.with_model(model),
+        None => config,
+    };
+    let format = match path.extension().and_then(|extension| extension.to_str()) {
+        Some("ogg") => AudioFormat::Ogg,
+        _ => AudioFormat::Wav,
+    };
+    let audio = std::fs::read(&path)
+        .map_err(|error| format!("Falha ao ler {}: {error}", path.display()))?;
+    let prepared = PreparedAudio {
+        audio: audio.into(),
+        format,
+    };
+
+    let transcript = providers::get(config.provider).transcribe(&config, &prepared)?;
+    let output = into_output(transcript, entry.duration_seconds)?;
+    if let Some(usage) = output.usage
+        && let Err(error) = usage::record_usage(&usage)
+    {
+        log_warn!("[openvoice][dictation] usage not recorded: {error}");
+    }
+
+    let entry = HistoryEntry {
+        provider: config.provider.code().to_owned(),
+        model: output.model,
+        api_key: output.api_key,
+        cost_usd: output.usage.map(|usage| usage.cost_usd),
+        text: output.transcript,
+        ..entry
+    };
+    history::update_transcription(&entry)?;
+
+    Ok(entry)
+}
+
+fn history_audio_path(id: i64) -> Result<PathBuf, String> {
+    history::get_entry(id)?
+        .and_then(|entry| entry.audio_path)
+        .filter(|path| path.exists())
+        .ok_or_else(|| String::from("O audio desta transcricao nao foi guardado."))
+}
+
+/// POSTs the transcription to the user's webhook.
+pub fn notify_webhook(url: &str, output: &DictationOutput) -> Result<(), String> {
+    let stats = output.stats();
+    webhook::post(
+        url,
+        &WebhookPayload {
+            text: &output.transcript,
+            duration: output.duration_seconds,
+            model: output.model.as_deref(),
+            timestamp: db::now_iso(),
+            words: stats.words,
+            characters: stats.characters,
+            latency_ms: stats.latency_ms,
+        },
+    )
+}
+
+/// Pipes the transcription into the user's post-transcription command.
+pub fn run_hook(command: &str, timeout_secs: u32, output: &DictationOutput) -> Result<(), String> {
+    hook::run(
+        command,
+        &output.transcript,
+        Duration::from_secs(u64::from(timeout_secs)),
+    )
+}
+
+/// Latest transcriptions, newest first.
+pub fn get_history() -> Result<Vec<HistoryEntry>, String> {
+    history::list_entries(HISTORY_LIMIT)
+}
+
+/// Text of the newest history entry, for copying it again after a restart.
+pub fn last_transcription() -> Result<Option<String>, String> {
+    history::list_entries(1).map(|entries| entries.into_iter().next().map(|entry| entry.text))
+}
+
+pub fn delete_history_entry(id: i64) -> Result<i64, String> {
+    history::delete_entry(id).map(|()| id)
+}
+
+pub fn clear_history() -> Result<(), String> {
+    history::clear()
+}
+
+/// Dictation requests, tokens and cost for today and the current month.
+pub fn get_usage_stats() -> Result<UsageStats, String> {
+    usage::usage_stats()
+}
+
+/// Waits for the streamed transcript, falling back to uploading the spooled
+/// take when the live session failed or heard nothing.
+pub fn finish_streaming(
+    config: DictationConfig,
+    stream: StreamingSession,
+    audio: SpooledAudio,
+) -> Result<DictationOutput, OpenVoiceError> {
+    match stream.finish() {
+        Ok(transcript) if !transcript.trim().is_empty() => into_output(
+            ProviderTranscript::text(transcript),
+            audio.duration_seconds(),
+        ),
+        Ok(_) => transcribe_spooled(config, audio),
+        Err(error) => {
+            log_warn!("[openvoice][dictation] streaming failed, uploading take: {error}");
+            transcribe_spooled(config, audio)
+        }
+    }
+}
+
+fn into_output(
+    transcript: ProviderTranscript,
+    duration_seconds: f32,
+) -> Result<DictationOutput, OpenVoiceError> {
+    let ProviderTranscript {
+        result,
+        usage,
+        model,
+        api_key,
+    } = transcript;
+    let transcript = result.text.trim().to_owned();
+
+    if transcript.is_empty() {
+        return Err(OpenVoiceError::InvalidResponse(String::from(
+            "A API respondeu sem texto. Tente falar de forma mais clara.",
+        )));
+    }
+
     Ok(DictationOutput {
         transcript,
         duration_seconds,
+        clipped: false,
+        usage,
+        model,
+        api_key,
+        segments: result.segments,
+        latency_ms: None,
     })
 }
 
@@ -37,9 +654,11 @@ pub fn transcribe_session(
 ) -> Result<DualTranscriptOutput, String> {
     let session = job.session;
     let microphone = transcribe_capture(config.clone(), session.microphone.audio.clone())
-        .map(|output| output.transcript);
-    let system =
-        transcribe_capture(config, session.system.audio.clone()).map(|output| output.transcript);
+        .map(|output| output.transcript)
+        .map_err(String::from);
+    let system = transcribe_capture(config, session.system.audio.clone())
+        .map(|output| output.transcript)
+        .map_err(String::from);
 
     let mic_transcript = microphone.as_ref().ok().cloned();
     let system_transcript = system.as_ref().ok().cloned();
@@ -68,20 +687,109 @@ pub fn transcribe_session(
     Ok(output)
 }
 
-fn prepare_audio(capture: CapturedAudio) -> Result<PreparedAudio, String> {
-    if capture.samples.is_empty() {
-        return Err(String::from("Nenhum audio foi capturado."));
+/// Runs interleaved chunks (each holding whole frames) through the mono
+/// conversion and resampler, returning the take, its rate and its channel
+/// count. A split capture keeps its microphone and system channels, each
+/// resampled on its own; anything else comes out mono.
+fn prepare_samples(
+    chunks: impl IntoIterator<Item = Result<Vec<f32>, String>>,
+    sample_rate: u32,
+    channels: u16,
+    config: &DictationConfig,
+) -> Result<(Vec<f32>, u32, u16), OpenVoiceError> {
+    let target_rate = config.target_sample_rate.resolve(sample_rate);
+    let kept_channels = if config.split_channels && channels == 2 {
+        channels
+    } else {
+        1
+    };
+    let mut resamplers = (0..kept_channels)
+        .map(|_| Resampler::new(sample_rate, target_rate, config.resample_quality))
+        .collect::<Vec<_>>();
+
+    for chunk in chunks {
+        let chunk = chunk?;
+        if kept_channels > 1 {
+            for (channel, resampler) in (0..kept_channels).zip(&mut resamplers) {
+                resampler.push(&extract_channel(&chunk, channels, channel)?);
+            }
+            continue;
+        }
+
+        let mono = match config.input_channel {
+            Some(channel) => extract_channel(&chunk, channels, channel)?,
+            None => downmix_to_mono(&chunk, channels)?,
+        };
+        resamplers[0].push(&mono);
+    }
+
+    if resamplers[0].consumed == 0 {
+        return Err(OpenVoiceError::NoAudio);
+    }
+
+    let tracks = resamplers
+        .into_iter()
+        .map(Resampler::finish)
+        .collect::<Vec<_>>();
+    let mut normalized = interleave(&tracks);
+    if config.normalize_audio {
+        normalize_loudness(&mut normalized);
+    }
+
+    Ok((normalized, target_rate, kept_channels))
+}
+
+/// Interleaves equally long tracks into frames, one sample per track.
+fn interleave(tracks: &[Vec<f32>]) -> Vec<f32> {
+    match tracks {
+        [track] => track.clone(),
+        _ => {
+            let frames = tracks.iter().map(Vec::len).min().unwrap_or_default();
+            (0..frames)
+                .flat_map(|index| tracks.iter().map(move |track| track[index]))
+                .collect()
+        }
     }
+}
 
-    let mono = downmix_to_mono(&capture.samples, capture.channels)?;
-    let normalized = resample_linear(&mono, capture.sample_rate, TARGET_SAMPLE_RATE);
-    let wav = samples_to_wav(&normalized, TARGET_SAMPLE_RATE)?;
+/// Encodes interleaved samples in the upload format the settings ask for.
+fn encode_samples(
+    samples: &[f32],
+    sample_rate: u32,
+    channels: u16,
+    config: &DictationConfig,
+) -> Result<PreparedAudio, OpenVoiceError> {
+    let wav = samples_to_wav(samples, sample_rate, channels)?;
+    let (audio, format) = encode_for_upload(wav, config.audio_format);
 
     Ok(PreparedAudio {
-        wav_base64: base64::engine::general_purpose::STANDARD.encode(wav),
+        audio: audio.into(),
+        format,
     })
 }
 
+/// Compresses the WAV when requested. Encoding failures (e.g. ffmpeg not
+/// installed) fall back to the WAV so a take is never lost over it.
+fn encode_for_upload(wav: Vec<u8>, format: AudioFormat) -> (Vec<u8>, AudioFormat) {
+    match format {
+        AudioFormat::Wav => (wav, AudioFormat::Wav),
+        AudioFormat::Ogg => match infrastructure::encode_ogg_opus(&wav) {
+            Ok(ogg) => {
+                log_debug!(
+                    "[openvoice][dictation] encoded ogg/opus wav_bytes={} ogg_bytes={}",
+                    wav.len(),
+                    ogg.len()
+                );
+                (ogg, AudioFormat::Ogg)
+            }
+            Err(error) => {
+                log_warn!("[openvoice][dictation] falling back to wav: {error}");
+                (wav, AudioFormat::Wav)
+            }
+        },
+    }
+}
+
 fn downmix_to_mono(samples: &[f32], channels: u16) -> Result<Vec<f32>, String> {
     match channels {
         0 => Err(String::from("O dispositivo retornou zero canais.")),
@@ -97,32 +805,188 @@ fn downmix_to_mono(samples: &[f32], channels: u16) -> Result<Vec<f32>, String> {
     }
 }
 
-fn resample_linear(samples: &[f32], source_rate: u32, target_rate: u32) -> Vec<f32> {
-    if samples.is_empty() || source_rate == 0 || source_rate == target_rate {
-        return samples.to_vec();
+/// Picks a single zero-based channel out of interleaved frames.
+fn extract_channel(samples: &[f32], channels: u16, channel: u16) -> Result<Vec<f32>, String> {
+    if channel >= channels {
+        return Err(format!(
+            "O canal de entrada {} nao existe; o microfone tem {channels} canal(is).",
+            channel + 1
+        ));
+    }
+
+    Ok(samples
+        .chunks_exact(usize::from(channels))
+        .map(|frame| frame[usize::from(channel)])
+        .collect())
+}
+
+/// Band-limited resampling with a Blackman-windowed sinc kernel. When
+/// downsampling, the kernel is widened so content above the new Nyquist
+/// frequency is filtered out instead of folding back as aliasing.
+fn resample(
+    samples: &[f32],
+    source_rate: u32,
+    target_rate: u32,
+    quality: ResampleQuality,
+) -> Vec<f32> {
+    let mut resampler = Resampler::new(source_rate, target_rate, quality);
+    resampler.push(samples);
+    resampler.finish()
+}
+
+/// Streaming form of `resample`: input arrives in chunks and only the
+/// samples still under the kernel window are kept between them.
+struct Resampler {
+    ratio: f64,
+    cutoff: f64,
+    half_width: f64,
+    passthrough: bool,
+    /// Source samples starting at absolute index `offset`.
+    window: Vec<f32>,
+    offset: usize,
+    consumed: usize,
+    next_output: usize,
+    output: Vec<f32>,
+}
+
+impl Resampler {
+    fn new(source_rate: u32, target_rate: u32, quality: ResampleQuality) -> Self {
+        let passthrough = source_rate == 0 || target_rate == 0 || source_rate == target_rate;
+        let ratio = if passthrough {
+            1.0
+        } else {
+            source_rate as f64 / target_rate as f64
+        };
+        let cutoff = RESAMPLE_CUTOFF * (1.0 / ratio).min(1.0);
+
+        Self {
+            ratio,
+            cutoff,
+            half_width: quality.half_taps() as f64 / cutoff,
+            passthrough,
+            window: Vec::new(),
+            offset: 0,
+            consumed: 0,
+            next_output: 0,
+            output: Vec::new(),
+        }
+    }
+
+    fn push(&mut self, samples: &[f32]) {
+        self.consumed += samples.len();
+
+        if self.passthrough {
+            self.output.extend_from_slice(samples);
+            return;
+        }
+
+        self.window.extend_from_slice(samples);
+
+        // Emit every output whose kernel is fully covered by the input so far.
+        loop {
+            let center = self.next_output as f64 * self.ratio;
+            if (center + self.half_width).floor() as usize >= self.consumed {
+                break;
+            }
+
+            let value = self.sample_at(center);
+            self.output.push(value);
+            self.next_output += 1;
+        }
+
+        let center = self.next_output as f64 * self.ratio;
+        let keep_from = ((center - self.half_width).ceil().max(0.0) as usize).min(self.consumed);
+        if keep_from > self.offset {
+            self.window.drain(..keep_from - self.offset);
+            self.offset = keep_from;
+        }
+    }
+
+    fn finish(mut self) -> Vec<f32> {
+        if !self.passthrough {
+            let target_len = ((self.consumed as f64) / self.ratio).ceil() as usize;
+
+            while self.next_output < target_len {
+                let center = self.next_output as f64 * self.ratio;
+                let value = self.sample_at(center);
+                self.output.push(value);
+                self.next_output += 1;
+            }
+        }
+
+        self.output
+    }
+
+    fn sample_at(&self, center: f64) -> f32 {
+        let last_index = self.consumed as isize - 1;
+        let first = ((center - self.half_width).ceil() as isize).max(self.offset as isize);
+        let last = ((center + self.half_width).floor() as isize).min(last_index);
+        let mut acc = 0.0;
+        let mut weight_sum = 0.0;
+
+        for source_index in first..=last {
+            let offset = source_index as f64 - center;
+            let weight =
+                self.cutoff * sinc(self.cutoff * offset) * blackman(offset / self.half_width);
+
+            acc += f64::from(self.window[source_index as usize - self.offset]) * weight;
+            weight_sum += weight;
+        }
+
+        // Normalising by the kernel sum keeps unity gain at the edges,
+        // where part of the kernel falls outside the buffer.
+        if weight_sum.abs() > f64::EPSILON {
+            (acc / weight_sum) as f32
+        } else {
+            0.0
+        }
+    }
+}
+
+/// Scales the recording towards `NORMALIZE_TARGET_RMS`. Gain is capped so
+/// near-silent takes are not turned into loud hiss and peaks never clip.
+/// Returns the gain that was applied.
+fn normalize_loudness(samples: &mut [f32]) -> f32 {
+    let rms = rms_level(samples);
+
+    if rms < NORMALIZE_NOISE_FLOOR_RMS {
+        return 1.0;
     }
 
-    let ratio = source_rate as f64 / target_rate as f64;
-    let target_len = ((samples.len() as f64) / ratio).ceil() as usize;
-    let mut output = Vec::with_capacity(target_len);
+    let peak = samples
+        .iter()
+        .fold(0.0_f32, |peak, sample| peak.max(sample.abs()));
+    let gain = (NORMALIZE_TARGET_RMS / rms)
+        .min(NORMALIZE_MAX_GAIN)
+        .min(NORMALIZE_PEAK_CEILING / peak);
+
+    for sample in samples.iter_mut() {
+        *sample *= gain;
+    }
+
+    gain
+}
 
-    for index in 0..target_len {
-        let source_position = index as f64 * ratio;
-        let left_index = source_position.floor() as usize;
-        let right_index = (left_index + 1).min(samples.len().saturating_sub(1));
-        let fraction = (source_position - left_index as f64) as f32;
-        let left = samples[left_index];
-        let right = samples[right_index];
+fn sinc(x: f64) -> f64 {
+    if x.abs() < 1e-9 {
+        1.0
+    } else {
+        (PI * x).sin() / (PI * x)
+    }
+}
 
-        output.push(left + ((right - left) * fraction));
+/// Blackman window over `position` in -1.0..=1.0.
+fn blackman(position: f64) -> f64 {
+    if position.abs() >= 1.0 {
+        return 0.0;
     }
 
-    output
+    0.42 + 0.5 * (PI * position).cos() + 0.08 * (2.0 * PI * position).cos()
 }
 
-fn samples_to_wav(samples: &[f32], sample_rate: u32) -> Result<Vec<u8>, String> {
+fn samples_to_wav(samples: &[f32], sample_rate: u32, channels: u16) -> Result<Vec<u8>, String> {
     let spec = WavSpec {
-        channels: 1,
+        channels,
         sample_rate,
         bits_per_sample: 16,
         sample_format: SampleFormat::Int,
@@ -152,7 +1016,40 @@ fn samples_to_wav(samples: &[f32], sample_rate: u32) -> Result<Vec<u8>, String>
 
 #[cfg(test)]
 mod tests {
-    use super::{downmix_to_mono, resample_linear, samples_to_wav};
+    use super::{
+        NORMALIZE_MAX_GAIN, NORMALIZE_PEAK_CEILING, NORMALIZE_TARGET_RMS, ResampleQuality,
+        Resampler, downmix_to_mono, encode_samples, extract_channel, mix_tracks,
+        normalize_loudness, prepare_samples, resample, samples_to_wav,
+    };
+    use crate::modules::audio::domain::rms_level;
+    use crate::modules::dictation::domain::{DictationCaptureMode, DictationConfig};
+    use crate::modules::settings::domain::AppSettings;
+
+    fn sine(frequency: f64, sample_rate: u32, len: usize) -> Vec<f32> {
+        (0..len)
+            .map(|index| {
+                let t = index as f64 / f64::from(sample_rate);
+                (2.0 * std::f64::consts::PI * frequency * t).sin() as f32 * 0.5
+            })
+            .collect()
+    }
+
+    /// Amplitude of a single frequency bin (Goertzel), ignoring the filter
+    /// warm-up at both ends of the buffer.
+    fn tone_amplitude(samples: &[f32], frequency: f64, sample_rate: u32) -> f64 {
+        let trimmed = &samples[samples.len() / 8..samples.len() * 7 / 8];
+        let omega = 2.0 * std::f64::consts::PI * frequency / f64::from(sample_rate);
+        let (mut prev, mut prev2) = (0.0, 0.0);
+
+        for sample in trimmed {
+            let current = f64::from(*sample) + 2.0 * omega.cos() * prev - prev2;
+            prev2 = prev;
+            prev = current;
+        }
+
+        let power = prev * prev + prev2 * prev2 - 2.0 * omega.cos() * prev * prev2;
+        2.0 * power.sqrt() / trimmed.len() as f64
+    }
 
     #[test]
     fn downmixes_stereo_frames() {
@@ -163,17 +1060,161 @@ mod tests {
     }
 
     #[test]
-    fn resamples_audio_with_linear_interpolation() {
-        let resampled = resample_linear(&[0.0, 0.5, 1.0, 0.5], 8_000, 16_000);
+    fn extracts_single_channel_from_interleaved_frames() {
+        let frames = [0.1, 0.0, 0.9, 0.2, 0.0, 0.8];
+
+        assert_eq!(
+            extract_channel(&frames, 3, 2).expect("channel"),
+            vec![0.9, 0.8]
+        );
+        assert!(extract_channel(&frames, 3, 3).is_err());
+    }
+
+    #[test]
+    fn resamples_to_expected_length_and_keeps_dc() {
+        let upsampled = resample(&[0.25; 4], 8_000, 16_000, ResampleQuality::Balanced);
+        let downsampled = resample(&[0.25; 4_800], 48_000, 16_000, ResampleQuality::Fast);
+
+        assert_eq!(upsampled.len(), 8);
+        assert_eq!(downsampled.len(), 1_600);
+        assert!(upsampled.iter().all(|sample| (sample - 0.25).abs() < 1e-4));
+        assert!(
+            downsampled
+                .iter()
+                .all(|sample| (sample - 0.25).abs() < 1e-4)
+        );
+    }
+
+    #[test]
+    fn chunked_resampling_matches_one_shot() {
+        let source = sine(440.0, 44_100, 10_000);
+        let expected = resample(&source, 44_100, 16_000, ResampleQuality::High);
+        let mut resampler = Resampler::new(44_100, 16_000, ResampleQuality::High);
+
+        for chunk in source.chunks(777) {
+            resampler.push(chunk);
+        }
+
+        assert_eq!(resampler.finish(), expected);
+    }
+
+    #[test]
+    fn aligns_microphone_and_system_tracks_at_their_ends() {
+        let microphone = [0.2, 0.4, 0.6];
+        let system = [0.8, 1.0];
+
+        assert_eq!(
+            mix_tracks(&microphone, &system, DictationCaptureMode::Split),
+            (2, vec![0.2, 0.0, 0.4, 0.8, 0.6, 1.0])
+        );
+        assert_eq!(
+            mix_tracks(&microphone, &system, DictationCaptureMode::Mixed),
+            (1, vec![0.1, 0.6, 0.8])
+        );
+    }
+
+    #[test]
+    fn uploads_split_captures_as_a_stereo_pair() {
+        let config = |mode: &str| {
+            DictationConfig::from_settings(&AppSettings {
+                openrouter_api_key: String::from("sk-or-test"),
+                dictation_capture_mode: String::from(mode),
+                dictation_normalize_audio: false,
+                ..AppSettings::default()
+            })
+            .expect("config")
+        };
+        let take = [0.5_f32, -0.25].repeat(16_000);
+
+        let split = config("split");
+        let (samples, sample_rate, channels) =
+            prepare_samples(std::iter::once(Ok(take.clone())), 16_000, 2, &split)
+                .expect("prepared");
+        assert_eq!(channels, 2);
+        let middle = samples.len() / 4 * 2;
+        assert!((samples[middle] - 0.5).abs() < 0.01);
+        assert!((samples[middle + 1] + 0.25).abs() < 0.01);
+        let prepared = encode_samples(&samples, sample_rate, channels, &split).expect("encoded");
+        assert_eq!(
+            u16::from_le_bytes([prepared.audio[22], prepared.audio[23]]),
+            2
+        );
+
+        let (_, _, channels) =
+            prepare_samples(std::iter::once(Ok(take)), 16_000, 2, &config("mixed"))
+                .expect("prepared");
+        assert_eq!(channels, 1);
+    }
+
+    #[test]
+    fn preserves_in_band_tones_when_downsampling() {
+        let source = sine(1_000.0, 48_000, 48_000);
+        let resampled = resample(&source, 48_000, 16_000, ResampleQuality::Balanced);
+
+        let amplitude = tone_amplitude(&resampled, 1_000.0, 16_000);
+
+        assert!((amplitude - 0.5).abs() < 0.01, "amplitude={amplitude}");
+    }
+
+    #[test]
+    fn suppresses_aliasing_from_out_of_band_tones() {
+        // 12 kHz is above the 8 kHz Nyquist limit of the output and would
+        // fold back to 4 kHz if the signal were simply decimated.
+        let source = sine(12_000.0, 48_000, 48_000);
+
+        for quality in [
+            ResampleQuality::Fast,
+            ResampleQuality::Balanced,
+            ResampleQuality::High,
+        ] {
+            let resampled = resample(&source, 48_000, 16_000, quality);
+            let alias = tone_amplitude(&resampled, 4_000.0, 16_000);
+
+            assert!(alias < 0.005, "quality={quality:?} alias={alias}");
+        }
+
+        let decimated = source.iter().step_by(3).copied().collect::<Vec<_>>();
+        assert!(tone_amplitude(&decimated, 4_000.0, 16_000) > 0.4);
+    }
+
+    #[test]
+    fn raises_quiet_speech_towards_target_loudness() {
+        let mut samples = sine(440.0, 16_000, 16_000)
+            .into_iter()
+            .map(|sample| sample * 0.05)
+            .collect::<Vec<_>>();
+
+        let gain = normalize_loudness(&mut samples);
+
+        assert!(gain > 1.0 && gain <= NORMALIZE_MAX_GAIN);
+        assert!((rms_level(&samples) - NORMALIZE_TARGET_RMS).abs() < 0.005);
+    }
+
+    #[test]
+    fn leaves_noise_floor_untouched() {
+        let mut samples = vec![0.001, -0.001, 0.0005, -0.0008];
+        let original = samples.clone();
+
+        assert_eq!(normalize_loudness(&mut samples), 1.0);
+        assert_eq!(samples, original);
+    }
+
+    #[test]
+    fn limits_gain_to_avoid_clipping() {
+        // Mostly quiet with one loud transient: the RMS target would push
+        // the transient past full scale.
+        let mut samples = vec![0.01; 1_000];
+        samples[500] = 0.9;
+
+        normalize_loudness(&mut samples);
 
-        assert_eq!(resampled.first().copied(), Some(0.0));
-        assert_eq!(resampled.len(), 8);
-        assert!(resampled[3] > resampled[2]);
+        let peak = samples.iter().fold(0.0_f32, |peak, s| peak.max(s.abs()));
+        assert!(peak <= NORMALIZE_PEAK_CEILING + f32::EPSILON);
     }
 
     #[test]
     fn encodes_pcm_as_wav() {
-        let wav = samples_to_wav(&[0.0, 0.5, -0.5, 0.2], 16_000).expect("wav");
+        let wav = samples_to_wav(&[0.0, 0.5, -0.5, 0.2], 16_000, 1).expect("wav");
 
         assert!(wav.len() > 44);
         assert_eq!(&wav[0..4], b"RIFF");
@@ -1,52 +1,1243 @@
 #![allow(dead_code)]
 
 use crate::modules::audio::domain::CaptureSession;
+use crate::support::error::OpenVoiceError;
 use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
 
 pub const TARGET_SAMPLE_RATE: u32 = 16_000;
-const DEFAULT_REFERER: &str = "https://github.com/IsraelAraujo70/openvoice";
-const DEFAULT_APP_TITLE: &str = "OpenVoice";
+/// Audio kept from before the shortcut was pressed when pre-roll is enabled.
+pub const PRE_ROLL_MS: u32 = 1_500;
+/// Takes longer than this are cut on silences and transcribed in parallel.
+pub const CHUNKED_TRANSCRIPTION_MIN_SECS: f32 = 60.0;
+/// Shortest and longest chunk a long take is cut into.
+pub const TRANSCRIPTION_CHUNK_MIN_SECS: u32 = 20;
+pub const TRANSCRIPTION_CHUNK_MAX_SECS: u32 = 40;
+/// Chunks in flight at once, to stay clear of provider rate limits.
+pub const MAX_PARALLEL_CHUNKS: usize = 4;
+/// Deepgram's code-switching mode, used when no language is forced.
+const DEEPGRAM_AUTO_LANGUAGE: &str = "multi";
 
 #[derive(Debug, Clone)]
 pub struct PreparedAudio {
-    pub wav_base64: String,
+    /// Shared so every upload (and fallback retry) streams the same bytes
+    /// instead of copying them into its request body.
+    pub audio: Arc<[u8]>,
+    pub format: AudioFormat,
+}
+
+/// Service a dictation take is sent to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DictationProvider {
+    OpenRouter,
+    Deepgram,
+    AssemblyAi,
+    OpenAi,
+}
+
+impl DictationProvider {
+    pub fn code(self) -> &'static str {
+        match self {
+            Self::OpenRouter => "openrouter",
+            Self::Deepgram => "deepgram",
+            Self::AssemblyAi => "assemblyai",
+            Self::OpenAi => "openai",
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::OpenRouter => "OpenRouter",
+            Self::Deepgram => "Deepgram",
+            Self::AssemblyAi => "AssemblyAI",
+            Self::OpenAi => "OpenAI",
+        }
+    }
+
+    pub fn from_code(value: &str) -> Self {
+        match value.trim() {
+            "deepgram" => Self::Deepgram,
+            "assemblyai" => Self::AssemblyAi,
+            "openai" => Self::OpenAi,
+            _ => Self::OpenRouter,
+        }
+    }
+}
+
+impl std::fmt::Display for DictationProvider {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.label())
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct AssemblyAiConfig {
+    pub api_key: String,
+    /// Empty turns on AssemblyAI's language detection.
+    pub language: String,
+    pub timestamps: bool,
+    /// Sent as `word_boost`.
+    pub vocabulary: Vec<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct OpenAiConfig {
+    pub api_key: String,
+    pub model: String,
+    /// ISO-639-1 code; empty lets the model detect it.
+    pub language: String,
+    /// Segment times only come back from `whisper-1`'s `verbose_json`.
+    pub timestamps: bool,
+    /// Sent as the `prompt` field, which steers spelling.
+    pub vocabulary: Vec<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct DeepgramConfig {
+    pub api_key: String,
+    pub model: String,
+    pub language: String,
+    pub timestamps: bool,
+    /// Sent as `keyterm` on Nova-3 and `keywords` on older models.
+    pub vocabulary: Vec<String>,
+}
+
+/// Container sent to the transcription API. Ogg carries Opus at a low
+/// speech bitrate, roughly a tenth of the 16 kHz PCM WAV size.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AudioFormat {
+    Wav,
+    Ogg,
+}
+
+impl AudioFormat {
+    pub fn code(self) -> &'static str {
+        match self {
+            Self::Wav => "wav",
+            Self::Ogg => "ogg",
+        }
+    }
+
+    pub fn mime_type(self) -> &'static str {
+        match self {
+            Self::Wav => "audio/wav",
+            Self::Ogg => "audio/ogg",
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Wav => "WAV (sem compressao)",
+            Self::Ogg => "Ogg/Opus (compacto)",
+        }
+    }
+
+    pub fn from_code(value: &str) -> Self {
+        match value.trim() {
+            "ogg" => Self::Ogg,
+            _ => Self::Wav,
+        }
+    }
+}
+
+impl std::fmt::Display for AudioFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.label())
+    }
+}
+
+/// Sample rate of the audio uploaded for transcription. `Original` skips
+/// resampling and sends the capture at the device rate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TargetSampleRate {
+    Hz16000,
+    Hz24000,
+    Hz48000,
+    Original,
+}
+
+impl TargetSampleRate {
+    pub fn code(self) -> &'static str {
+        match self {
+            Self::Hz16000 => "16000",
+            Self::Hz24000 => "24000",
+            Self::Hz48000 => "48000",
+            Self::Original => "original",
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Hz16000 => "16 kHz",
+            Self::Hz24000 => "24 kHz",
+            Self::Hz48000 => "48 kHz",
+            Self::Original => "Manter taxa original",
+        }
+    }
+
+    pub fn from_code(value: &str) -> Self {
+        match value.trim() {
+            "24000" => Self::Hz24000,
+            "48000" => Self::Hz48000,
+            "original" => Self::Original,
+            _ => Self::Hz16000,
+        }
+    }
+
+    /// Rate to upload at, given the rate the capture was recorded at.
+    pub fn resolve(self, source_rate: u32) -> u32 {
+        match self {
+            Self::Hz16000 => TARGET_SAMPLE_RATE,
+            Self::Hz24000 => 24_000,
+            Self::Hz48000 => 48_000,
+            Self::Original => source_rate,
+        }
+    }
+}
+
+impl std::fmt::Display for TargetSampleRate {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.label())
+    }
+}
+
+/// Which sources a dictation take records. The system-audio modes open the
+/// desktop monitor next to the microphone so both sides of a call are kept.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DictationCaptureMode {
+    Microphone,
+    /// Microphone and system audio averaged into one mono track.
+    Mixed,
+    /// Microphone on the left channel, system audio on the right.
+    Split,
+}
+
+impl DictationCaptureMode {
+    pub fn code(self) -> &'static str {
+        match self {
+            Self::Microphone => "microphone",
+            Self::Mixed => "mixed",
+            Self::Split => "split",
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Microphone => "Somente microfone",
+            Self::Mixed => "Microfone + sistema (mixado)",
+            Self::Split => "Microfone + sistema (canais separados)",
+        }
+    }
+
+    pub fn from_code(value: &str) -> Self {
+        match value.trim() {
+            "mixed" => Self::Mixed,
+            "split" => Self::Split,
+            _ => Self::Microphone,
+        }
+    }
+
+    pub fn includes_system_audio(self) -> bool {
+        self != Self::Microphone
+    }
+}
+
+impl std::fmt::Display for DictationCaptureMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.label())
+    }
+}
+
+/// Trade-off between CPU time and anti-aliasing when converting the capture
+/// to the target sample rate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResampleQuality {
+    Fast,
+    Balanced,
+    High,
+}
+
+impl ResampleQuality {
+    pub fn code(self) -> &'static str {
+        match self {
+            Self::Fast => "fast",
+            Self::Balanced => "balanced",
+            Self::High => "high",
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Fast => "Rapido",
+            Self::Balanced => "Equilibrado",
+            Self::High => "Alta qualidade",
+        }
+    }
+
+    pub fn from_code(value: &str) -> Self {
+        match value.trim() {
+            "fast" => Self::Fast,
+            "high" => Self::High,
+            _ => Self::Balanced,
+        }
+    }
+
+    /// Sinc zero crossings kept on each side of the filter kernel.
+    pub fn half_taps(self) -> usize {
+        match self {
+            Self::Fast => 8,
+            Self::Balanced => 16,
+            Self::High => 32,
+        }
+    }
+}
+
+impl std::fmt::Display for ResampleQuality {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.label())
+    }
+}
+
+/// Shared flag the UI flips to abandon a transcription. An upload still in
+/// progress is aborted mid-body and providers check it between steps; a
+/// request already waiting on the model cannot be interrupted, so its
+/// result is dropped when it arrives.
+#[derive(Debug, Clone, Default)]
+pub struct CancelToken(Arc<AtomicBool>);
+
+impl CancelToken {
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+
+    pub fn check(&self) -> Result<(), OpenVoiceError> {
+        if self.is_cancelled() {
+            Err(OpenVoiceError::Cancelled)
+        } else {
+            Ok(())
+        }
+    }
+
+    /// `error`, or `Cancelled` when the token was set meanwhile: an upload
+    /// aborted by the token comes back from reqwest as a network error.
+    pub fn or_cancelled(&self, error: OpenVoiceError) -> OpenVoiceError {
+        if self.is_cancelled() {
+            OpenVoiceError::Cancelled
+        } else {
+            error
+        }
+    }
+}
+
+/// Where a transcription job is, for the HUD progress indicator.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TranscriptionStage {
+    #[default]
+    Encoding,
+    Uploading {
+        sent: u64,
+        total: u64,
+    },
+    WaitingForModel,
+    Parsing,
+    /// Long takes report finished chunks instead of bytes.
+    Chunks {
+        done: usize,
+        total: usize,
+    },
+}
+
+impl TranscriptionStage {
+    pub fn label(self) -> String {
+        match self {
+            Self::Encoding => String::from("Preparando o audio..."),
+            Self::Uploading { sent, total } => format!(
+                "Enviando audio: {:.1} de {:.1} MB...",
+                sent as f64 / 1_048_576.0,
+                total as f64 / 1_048_576.0
+            ),
+            Self::WaitingForModel => String::from("Aguardando o modelo transcrever..."),
+            Self::Parsing => String::from("Lendo a resposta..."),
+            Self::Chunks { done, total } => {
+                format!("Transcrevendo em trechos: {done} de {total} prontos...")
+            }
+        }
+    }
+
+    /// Share of the work done, when the stage can tell.
+    pub fn fraction(self) -> Option<f32> {
+        match self {
+            Self::Uploading { sent, total } if total > 0 => Some(sent as f32 / total as f32),
+            Self::Chunks { done, total } if total > 0 => Some(done as f32 / total as f32),
+            _ => None,
+        }
+    }
+}
+
+/// Slot the pipeline writes its current stage to; the UI polls it while
+/// the job runs, the same way it checks the cancel flag.
+#[derive(Debug, Clone, Default)]
+pub struct ProgressTracker(Arc<Mutex<TranscriptionStage>>);
+
+impl ProgressTracker {
+    pub fn set(&self, stage: TranscriptionStage) {
+        if let Ok(mut current) = self.0.lock() {
+            *current = stage;
+        }
+    }
+
+    pub fn stage(&self) -> TranscriptionStage {
+        self.0.lock().map(|stage| *stage).unwrap_or_default()
+    }
 }
 
 #[derive(Debug, Clone)]
 pub struct DictationConfig {
+    pub provider: DictationProvider,
+    pub deepgram: DeepgramConfig,
+    pub assemblyai: AssemblyAiConfig,
+    pub openai: OpenAiConfig,
+    /// Send audio while recording instead of uploading the finished take,
+    /// when the provider supports it.
+    pub streaming: bool,
     pub api_key: String,
+    /// Tried in order when the previous key is refused or out of credit.
+    pub fallback_api_keys: Vec<String>,
     pub model: String,
-    pub referer: String,
-    pub app_title: String,
+    pub fallback_models: Vec<String>,
+    pub temperature: Option<f32>,
+    pub top_p: Option<f32>,
+    pub max_tokens: Option<u32>,
+    /// Attribution headers; `None` leaves the header out.
+    pub referer: Option<String>,
+    pub app_title: Option<String>,
+    /// Ask OpenRouter to skip providers that retain or train on requests.
+    pub deny_data_collection: bool,
+    /// OpenRouter or a compatible gateway, without the trailing slash.
+    pub base_url: String,
     pub prompt: String,
+    pub language: Option<String>,
+    /// Ask for segments with start/end times along with the text.
+    pub timestamps: bool,
+    /// Terms the user wants spelled exactly, passed to every provider.
+    pub vocabulary: Vec<String>,
+    pub cancel: CancelToken,
+    pub progress: ProgressTracker,
+    pub resample_quality: ResampleQuality,
+    pub target_sample_rate: TargetSampleRate,
+    pub normalize_audio: bool,
+    pub audio_format: AudioFormat,
+    /// Zero-based channel to transcribe; `None` averages every channel.
+    pub input_channel: Option<u16>,
+    /// Split capture mode: a two-channel take (microphone left, system
+    /// right) is uploaded as that stereo pair instead of mixed to mono.
+    pub split_channels: bool,
 }
 
 impl DictationConfig {
     pub fn from_settings(
         settings: &crate::modules::settings::domain::AppSettings,
     ) -> Result<Self, String> {
-        if !settings.has_api_key() {
-            return Err(String::from(
-                "Cadastre uma OpenRouter API key antes de tentar gravar.",
+        let provider = settings.dictation_provider();
+        if !settings.has_dictation_credentials() {
+            return Err(format!(
+                "Cadastre uma {} API key antes de tentar gravar.",
+                provider.label()
             ));
         }
 
+        let language = settings.dictation_language();
+        let timestamps = settings.dictation_timestamps;
+        let vocabulary = settings.dictation_vocabulary.clone();
+
         Ok(Self {
+            provider,
+            deepgram: DeepgramConfig {
+                api_key: settings.deepgram_api_key.clone(),
+                model: settings.deepgram_model.clone(),
+                language: language
+                    .clone()
+                    .unwrap_or_else(|| String::from(DEEPGRAM_AUTO_LANGUAGE)),
+                timestamps,
+                vocabulary: vocabulary.clone(),
+            },
+            assemblyai: AssemblyAiConfig {
+                api_key: settings.assemblyai_api_key.clone(),
+                // AssemblyAI takes bare language codes, not regions.
+                language: language
+                    .as_deref()
+                    .map(primary_language_subtag)
+                    .unwrap_or_default(),
+                timestamps,
+                vocabulary: vocabulary.clone(),
+            },
+            openai: OpenAiConfig {
+                api_key: settings.openai_realtime_api_key.clone(),
+                model: settings.openai_realtime_model.clone(),
+                language: language
+                    .as_deref()
+                    .map(primary_language_subtag)
+                    .unwrap_or_default(),
+                timestamps: timestamps && settings.openai_realtime_model == "whisper-1",
+                vocabulary: vocabulary.clone(),
+            },
+            streaming: settings.dictation_streaming,
             api_key: settings.openrouter_api_key.clone(),
+            fallback_api_keys: settings.openrouter_fallback_api_keys.clone(),
             model: settings.openrouter_model.clone(),
-            referer: String::from(DEFAULT_REFERER),
-            app_title: String::from(DEFAULT_APP_TITLE),
-            prompt: String::from(
-                "Transcribe this audio exactly as spoken. Output only the transcription, nothing else. Preserve the original language and do not add formatting or commentary.",
+            fallback_models: settings.openrouter_fallback_models.clone(),
+            temperature: settings.dictation_temperature(),
+            top_p: settings.openrouter_top_p,
+            max_tokens: settings.openrouter_max_tokens,
+            referer: attribution_header(settings, &settings.openrouter_referer),
+            app_title: attribution_header(settings, &settings.openrouter_app_title),
+            deny_data_collection: settings.openrouter_privacy_mode,
+            base_url: settings.openrouter_base_url.clone(),
+            prompt: transcription_prompt(
+                language.as_deref(),
+                timestamps,
+                &vocabulary,
+                settings.dictation_prompt_extra(),
             ),
+            language,
+            timestamps,
+            vocabulary,
+            cancel: CancelToken::default(),
+            progress: ProgressTracker::default(),
+            resample_quality: settings.dictation_resample_quality(),
+            target_sample_rate: settings.dictation_target_sample_rate(),
+            normalize_audio: settings.dictation_normalize_audio,
+            audio_format: settings.dictation_audio_format(),
+            input_channel: settings.dictation_input_channel(),
+            split_channels: settings.dictation_capture_mode() == DictationCaptureMode::Split,
         })
     }
+
+    /// Points the config at `model` alone, on whichever provider it uses.
+    /// AssemblyAI has a single model, so it is left as is.
+    pub fn with_model(mut self, model: &str) -> Self {
+        match self.provider {
+            DictationProvider::OpenRouter => {
+                self.model = model.to_owned();
+                self.fallback_models.clear();
+            }
+            DictationProvider::Deepgram => self.deepgram.model = model.to_owned(),
+            DictationProvider::OpenAi => {
+                self.openai.model = model.to_owned();
+                self.openai.timestamps = self.timestamps && model == "whisper-1";
+            }
+            DictationProvider::AssemblyAi => {}
+        }
+        self
+    }
+}
+
+/// Header value to send, unless it was left empty or privacy mode is on.
+fn attribution_header(
+    settings: &crate::modules::settings::domain::AppSettings,
+    value: &str,
+) -> Option<String> {
+    let value = value.trim();
+    (!settings.openrouter_privacy_mode && !value.is_empty()).then(|| value.to_owned())
+}
+
+fn transcription_prompt(
+    language: Option<&str>,
+    timestamps: bool,
+    vocabulary: &[String],
+    extra: Option<&str>,
+) -> String {
+    let language_rule = match language {
+        Some(language) => {
+            format!("The speech is in {language}; transcribe it in that language")
+        }
+        None => String::from("Preserve the original language"),
+    };
+
+    let mut prompt = if timestamps {
+        format!(
+            "Transcribe this audio exactly as spoken, split into short segments of one sentence or phrase. Output one segment per line as `[MM:SS.s --> MM:SS.s] text`, with the start and end time of the segment in the audio, and nothing else. {language_rule} and do not add commentary."
+        )
+    } else {
+        format!(
+            "Transcribe this audio exactly as spoken. Output only the transcription, nothing else. {language_rule} and do not add formatting or commentary."
+        )
+    };
+
+    if !vocabulary.is_empty() {
+        prompt.push_str(&format!(
+            " The speaker may use these names and terms; spell them exactly like this when they are said: {}.",
+            vocabulary.join(", ")
+        ));
+    }
+
+    if let Some(extra) = extra {
+        prompt.push(' ');
+        prompt.push_str(extra);
+    }
+
+    prompt
+}
+
+fn primary_language_subtag(language: &str) -> String {
+    language
+        .split(['-', '_'])
+        .next()
+        .unwrap_or(language)
+        .to_lowercase()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DictationAutoStop {
+    Silence,
+    MaxDuration,
+    /// Nobody spoke for the idle timeout, e.g. the user walked away.
+    Idle,
+}
+
+impl DictationAutoStop {
+    pub fn hint(self) -> &'static str {
+        match self {
+            Self::Silence => "Silencio detectado; ditado encerrado automaticamente.",
+            Self::MaxDuration => "Duracao maxima atingida; ditado encerrado automaticamente.",
+            Self::Idle => "Nenhuma fala por muito tempo; ditado encerrado automaticamente.",
+        }
+    }
+}
+
+/// Stands for the transcription inside a template body.
+pub const TEMPLATE_PLACEHOLDER: &str = "{text}";
+
+/// Wrapper applied to a transcription before it is delivered, such as
+/// `- [ ] {text}`. A literal `\n` in the body is a line break.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TranscriptTemplate {
+    pub name: String,
+    pub body: String,
+    /// Global shortcut that dictates straight into this template; empty =
+    /// none.
+    #[serde(default)]
+    pub shortcut: String,
+}
+
+impl TranscriptTemplate {
+    pub fn apply(&self, text: &str) -> String {
+        self.body
+            .replace("\\n", "\n")
+            .replace(TEMPLATE_PLACEHOLDER, text)
+    }
+}
+
+/// Takes dictated during a session, kept as one growing document that is
+/// only delivered when the session ends.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DictationSession {
+    takes: Vec<String>,
+}
+
+impl DictationSession {
+    pub fn push(&mut self, text: &str) {
+        let text = text.trim();
+        if !text.is_empty() {
+            self.takes.push(text.to_owned());
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.takes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.takes.is_empty()
+    }
+
+    /// The document so far, one paragraph per take.
+    pub fn text(&self) -> String {
+        self.takes.join("\n\n")
+    }
+}
+
+/// Fills `{text}` in a URI template such as `obsidian://new?content={text}`
+/// with the percent-encoded transcription.
+pub fn fill_uri_template(template: &str, text: &str) -> String {
+    template.replace(TEMPLATE_PLACEHOLDER, &percent_encode(text))
+}
+
+/// Encodes everything but RFC 3986 unreserved characters, so the text is
+/// safe in any query value or path segment.
+fn percent_encode(value: &str) -> String {
+    let mut encoded = String::with_capacity(value.len());
+
+    for byte in value.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                encoded.push(char::from(byte));
+            }
+            byte => encoded.push_str(&format!("%{byte:02X}")),
+        }
+    }
+
+    encoded
+}
+
+/// Characters of the transcription shown in the desktop notification.
+pub const NOTIFICATION_PREVIEW_CHARS: usize = 80;
+
+/// First [`NOTIFICATION_PREVIEW_CHARS`] characters of `text` on a single
+/// line, with an ellipsis when it was cut.
+pub fn notification_preview(text: &str) -> String {
+    let line = text.split_whitespace().collect::<Vec<_>>().join(" ");
+
+    if line.chars().count() <= NOTIFICATION_PREVIEW_CHARS {
+        return line;
+    }
+
+    let mut preview = line
+        .chars()
+        .take(NOTIFICATION_PREVIEW_CHARS)
+        .collect::<String>();
+    preview.push('…');
+    preview
+}
+
+/// What the last delivery changed, kept so it can be undone.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LastOutput {
+    /// `copied` replaced `previous` on the clipboard.
+    Clipboard {
+        previous: Option<String>,
+        copied: String,
+    },
+    /// This many characters were typed into the focused window.
+    Typed { chars: usize },
+}
+
+/// Where a finished transcription goes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DictationOutputMode {
+    Clipboard,
+    /// Typed into the focused window, for apps that refuse pastes.
+    Type,
+    /// Handed to a URI such as `obsidian://new?content={text}`.
+    Uri,
+    /// Pasted into the focused window and sent with Enter, for chat apps.
+    Submit,
+}
+
+impl DictationOutputMode {
+    pub fn code(self) -> &'static str {
+        match self {
+            Self::Clipboard => "clipboard",
+            Self::Type => "type",
+            Self::Uri => "uri",
+            Self::Submit => "submit",
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Clipboard => "Copiar para o clipboard",
+            Self::Type => "Digitar no app em foco",
+            Self::Uri => "Abrir URI (Obsidian, notas...)",
+            Self::Submit => "Colar e enviar (Enter)",
+        }
+    }
+
+    /// Title of the desktop notification sent after delivering.
+    pub fn notification_title(self) -> &'static str {
+        match self {
+            Self::Clipboard => "Copiado",
+            Self::Type => "Digitado",
+            Self::Uri => "Enviado",
+            Self::Submit => "Colado e enviado",
+        }
+    }
+
+    pub fn from_code(value: &str) -> Self {
+        match value.trim() {
+            "type" => Self::Type,
+            "uri" => Self::Uri,
+            "submit" => Self::Submit,
+            _ => Self::Clipboard,
+        }
+    }
+}
+
+impl std::fmt::Display for DictationOutputMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.label())
+    }
+}
+
+/// What happens to a take stopped by the idle timeout.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IdleStopAction {
+    Discard,
+    Transcribe,
+}
+
+impl IdleStopAction {
+    pub fn code(self) -> &'static str {
+        match self {
+            Self::Discard => "discard",
+            Self::Transcribe => "transcribe",
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Discard => "Descartar a gravacao",
+            Self::Transcribe => "Transcrever o que foi gravado",
+        }
+    }
+
+    pub fn from_code(value: &str) -> Self {
+        match value.trim() {
+            "transcribe" => Self::Transcribe,
+            _ => Self::Discard,
+        }
+    }
+}
+
+impl std::fmt::Display for IdleStopAction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.label())
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DictationOutput {
     pub transcript: String,
     pub duration_seconds: f32,
+    /// The input hit full scale for sustained stretches while recording.
+    pub clipped: bool,
+    /// Tokens and cost of the request, when the provider reports them.
+    #[serde(default)]
+    pub usage: Option<TranscriptionUsage>,
+    /// Model that produced the transcript, for providers with a model chain.
+    #[serde(default)]
+    pub model: Option<String>,
+    /// Which key served it, for providers with a key chain.
+    #[serde(default)]
+    pub api_key: Option<String>,
+    /// Timed segments, when the take was transcribed with timestamps.
+    #[serde(default)]
+    pub segments: Vec<TranscriptSegment>,
+    /// Milliseconds between stopping the take and having its text.
+    #[serde(default)]
+    pub latency_ms: Option<u64>,
+}
+
+/// Counts shown with a finished transcription.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct TranscriptionStats {
+    pub words: usize,
+    pub characters: usize,
+    /// Seconds of recorded audio.
+    pub duration_seconds: f32,
+    pub latency_ms: Option<u64>,
+}
+
+impl TranscriptionStats {
+    pub fn new(text: &str, duration_seconds: f32, latency_ms: Option<u64>) -> Self {
+        Self {
+            words: text.split_whitespace().count(),
+            characters: text.chars().count(),
+            duration_seconds,
+            latency_ms,
+        }
+    }
+
+    /// `12 palavras · 64 caracteres · 4.5s de audio · 1.2s de espera`.
+    pub fn label(&self) -> String {
+        let mut parts = vec![
+            format!("{} palavra(s)", self.words),
+            format!("{} caracteres", self.characters),
+            format!("{:.1}s de audio", self.duration_seconds),
+        ];
+        if let Some(latency_ms) = self.latency_ms {
+            parts.push(format!("{:.1}s de espera", latency_ms as f64 / 1000.0));
+        }
+        parts.join(" · ")
+    }
+}
+
+/// A stretch of the transcript with its position in the take, in seconds.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TranscriptSegment {
+    pub start_secs: f32,
+    pub end_secs: f32,
+    pub text: String,
+}
+
+impl TranscriptSegment {
+    /// `[MM:SS - MM:SS] text`, as the history lists it.
+    pub fn label(&self) -> String {
+        format!(
+            "[{} - {}] {}",
+            clock_label(self.start_secs),
+            clock_label(self.end_secs),
+            self.text
+        )
+    }
+}
+
+/// Transcript text plus its timed segments; `segments` stays empty unless
+/// timestamps were requested and the provider returned them.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct TranscriptionResult {
+    pub text: String,
+    #[serde(default)]
+    pub segments: Vec<TranscriptSegment>,
+}
+
+impl TranscriptionResult {
+    pub fn plain(text: String) -> Self {
+        Self {
+            text,
+            segments: Vec::new(),
+        }
+    }
+
+    /// Builds the text from the segments, skipping empty ones.
+    pub fn from_segments(segments: Vec<TranscriptSegment>) -> Self {
+        let segments: Vec<_> = segments
+            .into_iter()
+            .filter(|segment| !segment.text.trim().is_empty())
+            .collect();
+        let text = segments
+            .iter()
+            .map(|segment| segment.text.trim())
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        Self { text, segments }
+    }
+
+    /// Reads the `[MM:SS.s --> MM:SS.s] text` lines the timestamp prompt asks
+    /// for. A reply without any such line is kept as plain text.
+    pub fn parse_timestamped(raw: &str) -> Self {
+        let segments: Vec<_> = raw.lines().filter_map(parse_segment_line).collect();
+
+        if segments.is_empty() {
+            return Self::plain(raw.trim().to_owned());
+        }
+
+        Self::from_segments(segments)
+    }
+
+    /// SubRip subtitles, one cue per segment.
+    pub fn to_srt(&self) -> String {
+        self.segments
+            .iter()
+            .enumerate()
+            .map(|(index, segment)| {
+                format!(
+                    "{}\n{} --> {}\n{}\n",
+                    index + 1,
+                    srt_timestamp(segment.start_secs),
+                    srt_timestamp(segment.end_secs),
+                    segment.text.trim()
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+fn parse_segment_line(line: &str) -> Option<TranscriptSegment> {
+    let rest = line.trim().strip_prefix('[')?;
+    let (range, text) = rest.split_once(']')?;
+    let (start, end) = range
+        .split_once("-->")
+        .or_else(|| range.split_once(" - "))?;
+
+    Some(TranscriptSegment {
+        start_secs: parse_clock(start)?,
+        end_secs: parse_clock(end)?,
+        text: text.trim().to_owned(),
+    })
+}
+
+/// `SS`, `MM:SS` or `HH:MM:SS`, with optional fractional seconds.
+fn parse_clock(value: &str) -> Option<f32> {
+    value
+        .trim()
+        .replace(',', ".")
+        .split(':')
+        .try_fold(0.0_f32, |total, part| {
+            part.parse::<f32>().ok().map(|part| total * 60.0 + part)
+        })
+        .filter(|seconds| seconds.is_finite() && *seconds >= 0.0)
+}
+
+/// Running time of a take as `0:37`, or `1:02:05` past an hour.
+pub fn elapsed_label(elapsed: Duration) -> String {
+    let total = elapsed.as_secs();
+    let (hours, minutes, seconds) = (total / 3_600, total / 60 % 60, total % 60);
+
+    if hours > 0 {
+        format!("{hours}:{minutes:02}:{seconds:02}")
+    } else {
+        format!("{minutes}:{seconds:02}")
+    }
+}
+
+fn clock_label(seconds: f32) -> String {
+    let total = seconds.max(0.0) as u32;
+    format!("{:02}:{:02}", total / 60, total % 60)
+}
+
+fn srt_timestamp(seconds: f32) -> String {
+    let millis = (seconds.max(0.0) * 1_000.0).round() as u64;
+
+    format!(
+        "{:02}:{:02}:{:02},{:03}",
+        millis / 3_600_000,
+        millis / 60_000 % 60,
+        millis / 1_000 % 60,
+        millis % 1_000
+    )
+}
+
+/// What one model made of the audio in a comparison run.
+#[derive(Debug, Clone)]
+pub struct ModelTranscript {
+    pub model: String,
+    pub result: Result<String, OpenVoiceError>,
+}
+
+/// The same take transcribed by several models, in the order requested.
+#[derive(Debug, Clone, Default)]
+pub struct ModelComparison {
+    pub transcripts: Vec<ModelTranscript>,
+}
+
+impl ModelComparison {
+    /// Word diff between the first two transcripts, when both succeeded.
+    pub fn diff(&self) -> Option<String> {
+        match self.transcripts.as_slice() {
+            [
+                ModelTranscript {
+                    result: Ok(first), ..
+                },
+                ModelTranscript {
+                    result: Ok(second), ..
+                },
+                ..,
+            ] => Some(word_diff(first, second)),
+            _ => None,
+        }
+    }
+}
+
+/// Marks words only in `before` as `[-word-]` and only in `after` as
+/// `{+word+}`, keeping the longest common run of words unmarked.
+pub fn word_diff(before: &str, after: &str) -> String {
+    let before: Vec<&str> = before.split_whitespace().collect();
+    let after: Vec<&str> = after.split_whitespace().collect();
+    let mut common = vec![vec![0_usize; after.len() + 1]; before.len() + 1];
+
+    for i in (0..before.len()).rev() {
+        for j in (0..after.len()).rev() {
+            common[i][j] = if before[i] == after[j] {
+                common[i + 1][j + 1] + 1
+            } else {
+                common[i + 1][j].max(common[i][j + 1])
+            };
+        }
+    }
+
+    let (mut i, mut j) = (0, 0);
+    let mut words = Vec::new();
+
+    while i < before.len() || j < after.len() {
+        if i < before.len() && j < after.len() && before[i] == after[j] {
+            words.push(before[i].to_owned());
+            i += 1;
+            j += 1;
+        } else if j == after.len() || (i < before.len() && common[i + 1][j] >= common[i][j + 1]) {
+            words.push(format!("[-{}-]", before[i]));
+            i += 1;
+        } else {
+            words.push(format!("{{+{}+}}", after[j]));
+            j += 1;
+        }
+    }
+
+    words.join(" ")
+}
+
+/// Characters of a key kept by `api_key_hint`.
+const API_KEY_HINT_CHARS: usize = 4;
+
+/// End of a key, enough to tell the user's keys apart in the history
+/// without storing the secret.
+pub fn api_key_hint(key: &str) -> String {
+    let key: Vec<char> = key.trim().chars().collect();
+    let tail: String = key[key.len().saturating_sub(API_KEY_HINT_CHARS)..]
+        .iter()
+        .collect();
+    format!("...{tail}")
+}
+
+/// What a provider returned for one request.
+#[derive(Debug, Clone)]
+pub struct ProviderTranscript {
+    pub result: TranscriptionResult,
+    pub usage: Option<TranscriptionUsage>,
+    pub model: Option<String>,
+    /// `api_key_hint` of the key that served the request, for providers
+    /// with a key chain.
+    pub api_key: Option<String>,
+}
+
+impl From<TranscriptionResult> for ProviderTranscript {
+    fn from(result: TranscriptionResult) -> Self {
+        Self {
+            result,
+            usage: None,
+            model: None,
+            api_key: None,
+        }
+    }
+}
+
+impl ProviderTranscript {
+    pub fn text(text: String) -> Self {
+        TranscriptionResult::plain(text).into()
+    }
+
+    /// Joins the transcripts of consecutive chunks, each paired with where
+    /// it starts in the take, shifting segment times and adding up usage.
+    pub fn stitch(parts: Vec<(f32, ProviderTranscript)>) -> Self {
+        let mut stitched = Self::text(String::new());
+
+        for (offset_secs, part) in parts {
+            let text = part.result.text.trim();
+            if !text.is_empty() {
+                if !stitched.result.text.is_empty() {
+                    stitched.result.text.push(' ');
+                }
+                stitched.result.text.push_str(text);
+            }
+            stitched
+                .result
+                .segments
+                .extend(
+                    part.result
+                        .segments
+                        .into_iter()
+                        .map(|segment| TranscriptSegment {
+                            start_secs: segment.start_secs + offset_secs,
+                            end_secs: segment.end_secs + offset_secs,
+                            ..segment
+                        }),
+                );
+
+            if let Some(usage) = part.usage {
+                let total = stitched
+                    .usage
+                    .get_or_insert_with(TranscriptionUsage::default);
+                total.prompt_tokens += usage.prompt_tokens;
+                total.completion_tokens += usage.completion_tokens;
+                total.cost_usd += usage.cost_usd;
+            }
+            stitched.model = stitched.model.or(part.model);
+            stitched.api_key = stitched.api_key.or(part.api_key);
+        }
+
+        stitched
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct TranscriptionUsage {
+    pub prompt_tokens: u64,
+    pub completion_tokens: u64,
+    pub cost_usd: f64,
+}
+
+/// Dictation requests and what they cost over some period.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct UsageTotals {
+    pub requests: u64,
+    pub prompt_tokens: u64,
+    pub completion_tokens: u64,
+    pub cost_usd: f64,
+}
+
+impl UsageTotals {
+    pub fn total_tokens(&self) -> u64 {
+        self.prompt_tokens + self.completion_tokens
+    }
+
+    pub fn summary(&self) -> String {
+        format!(
+            "{} ditados, {} tokens, US$ {:.4}",
+            self.requests,
+            self.total_tokens(),
+            self.cost_usd
+        )
+    }
+}
+
+/// A finished transcription kept in the local history.
+#[derive(Debug, Clone, PartialEq)]
+pub struct HistoryEntry {
+    pub id: i64,
+    /// ISO-8601 UTC.
+    pub created_at: String,
+    pub provider: String,
+    pub model: Option<String>,
+    /// `api_key_hint` of the key that served the request.
+    pub api_key: Option<String>,
+    pub duration_seconds: f32,
+    pub cost_usd: Option<f64>,
+    pub text: String,
+    /// Compressed take kept for replay and re-transcription.
+    pub audio_path: Option<PathBuf>,
+    pub latency_ms: Option<u64>,
+}
+
+impl HistoryEntry {
+    pub fn stats(&self) -> TranscriptionStats {
+        TranscriptionStats::new(&self.text, self.duration_seconds, self.latency_ms)
+    }
+
+    /// Case-insensitive match against the text and model; an empty query
+    /// matches everything.
+    pub fn matches(&self, query: &str) -> bool {
+        let query = query.trim().to_lowercase();
+
+        query.is_empty()
+            || self.text.to_lowercase().contains(&query)
+            || self
+                .model
+                .as_deref()
+                .is_some_and(|model| model.to_lowercase().contains(&query))
+    }
+}
+
+/// Limits on the audio kept next to history entries; the oldest files go
+/// first once either is exceeded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HistoryAudioRetention {
+    pub max_bytes: u64,
+    pub max_age: Option<Duration>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct UsageStats {
+    pub today: UsageTotals,
+    pub month: UsageTotals,
 }
 
 impl DictationOutput {
@@ -61,6 +1252,17 @@ impl DictationOutput {
         shortened.push_str("...");
         shortened
     }
+
+    pub fn result(&self) -> TranscriptionResult {
+        TranscriptionResult {
+            text: self.transcript.clone(),
+            segments: self.segments.clone(),
+        }
+    }
+
+    pub fn stats(&self) -> TranscriptionStats {
+        TranscriptionStats::new(&self.transcript, self.duration_seconds, self.latency_ms)
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -150,18 +1352,347 @@ impl TranscriptionJob {
 
 #[cfg(test)]
 mod tests {
-    use super::{DictationOutput, DualTranscriptOutput};
+    use super::{
+        DictationConfig, DictationOutput, DictationSession, DualTranscriptOutput, HistoryEntry,
+        ModelComparison, ModelTranscript, ProgressTracker, ProviderTranscript, TargetSampleRate,
+        TranscriptSegment, TranscriptTemplate, TranscriptionResult, TranscriptionStage,
+        TranscriptionStats, TranscriptionUsage, api_key_hint, elapsed_label, fill_uri_template,
+        notification_preview, word_diff,
+    };
+    use crate::modules::settings::domain::AppSettings;
+    use crate::support::error::OpenVoiceError;
+    use std::time::Duration;
+
+    #[test]
+    fn labels_elapsed_recording_time() {
+        assert_eq!(elapsed_label(Duration::from_millis(37_900)), "0:37");
+        assert_eq!(elapsed_label(Duration::from_secs(605)), "10:05");
+        assert_eq!(elapsed_label(Duration::from_secs(3_725)), "1:02:05");
+    }
+
+    #[test]
+    fn matches_history_entries_by_text_or_model() {
+        let entry = HistoryEntry {
+            id: 1,
+            created_at: String::from("2026-01-01T00:00:00Z"),
+            provider: String::from("openrouter"),
+            model: Some(String::from("google/gemini-2.5-flash")),
+            api_key: None,
+            duration_seconds: 2.0,
+            cost_usd: None,
+            text: String::from("Reuniao com o Time de Vendas"),
+            audio_path: None,
+            latency_ms: None,
+        };
+
+        assert!(entry.matches(""));
+        assert!(entry.matches(" time de "));
+        assert!(entry.matches("GEMINI"));
+        assert!(!entry.matches("marketing"));
+    }
+
+    #[test]
+    fn fills_uri_templates_with_encoded_text() {
+        assert_eq!(
+            fill_uri_template(
+                "obsidian://new?vault=Notas&content={text}",
+                "Reuniao às 10h & café\nok?"
+            ),
+            "obsidian://new?vault=Notas&content=Reuniao%20%C3%A0s%2010h%20%26%20caf%C3%A9%0Aok%3F"
+        );
+    }
+
+    #[test]
+    fn joins_session_takes_into_paragraphs() {
+        let mut session = DictationSession::default();
+        session.push(" Primeiro trecho. ");
+        session.push("   ");
+        session.push("Segundo trecho.");
+
+        assert_eq!(session.len(), 2);
+        assert_eq!(session.text(), "Primeiro trecho.\n\nSegundo trecho.");
+    }
+
+    #[test]
+    fn shortens_notification_previews() {
+        assert_eq!(notification_preview("  ola\n mundo "), "ola mundo");
+
+        let long = "a".repeat(100);
+        let preview = notification_preview(&long);
+        assert_eq!(preview.chars().count(), 81);
+        assert!(preview.ends_with('…'));
+    }
+
+    #[test]
+    fn applies_transcript_templates() {
+        let todo = TranscriptTemplate {
+            name: String::from("Tarefa"),
+            body: String::from("- [ ] {text}"),
+            shortcut: String::new(),
+        };
+        let email = TranscriptTemplate {
+            name: String::from("Email"),
+            body: String::from("{text}\\n\\n-- \\nIsrael"),
+            shortcut: String::new(),
+        };
+
+        assert_eq!(todo.apply("comprar pao"), "- [ ] comprar pao");
+        assert_eq!(
+            email.apply("ate amanha \\n"),
+            "ate amanha \\n\n\n-- \nIsrael"
+        );
+    }
+
+    #[test]
+    fn describes_transcription_stages() {
+        let uploading = TranscriptionStage::Uploading {
+            sent: 524_288,
+            total: 2_097_152,
+        };
+
+        assert_eq!(uploading.fraction(), Some(0.25));
+        assert_eq!(uploading.label(), "Enviando audio: 0.5 de 2.0 MB...");
+        assert_eq!(TranscriptionStage::WaitingForModel.fraction(), None);
+        assert_eq!(
+            TranscriptionStage::Chunks { done: 1, total: 4 }.fraction(),
+            Some(0.25)
+        );
+
+        let tracker = ProgressTracker::default();
+        tracker.clone().set(TranscriptionStage::Parsing);
+        assert_eq!(tracker.stage(), TranscriptionStage::Parsing);
+    }
+
+    #[test]
+    fn diffs_transcripts_word_by_word() {
+        assert_eq!(
+            word_diff("eu uso tauri com rust", "eu uso Tauri com rust hoje"),
+            "eu uso [-tauri-] {+Tauri+} com rust {+hoje+}"
+        );
+        assert_eq!(word_diff("igual", "igual"), "igual");
+
+        let comparison = ModelComparison {
+            transcripts: vec![
+                ModelTranscript {
+                    model: String::from("a"),
+                    result: Ok(String::from("ola")),
+                },
+                ModelTranscript {
+                    model: String::from("b"),
+                    result: Err(OpenVoiceError::Other(String::from("quota"))),
+                },
+            ],
+        };
+        assert_eq!(comparison.diff(), None);
+    }
+
+    #[test]
+    fn parses_prompted_timestamps_into_segments() {
+        let result = TranscriptionResult::parse_timestamped(
+            "[00:00.0 --> 00:02.4] Bom dia.\n[00:02.4 --> 01:05] Vamos comecar.\n",
+        );
+
+        assert_eq!(result.text, "Bom dia. Vamos comecar.");
+        assert_eq!(
+            result.segments[1],
+            TranscriptSegment {
+                start_secs: 2.4,
+                end_secs: 65.0,
+                text: String::from("Vamos comecar."),
+            }
+        );
+        assert_eq!(
+            TranscriptionResult::parse_timestamped("so texto").segments,
+            Vec::new()
+        );
+    }
+
+    #[test]
+    fn exports_segments_as_srt() {
+        let result = TranscriptionResult::from_segments(vec![
+            TranscriptSegment {
+                start_secs: 0.0,
+                end_secs: 2.5,
+                text: String::from("Ola."),
+            },
+            TranscriptSegment {
+                start_secs: 3_661.25,
+                end_secs: 3_662.0,
+                text: String::from("Tchau."),
+            },
+        ]);
+
+        assert_eq!(
+            result.to_srt(),
+            "1\n00:00:00,000 --> 00:00:02,500\nOla.\n\n2\n01:01:01,250 --> 01:01:02,000\nTchau.\n"
+        );
+        assert_eq!(result.segments[0].label(), "[00:00 - 00:02] Ola.");
+    }
+
+    #[test]
+    fn stitches_chunk_transcripts_in_order() {
+        let usage = TranscriptionUsage {
+            prompt_tokens: 10,
+            completion_tokens: 2,
+            cost_usd: 0.5,
+        };
+        let parts = vec![
+            (
+                0.0,
+                ProviderTranscript {
+                    usage: Some(usage),
+                    ..ProviderTranscript::text(String::from(" primeira parte "))
+                },
+            ),
+            (20.0, ProviderTranscript::text(String::new())),
+            (
+                40.0,
+                ProviderTranscript {
+                    usage: Some(usage),
+                    ..TranscriptionResult::parse_timestamped("[00:01.5 --> 00:03] segunda parte")
+                        .into()
+                },
+            ),
+        ];
+
+        let stitched = ProviderTranscript::stitch(parts);
+
+        assert_eq!(stitched.result.text, "primeira parte segunda parte");
+        assert_eq!(stitched.result.segments[0].start_secs, 41.5);
+        assert_eq!(
+            stitched
+                .usage
+                .map(|usage| usage.prompt_tokens + usage.completion_tokens),
+            Some(24)
+        );
+    }
+
+    #[test]
+    fn hints_keys_without_keeping_them() {
+        assert_eq!(api_key_hint(" sk-or-v1-0123abcd "), "...abcd");
+        assert_eq!(api_key_hint("ab"), "...ab");
+
+        let settings = AppSettings {
+            openrouter_api_key: String::from("personal"),
+            openrouter_fallback_api_keys: vec![String::from("team")],
+            ..AppSettings::default()
+        };
+        let config = DictationConfig::from_settings(&settings).expect("config");
+        assert_eq!(config.fallback_api_keys, ["team"]);
+    }
+
+    #[test]
+    fn plumbs_vocabulary_into_every_provider() {
+        let settings = AppSettings {
+            openrouter_api_key: String::from("key"),
+            dictation_vocabulary: vec![String::from("Tauri"), String::from("cpal")],
+            ..AppSettings::default()
+        };
+
+        let config = DictationConfig::from_settings(&settings).expect("config");
+
+        assert!(config.prompt.contains("Tauri, cpal"));
+        assert_eq!(config.deepgram.vocabulary, settings.dictation_vocabulary);
+        assert_eq!(config.assemblyai.vocabulary, settings.dictation_vocabulary);
+        assert!(
+            !DictationConfig::from_settings(&AppSettings {
+                dictation_vocabulary: Vec::new(),
+                ..settings
+            })
+            .expect("config")
+            .prompt
+            .contains("names and terms")
+        );
+    }
+
+    #[test]
+    fn privacy_mode_drops_attribution_headers() {
+        let mut settings = AppSettings {
+            openrouter_api_key: String::from("key"),
+            openrouter_app_title: String::from("  "),
+            ..AppSettings::default()
+        };
+
+        let attributed = DictationConfig::from_settings(&settings).expect("config");
+        assert_eq!(
+            attributed.referer.as_deref(),
+            Some("https://github.com/IsraelAraujo70/openvoice")
+        );
+        assert_eq!(attributed.app_title, None);
+        assert!(!attributed.deny_data_collection);
+
+        settings.openrouter_privacy_mode = true;
+        let private = DictationConfig::from_settings(&settings).expect("config");
+        assert_eq!(private.referer, None);
+        assert!(private.deny_data_collection);
+    }
+
+    #[test]
+    fn plumbs_forced_language_into_every_provider() {
+        let mut settings = AppSettings {
+            openrouter_api_key: String::from("key"),
+            ..AppSettings::default()
+        };
+
+        let auto = DictationConfig::from_settings(&settings).expect("config");
+        assert_eq!(auto.language, None);
+        assert_eq!(auto.deepgram.language, "multi");
+        assert_eq!(auto.assemblyai.language, "");
+        assert!(auto.prompt.contains("Preserve the original language"));
+
+        settings.dictation_language = String::from(" pt-BR ");
+        let forced = DictationConfig::from_settings(&settings).expect("config");
+        assert_eq!(forced.language.as_deref(), Some("pt-BR"));
+        assert_eq!(forced.deepgram.language, "pt-BR");
+        assert_eq!(forced.assemblyai.language, "pt");
+        assert!(forced.prompt.contains("The speech is in pt-BR"));
+    }
 
     #[test]
     fn preview_shortens_single_dictation_output() {
         let output = DictationOutput {
             transcript: "a".repeat(200),
             duration_seconds: 3.0,
+            clipped: false,
+            usage: None,
+            model: None,
+            api_key: None,
+            segments: Vec::new(),
+            latency_ms: None,
         };
 
         assert_eq!(output.preview().chars().count(), 160);
     }
 
+    #[test]
+    fn counts_transcription_stats() {
+        let stats = TranscriptionStats::new("Ola, mundo  novo\n", 4.5, Some(1_250));
+
+        assert_eq!(stats.words, 3);
+        assert_eq!(stats.characters, 17);
+        assert_eq!(
+            stats.label(),
+            "3 palavra(s) · 17 caracteres · 4.5s de audio · 1.2s de espera"
+        );
+        assert_eq!(
+            TranscriptionStats::new("ok", 1.0, None).label(),
+            "1 palavra(s) · 2 caracteres · 1.0s de audio"
+        );
+    }
+
+    #[test]
+    fn resolves_target_sample_rate() {
+        assert_eq!(
+            TargetSampleRate::from_code("bogus"),
+            TargetSampleRate::Hz16000
+        );
+        assert_eq!(TargetSampleRate::Hz24000.resolve(44_100), 24_000);
+        assert_eq!(
+            TargetSampleRate::from_code("original").resolve(44_100),
+            44_100
+        );
+    }
+
     #[test]
     fn builds_clipboard_text_with_both_tracks() {
         let output = DualTranscriptOutput {
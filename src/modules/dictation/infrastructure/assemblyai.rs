@@ -0,0 +1,272 @@
+use crate::modules::dictation::domain::{
+    AssemblyAiConfig, CancelToken, DictationProvider, PreparedAudio, ProgressTracker,
+    TranscriptSegment, TranscriptionResult, TranscriptionStage,
+};
+use crate::modules::dictation::infrastructure::providers;
+use crate::support::error::OpenVoiceError;
+use reqwest::blocking::Client;
+use serde_json::{Value, json};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+const ASSEMBLYAI_UPLOAD_URL: &str = "https://api.assemblyai.com/v2/upload";
+const ASSEMBLYAI_TRANSCRIPT_URL: &str = "https://api.assemblyai.com/v2/transcript";
+const POLL_INTERVAL_MS: u64 = 1_000;
+/// AssemblyAI usually finishes in a fraction of the audio length; this only
+/// guards against a job that never leaves the queue.
+const POLL_TIMEOUT_SECS: u64 = 300;
+
+/// Uploads the take, queues a transcript for it and polls until it is done
+/// or `cancel` is set.
+pub fn transcribe(
+    config: &AssemblyAiConfig,
+    audio: &PreparedAudio,
+    cancel: &CancelToken,
+    progress: &ProgressTracker,
+) -> Result<TranscriptionResult, OpenVoiceError> {
+    let client = Client::new();
+    let upload_url = upload(&client, config, audio, progress, cancel)?;
+    cancel.check()?;
+    let transcript_id = request_transcript(&client, config, &upload_url)?;
+    progress.set(TranscriptionStage::WaitingForModel);
+    let started_at = Instant::now();
+
+    loop {
+        cancel.check()?;
+        let parsed = providers::send_json(
+            client
+                .get(format!("{ASSEMBLYAI_TRANSCRIPT_URL}/{transcript_id}"))
+                .header("Authorization", &config.api_key),
+            DictationProvider::AssemblyAi,
+        )?;
+
+        match parse_status(&parsed)? {
+            TranscriptStatus::Completed(result) => {
+                progress.set(TranscriptionStage::Parsing);
+                return Ok(if config.timestamps {
+                    result
+                } else {
+                    TranscriptionResult::plain(result.text)
+                });
+            }
+            TranscriptStatus::Pending => {}
+        }
+
+        if started_at.elapsed() >= Duration::from_secs(POLL_TIMEOUT_SECS) {
+            return Err(OpenVoiceError::Network(String::from(
+                "A AssemblyAI demorou demais para concluir a transcricao.",
+            )));
+        }
+
+        thread::sleep(Duration::from_millis(POLL_INTERVAL_MS));
+    }
+}
+
+fn upload(
+    client: &Client,
+    config: &AssemblyAiConfig,
+    audio: &PreparedAudio,
+    progress: &ProgressTracker,
+    cancel: &CancelToken,
+) -> Result<String, OpenVoiceError> {
+    let parsed = providers::send_json(
+        client
+            .post(ASSEMBLYAI_UPLOAD_URL)
+            .header("Authorization", &config.api_key)
+            .header("Content-Type", "application/octet-stream")
+            .body(providers::upload_body(
+                Arc::clone(&audio.audio),
+                progress,
+                cancel,
+            )),
+        DictationProvider::AssemblyAi,
+    )
+    .map_err(|error| cancel.or_cancelled(error))?;
+
+    parsed
+        .get("upload_url")
+        .and_then(Value::as_str)
+        .map(str::to_owned)
+        .ok_or_else(|| {
+            OpenVoiceError::InvalidResponse(String::from(
+                "A AssemblyAI nao retornou a URL do upload.",
+            ))
+        })
+}
+
+fn request_transcript(
+    client: &Client,
+    config: &AssemblyAiConfig,
+    upload_url: &str,
+) -> Result<String, OpenVoiceError> {
+    let parsed = providers::send_json(
+        client
+            .post(ASSEMBLYAI_TRANSCRIPT_URL)
+            .header("Authorization", &config.api_key)
+            .json(&transcript_request(config, upload_url)),
+        DictationProvider::AssemblyAi,
+    )?;
+
+    parsed
+        .get("id")
+        .and_then(Value::as_str)
+        .map(str::to_owned)
+        .ok_or_else(|| {
+            OpenVoiceError::InvalidResponse(String::from(
+                "A AssemblyAI nao retornou o id da transcricao.",
+            ))
+        })
+}
+
+fn transcript_request(config: &AssemblyAiConfig, upload_url: &str) -> Value {
+    let language = config.language.trim();
+    let mut request = if language.is_empty() {
+        json!({ "audio_url": upload_url, "language_detection": true })
+    } else {
+        json!({ "audio_url": upload_url, "language_code": language })
+    };
+
+    if !config.vocabulary.is_empty() {
+        request["word_boost"] = json!(config.vocabulary);
+    }
+
+    request
+}
+
+#[derive(Debug, PartialEq)]
+enum TranscriptStatus {
+    Pending,
+    Completed(TranscriptionResult),
+}
+
+fn parse_status(parsed: &Value) -> Result<TranscriptStatus, OpenVoiceError> {
+    match parsed.get("status").and_then(Value::as_str) {
+        Some("completed") => Ok(TranscriptStatus::Completed(TranscriptionResult {
+            text: parsed
+                .get("text")
+                .and_then(Value::as_str)
+                .unwrap_or_default()
+                .trim()
+                .to_owned(),
+            segments: sentence_segments(parsed),
+        })),
+        Some("error") => Err(OpenVoiceError::InvalidResponse(format!(
+            "A AssemblyAI nao conseguiu transcrever: {}",
+            parsed
+                .get("error")
+                .and_then(Value::as_str)
+                .unwrap_or("falha desconhecida")
+        ))),
+        Some("queued" | "processing") => Ok(TranscriptStatus::Pending),
+        other => Err(OpenVoiceError::InvalidResponse(format!(
+            "A AssemblyAI retornou um status inesperado: {}",
+            other.unwrap_or("vazio")
+        ))),
+    }
+}
+
+/// Groups the word timings (in milliseconds) into one segment per sentence.
+fn sentence_segments(parsed: &Value) -> Vec<TranscriptSegment> {
+    let mut segments = Vec::new();
+    let mut current: Option<TranscriptSegment> = None;
+
+    for word in parsed
+        .get("words")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+    {
+        let (Some(text), Some(start), Some(end)) = (
+            word.get("text").and_then(Value::as_str),
+            word.get("start").and_then(Value::as_f64),
+            word.get("end").and_then(Value::as_f64),
+        ) else {
+            continue;
+        };
+
+        let segment = current.get_or_insert_with(|| TranscriptSegment {
+            start_secs: (start / 1_000.0) as f32,
+            end_secs: 0.0,
+            text: String::new(),
+        });
+        if !segment.text.is_empty() {
+            segment.text.push(' ');
+        }
+        segment.text.push_str(text.trim());
+        segment.end_secs = (end / 1_000.0) as f32;
+
+        if text.trim_end().ends_with(['.', '?', '!']) {
+            segments.extend(current.take());
+        }
+    }
+
+    segments.extend(current);
+    segments
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{TranscriptStatus, parse_status, sentence_segments, transcript_request};
+    use crate::modules::dictation::domain::{AssemblyAiConfig, TranscriptionResult};
+    use serde_json::json;
+
+    #[test]
+    fn reads_transcript_job_status() {
+        assert_eq!(
+            parse_status(&json!({ "status": "processing" })),
+            Ok(TranscriptStatus::Pending)
+        );
+        assert_eq!(
+            parse_status(&json!({ "status": "completed", "text": " ola mundo " })),
+            Ok(TranscriptStatus::Completed(TranscriptionResult::plain(
+                String::from("ola mundo")
+            )))
+        );
+        assert!(parse_status(&json!({ "status": "error", "error": "bad audio" })).is_err());
+    }
+
+    #[test]
+    fn groups_word_timings_into_sentences() {
+        let segments = sentence_segments(&json!({
+            "words": [
+                { "text": "Ola.", "start": 100, "end": 500 },
+                { "text": "Tudo", "start": 900, "end": 1200 },
+                { "text": "bem", "start": 1200, "end": 1600 }
+            ]
+        }));
+
+        assert_eq!(segments.len(), 2);
+        assert_eq!(segments[0].text, "Ola.");
+        assert_eq!(segments[1].text, "Tudo bem");
+        assert_eq!(segments[1].start_secs, 0.9);
+        assert_eq!(segments[1].end_secs, 1.6);
+    }
+
+    #[test]
+    fn detects_language_when_none_is_set() {
+        let mut config = AssemblyAiConfig {
+            api_key: String::from("key"),
+            language: String::new(),
+            timestamps: false,
+            vocabulary: Vec::new(),
+        };
+
+        assert_eq!(
+            transcript_request(&config, "https://cdn/upload"),
+            json!({ "audio_url": "https://cdn/upload", "language_detection": true })
+        );
+
+        config.language = String::from("pt");
+        assert_eq!(
+            transcript_request(&config, "https://cdn/upload"),
+            json!({ "audio_url": "https://cdn/upload", "language_code": "pt" })
+        );
+
+        config.vocabulary = vec![String::from("Tauri")];
+        assert_eq!(
+            transcript_request(&config, "https://cdn/upload")["word_boost"],
+            json!(["Tauri"])
+        );
+    }
+}
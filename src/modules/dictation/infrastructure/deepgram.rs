@@ -0,0 +1,485 @@
+use crate::modules::dictation::domain::{
+    CancelToken, DeepgramConfig, DictationProvider, PreparedAudio, ProgressTracker,
+    TranscriptSegment, TranscriptionResult, TranscriptionStage,
+};
+use crate::modules::dictation::infrastructure::providers::{self, error_message};
+use crate::support::error::OpenVoiceError;
+use reqwest::Url;
+use reqwest::blocking::Client;
+use serde_json::Value;
+use std::net::TcpStream;
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+use tungstenite::stream::MaybeTlsStream;
+use tungstenite::{Message, WebSocket, connect};
+
+const DEEPGRAM_LISTEN_URL: &str = "https://api.deepgram.com/v1/listen";
+const DEEPGRAM_STREAM_URL: &str = "wss://api.deepgram.com/v1/listen";
+const SOCKET_TIMEOUT_MS: u64 = 20;
+/// Audio batched per websocket frame; Deepgram suggests 20-250 ms.
+const STREAM_CHUNK_MS: usize = 100;
+/// How long to wait for the last results after `CloseStream`.
+const CLOSE_TIMEOUT_MS: u64 = 5_000;
+
+type SharedPartial = Arc<Mutex<Option<String>>>;
+
+/// Uploads a finished take to the prerecorded endpoint.
+pub fn transcribe(
+    config: &DeepgramConfig,
+    audio: &PreparedAudio,
+    progress: &ProgressTracker,
+    cancel: &CancelToken,
+) -> Result<TranscriptionResult, OpenVoiceError> {
+    let url = listen_url(DEEPGRAM_LISTEN_URL, config, None)?;
+
+    let parsed = providers::send_json(
+        Client::new()
+            .post(url)
+            .header("Authorization", format!("Token {}", config.api_key))
+            .header("Content-Type", audio.format.mime_type())
+            .body(providers::upload_body(
+                Arc::clone(&audio.audio),
+                progress,
+                cancel,
+            )),
+        DictationProvider::Deepgram,
+    )
+    .map_err(|error| cancel.or_cancelled(error))?;
+    progress.set(TranscriptionStage::Parsing);
+
+    parse_prerecorded(&parsed)
+}
+
+/// Reads the transcript and, when utterances were requested, one segment
+/// per utterance.
+fn parse_prerecorded(parsed: &Value) -> Result<TranscriptionResult, OpenVoiceError> {
+    let text = parsed
+        .pointer("/results/channels/0/alternatives/0/transcript")
+        .and_then(Value::as_str)
+        .map(|transcript| transcript.trim().to_owned())
+        .ok_or_else(|| {
+            OpenVoiceError::InvalidResponse(String::from("Deepgram nao retornou transcricao."))
+        })?;
+    let segments = parsed
+        .pointer("/results/utterances")
+        .and_then(Value::as_array)
+        .map(|utterances| {
+            utterances
+                .iter()
+                .filter_map(|utterance| {
+                    Some(TranscriptSegment {
+                        start_secs: utterance.get("start")?.as_f64()? as f32,
+                        end_secs: utterance.get("end")?.as_f64()? as f32,
+                        text: utterance.get("transcript")?.as_str()?.trim().to_owned(),
+                    })
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    Ok(TranscriptionResult { text, segments })
+}
+
+/// Live websocket session fed with microphone samples while recording.
+/// Interim results are exposed through `take_partial`; `finish` waits for
+/// the final transcript once every sender is gone.
+pub struct StreamingSession {
+    sender: Sender<Vec<f32>>,
+    partial: SharedPartial,
+    worker: JoinHandle<Result<String, String>>,
+}
+
+impl std::fmt::Debug for StreamingSession {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("StreamingSession").finish_non_exhaustive()
+    }
+}
+
+impl StreamingSession {
+    /// Interleaved `f32` samples at the rate and channel count given to
+    /// `start_streaming`.
+    pub fn sender(&self) -> Sender<Vec<f32>> {
+        self.sender.clone()
+    }
+
+    /// Transcript so far (final plus interim text), if it changed since the
+    /// previous call.
+    pub fn take_partial(&self) -> Option<String> {
+        self.partial.lock().ok()?.take()
+    }
+
+    pub fn finish(self) -> Result<String, String> {
+        let StreamingSession { sender, worker, .. } = self;
+
+        drop(sender);
+        worker
+            .join()
+            .map_err(|_| String::from("O streaming do Deepgram terminou de forma inesperada."))?
+    }
+}
+
+pub fn start_streaming(
+    config: DeepgramConfig,
+    sample_rate: u32,
+    channels: u16,
+) -> StreamingSession {
+    let (sender, receiver) = mpsc::channel();
+    let partial = Arc::new(Mutex::new(None));
+    let worker = {
+        let partial = Arc::clone(&partial);
+        thread::spawn(move || run_stream(&config, sample_rate, channels, &receiver, &partial))
+    };
+
+    StreamingSession {
+        sender,
+        partial,
+        worker,
+    }
+}
+
+fn run_stream(
+    config: &DeepgramConfig,
+    sample_rate: u32,
+    channels: u16,
+    audio_rx: &Receiver<Vec<f32>>,
+    partial: &SharedPartial,
+) -> Result<String, String> {
+    let url = listen_url(DEEPGRAM_STREAM_URL, config, Some(sample_rate))?;
+    let mut request = tungstenite::client::IntoClientRequest::into_client_request(url.as_str())
+        .map_err(|error| format!("Falha ao criar request do Deepgram: {error}"))?;
+    let authorization = format!("Token {}", config.api_key)
+        .parse()
+        .map_err(|error| format!("Falha ao montar o header Authorization: {error}"))?;
+    request.headers_mut().insert("Authorization", authorization);
+
+    let (mut socket, _) =
+        connect(request).map_err(|error| format!("Falha ao conectar ao Deepgram: {error}"))?;
+    configure_stream_timeout(&mut socket)?;
+
+    let chunk_bytes = sample_rate as usize * 2 * STREAM_CHUNK_MS / 1_000;
+    let mut pending = Vec::with_capacity(chunk_bytes);
+    let mut transcript = StreamTranscript::default();
+    let mut closing_since = None::<Instant>;
+
+    loop {
+        match closing_since {
+            None => match audio_rx.recv_timeout(Duration::from_millis(SOCKET_TIMEOUT_MS)) {
+                Ok(samples) => {
+                    pending.extend(pcm16_mono(&samples, channels));
+                    // The socket read below also blocks, so catch up on
+                    // whatever the recorder queued meanwhile.
+                    for samples in audio_rx.try_iter() {
+                        pending.extend(pcm16_mono(&samples, channels));
+                    }
+                    if pending.len() >= chunk_bytes {
+                        send_audio(&mut socket, std::mem::take(&mut pending))?;
+                    }
+                }
+                Err(RecvTimeoutError::Timeout) => {}
+                Err(RecvTimeoutError::Disconnected) => {
+                    if !pending.is_empty() {
+                        send_audio(&mut socket, std::mem::take(&mut pending))?;
+                    }
+                    socket
+                        .send(Message::Text(String::from(r#"{"type":"CloseStream"}"#)))
+                        .map_err(|error| format!("Falha ao encerrar o streaming: {error}"))?;
+                    closing_since = Some(Instant::now());
+                }
+            },
+            Some(since) if since.elapsed() >= Duration::from_millis(CLOSE_TIMEOUT_MS) => {
+                log_warn!("[openvoice][deepgram] timed out waiting for the final results");
+                break;
+            }
+            Some(_) => {}
+        }
+
+        match socket.read() {
+            Ok(Message::Text(text)) => {
+                if let Some(result) = parse_stream_message(&text)? {
+                    transcript.apply(result);
+                    if let Ok(mut slot) = partial.lock() {
+                        *slot = Some(transcript.text());
+                    }
+                }
+            }
+            Ok(Message::Close(_)) => break,
+            Ok(_) => {}
+            Err(tungstenite::Error::Io(error))
+                if matches!(
+                    error.kind(),
+                    std::io::ErrorKind::WouldBlock | std::io::ErrorKind::TimedOut
+                ) => {}
+            Err(tungstenite::Error::AlreadyClosed) | Err(tungstenite::Error::ConnectionClosed) => {
+                break;
+            }
+            Err(error) => return Err(format!("Falha ao ler eventos do Deepgram: {error}")),
+        }
+    }
+
+    Ok(transcript.final_text())
+}
+
+fn send_audio(
+    socket: &mut WebSocket<MaybeTlsStream<TcpStream>>,
+    chunk: Vec<u8>,
+) -> Result<(), String> {
+    socket
+        .send(Message::Binary(chunk))
+        .map_err(|error| format!("Falha ao enviar audio para o Deepgram: {error}"))
+}
+
+fn listen_url(
+    base: &str,
+    config: &DeepgramConfig,
+    stream_rate: Option<u32>,
+) -> Result<Url, String> {
+    let mut params = vec![
+        ("model", config.model.clone()),
+        ("smart_format", String::from("true")),
+    ];
+
+    if !config.language.trim().is_empty() {
+        params.push(("language", config.language.trim().to_owned()));
+    }
+
+    // Nova-3 replaced keyword boosting with key terms.
+    let vocabulary_param = if config.model.starts_with("nova-3") {
+        "keyterm"
+    } else {
+        "keywords"
+    };
+    params.extend(
+        config
+            .vocabulary
+            .iter()
+            .map(|term| (vocabulary_param, term.clone())),
+    );
+
+    if config.timestamps && stream_rate.is_none() {
+        params.push(("utterances", String::from("true")));
+    }
+
+    if let Some(sample_rate) = stream_rate {
+        params.extend([
+            ("encoding", String::from("linear16")),
+            ("sample_rate", sample_rate.to_string()),
+            ("channels", String::from("1")),
+            ("interim_results", String::from("true")),
+        ]);
+    }
+
+    Url::parse_with_params(base, &params)
+        .map_err(|error| format!("Falha ao montar a URL do Deepgram: {error}"))
+}
+
+#[derive(Debug, PartialEq)]
+struct StreamResult {
+    transcript: String,
+    is_final: bool,
+}
+
+/// Reads a websocket message; `Ok(None)` for metadata and other events.
+fn parse_stream_message(text: &str) -> Result<Option<StreamResult>, String> {
+    let parsed: Value = serde_json::from_str(text)
+        .map_err(|error| format!("Recebi um evento invalido do Deepgram: {error}"))?;
+
+    match parsed.get("type").and_then(Value::as_str) {
+        Some("Results") => Ok(Some(StreamResult {
+            transcript: parsed
+                .pointer("/channel/alternatives/0/transcript")
+                .and_then(Value::as_str)
+                .unwrap_or_default()
+                .trim()
+                .to_owned(),
+            is_final: parsed
+                .get("is_final")
+                .and_then(Value::as_bool)
+                .unwrap_or(false),
+        })),
+        Some("Error") => Err(format!(
+            "Deepgram retornou erro: {}",
+            error_message(&parsed).unwrap_or("falha desconhecida")
+        )),
+        _ => Ok(None),
+    }
+}
+
+/// Final segments plus the interim text of the segment still being spoken.
+#[derive(Debug, Default)]
+struct StreamTranscript {
+    finals: Vec<String>,
+    interim: String,
+}
+
+impl StreamTranscript {
+    fn apply(&mut self, result: StreamResult) {
+        if result.is_final {
+            if !result.transcript.is_empty() {
+                self.finals.push(result.transcript);
+            }
+            self.interim.clear();
+        } else {
+            self.interim = result.transcript;
+        }
+    }
+
+    fn text(&self) -> String {
+        self.finals
+            .iter()
+            .map(String::as_str)
+            .chain((!self.interim.is_empty()).then_some(self.interim.as_str()))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    fn final_text(&self) -> String {
+        self.finals.join(" ")
+    }
+}
+
+/// Averages interleaved frames to mono 16-bit little-endian PCM.
+fn pcm16_mono(samples: &[f32], channels: u16) -> Vec<u8> {
+    samples
+        .chunks(usize::from(channels.max(1)))
+        .flat_map(|frame| {
+            let mono = frame.iter().sum::<f32>() / frame.len() as f32;
+            ((mono.clamp(-1.0, 1.0) * i16::MAX as f32) as i16).to_le_bytes()
+        })
+        .collect()
+}
+
+fn configure_stream_timeout(
+    socket: &mut WebSocket<MaybeTlsStream<TcpStream>>,
+) -> Result<(), String> {
+    let timeout = Some(Duration::from_millis(SOCKET_TIMEOUT_MS));
+
+    match socket.get_mut() {
+        MaybeTlsStream::Plain(tcp) => tcp
+            .set_read_timeout(timeout)
+            .map_err(|error| format!("Falha ao configurar timeout do socket: {error}")),
+        MaybeTlsStream::Rustls(tls) => tls
+            .get_mut()
+            .set_read_timeout(timeout)
+            .map_err(|error| format!("Falha ao configurar timeout do socket TLS: {error}")),
+        _ => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        DEEPGRAM_LISTEN_URL, DEEPGRAM_STREAM_URL, StreamResult, StreamTranscript, listen_url,
+        parse_prerecorded, parse_stream_message, pcm16_mono,
+    };
+    use crate::modules::dictation::domain::DeepgramConfig;
+    use serde_json::json;
+
+    #[test]
+    fn builds_streaming_url_with_audio_params() {
+        let config = DeepgramConfig {
+            api_key: String::from("key"),
+            model: String::from("nova-3"),
+            language: String::from("pt-BR"),
+            timestamps: true,
+            vocabulary: Vec::new(),
+        };
+
+        let url = listen_url(DEEPGRAM_STREAM_URL, &config, Some(48_000)).expect("url");
+
+        assert_eq!(
+            url.as_str(),
+            "wss://api.deepgram.com/v1/listen?model=nova-3&smart_format=true&language=pt-BR&encoding=linear16&sample_rate=48000&channels=1&interim_results=true"
+        );
+        assert!(
+            listen_url(DEEPGRAM_LISTEN_URL, &config, None)
+                .expect("url")
+                .as_str()
+                .ends_with("&utterances=true")
+        );
+    }
+
+    #[test]
+    fn boosts_vocabulary_with_the_param_the_model_takes() {
+        let mut config = DeepgramConfig {
+            api_key: String::from("key"),
+            model: String::from("nova-3"),
+            language: String::new(),
+            timestamps: false,
+            vocabulary: vec![String::from("Tauri"), String::from("cpal")],
+        };
+
+        assert!(
+            listen_url(DEEPGRAM_LISTEN_URL, &config, None)
+                .expect("url")
+                .as_str()
+                .ends_with("&keyterm=Tauri&keyterm=cpal")
+        );
+
+        config.model = String::from("nova-2");
+        assert!(
+            listen_url(DEEPGRAM_LISTEN_URL, &config, None)
+                .expect("url")
+                .as_str()
+                .ends_with("&keywords=Tauri&keywords=cpal")
+        );
+    }
+
+    #[test]
+    fn reads_utterances_as_segments() {
+        let result = parse_prerecorded(&json!({
+            "results": {
+                "channels": [{ "alternatives": [{ "transcript": "ola. tudo bem?" }] }],
+                "utterances": [
+                    { "start": 0.1, "end": 0.9, "transcript": "ola." },
+                    { "start": 1.5, "end": 2.25, "transcript": "tudo bem?" }
+                ]
+            }
+        }))
+        .expect("result");
+
+        assert_eq!(result.text, "ola. tudo bem?");
+        assert_eq!(result.segments.len(), 2);
+        assert_eq!(result.segments[1].end_secs, 2.25);
+    }
+
+    #[test]
+    fn accumulates_interim_and_final_results() {
+        let mut transcript = StreamTranscript::default();
+        let interim = parse_stream_message(
+            r#"{"type":"Results","is_final":false,"channel":{"alternatives":[{"transcript":"ola mun"}]}}"#,
+        )
+        .expect("parse")
+        .expect("result");
+
+        transcript.apply(interim);
+        assert_eq!(transcript.text(), "ola mun");
+        assert_eq!(transcript.final_text(), "");
+
+        transcript.apply(StreamResult {
+            transcript: String::from("ola mundo"),
+            is_final: true,
+        });
+        transcript.apply(StreamResult {
+            transcript: String::from("tudo"),
+            is_final: false,
+        });
+
+        assert_eq!(transcript.text(), "ola mundo tudo");
+        assert_eq!(transcript.final_text(), "ola mundo");
+        assert_eq!(
+            parse_stream_message(r#"{"type":"Metadata"}"#).expect("parse"),
+            None
+        );
+        assert!(parse_stream_message(r#"{"type":"Error","description":"bad"}"#).is_err());
+    }
+
+    #[test]
+    fn converts_stereo_frames_to_mono_pcm16() {
+        assert_eq!(
+            pcm16_mono(&[1.0, 1.0, -1.0, 0.0], 2),
+            vec![0xff, 0x7f, 0x01, 0xc0]
+        );
+    }
+}
@@ -0,0 +1,362 @@
+use crate::modules::audio::infrastructure::storage as audio_storage;
+use crate::modules::dictation::domain::{AudioFormat, HistoryAudioRetention, HistoryEntry};
+use crate::modules::live_transcription::infrastructure::db;
+use rusqlite::{Connection, OptionalExtension, params};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+const AUDIO_DIR: &str = "history-audio";
+const ENTRY_COLUMNS: &str = "id, created_at, provider, model, duration_seconds, cost_usd, text, audio_path, latency_ms, api_key";
+
+pub fn ensure_schema(conn: &Connection) -> Result<(), String> {
+    conn.execute_batch(
+        "CREATE TABLE IF NOT EXISTS dt_history (
+            id               INTEGER PRIMARY KEY AUTOINCREMENT,
+            created_at       TEXT NOT NULL,
+            provider         TEXT NOT NULL,
+            model            TEXT,
+            duration_seconds REAL NOT NULL DEFAULT 0,
+            cost_usd         REAL,
+            text             TEXT NOT NULL,
+            audio_path       TEXT,
+            latency_ms       INTEGER,
+            api_key          TEXT
+        );",
+    )
+    .map_err(|error| format!("Nao consegui criar schema do historico: {error}"))?;
+
+    db::add_column_if_missing(conn, "dt_history", "audio_path", "TEXT")?;
+    db::add_column_if_missing(conn, "dt_history", "latency_ms", "INTEGER")?;
+    db::add_column_if_missing(conn, "dt_history", "api_key", "TEXT")
+}
+
+/// Stores a finished transcription and returns it with its id.
+pub fn add_entry(entry: HistoryEntry) -> Result<HistoryEntry, String> {
+    let conn = db::open_db()?;
+    ensure_schema(&conn)?;
+    add_entry_in_conn(&conn, entry)
+}
+
+/// Most recent transcriptions first.
+pub fn list_entries(limit: usize) -> Result<Vec<HistoryEntry>, String> {
+    let conn = db::open_db()?;
+    ensure_schema(&conn)?;
+    list_entries_in_conn(&conn, limit)
+}
+
+pub fn get_entry(id: i64) -> Result<Option<HistoryEntry>, String> {
+    let conn = db::open_db()?;
+    ensure_schema(&conn)?;
+    get_entry_in_conn(&conn, id)
+}
+
+/// Replaces the text of an entry after it was transcribed again.
+pub fn update_transcription(entry: &HistoryEntry) -> Result<(), String> {
+    let conn = db::open_db()?;
+    ensure_schema(&conn)?;
+    conn.execute(
+        "UPDATE dt_history SET provider = ?2, model = ?3, cost_usd = ?4, text = ?5, api_key = ?6
+         WHERE id = ?1",
+        params![
+            entry.id,
+            entry.provider,
+            entry.model,
+            entry.cost_usd,
+            entry.text,
+            entry.api_key
+        ],
+    )
+    .map_err(|error| format!("Nao consegui atualizar a transcricao: {error}"))?;
+    Ok(())
+}
+
+pub fn delete_entry(id: i64) -> Result<(), String> {
+    let conn = db::open_db()?;
+    ensure_schema(&conn)?;
+    if let Some(path) = get_entry_in_conn(&conn, id)?.and_then(|entry| entry.audio_path) {
+        remove_audio(&path);
+    }
+    conn.execute("DELETE FROM dt_history WHERE id = ?1", params![id])
+        .map_err(|error| format!("Nao consegui apagar a transcricao: {error}"))?;
+    Ok(())
+}
+
+pub fn clear() -> Result<(), String> {
+    let conn = db::open_db()?;
+    ensure_schema(&conn)?;
+    conn.execute("DELETE FROM dt_history", [])
+        .map_err(|error| format!("Nao consegui limpar o historico: {error}"))?;
+    if let Ok(dir) = audio_dir()
+        && dir.exists()
+        && let Err(error) = fs::remove_dir_all(&dir)
+    {
+        log_error!(
+            "[openvoice][dictation] failed to remove {}: {error}",
+            dir.display()
+        );
+    }
+    Ok(())
+}
+
+/// Writes the compressed take of entry `id` and links it to the entry.
+pub fn save_audio(id: i64, audio: &[u8], format: AudioFormat) -> Result<PathBuf, String> {
+    let dir = audio_dir()?;
+    fs::create_dir_all(&dir)
+        .map_err(|error| format!("Falha ao criar {}: {error}", dir.display()))?;
+    let path = dir.join(format!("{id}.{}", format.code()));
+    fs::write(&path, audio)
+        .map_err(|error| format!("Falha ao salvar o audio em {}: {error}", path.display()))?;
+
+    let conn = db::open_db()?;
+    ensure_schema(&conn)?;
+    conn.execute(
+        "UPDATE dt_history SET audio_path = ?2 WHERE id = ?1",
+        params![id, path.to_string_lossy()],
+    )
+    .map_err(|error| format!("Nao consegui ligar o audio a transcricao: {error}"))?;
+
+    Ok(path)
+}
+
+/// Deletes kept takes beyond the retention limits, newest kept first.
+pub fn prune_audio(retention: HistoryAudioRetention) -> Result<(), String> {
+    let conn = db::open_db()?;
+    ensure_schema(&conn)?;
+    let kept: Vec<(i64, PathBuf)> = conn
+        .prepare(
+            "SELECT id, audio_path FROM dt_history
+             WHERE audio_path IS NOT NULL ORDER BY id DESC",
+        )
+        .and_then(|mut statement| {
+            statement
+                .query_map([], |row| {
+                    Ok((row.get(0)?, PathBuf::from(row.get::<_, String>(1)?)))
+                })
+                .and_then(|rows| rows.collect())
+        })
+        .map_err(|error| format!("Nao consegui ler os audios do historico: {error}"))?;
+
+    let now = SystemTime::now();
+    let files: Vec<_> = kept
+        .iter()
+        .map(|(id, path)| {
+            let metadata = fs::metadata(path).ok();
+            let size = metadata.as_ref().map_or(0, |metadata| metadata.len());
+            let age = metadata
+                .and_then(|metadata| metadata.modified().ok())
+                .and_then(|modified| now.duration_since(modified).ok())
+                .unwrap_or_default();
+            (*id, size, age)
+        })
+        .collect();
+
+    for id in audio_to_prune(&files, retention) {
+        if let Some((_, path)) = kept.iter().find(|(kept_id, _)| *kept_id == id) {
+            remove_audio(path);
+        }
+        conn.execute(
+            "UPDATE dt_history SET audio_path = NULL WHERE id = ?1",
+            params![id],
+        )
+        .map_err(|error| format!("Nao consegui atualizar o historico: {error}"))?;
+    }
+
+    Ok(())
+}
+
+/// Ids whose audio falls outside `retention`, given `(id, bytes, age)`
+/// newest first.
+fn audio_to_prune(files: &[(i64, u64, Duration)], retention: HistoryAudioRetention) -> Vec<i64> {
+    let mut total = 0_u64;
+
+    files
+        .iter()
+        .filter(|(_, size, age)| {
+            total = total.saturating_add(*size);
+            total > retention.max_bytes || retention.max_age.is_some_and(|max| *age > max)
+        })
+        .map(|(id, _, _)| *id)
+        .collect()
+}
+
+pub fn audio_dir() -> Result<PathBuf, String> {
+    Ok(audio_storage::data_dir()?.join(AUDIO_DIR))
+}
+
+fn remove_audio(path: &Path) {
+    if let Err(error) = fs::remove_file(path)
+        && error.kind() != std::io::ErrorKind::NotFound
+    {
+        log_error!(
+            "[openvoice][dictation] failed to remove {}: {error}",
+            path.display()
+        );
+    }
+}
+
+fn add_entry_in_conn(conn: &Connection, entry: HistoryEntry) -> Result<HistoryEntry, String> {
+    conn.execute(
+        "INSERT INTO dt_history (created_at, provider, model, duration_seconds, cost_usd, text, latency_ms, api_key)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+        params![
+            entry.created_at,
+            entry.provider,
+            entry.model,
+            f64::from(entry.duration_seconds),
+            entry.cost_usd,
+            entry.text,
+            entry.latency_ms.map(|latency| latency as i64),
+            entry.api_key
+        ],
+    )
+    .map_err(|error| format!("Nao consegui salvar a transcricao no historico: {error}"))?;
+
+    Ok(HistoryEntry {
+        id: conn.last_insert_rowid(),
+        ..entry
+    })
+}
+
+fn list_entries_in_conn(conn: &Connection, limit: usize) -> Result<Vec<HistoryEntry>, String> {
+    let mut statement = conn
+        .prepare(&format!(
+            "SELECT {ENTRY_COLUMNS} FROM dt_history ORDER BY id DESC LIMIT ?1"
+        ))
+        .map_err(|error| format!("Nao consegui ler o historico: {error}"))?;
+
+    statement
+        .query_map(params![limit as i64], entry_from_row)
+        .and_then(|rows| rows.collect())
+        .map_err(|error| format!("Nao consegui ler o historico: {error}"))
+}
+
+fn get_entry_in_conn(conn: &Connection, id: i64) -> Result<Option<HistoryEntry>, String> {
+    conn.query_row(
+        &format!("SELECT {ENTRY_COLUMNS} FROM dt_history WHERE id = ?1"),
+        params![id],
+        entry_from_row,
+    )
+    .optional()
+    .map_err(|error| format!("Nao consegui ler a transcricao: {error}"))
+}
+
+fn entry_from_row(row: &rusqlite::Row<'_>) -> rusqlite::Result<HistoryEntry> {
+    Ok(HistoryEntry {
+        id: row.get(0)?,
+        created_at: row.get(1)?,
+        provider: row.get(2)?,
+        model: row.get(3)?,
+        duration_seconds: row.get::<_, f64>(4)? as f32,
+        cost_usd: row.get(5)?,
+        text: row.get(6)?,
+        audio_path: row.get::<_, Option<String>>(7)?.map(PathBuf::from),
+        latency_ms: row.get::<_, Option<i64>>(8)?.map(|latency| latency as u64),
+        api_key: row.get(9)?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        add_entry_in_conn, audio_to_prune, ensure_schema, get_entry_in_conn, list_entries_in_conn,
+    };
+    use crate::modules::dictation::domain::{HistoryAudioRetention, HistoryEntry};
+    use rusqlite::Connection;
+    use std::time::Duration;
+
+    #[test]
+    fn lists_newest_entries_first() {
+        let conn = Connection::open_in_memory().expect("in-memory db");
+        ensure_schema(&conn).expect("schema");
+        let entry = |text: &str| HistoryEntry {
+            id: 0,
+            created_at: String::from("2026-03-02T10:00:00Z"),
+            provider: String::from("openrouter"),
+            model: Some(String::from("google/gemini-2.5-flash")),
+            api_key: Some(String::from("...a1b2")),
+            duration_seconds: 4.5,
+            cost_usd: None,
+            text: text.to_owned(),
+            audio_path: None,
+            latency_ms: Some(900),
+        };
+
+        let first = add_entry_in_conn(&conn, entry("primeira")).expect("add");
+        add_entry_in_conn(&conn, entry("segunda")).expect("add");
+        add_entry_in_conn(&conn, entry("terceira")).expect("add");
+
+        let entries = list_entries_in_conn(&conn, 2).expect("list");
+
+        assert!(first.id > 0);
+        assert_eq!(
+            entries
+                .iter()
+                .map(|entry| entry.text.as_str())
+                .collect::<Vec<_>>(),
+            ["terceira", "segunda"]
+        );
+        assert_eq!(entries[0].model.as_deref(), Some("google/gemini-2.5-flash"));
+        assert_eq!(entries[0].api_key.as_deref(), Some("...a1b2"));
+        assert_eq!(
+            get_entry_in_conn(&conn, first.id)
+                .expect("get")
+                .map(|entry| (entry.text, entry.latency_ms)),
+            Some((String::from("primeira"), Some(900)))
+        );
+    }
+
+    #[test]
+    fn adds_missing_columns_to_an_old_history_table() {
+        let conn = Connection::open_in_memory().expect("in-memory db");
+        conn.execute_batch(
+            "CREATE TABLE dt_history (
+                id               INTEGER PRIMARY KEY AUTOINCREMENT,
+                created_at       TEXT NOT NULL,
+                provider         TEXT NOT NULL,
+                model            TEXT,
+                duration_seconds REAL NOT NULL DEFAULT 0,
+                cost_usd         REAL,
+                text             TEXT NOT NULL
+            );",
+        )
+        .expect("old schema");
+
+        ensure_schema(&conn).expect("schema");
+        ensure_schema(&conn).expect("schema again");
+
+        assert!(list_entries_in_conn(&conn, 1).expect("list").is_empty());
+    }
+
+    #[test]
+    fn prunes_oldest_audio_beyond_size_and_age() {
+        let day = Duration::from_secs(24 * 60 * 60);
+        let files = [
+            (4, 40, day),
+            (3, 40, 2 * day),
+            (2, 40, 3 * day),
+            (1, 10, 40 * day),
+        ];
+
+        assert_eq!(
+            audio_to_prune(
+                &files,
+                HistoryAudioRetention {
+                    max_bytes: 100,
+                    max_age: None,
+                }
+            ),
+            [2, 1]
+        );
+        assert_eq!(
+            audio_to_prune(
+                &files,
+                HistoryAudioRetention {
+                    max_bytes: 1_000,
+                    max_age: Some(30 * day),
+                }
+            ),
+            [1]
+        );
+    }
+}
@@ -0,0 +1,89 @@
+use std::io::{Read, Write};
+use std::process::{Command, Stdio};
+use std::thread;
+use std::time::{Duration, Instant};
+
+const HOOK_POLL_MS: u64 = 50;
+/// Enough stderr to explain a failure without flooding the hint.
+const HOOK_STDERR_CHARS: usize = 300;
+
+/// Runs `command` through `sh -c` with the transcript on stdin and in
+/// `$OPENVOICE_TEXT`. The command is killed when it outlives `timeout`.
+pub fn run(command: &str, text: &str, timeout: Duration) -> Result<(), String> {
+    let mut child = Command::new("sh")
+        .arg("-c")
+        .arg(command)
+        .env("OPENVOICE_TEXT", text)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|error| format!("Falha ao executar o comando pos-transcricao: {error}"))?;
+
+    // Commands that ignore stdin close the pipe early; that is not an error.
+    if let Some(mut stdin) = child.stdin.take() {
+        let _ = stdin.write_all(text.as_bytes());
+    }
+
+    let started = Instant::now();
+    let status = loop {
+        match child.try_wait() {
+            Ok(Some(status)) => break status,
+            Ok(None) if started.elapsed() >= timeout => {
+                let _ = child.kill();
+                let _ = child.wait();
+                return Err(format!(
+                    "O comando pos-transcricao passou de {}s e foi encerrado.",
+                    timeout.as_secs()
+                ));
+            }
+            Ok(None) => thread::sleep(Duration::from_millis(HOOK_POLL_MS)),
+            Err(error) => {
+                return Err(format!(
+                    "Falha ao aguardar o comando pos-transcricao: {error}"
+                ));
+            }
+        }
+    };
+
+    if status.success() {
+        return Ok(());
+    }
+
+    let mut stderr = String::new();
+    if let Some(mut pipe) = child.stderr.take() {
+        let _ = pipe.read_to_string(&mut stderr);
+    }
+    let stderr: String = stderr.trim().chars().take(HOOK_STDERR_CHARS).collect();
+
+    Err(if stderr.is_empty() {
+        format!("O comando pos-transcricao terminou com {status}.")
+    } else {
+        format!("O comando pos-transcricao terminou com {status}: {stderr}")
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::run;
+    use std::time::Duration;
+
+    #[test]
+    fn passes_text_on_stdin_and_env() {
+        run(
+            r#"test "$(cat)" = "ola mundo" && test "$OPENVOICE_TEXT" = "ola mundo""#,
+            "ola mundo",
+            Duration::from_secs(5),
+        )
+        .expect("hook");
+    }
+
+    #[test]
+    fn reports_failures_and_timeouts() {
+        let error = run("echo nope >&2; exit 3", "", Duration::from_secs(5)).unwrap_err();
+        assert!(error.ends_with(": nope"), "{error}");
+
+        let error = run("sleep 5", "", Duration::from_millis(100)).unwrap_err();
+        assert!(error.contains("foi encerrado"), "{error}");
+    }
+}
@@ -0,0 +1,521 @@
+#![allow(dead_code)]
+
+pub mod assemblyai;
+pub mod deepgram;
+pub mod history;
+pub mod hook;
+pub mod models;
+pub mod openai;
+pub mod providers;
+pub mod usage;
+pub mod webhook;
+
+use crate::modules::{
+    audio::domain::{CaptureSession, CapturedAudio},
+    audio::infrastructure::storage as audio_storage,
+    dictation::domain::{
+        DictationConfig, DictationProvider, DualTranscriptOutput, PreparedAudio,
+        ProviderTranscript, TranscriptionResult, TranscriptionStage, TranscriptionUsage,
+        api_key_hint,
+    },
+};
+use crate::support::error::OpenVoiceError;
+use base64::Engine;
+use reqwest::blocking::Client;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io::{Cursor, Read, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::Arc;
+use std::thread;
+
+const CHAT_COMPLETIONS_PATH: &str = "/chat/completions";
+const OPUS_BITRATE: &str = "24k";
+/// Stands in for the audio while the request JSON is serialized; the
+/// base64 is spliced in as the body is sent.
+const AUDIO_PLACEHOLDER: &str = "__openvoice_audio__";
+/// Audio encoded per read of the request body; a multiple of 3 so the
+/// blocks concatenate into one valid base64 string.
+const BASE64_BLOCK_BYTES: usize = 48 * 1024;
+
+#[derive(Debug, Serialize)]
+struct ChatRequest {
+    model: String,
+    messages: Vec<ChatMessage>,
+    usage: UsageRequest,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    temperature: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    top_p: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    max_tokens: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    provider: Option<ProviderPreferences>,
+}
+
+/// OpenRouter routing preferences; only sent in privacy mode.
+#[derive(Debug, Serialize)]
+struct ProviderPreferences {
+    data_collection: &'static str,
+}
+
+/// Asks OpenRouter to include token counts and cost in the response.
+#[derive(Debug, Serialize)]
+struct UsageRequest {
+    include: bool,
+}
+
+#[derive(Debug, Serialize)]
+struct ChatMessage {
+    role: String,
+    content: Vec<ContentPart>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(tag = "type")]
+enum ContentPart {
+    #[serde(rename = "text")]
+    Text { text: String },
+    #[serde(rename = "input_audio")]
+    InputAudio { input_audio: InputAudio },
+}
+
+#[derive(Debug, Serialize)]
+struct InputAudio {
+    data: String,
+    format: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChatResponse {
+    choices: Vec<Choice>,
+    #[serde(default)]
+    error: Option<ApiError>,
+    #[serde(default)]
+    usage: Option<ResponseUsage>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ResponseUsage {
+    #[serde(default)]
+    prompt_tokens: u64,
+    #[serde(default)]
+    completion_tokens: u64,
+    #[serde(default)]
+    cost: f64,
+}
+
+#[derive(Debug, Deserialize)]
+struct Choice {
+    message: ResponseMessage,
+}
+
+#[derive(Debug, Deserialize)]
+struct ResponseMessage {
+    content: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct ApiError {
+    message: String,
+}
+
+/// The serialized request with the audio's base64 streamed into it, so
+/// neither the base64 string nor the full JSON is ever held in memory.
+fn request_body(
+    request: &ChatRequest,
+    audio: Arc<[u8]>,
+) -> Result<(u64, impl Read + Send + 'static), OpenVoiceError> {
+    let json = serde_json::to_string(request)
+        .map_err(|error| format!("Falha ao serializar pedido ao OpenRouter: {error}"))?;
+    // Only sampling numbers follow the audio, so the last match is the
+    // placeholder even if the prompt happens to contain the same text.
+    let (head, tail) = json.rsplit_once(AUDIO_PLACEHOLDER).ok_or_else(|| {
+        OpenVoiceError::Other(String::from("Falha ao montar o pedido ao OpenRouter."))
+    })?;
+    let encoded_len = base64::encoded_len(audio.len(), true).unwrap_or_default();
+    let total = (head.len() + encoded_len + tail.len()) as u64;
+    let reader = Cursor::new(head.to_owned().into_bytes())
+        .chain(Base64Reader::new(audio))
+        .chain(Cursor::new(tail.to_owned().into_bytes()));
+
+    Ok((total, reader))
+}
+
+/// Base64 of a buffer, encoded one block at a time as it is read.
+struct Base64Reader {
+    bytes: Arc<[u8]>,
+    position: usize,
+    block: Vec<u8>,
+    block_position: usize,
+}
+
+impl Base64Reader {
+    fn new(bytes: Arc<[u8]>) -> Self {
+        Self {
+            bytes,
+            position: 0,
+            block: Vec::new(),
+            block_position: 0,
+        }
+    }
+}
+
+impl Read for Base64Reader {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        if self.block_position >= self.block.len() {
+            if self.position >= self.bytes.len() {
+                return Ok(0);
+            }
+
+            let end = (self.position + BASE64_BLOCK_BYTES).min(self.bytes.len());
+            self.block = base64::engine::general_purpose::STANDARD
+                .encode(&self.bytes[self.position..end])
+                .into_bytes();
+            self.block_position = 0;
+            self.position = end;
+        }
+
+        let read = buf.len().min(self.block.len() - self.block_position);
+        buf[..read].copy_from_slice(&self.block[self.block_position..self.block_position + read]);
+        self.block_position += read;
+
+        Ok(read)
+    }
+}
+
+pub fn transcribe(
+    config: &DictationConfig,
+    model: &str,
+    api_key: &str,
+    audio: &PreparedAudio,
+) -> Result<ProviderTranscript, OpenVoiceError> {
+    let client = Client::new();
+    let request = ChatRequest {
+        model: model.to_owned(),
+        messages: vec![ChatMessage {
+            role: String::from("user"),
+            content: vec![
+                ContentPart::Text {
+                    text: config.prompt.clone(),
+                },
+                ContentPart::InputAudio {
+                    input_audio: InputAudio {
+                        data: String::from(AUDIO_PLACEHOLDER),
+                        format: audio.format.code().to_owned(),
+                    },
+                },
+            ],
+        }],
+        usage: UsageRequest { include: true },
+        temperature: config.temperature,
+        top_p: config.top_p,
+        max_tokens: config.max_tokens,
+        provider: config.deny_data_collection.then_some(ProviderPreferences {
+            data_collection: "deny",
+        }),
+    };
+
+    let (total, payload) = request_body(&request, Arc::clone(&audio.audio))?;
+
+    let mut builder = client
+        .post(format!("{}{CHAT_COMPLETIONS_PATH}", config.base_url))
+        .header("Authorization", format!("Bearer {api_key}"))
+        .header("Content-Type", "application/json");
+    if let Some(referer) = &config.referer {
+        builder = builder.header("HTTP-Referer", referer);
+    }
+    if let Some(app_title) = &config.app_title {
+        builder = builder.header("X-Title", app_title);
+    }
+
+    let body = providers::send_json(
+        builder.body(providers::upload_reader(
+            payload,
+            total,
+            &config.progress,
+            &config.cancel,
+        )),
+        DictationProvider::OpenRouter,
+    )
+    .map_err(|error| config.cancel.or_cancelled(error))?;
+    config.progress.set(TranscriptionStage::Parsing);
+
+    let chat_response: ChatResponse = serde_json::from_value(body).map_err(|error| {
+        OpenVoiceError::InvalidResponse(format!(
+            "Falha ao interpretar resposta do OpenRouter: {error}"
+        ))
+    })?;
+
+    if let Some(error) = chat_response.error {
+        return Err(OpenVoiceError::InvalidResponse(format!(
+            "OpenRouter retornou erro: {}",
+            error.message
+        )));
+    }
+
+    let text = chat_response
+        .choices
+        .first()
+        .map(|choice| choice.message.content.trim().to_owned())
+        .filter(|content| !content.is_empty())
+        .ok_or_else(|| {
+            OpenVoiceError::InvalidResponse(String::from("OpenRouter nao retornou transcricao."))
+        })?;
+
+    let result = if config.timestamps {
+        TranscriptionResult::parse_timestamped(&text)
+    } else {
+        TranscriptionResult::plain(text)
+    };
+
+    Ok(ProviderTranscript {
+        result,
+        usage: chat_response.usage.map(|usage| TranscriptionUsage {
+            prompt_tokens: usage.prompt_tokens,
+            completion_tokens: usage.completion_tokens,
+            cost_usd: usage.cost,
+        }),
+        model: Some(model.to_owned()),
+        api_key: Some(api_key_hint(api_key)),
+    })
+}
+
+/// Transcodes an in-memory WAV to Ogg/Opus through `ffmpeg`.
+pub fn encode_ogg_opus(wav: &[u8]) -> Result<Vec<u8>, String> {
+    let mut child = Command::new("ffmpeg")
+        .args([
+            "-hide_banner",
+            "-loglevel",
+            "error",
+            "-f",
+            "wav",
+            "-i",
+            "pipe:0",
+            "-c:a",
+            "libopus",
+            "-b:a",
+            OPUS_BITRATE,
+            "-application",
+            "voip",
+            "-f",
+            "ogg",
+            "pipe:1",
+        ])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|error| format!("Falha ao iniciar ffmpeg para codificar Opus: {error}"))?;
+
+    let mut stdin = child
+        .stdin
+        .take()
+        .ok_or_else(|| String::from("Falha ao abrir stdin do ffmpeg."))?;
+    let input = wav.to_vec();
+    // Feed stdin from another thread while `wait_with_output` drains stdout
+    // and stderr together, so no full pipe can deadlock us.
+    let writer = thread::spawn(move || stdin.write_all(&input));
+
+    let output = child
+        .wait_with_output()
+        .map_err(|error| format!("Falha ao aguardar o ffmpeg: {error}"))?;
+    let _ = writer.join();
+
+    if !output.status.success() || output.stdout.is_empty() {
+        return Err(format!(
+            "ffmpeg falhou com status {}: {}",
+            output.status,
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+
+    Ok(output.stdout)
+}
+
+/// Reads a kept take back for playback, going through `ffmpeg` for
+/// anything that is not a WAV.
+pub fn decode_audio(path: &Path) -> Result<CapturedAudio, String> {
+    let wav = if path.extension().is_some_and(|extension| extension == "wav") {
+        fs::read(path).map_err(|error| format!("Falha ao ler {}: {error}", path.display()))?
+    } else {
+        let output = Command::new("ffmpeg")
+            .args(["-hide_banner", "-loglevel", "error", "-i"])
+            .arg(path)
+            .args(["-c:a", "pcm_s16le", "-f", "wav", "pipe:1"])
+            .stdin(Stdio::null())
+            .output()
+            .map_err(|error| format!("Falha ao iniciar ffmpeg para decodificar: {error}"))?;
+        if !output.status.success() {
+            return Err(format!(
+                "ffmpeg falhou com status {}: {}",
+                output.status,
+                String::from_utf8_lossy(&output.stderr).trim()
+            ));
+        }
+        output.stdout
+    };
+
+    let reader = hound::WavReader::new(Cursor::new(wav))
+        .map_err(|error| format!("Falha ao ler o audio de {}: {error}", path.display()))?;
+    let spec = reader.spec();
+    let samples = match spec.sample_format {
+        hound::SampleFormat::Float => reader.into_samples::<f32>().collect::<Result<_, _>>(),
+        hound::SampleFormat::Int => {
+            let scale = (1_i64 << (spec.bits_per_sample.saturating_sub(1))) as f32;
+            reader
+                .into_samples::<i32>()
+                .map(|sample| sample.map(|sample| sample as f32 / scale))
+                .collect()
+        }
+    }
+    .map_err(|error| format!("Falha ao ler o audio de {}: {error}", path.display()))?;
+
+    Ok(CapturedAudio {
+        samples,
+        sample_rate: spec.sample_rate,
+        channels: spec.channels,
+    })
+}
+
+/// Writes the segments as `dictation-<unix_ms>.srt` in `dir`.
+pub fn save_srt(result: &TranscriptionResult, dir: &Path) -> Result<PathBuf, String> {
+    fs::create_dir_all(dir).map_err(|error| {
+        format!(
+            "Falha ao criar pasta de legendas {}: {error}",
+            dir.display()
+        )
+    })?;
+
+    let path = dir.join(format!(
+        "dictation-{}.srt",
+        audio_storage::unix_timestamp_ms()
+    ));
+    fs::write(&path, result.to_srt())
+        .map_err(|error| format!("Falha ao salvar legenda em {}: {error}", path.display()))?;
+
+    Ok(path)
+}
+
+pub fn save_transcripts(
+    session: &CaptureSession,
+    output: &DualTranscriptOutput,
+) -> Result<PathBuf, String> {
+    let path = session.artifacts.session_dir.join("transcripts.json");
+    let contents = serde_json::to_string_pretty(output)
+        .map_err(|error| format!("Falha ao serializar transcricoes: {error}"))?;
+
+    fs::write(&path, contents).map_err(|error| {
+        format!(
+            "Falha ao salvar transcricoes em {}: {error}",
+            path.display()
+        )
+    })?;
+
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        AUDIO_PLACEHOLDER, ChatMessage, ChatRequest, ChatResponse, ContentPart, InputAudio,
+        ProviderPreferences, UsageRequest, request_body,
+    };
+    use base64::Engine;
+    use std::io::Read;
+    use std::sync::Arc;
+
+    #[test]
+    fn streams_base64_audio_into_the_request_json() {
+        // Longer than one base64 block, and not a multiple of 3.
+        let audio: Vec<u8> = (0..100_001).map(|index| (index % 251) as u8).collect();
+        let request = |data: String| ChatRequest {
+            model: String::from("google/gemini-2.5-flash"),
+            messages: vec![ChatMessage {
+                role: String::from("user"),
+                content: vec![
+                    ContentPart::Text {
+                        text: format!("prompt mentioning {AUDIO_PLACEHOLDER}"),
+                    },
+                    ContentPart::InputAudio {
+                        input_audio: InputAudio {
+                            data,
+                            format: String::from("wav"),
+                        },
+                    },
+                ],
+            }],
+            usage: UsageRequest { include: true },
+            temperature: Some(0.2),
+            top_p: None,
+            max_tokens: None,
+            provider: None,
+        };
+
+        let (total, mut reader) = request_body(
+            &request(String::from(AUDIO_PLACEHOLDER)),
+            Arc::from(audio.as_slice()),
+        )
+        .expect("body");
+        let mut streamed = String::new();
+        reader.read_to_string(&mut streamed).expect("read");
+        let expected = serde_json::to_string(&request(
+            base64::engine::general_purpose::STANDARD.encode(&audio),
+        ))
+        .expect("json");
+
+        assert_eq!(streamed, expected);
+        assert_eq!(total, expected.len() as u64);
+    }
+
+    #[test]
+    fn serializes_input_audio_request() {
+        let request = ChatRequest {
+            model: String::from("google/gemini-2.5-flash-lite:nitro"),
+            messages: vec![ChatMessage {
+                role: String::from("user"),
+                content: vec![
+                    ContentPart::Text {
+                        text: String::from("Transcribe this audio"),
+                    },
+                    ContentPart::InputAudio {
+                        input_audio: InputAudio {
+                            data: String::from("base64"),
+                            format: String::from("wav"),
+                        },
+                    },
+                ],
+            }],
+            usage: UsageRequest { include: true },
+            temperature: None,
+            top_p: None,
+            max_tokens: Some(256),
+            provider: Some(ProviderPreferences {
+                data_collection: "deny",
+            }),
+        };
+
+        let json = serde_json::to_string(&request).expect("json");
+
+        assert!(json.contains("input_audio"));
+        assert!(json.contains("google/gemini-2.5-flash-lite:nitro"));
+        assert!(json.contains(r#""usage":{"include":true}"#));
+        assert!(json.contains(r#""max_tokens":256"#));
+        assert!(!json.contains("temperature"));
+        assert!(json.contains(r#""provider":{"data_collection":"deny"}"#));
+    }
+
+    #[test]
+    fn reads_usage_from_response() {
+        let response: ChatResponse = serde_json::from_str(
+            r#"{"choices":[{"message":{"content":"ola"}}],"usage":{"prompt_tokens":120,"completion_tokens":4,"total_tokens":124,"cost":0.00031}}"#,
+        )
+        .expect("response");
+        let usage = response.usage.expect("usage");
+
+        assert_eq!(usage.prompt_tokens, 120);
+        assert_eq!(usage.completion_tokens, 4);
+        assert!((usage.cost - 0.00031).abs() < f64::EPSILON);
+    }
+}
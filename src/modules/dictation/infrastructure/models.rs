@@ -0,0 +1,151 @@
+use crate::modules::audio::infrastructure::storage as audio_storage;
+use crate::modules::dictation::domain::DictationProvider;
+use crate::modules::dictation::infrastructure::providers;
+use reqwest::blocking::Client;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fs;
+use std::path::PathBuf;
+
+const MODELS_PATH: &str = "/models";
+const CACHE_FILE: &str = "openrouter-audio-models.json";
+/// The catalogue changes a few times a week at most.
+const CACHE_TTL_MS: u128 = 24 * 60 * 60 * 1_000;
+
+#[derive(Debug, Serialize, Deserialize)]
+struct ModelCache {
+    fetched_at_unix_ms: u128,
+    /// Gateway the list came from; a cache of another one is ignored.
+    #[serde(default)]
+    base_url: String,
+    models: Vec<String>,
+}
+
+/// Audio-capable OpenRouter model ids, from the on-disk cache while it is
+/// fresh. A failed fetch falls back to a stale cache when there is one.
+pub fn list_audio_models(
+    api_key: &str,
+    base_url: &str,
+    refresh: bool,
+) -> Result<Vec<String>, String> {
+    let cached = read_cache().filter(|cache| cache.base_url == base_url);
+
+    if let Some(cache) = cached.as_ref().filter(|_| !refresh)
+        && audio_storage::unix_timestamp_ms().saturating_sub(cache.fetched_at_unix_ms)
+            < CACHE_TTL_MS
+    {
+        return Ok(cache.models.clone());
+    }
+
+    match fetch_audio_models(api_key, base_url) {
+        Ok(models) => {
+            if let Err(error) = write_cache(base_url, &models) {
+                log_error!("[openvoice][dictation] failed to cache models: {error}");
+            }
+            Ok(models)
+        }
+        Err(error) => match cached {
+            Some(cache) => {
+                log_warn!("[openvoice][dictation] using cached models: {error}");
+                Ok(cache.models)
+            }
+            None => Err(error),
+        },
+    }
+}
+
+fn fetch_audio_models(api_key: &str, base_url: &str) -> Result<Vec<String>, String> {
+    let mut request = Client::new().get(format!("{base_url}{MODELS_PATH}"));
+    if !api_key.trim().is_empty() {
+        request = request.header("Authorization", format!("Bearer {}", api_key.trim()));
+    }
+
+    let parsed = providers::send_json(request, DictationProvider::OpenRouter)?;
+    let models = audio_models(&parsed);
+
+    if models.is_empty() {
+        return Err(String::from(
+            "O OpenRouter nao listou nenhum modelo com entrada de audio.",
+        ));
+    }
+
+    Ok(models)
+}
+
+/// Ids of the models whose `architecture.input_modalities` include audio.
+fn audio_models(parsed: &Value) -> Vec<String> {
+    let mut models: Vec<String> = parsed
+        .get("data")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter(|model| {
+            model
+                .pointer("/architecture/input_modalities")
+                .and_then(Value::as_array)
+                .is_some_and(|modalities| {
+                    modalities
+                        .iter()
+                        .any(|modality| modality.as_str() == Some("audio"))
+                })
+        })
+        .filter_map(|model| model.get("id").and_then(Value::as_str).map(str::to_owned))
+        .collect();
+
+    models.sort();
+    models.dedup();
+    models
+}
+
+fn cache_path() -> Result<PathBuf, String> {
+    Ok(audio_storage::data_dir()?.join(CACHE_FILE))
+}
+
+fn read_cache() -> Option<ModelCache> {
+    let contents = fs::read_to_string(cache_path().ok()?).ok()?;
+    serde_json::from_str(&contents).ok()
+}
+
+fn write_cache(base_url: &str, models: &[String]) -> Result<(), String> {
+    let path = cache_path()?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|error| format!("Falha ao criar {}: {error}", parent.display()))?;
+    }
+
+    let contents = serde_json::to_string_pretty(&ModelCache {
+        fetched_at_unix_ms: audio_storage::unix_timestamp_ms(),
+        base_url: base_url.to_owned(),
+        models: models.to_vec(),
+    })
+    .map_err(|error| format!("Falha ao serializar modelos: {error}"))?;
+
+    fs::write(&path, contents)
+        .map_err(|error| format!("Falha ao salvar {}: {error}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::audio_models;
+    use serde_json::json;
+
+    #[test]
+    fn keeps_only_models_with_audio_input() {
+        let models = audio_models(&json!({
+            "data": [
+                { "id": "openai/gpt-4o-audio-preview", "architecture": { "input_modalities": ["text", "audio"] } },
+                { "id": "anthropic/text-only", "architecture": { "input_modalities": ["text", "image"] } },
+                { "id": "google/gemini-2.5-flash", "architecture": { "input_modalities": ["text", "image", "audio"] } },
+                { "id": "broken/no-architecture" }
+            ]
+        }));
+
+        assert_eq!(
+            models,
+            vec![
+                String::from("google/gemini-2.5-flash"),
+                String::from("openai/gpt-4o-audio-preview"),
+            ]
+        );
+    }
+}
@@ -0,0 +1,107 @@
+use crate::modules::dictation::domain::{
+    CancelToken, DictationProvider, OpenAiConfig, PreparedAudio, ProgressTracker,
+    TranscriptSegment, TranscriptionResult, TranscriptionStage,
+};
+use crate::modules::dictation::infrastructure::providers::{self, MultipartForm};
+use crate::support::error::OpenVoiceError;
+use reqwest::blocking::Client;
+use serde_json::Value;
+use std::sync::Arc;
+
+const OPENAI_TRANSCRIPTIONS_URL: &str = "https://api.openai.com/v1/audio/transcriptions";
+
+/// Uploads a finished take to `audio/transcriptions` as multipart form
+/// data, so the audio travels as raw bytes instead of base64 in JSON.
+pub fn transcribe(
+    config: &OpenAiConfig,
+    audio: &PreparedAudio,
+    progress: &ProgressTracker,
+    cancel: &CancelToken,
+) -> Result<TranscriptionResult, OpenVoiceError> {
+    let mut form = MultipartForm::new();
+    form.text("model", &config.model);
+    if config.timestamps {
+        form.text("response_format", "verbose_json");
+        form.text("timestamp_granularities[]", "segment");
+    } else {
+        form.text("response_format", "json");
+    }
+    if !config.language.is_empty() {
+        form.text("language", &config.language);
+    }
+    if !config.vocabulary.is_empty() {
+        form.text("prompt", &config.vocabulary.join(", "));
+    }
+    let (content_type, total, body) = form.finish_with_file(
+        "file",
+        &format!("audio.{}", audio.format.code()),
+        audio.format.mime_type(),
+        Arc::clone(&audio.audio),
+    );
+
+    let parsed = providers::send_json(
+        Client::new()
+            .post(OPENAI_TRANSCRIPTIONS_URL)
+            .header("Authorization", format!("Bearer {}", config.api_key))
+            .header("Content-Type", content_type)
+            .body(providers::upload_reader(body, total, progress, cancel)),
+        DictationProvider::OpenAi,
+    )
+    .map_err(|error| cancel.or_cancelled(error))?;
+    progress.set(TranscriptionStage::Parsing);
+
+    parse_transcription(&parsed)
+}
+
+/// Reads `text` and, for `verbose_json`, its `segments`.
+fn parse_transcription(parsed: &Value) -> Result<TranscriptionResult, OpenVoiceError> {
+    let text = parsed
+        .get("text")
+        .and_then(Value::as_str)
+        .map(|text| text.trim().to_owned())
+        .filter(|text| !text.is_empty())
+        .ok_or_else(|| {
+            OpenVoiceError::InvalidResponse(String::from("A OpenAI nao retornou transcricao."))
+        })?;
+    let segments = parsed
+        .get("segments")
+        .and_then(Value::as_array)
+        .map(|segments| {
+            segments
+                .iter()
+                .filter_map(|segment| {
+                    Some(TranscriptSegment {
+                        start_secs: segment.get("start")?.as_f64()? as f32,
+                        end_secs: segment.get("end")?.as_f64()? as f32,
+                        text: segment.get("text")?.as_str()?.trim().to_owned(),
+                    })
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    Ok(TranscriptionResult { text, segments })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_transcription;
+    use serde_json::json;
+
+    #[test]
+    fn reads_text_and_verbose_segments() {
+        let result = parse_transcription(&json!({
+            "text": " ola mundo ",
+            "segments": [
+                { "start": 0.0, "end": 1.2, "text": " ola" },
+                { "start": 1.2, "end": 2.0, "text": " mundo" }
+            ]
+        }))
+        .expect("result");
+
+        assert_eq!(result.text, "ola mundo");
+        assert_eq!(result.segments.len(), 2);
+        assert_eq!(result.segments[1].text, "mundo");
+        assert!(parse_transcription(&json!({ "text": "" })).is_err());
+    }
+}
@@ -0,0 +1,425 @@
+use crate::modules::dictation::domain::{
+    CancelToken, DictationConfig, DictationProvider, PreparedAudio, ProgressTracker,
+    ProviderTranscript, TranscriptionStage, api_key_hint,
+};
+use crate::modules::dictation::infrastructure::{self, assemblyai, deepgram, openai};
+use crate::support::error::OpenVoiceError;
+use deepgram::StreamingSession;
+use reqwest::blocking::{Body, RequestBuilder};
+use serde_json::Value;
+use std::io::{Cursor, Read};
+use std::sync::Arc;
+
+/// A backend that turns a prepared take into text. Providers that can
+/// transcribe while the user is still talking also open a streaming session.
+pub trait TranscriptionProvider: Sync {
+    fn id(&self) -> DictationProvider;
+
+    fn transcribe(
+        &self,
+        config: &DictationConfig,
+        audio: &PreparedAudio,
+    ) -> Result<ProviderTranscript, OpenVoiceError>;
+
+    fn supports_streaming(&self) -> bool {
+        false
+    }
+
+    fn start_streaming(
+        &self,
+        _config: &DictationConfig,
+        _sample_rate: u32,
+        _channels: u16,
+    ) -> Option<StreamingSession> {
+        None
+    }
+}
+
+struct OpenRouter;
+struct Deepgram;
+struct AssemblyAi;
+struct OpenAi;
+
+impl TranscriptionProvider for OpenRouter {
+    fn id(&self) -> DictationProvider {
+        DictationProvider::OpenRouter
+    }
+
+    fn transcribe(
+        &self,
+        config: &DictationConfig,
+        audio: &PreparedAudio,
+    ) -> Result<ProviderTranscript, OpenVoiceError> {
+        // Walk the chain until a model returns text; empty answers already
+        // come back as errors. A rejected or spent key fails every model
+        // alike, so it moves on to the next key instead.
+        let mut last_error = None;
+
+        'keys: for api_key in std::iter::once(&config.api_key).chain(&config.fallback_api_keys) {
+            for model in std::iter::once(&config.model).chain(&config.fallback_models) {
+                config.cancel.check()?;
+
+                match infrastructure::transcribe(config, model, api_key, audio) {
+                    Ok(transcript) => return Ok(transcript),
+                    Err(error) if error.is_key_refused() => {
+                        log_warn!(
+                            "[openvoice][dictation] key {} refused: {error}",
+                            api_key_hint(api_key)
+                        );
+                        last_error = Some(error);
+                        continue 'keys;
+                    }
+                    Err(error) if !error.is_retryable() => return Err(error),
+                    Err(error) => {
+                        log_error!("[openvoice][dictation] {model} failed: {error}");
+                        last_error = Some(error);
+                    }
+                }
+            }
+            break;
+        }
+
+        Err(last_error.unwrap_or_else(|| {
+            OpenVoiceError::Other(String::from("Nenhum modelo do OpenRouter configurado."))
+        }))
+    }
+}
+
+impl TranscriptionProvider for Deepgram {
+    fn id(&self) -> DictationProvider {
+        DictationProvider::Deepgram
+    }
+
+    fn transcribe(
+        &self,
+        config: &DictationConfig,
+        audio: &PreparedAudio,
+    ) -> Result<ProviderTranscript, OpenVoiceError> {
+        deepgram::transcribe(&config.deepgram, audio, &config.progress, &config.cancel)
+            .map(ProviderTranscript::from)
+    }
+
+    fn supports_streaming(&self) -> bool {
+        true
+    }
+
+    fn start_streaming(
+        &self,
+        config: &DictationConfig,
+        sample_rate: u32,
+        channels: u16,
+    ) -> Option<StreamingSession> {
+        Some(deepgram::start_streaming(
+            config.deepgram.clone(),
+            sample_rate,
+            channels,
+        ))
+    }
+}
+
+impl TranscriptionProvider for AssemblyAi {
+    fn id(&self) -> DictationProvider {
+        DictationProvider::AssemblyAi
+    }
+
+    fn transcribe(
+        &self,
+        config: &DictationConfig,
+        audio: &PreparedAudio,
+    ) -> Result<ProviderTranscript, OpenVoiceError> {
+        assemblyai::transcribe(&config.assemblyai, audio, &config.cancel, &config.progress)
+            .map(ProviderTranscript::from)
+    }
+}
+
+impl TranscriptionProvider for OpenAi {
+    fn id(&self) -> DictationProvider {
+        DictationProvider::OpenAi
+    }
+
+    fn transcribe(
+        &self,
+        config: &DictationConfig,
+        audio: &PreparedAudio,
+    ) -> Result<ProviderTranscript, OpenVoiceError> {
+        openai::transcribe(&config.openai, audio, &config.progress, &config.cancel)
+            .map(ProviderTranscript::from)
+    }
+}
+
+/// Every registered backend, in the order the settings list them.
+static REGISTRY: [&dyn TranscriptionProvider; 4] = [&OpenRouter, &Deepgram, &AssemblyAi, &OpenAi];
+
+pub fn all() -> impl Iterator<Item = &'static dyn TranscriptionProvider> {
+    REGISTRY.iter().copied()
+}
+
+/// Looks a provider up by its settings id (`DictationProvider::code`).
+pub fn find(id: &str) -> Option<&'static dyn TranscriptionProvider> {
+    all().find(|provider| provider.id().code() == id.trim())
+}
+
+pub fn get(id: DictationProvider) -> &'static dyn TranscriptionProvider {
+    all()
+        .find(|provider| provider.id() == id)
+        .unwrap_or(REGISTRY[0])
+}
+
+/// Sends a request and reads a JSON body, turning non-2xx answers into an
+/// error carrying the provider's own message when it has one.
+pub fn send_json(
+    request: RequestBuilder,
+    provider: DictationProvider,
+) -> Result<Value, OpenVoiceError> {
+    let response = request
+        .send()
+        .map_err(|error| OpenVoiceError::Network(format!("Falha ao chamar {provider}: {error}")))?;
+    let status = response.status();
+    let body = response.text().map_err(|error| {
+        OpenVoiceError::Network(format!("Falha ao ler resposta de {provider}: {error}"))
+    })?;
+    let parsed = serde_json::from_str::<Value>(&body);
+
+    if !status.is_success() {
+        let message = parsed
+            .as_ref()
+            .ok()
+            .and_then(error_message)
+            .unwrap_or(&body);
+        return Err(OpenVoiceError::from_status(
+            provider,
+            status.as_u16(),
+            message,
+        ));
+    }
+
+    parsed.map_err(|error| {
+        OpenVoiceError::InvalidResponse(format!(
+            "Falha ao interpretar resposta de {provider}: {error}"
+        ))
+    })
+}
+
+/// Request body that reports upload progress as reqwest reads it, moving
+/// on to waiting for the model once the last byte is out. Setting `cancel`
+/// aborts the upload at the next chunk.
+pub fn upload_body(
+    bytes: impl AsRef<[u8]> + Send + 'static,
+    progress: &ProgressTracker,
+    cancel: &CancelToken,
+) -> Body {
+    let total = bytes.as_ref().len() as u64;
+    upload_reader(Cursor::new(bytes), total, progress, cancel)
+}
+
+/// Like `upload_body`, for a body produced while it is sent; `total` must
+/// be the exact number of bytes `reader` yields.
+pub fn upload_reader(
+    reader: impl Read + Send + 'static,
+    total: u64,
+    progress: &ProgressTracker,
+    cancel: &CancelToken,
+) -> Body {
+    progress.set(TranscriptionStage::Uploading { sent: 0, total });
+
+    Body::sized(
+        ProgressReader {
+            inner: reader,
+            sent: 0,
+            total,
+            progress: progress.clone(),
+            cancel: cancel.clone(),
+        },
+        total,
+    )
+}
+
+struct ProgressReader<R> {
+    inner: R,
+    sent: u64,
+    total: u64,
+    progress: ProgressTracker,
+    cancel: CancelToken,
+}
+
+impl<R: Read> Read for ProgressReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        if self.cancel.is_cancelled() {
+            return Err(std::io::Error::other("upload cancelled"));
+        }
+
+        let read = self.inner.read(buf)?;
+        self.sent += read as u64;
+        self.progress.set(if self.sent >= self.total {
+            TranscriptionStage::WaitingForModel
+        } else {
+            TranscriptionStage::Uploading {
+                sent: self.sent,
+                total: self.total,
+            }
+        });
+
+        Ok(read)
+    }
+}
+
+/// `multipart/form-data` body: text fields, then the take as raw bytes
+/// streamed from the shared buffer rather than copied into the body.
+pub struct MultipartForm {
+    boundary: String,
+    head: Vec<u8>,
+}
+
+impl MultipartForm {
+    pub fn new() -> Self {
+        Self {
+            boundary: format!("openvoice-{:016x}", rand::random::<u64>()),
+            head: Vec::new(),
+        }
+    }
+
+    pub fn text(&mut self, name: &str, value: &str) {
+        self.head.extend_from_slice(
+            format!(
+                "--{}\r\nContent-Disposition: form-data; name=\"{name}\"\r\n\r\n{value}\r\n",
+                self.boundary
+            )
+            .as_bytes(),
+        );
+    }
+
+    /// Closes the form with the file part. Returns the `Content-Type`
+    /// header value, the body length and the body.
+    pub fn finish_with_file(
+        mut self,
+        name: &str,
+        file_name: &str,
+        content_type: &str,
+        bytes: Arc<[u8]>,
+    ) -> (String, u64, impl Read + Send + 'static) {
+        self.head.extend_from_slice(
+            format!(
+                "--{}\r\nContent-Disposition: form-data; name=\"{name}\"; filename=\"{file_name}\"\r\nContent-Type: {content_type}\r\n\r\n",
+                self.boundary
+            )
+            .as_bytes(),
+        );
+        let tail = format!("\r\n--{}--\r\n", self.boundary).into_bytes();
+        let total = (self.head.len() + bytes.len() + tail.len()) as u64;
+
+        (
+            format!("multipart/form-data; boundary={}", self.boundary),
+            total,
+            Cursor::new(self.head)
+                .chain(Cursor::new(bytes))
+                .chain(Cursor::new(tail)),
+        )
+    }
+}
+
+/// Error text as each provider shapes it: OpenRouter nests it under
+/// `error.message`, AssemblyAI uses a plain `error`, Deepgram `err_msg`.
+pub fn error_message(parsed: &Value) -> Option<&str> {
+    parsed
+        .pointer("/error/message")
+        .or_else(|| {
+            ["error", "err_msg", "description", "message"]
+                .iter()
+                .find_map(|key| parsed.get(*key).filter(|value| value.is_string()))
+        })
+        .and_then(Value::as_str)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{MultipartForm, ProgressReader, all, error_message, find};
+    use crate::modules::dictation::domain::{
+        CancelToken, DictationProvider, ProgressTracker, TranscriptionStage,
+    };
+    use serde_json::json;
+    use std::io::{Cursor, Read};
+    use std::sync::Arc;
+
+    #[test]
+    fn reports_upload_progress_while_the_body_is_read() {
+        let progress = ProgressTracker::default();
+        let cancel = CancelToken::default();
+        let mut reader = ProgressReader {
+            inner: Cursor::new(vec![0; 10]),
+            sent: 0,
+            total: 10,
+            progress: progress.clone(),
+            cancel: cancel.clone(),
+        };
+        let mut buf = [0; 4];
+
+        assert_eq!(reader.read(&mut buf).expect("read"), 4);
+        assert_eq!(
+            progress.stage(),
+            TranscriptionStage::Uploading { sent: 4, total: 10 }
+        );
+
+        cancel.cancel();
+        assert!(reader.read(&mut buf).is_err());
+        assert_eq!(reader.sent, 4);
+
+        let mut reader = ProgressReader {
+            inner: Cursor::new(vec![0; 10]),
+            sent: 0,
+            total: 10,
+            progress: progress.clone(),
+            cancel: CancelToken::default(),
+        };
+        reader.read_to_end(&mut Vec::new()).expect("read");
+        assert_eq!(progress.stage(), TranscriptionStage::WaitingForModel);
+    }
+
+    #[test]
+    fn builds_multipart_body() {
+        let mut form = MultipartForm::new();
+        form.text("model", "whisper-1");
+        let boundary = form.boundary.clone();
+        let (content_type, total, mut reader) =
+            form.finish_with_file("file", "audio.wav", "audio/wav", Arc::from(&b"RIFF"[..]));
+        let mut body = String::new();
+        reader.read_to_string(&mut body).expect("read");
+
+        assert_eq!(
+            content_type,
+            format!("multipart/form-data; boundary={boundary}")
+        );
+        assert_eq!(
+            body,
+            format!(
+                "--{boundary}\r\nContent-Disposition: form-data; name=\"model\"\r\n\r\nwhisper-1\r\n\
+                 --{boundary}\r\nContent-Disposition: form-data; name=\"file\"; filename=\"audio.wav\"\r\nContent-Type: audio/wav\r\n\r\nRIFF\r\n\
+                 --{boundary}--\r\n"
+            )
+        );
+        assert_eq!(total, body.len() as u64);
+    }
+
+    #[test]
+    fn registry_is_keyed_by_provider_id() {
+        assert_eq!(all().count(), 4);
+        assert_eq!(
+            find("assemblyai").map(|provider| provider.id()),
+            Some(DictationProvider::AssemblyAi)
+        );
+        assert!(find("whisper").is_none());
+    }
+
+    #[test]
+    fn reads_error_message_from_each_shape() {
+        assert_eq!(
+            error_message(&json!({ "error": { "message": "quota" } })),
+            Some("quota")
+        );
+        assert_eq!(
+            error_message(&json!({ "error": "bad key" })),
+            Some("bad key")
+        );
+        assert_eq!(error_message(&json!({ "err_msg": "nope" })), Some("nope"));
+        assert_eq!(error_message(&json!({ "ok": true })), None);
+    }
+}
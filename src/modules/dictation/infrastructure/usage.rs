@@ -0,0 +1,119 @@
+use crate::modules::dictation::domain::{TranscriptionUsage, UsageStats, UsageTotals};
+use crate::modules::live_transcription::infrastructure::db;
+use rusqlite::{Connection, params};
+
+pub fn ensure_schema(conn: &Connection) -> Result<(), String> {
+    conn.execute_batch(
+        "CREATE TABLE IF NOT EXISTS dt_usage_daily (
+            day               TEXT PRIMARY KEY,
+            requests          INTEGER NOT NULL DEFAULT 0,
+            prompt_tokens     INTEGER NOT NULL DEFAULT 0,
+            completion_tokens INTEGER NOT NULL DEFAULT 0,
+            cost_usd          REAL NOT NULL DEFAULT 0
+        );",
+    )
+    .map_err(|error| format!("Nao consegui criar schema de uso do ditado: {error}"))
+}
+
+/// Adds one request to today's counters and returns the updated totals.
+pub fn record_usage(usage: &TranscriptionUsage) -> Result<UsageStats, String> {
+    let conn = db::open_db()?;
+    ensure_schema(&conn)?;
+    let today = today();
+
+    record_usage_in_conn(&conn, &today, usage)?;
+    usage_stats_in_conn(&conn, &today)
+}
+
+pub fn usage_stats() -> Result<UsageStats, String> {
+    let conn = db::open_db()?;
+    ensure_schema(&conn)?;
+    usage_stats_in_conn(&conn, &today())
+}
+
+fn record_usage_in_conn(
+    conn: &Connection,
+    day: &str,
+    usage: &TranscriptionUsage,
+) -> Result<(), String> {
+    conn.execute(
+        "INSERT INTO dt_usage_daily (day, requests, prompt_tokens, completion_tokens, cost_usd)
+         VALUES (?1, 1, ?2, ?3, ?4)
+         ON CONFLICT(day) DO UPDATE SET
+            requests = requests + 1,
+            prompt_tokens = prompt_tokens + excluded.prompt_tokens,
+            completion_tokens = completion_tokens + excluded.completion_tokens,
+            cost_usd = cost_usd + excluded.cost_usd",
+        params![
+            day,
+            usage.prompt_tokens as i64,
+            usage.completion_tokens as i64,
+            usage.cost_usd
+        ],
+    )
+    .map_err(|error| format!("Nao consegui registrar o uso do ditado: {error}"))?;
+
+    Ok(())
+}
+
+/// `day` is `YYYY-MM-DD`; the month is the same prefix up to the month.
+fn usage_stats_in_conn(conn: &Connection, day: &str) -> Result<UsageStats, String> {
+    let month = day.get(..7).unwrap_or(day);
+
+    Ok(UsageStats {
+        today: totals_in_conn(conn, day)?,
+        month: totals_in_conn(conn, &format!("{month}%"))?,
+    })
+}
+
+fn totals_in_conn(conn: &Connection, day_pattern: &str) -> Result<UsageTotals, String> {
+    conn.query_row(
+        "SELECT COALESCE(SUM(requests), 0), COALESCE(SUM(prompt_tokens), 0),
+                COALESCE(SUM(completion_tokens), 0), COALESCE(SUM(cost_usd), 0)
+         FROM dt_usage_daily WHERE day LIKE ?1",
+        params![day_pattern],
+        |row| {
+            Ok(UsageTotals {
+                requests: row.get::<_, i64>(0)? as u64,
+                prompt_tokens: row.get::<_, i64>(1)? as u64,
+                completion_tokens: row.get::<_, i64>(2)? as u64,
+                cost_usd: row.get(3)?,
+            })
+        },
+    )
+    .map_err(|error| format!("Nao consegui ler o uso do ditado: {error}"))
+}
+
+fn today() -> String {
+    db::now_iso().chars().take(10).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ensure_schema, record_usage_in_conn, usage_stats_in_conn};
+    use crate::modules::dictation::domain::TranscriptionUsage;
+    use rusqlite::Connection;
+
+    #[test]
+    fn accumulates_daily_and_monthly_usage() {
+        let conn = Connection::open_in_memory().expect("in-memory db");
+        ensure_schema(&conn).expect("schema");
+        let usage = TranscriptionUsage {
+            prompt_tokens: 100,
+            completion_tokens: 10,
+            cost_usd: 0.25,
+        };
+
+        record_usage_in_conn(&conn, "2026-03-01", &usage).expect("record");
+        record_usage_in_conn(&conn, "2026-03-02", &usage).expect("record");
+        record_usage_in_conn(&conn, "2026-03-02", &usage).expect("record");
+        record_usage_in_conn(&conn, "2026-02-28", &usage).expect("record");
+
+        let stats = usage_stats_in_conn(&conn, "2026-03-02").expect("stats");
+
+        assert_eq!(stats.today.requests, 2);
+        assert_eq!(stats.today.total_tokens(), 220);
+        assert_eq!(stats.month.requests, 3);
+        assert!((stats.month.cost_usd - 0.75).abs() < 1e-9);
+    }
+}
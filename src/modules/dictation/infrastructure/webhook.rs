@@ -0,0 +1,57 @@
+use reqwest::blocking::Client;
+use serde::Serialize;
+use std::thread;
+use std::time::Duration;
+
+const WEBHOOK_ATTEMPTS: u32 = 3;
+const WEBHOOK_TIMEOUT_SECS: u64 = 10;
+/// Doubled after each failed attempt.
+const WEBHOOK_RETRY_DELAY_MS: u64 = 1_000;
+
+/// Body POSTed to the user's webhook after each transcription.
+#[derive(Debug, Serialize)]
+pub struct WebhookPayload<'a> {
+    pub text: &'a str,
+    /// Seconds of recorded audio.
+    pub duration: f32,
+    pub model: Option<&'a str>,
+    /// ISO-8601 UTC.
+    pub timestamp: String,
+    pub words: usize,
+    pub characters: usize,
+    /// Milliseconds between stopping the take and having its text.
+    pub latency_ms: Option<u64>,
+}
+
+/// Delivers `payload`, retrying timeouts, 429s and 5xx answers; any other
+/// 4xx means the request itself is wrong and is not retried.
+pub fn post(url: &str, payload: &WebhookPayload) -> Result<(), String> {
+    let client = Client::builder()
+        .timeout(Duration::from_secs(WEBHOOK_TIMEOUT_SECS))
+        .build()
+        .map_err(|error| format!("Falha ao preparar o webhook: {error}"))?;
+    let mut delay = Duration::from_millis(WEBHOOK_RETRY_DELAY_MS);
+    let mut last_error = String::new();
+
+    for attempt in 1..=WEBHOOK_ATTEMPTS {
+        match client.post(url).json(payload).send() {
+            Ok(response) if response.status().is_success() => return Ok(()),
+            Ok(response) => {
+                let status = response.status();
+                last_error = format!("O webhook respondeu {status}.");
+                if status.is_client_error() && status.as_u16() != 429 {
+                    return Err(last_error);
+                }
+            }
+            Err(error) => last_error = format!("Falha ao chamar o webhook: {error}"),
+        }
+
+        if attempt < WEBHOOK_ATTEMPTS {
+            log_warn!("[openvoice][dictation] webhook attempt {attempt} failed: {last_error}");
+            thread::sleep(delay);
+            delay *= 2;
+        }
+    }
+
+    Err(last_error)
+}
@@ -81,10 +81,10 @@ pub fn poll_next_event(receiver: SharedReceiver) -> Option<RuntimeEvent> {
 }
 
 pub fn generate_session_title(session_id: i64) -> Result<(i64, String), String> {
-    eprintln!("[openvoice][title] generating title for session_id={session_id}");
+    log_debug!("[openvoice][title] generating title for session_id={session_id}");
 
     let session = auth_application::load_or_refresh_session().map_err(|error| {
-        eprintln!("[openvoice][title] auth failed: {error}");
+        log_error!("[openvoice][title] auth failed: {error}");
         error
     })?;
 
@@ -95,11 +95,11 @@ pub fn generate_session_title(session_id: i64) -> Result<(i64, String), String>
 
     let segments = db::get_session_segments(session_id)?;
     if segments.is_empty() {
-        eprintln!("[openvoice][title] session {session_id} has no segments, skipping");
+        log_debug!("[openvoice][title] session {session_id} has no segments, skipping");
         return Err(String::from("Sessao sem segmentos para gerar titulo."));
     }
 
-    eprintln!(
+    log_debug!(
         "[openvoice][title] session {session_id} has {} segments, building transcript",
         segments.len()
     );
@@ -127,7 +127,7 @@ pub fn generate_session_title(session_id: i64) -> Result<(i64, String), String>
 
     db::update_session_title(session_id, &title)?;
 
-    eprintln!("[openvoice][title] session {session_id} title saved: {title}");
+    log_info!("[openvoice][title] session {session_id} title saved: {title}");
     Ok((session_id, title))
 }
 
@@ -21,7 +21,7 @@ pub struct SessionSummary {
 // DB path
 // ---------------------------------------------------------------------------
 
-fn db_path() -> PathBuf {
+pub fn db_path() -> PathBuf {
     let home = std::env::var("HOME").unwrap_or_else(|_| ".".to_string());
     PathBuf::from(home)
         .join(".local")
@@ -67,17 +67,27 @@ pub fn ensure_schema(conn: &Connection) -> Result<(), String> {
     .map_err(|e| format!("Nao consegui criar o schema SQLite: {e}"))?;
 
     // Safe migration: add title column if it doesn't exist yet.
-    let has_title: bool = conn
-        .prepare("SELECT 1 FROM pragma_table_info('lt_sessions') WHERE name='title'")
-        .and_then(|mut stmt| stmt.exists([]))
-        .unwrap_or(false);
+    add_column_if_missing(conn, "lt_sessions", "title", "TEXT")
+}
 
-    if !has_title {
-        conn.execute_batch("ALTER TABLE lt_sessions ADD COLUMN title TEXT;")
-            .map_err(|e| format!("Nao consegui adicionar coluna title: {e}"))?;
+/// Adds `column` with type `ddl` to `table` when a database created by an
+/// older version lacks it.
+pub fn add_column_if_missing(
+    conn: &Connection,
+    table: &str,
+    column: &str,
+    ddl: &str,
+) -> Result<(), String> {
+    let exists = conn
+        .prepare("SELECT 1 FROM pragma_table_info(?1) WHERE name = ?2")
+        .and_then(|mut stmt| stmt.exists([table, column]))
+        .unwrap_or(false);
+    if exists {
+        return Ok(());
     }
 
-    Ok(())
+    conn.execute_batch(&format!("ALTER TABLE {table} ADD COLUMN {column} {ddl};"))
+        .map_err(|e| format!("Nao consegui adicionar coluna {column}: {e}"))
 }
 
 // ---------------------------------------------------------------------------
@@ -108,7 +108,7 @@ impl RealtimeTelemetry {
 
     fn log(&self, message: String) {
         if self.enabled {
-            eprintln!("[openvoice][realtime][metrics] {message}");
+            log_debug!("[openvoice][realtime][metrics] {message}");
         }
     }
 }
@@ -379,7 +379,7 @@ fn handle_server_message(
             if !item_id.is_empty() && !delta.is_empty() {
                 telemetry.mark_delta(delta.len());
                 if should_log_realtime_deltas() {
-                    eprintln!("[openvoice][realtime][delta] {delta}");
+                    log_debug!("[openvoice][realtime][delta] {delta}");
                 }
                 let _ = event_tx.send(RuntimeEvent::TranscriptDelta { item_id, delta });
             }
@@ -401,7 +401,7 @@ fn handle_server_message(
                     telemetry.mark_completed(transcript.len());
                 }
                 if should_log_realtime_transcripts() && !transcript.trim().is_empty() {
-                    eprintln!("[openvoice][realtime][transcript] {transcript}");
+                    log_debug!("[openvoice][realtime][transcript] {transcript}");
                 }
 
                 let _ = event_tx.send(RuntimeEvent::TranscriptCompleted {
@@ -1,6 +1,8 @@
 pub mod audio;
 pub mod auth;
+pub mod backup;
 pub mod copilot;
 pub mod dictation;
 pub mod live_transcription;
 pub mod settings;
+pub mod shortcuts;
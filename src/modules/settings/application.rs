@@ -1,41 +1,217 @@
-use crate::modules::settings::domain::AppSettings;
+use crate::modules::audio::infrastructure::microphone;
+use crate::modules::settings::domain::{
+    AppSettings, ConfigProblem, OnboardingState, OnboardingStep, ResetScope, SettingsForm,
+};
 use crate::modules::settings::infrastructure;
+use crate::platform::desktop;
+use crate::support::cli;
+use crate::support::log::{self, LogLevel};
+use std::path::PathBuf;
+use std::time::SystemTime;
 
+const EXPORT_CONFIG_FLAG: &str = "--export-config";
+const IMPORT_CONFIG_FLAG: &str = "--import-config";
+const WITH_SECRETS_FLAG: &str = "--with-secrets";
+const VALIDATE_CONFIG_FLAG: &str = "--validate-config";
+const RESET_CONFIG_FLAG: &str = "--reset-config";
+/// Written to the home folder when no path is given.
+pub const DEFAULT_CONFIG_EXPORT_FILE: &str = "openvoice-settings.json";
+
+/// Settings as stored in settings.json, without the environment overrides;
+/// the only copy that is ever saved or exported.
 pub fn load_settings() -> Result<AppSettings, String> {
     infrastructure::load_settings()
 }
 
-pub fn save_settings(
-    openrouter_api_key: String,
-    openai_realtime_api_key: String,
-    openrouter_model: String,
-    openai_realtime_model: String,
-    openai_realtime_language: String,
-    openai_realtime_profile: String,
-    copilot_model: String,
-    copilot_default_mode: String,
-    copilot_auto_include_transcript: bool,
-    copilot_save_history: bool,
-) -> Result<AppSettings, String> {
-    let settings = AppSettings::new(
-        openrouter_api_key,
-        openai_realtime_api_key,
-        openrouter_model,
-        openai_realtime_model,
-        openai_realtime_language,
-        openai_realtime_profile,
-        copilot_model,
-        copilot_default_mode,
-        copilot_auto_include_transcript,
-        copilot_save_history,
-    )?;
+/// `stored` with the `OPENVOICE_*` environment overrides on top, for the
+/// running app. It is never saved, so a key given only through the
+/// environment stays off disk.
+pub fn runtime_settings(stored: &AppSettings) -> AppSettings {
+    let mut settings = stored.clone();
+    if let Err(error) = settings.apply_env_overrides(infrastructure::env_override) {
+        log_warn!("[openvoice][settings] {error}");
+    }
+    settings
+}
+
+pub fn save_settings(form: SettingsForm) -> Result<AppSettings, String> {
+    let settings = AppSettings::from_form(&form)?;
     infrastructure::save_settings(&settings)?;
     Ok(settings)
 }
 
-#[allow(dead_code)]
 pub fn persist_settings(settings: AppSettings) -> Result<AppSettings, String> {
     let normalized = settings.normalized();
     infrastructure::save_settings(&normalized)?;
     Ok(normalized)
 }
+
+/// Names of the saved profiles, in the order they were created.
+pub fn list_profiles(settings: &AppSettings) -> Vec<String> {
+    settings
+        .dictation_profiles
+        .iter()
+        .map(|profile| profile.name.clone())
+        .collect()
+}
+
+/// Stores the current setup as the profile `name` and persists it.
+pub fn save_profile(mut settings: AppSettings, name: String) -> Result<AppSettings, String> {
+    settings.save_profile(&name)?;
+    persist_settings(settings)
+}
+
+/// Applies the profile `name` and persists the result.
+pub fn activate_profile(settings: AppSettings, name: String) -> Result<AppSettings, String> {
+    let settings = settings.with_profile(&name)?;
+    infrastructure::save_settings(&settings)?;
+    Ok(settings)
+}
+
+/// Forgets the profile `name` and persists it.
+pub fn delete_profile(mut settings: AppSettings, name: String) -> Result<AppSettings, String> {
+    settings.delete_profile(&name);
+    persist_settings(settings)
+}
+
+/// Problems in the stored settings, checked against the microphones
+/// plugged in now.
+pub fn validate_config(settings: &AppSettings) -> Vec<ConfigProblem> {
+    let devices = microphone::list_input_devices().unwrap_or_default();
+    SettingsForm::from(settings).validate(&devices)
+}
+
+/// First-run steps still pending, checked against the microphones plugged
+/// in now.
+pub fn get_onboarding_state(settings: &AppSettings) -> OnboardingState {
+    let devices = microphone::list_input_devices().unwrap_or_default();
+    settings.onboarding_state(&devices)
+}
+
+/// Marks `step` done and persists it; confirming the shortcut finishes
+/// the onboarding.
+pub fn complete_onboarding_step(
+    mut settings: AppSettings,
+    step: OnboardingStep,
+) -> Result<AppSettings, String> {
+    let devices = microphone::list_input_devices().unwrap_or_default();
+    settings.complete_onboarding_step(step, &devices)?;
+    persist_settings(settings)
+}
+
+/// Writes every setting to `path` as JSON; API keys only go along with
+/// `include_secrets`.
+pub fn export_config(
+    settings: &AppSettings,
+    path: &str,
+    include_secrets: bool,
+) -> Result<PathBuf, String> {
+    let path = config_path(path)?;
+    let exported = if include_secrets {
+        settings.clone()
+    } else {
+        settings.clone().without_api_keys()
+    };
+    infrastructure::write_settings_file(&path, &exported)?;
+    Ok(path)
+}
+
+/// Replaces the settings with those exported to `path`. Keys the file
+/// leaves out are kept from `current`.
+pub fn import_config(current: &AppSettings, path: &str) -> Result<AppSettings, String> {
+    let mut imported = infrastructure::read_settings_file(&config_path(path)?)?;
+    imported.keep_api_keys_from(current);
+    infrastructure::save_settings(&imported)?;
+    Ok(imported)
+}
+
+/// Restores the defaults of `scope` and saves them, for recovering from a
+/// broken setup. Returns the new settings.
+pub fn reset_config(current: &AppSettings, scope: ResetScope) -> Result<AppSettings, String> {
+    let reset = current.reset(scope);
+    infrastructure::save_settings(&reset)?;
+    log_info!("[openvoice][settings] reset {} to defaults", scope.code());
+    Ok(reset)
+}
+
+/// Handles `openvoice --export-config [path] [--with-secrets]`,
+/// `openvoice --import-config [path]`, `openvoice --validate-config` and
+/// `openvoice --reset-config <all|shortcuts|audio|providers>`. Returns
+/// `None` for a normal launch.
+pub fn run_cli_command(args: &[String]) -> Option<Result<String, String>> {
+    if let Some(position) = args.iter().position(|arg| arg == RESET_CONFIG_FLAG) {
+        let scope = args.get(position + 1).map_or("all", String::as_str);
+        return Some(
+            ResetScope::from_code(scope)
+                .ok_or_else(|| {
+                    format!(
+                        "Escopo desconhecido: {scope} (use all, shortcuts, audio ou providers)."
+                    )
+                })
+                .and_then(|scope| {
+                    load_settings().and_then(|settings| reset_config(&settings, scope))
+                })
+                .map(|_| String::from("Settings restauradas.")),
+        );
+    }
+
+    if cli::has_flag(args, VALIDATE_CONFIG_FLAG) {
+        return Some(load_settings().and_then(|settings| {
+            let problems = validate_config(&runtime_settings(&settings));
+            if problems.is_empty() {
+                return Ok(String::from("Settings sem problemas."));
+            }
+            Err(problems
+                .iter()
+                .map(|problem| format!("{}: {}", problem.kind.label(), problem.message))
+                .collect::<Vec<_>>()
+                .join("\n"))
+        }));
+    }
+
+    let (flag, path) = cli::path_command(args, &[EXPORT_CONFIG_FLAG, IMPORT_CONFIG_FLAG])?;
+
+    Some(load_settings().and_then(|settings| {
+        if flag == EXPORT_CONFIG_FLAG {
+            let include_secrets = cli::has_flag(args, WITH_SECRETS_FLAG);
+            export_config(&settings, path, include_secrets)
+                .map(|path| format!("Settings exportadas para {}.", path.display()))
+        } else {
+            import_config(&settings, path).map(|_| String::from("Settings importadas."))
+        }
+    }))
+}
+
+fn config_path(path: &str) -> Result<PathBuf, String> {
+    cli::path_or_home(
+        path,
+        DEFAULT_CONFIG_EXPORT_FILE,
+        "Informe o arquivo das settings.",
+    )
+}
+
+/// Applies `level` to every later log line, from any thread.
+pub fn set_log_level(level: LogLevel) {
+    log::set_level(level);
+    log_info!("[openvoice][settings] log level set to {}", level.code());
+}
+
+/// The last `count` log lines, oldest first, for attaching to bug reports.
+pub fn get_recent_logs(count: usize) -> Result<Vec<String>, String> {
+    log::recent_logs(count)
+}
+
+/// Polled to pick up settings.json edited outside the app.
+pub fn settings_modified_at() -> Option<SystemTime> {
+    infrastructure::settings_modified_at()
+}
+
+/// Whether OpenVoice is currently set to start at login.
+pub fn get_autostart() -> bool {
+    desktop::autostart_enabled()
+}
+
+/// Creates or removes the login entry to match `enabled`.
+pub fn set_autostart(enabled: bool) -> Result<(), String> {
+    desktop::set_autostart(enabled)
+}
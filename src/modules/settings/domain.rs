@@ -1,8 +1,23 @@
 use serde::{Deserialize, Serialize};
 
+use crate::modules::audio::domain::CaptureOverrides;
 use crate::modules::copilot::domain::CopilotMode;
+use crate::modules::dictation::domain::{
+    AudioFormat, DictationCaptureMode, DictationOutputMode, DictationProvider,
+    HistoryAudioRetention, IdleStopAction, ResampleQuality, TEMPLATE_PLACEHOLDER, TargetSampleRate,
+    TranscriptTemplate,
+};
+use crate::modules::shortcuts::domain::{Shortcut, ShortcutAction, ShortcutMode};
+use crate::support::log::LogLevel;
 
 pub const DEFAULT_OPENROUTER_MODEL: &str = "google/gemini-2.5-flash-lite:nitro";
+pub const DEFAULT_OPENROUTER_REFERER: &str = "https://github.com/IsraelAraujo70/openvoice";
+pub const DEFAULT_OPENROUTER_APP_TITLE: &str = "OpenVoice";
+/// Any OpenAI-compatible gateway (LiteLLM, a corporate proxy) can stand in.
+pub const DEFAULT_OPENROUTER_BASE_URL: &str = "https://openrouter.ai/api/v1";
+pub const DEFAULT_DICTATION_PROVIDER: &str = "openrouter";
+pub const DEFAULT_DEEPGRAM_MODEL: &str = "nova-3";
+pub const DEFAULT_DICTATION_STREAMING: bool = true;
 pub const DEFAULT_OPENAI_REALTIME_MODEL: &str = "gpt-4o-transcribe";
 pub const DEFAULT_OPENAI_REALTIME_LANGUAGE: &str = "";
 pub const DEFAULT_OPENAI_REALTIME_PROFILE: &str = "balanced";
@@ -10,6 +25,57 @@ pub const DEFAULT_COPILOT_MODEL: &str = "gpt-5.1-codex-mini";
 pub const DEFAULT_COPILOT_MODE: &str = "general";
 pub const DEFAULT_COPILOT_AUTO_INCLUDE_TRANSCRIPT: bool = true;
 pub const DEFAULT_COPILOT_SAVE_HISTORY: bool = true;
+pub const DEFAULT_DICTATION_VAD_ENABLED: bool = false;
+pub const DEFAULT_DICTATION_SILENCE_THRESHOLD_MS: u32 = 1_500;
+pub const MIN_DICTATION_SILENCE_THRESHOLD_MS: u32 = 300;
+pub const MAX_DICTATION_SILENCE_THRESHOLD_MS: u32 = 30_000;
+pub const DEFAULT_DICTATION_SHORTCUT: &str = "Super+Shift+D";
+pub const DEFAULT_DICTATION_STOP_KEY: &str = "Escape";
+pub const DEFAULT_API_KEYS_IN_KEYRING: bool = true;
+/// Environment variables that override the stored settings at startup, for
+/// provisioned machines and tests.
+pub const ENV_API_KEY: &str = "OPENVOICE_API_KEY";
+pub const ENV_MODEL: &str = "OPENVOICE_MODEL";
+pub const ENV_SHORTCUT: &str = "OPENVOICE_SHORTCUT";
+pub const DEFAULT_DICTATION_SHORTCUT_MODE: &str = "toggle";
+pub const DEFAULT_DICTATION_RESAMPLE_QUALITY: &str = "balanced";
+pub const DEFAULT_DICTATION_TARGET_SAMPLE_RATE: &str = "16000";
+pub const DEFAULT_DICTATION_NORMALIZE_AUDIO: bool = true;
+pub const DEFAULT_DICTATION_WRITE_PRIMARY: bool = true;
+pub const DEFAULT_DICTATION_AUDIO_FORMAT: &str = "wav";
+pub const DEFAULT_DICTATION_PRE_ROLL_ENABLED: bool = false;
+pub const DEFAULT_DICTATION_SOUND_FEEDBACK: bool = false;
+pub const DEFAULT_DICTATION_SHOW_WINDOW: bool = true;
+pub const DEFAULT_DICTATION_CAPTURE_MODE: &str = "microphone";
+pub const DEFAULT_DICTATION_IDLE_TIMEOUT_SECS: u32 = 60;
+pub const MIN_DICTATION_IDLE_TIMEOUT_SECS: u32 = 10;
+pub const MAX_DICTATION_IDLE_TIMEOUT_SECS: u32 = 3_600;
+pub const DEFAULT_DICTATION_IDLE_ACTION: &str = "discard";
+pub const DEFAULT_DICTATION_OUTPUT_MODE: &str = "clipboard";
+pub const DEFAULT_HUD_CLICK_ACTION: &str = "settings";
+pub const DEFAULT_HUD_KEEP_OPEN: bool = true;
+pub const DEFAULT_LOG_LEVEL: &str = "info";
+pub const DEFAULT_HUD_HIDE_DELAY_MS: u32 = 1_500;
+pub const MAX_HUD_HIDE_DELAY_MS: u32 = 60_000;
+pub const DEFAULT_DICTATION_TYPING_DELAY_MS: u32 = 12;
+pub const MAX_DICTATION_TYPING_DELAY_MS: u32 = 500;
+pub const DEFAULT_DICTATION_URI_TEMPLATE: &str = "obsidian://new?content={text}";
+pub const DEFAULT_DICTATION_HOOK_TIMEOUT_SECS: u32 = 10;
+pub const MAX_DICTATION_CLIPBOARD_RESTORE_SECS: u32 = 600;
+pub const DEFAULT_DICTATION_HISTORY_AUDIO_MAX_MB: u32 = 200;
+pub const MAX_DICTATION_HISTORY_AUDIO_MAX_MB: u32 = 10_000;
+pub const DEFAULT_DICTATION_HISTORY_AUDIO_MAX_DAYS: u32 = 30;
+pub const MAX_DICTATION_HOOK_TIMEOUT_SECS: u32 = 300;
+pub const DEFAULT_DICTATION_HOLD_THRESHOLD_MS: u32 = 400;
+pub const MIN_DICTATION_HOLD_THRESHOLD_MS: u32 = 100;
+pub const MAX_DICTATION_HOLD_THRESHOLD_MS: u32 = 3_000;
+pub const DEFAULT_DICTATION_MAX_DURATION_SECS: u32 = 300;
+pub const MIN_DICTATION_MAX_DURATION_SECS: u32 = 10;
+pub const MAX_DICTATION_MAX_DURATION_SECS: u32 = 3_600;
+pub const MIN_DICTATION_CAPTURE_SAMPLE_RATE: u32 = 8_000;
+pub const MAX_DICTATION_CAPTURE_SAMPLE_RATE: u32 = 192_000;
+pub const MIN_DICTATION_CAPTURE_BUFFER_FRAMES: u32 = 32;
+pub const MAX_DICTATION_CAPTURE_BUFFER_FRAMES: u32 = 16_384;
 pub const SUPPORTED_OPENAI_REALTIME_MODELS: &[&str] = &[
     "whisper-1",
     "gpt-4o-transcribe",
@@ -21,10 +87,37 @@ pub const SUPPORTED_OPENAI_REALTIME_LANGUAGES: &[&str] =
     &["", "pt", "en", "de", "es", "fr", "it", "ja"];
 pub const SUPPORTED_OPENAI_REALTIME_PROFILES: &[&str] = &["caption", "balanced", "accuracy"];
 
+const OPENROUTER_TEMPERATURE_RANGE: std::ops::RangeInclusive<f32> = 0.0..=2.0;
+const OPENROUTER_TOP_P_RANGE: std::ops::RangeInclusive<f32> = 0.0..=1.0;
+
 fn default_openrouter_model() -> String {
     String::from(DEFAULT_OPENROUTER_MODEL)
 }
 
+fn default_openrouter_referer() -> String {
+    String::from(DEFAULT_OPENROUTER_REFERER)
+}
+
+fn default_openrouter_app_title() -> String {
+    String::from(DEFAULT_OPENROUTER_APP_TITLE)
+}
+
+fn default_openrouter_base_url() -> String {
+    String::from(DEFAULT_OPENROUTER_BASE_URL)
+}
+
+fn default_dictation_provider() -> String {
+    String::from(DEFAULT_DICTATION_PROVIDER)
+}
+
+fn default_deepgram_model() -> String {
+    String::from(DEFAULT_DEEPGRAM_MODEL)
+}
+
+fn default_dictation_streaming() -> bool {
+    DEFAULT_DICTATION_STREAMING
+}
+
 fn default_openai_realtime_model() -> String {
     String::from(DEFAULT_OPENAI_REALTIME_MODEL)
 }
@@ -45,14 +138,413 @@ fn default_copilot_save_history() -> bool {
     DEFAULT_COPILOT_SAVE_HISTORY
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+fn default_dictation_silence_threshold_ms() -> u32 {
+    DEFAULT_DICTATION_SILENCE_THRESHOLD_MS
+}
+
+fn default_dictation_shortcut() -> String {
+    String::from(DEFAULT_DICTATION_SHORTCUT)
+}
+
+fn default_api_keys_in_keyring() -> bool {
+    DEFAULT_API_KEYS_IN_KEYRING
+}
+
+fn default_dictation_stop_key() -> String {
+    String::from(DEFAULT_DICTATION_STOP_KEY)
+}
+
+fn default_dictation_shortcut_mode() -> String {
+    String::from(DEFAULT_DICTATION_SHORTCUT_MODE)
+}
+
+fn default_dictation_resample_quality() -> String {
+    String::from(DEFAULT_DICTATION_RESAMPLE_QUALITY)
+}
+
+fn default_dictation_target_sample_rate() -> String {
+    String::from(DEFAULT_DICTATION_TARGET_SAMPLE_RATE)
+}
+
+fn default_dictation_capture_mode() -> String {
+    String::from(DEFAULT_DICTATION_CAPTURE_MODE)
+}
+
+fn default_dictation_idle_timeout_secs() -> u32 {
+    DEFAULT_DICTATION_IDLE_TIMEOUT_SECS
+}
+
+fn default_dictation_output_mode() -> String {
+    String::from(DEFAULT_DICTATION_OUTPUT_MODE)
+}
+
+fn default_dictation_uri_template() -> String {
+    String::from(DEFAULT_DICTATION_URI_TEMPLATE)
+}
+
+fn default_dictation_hook_timeout_secs() -> u32 {
+    DEFAULT_DICTATION_HOOK_TIMEOUT_SECS
+}
+
+fn default_dictation_typing_delay_ms() -> u32 {
+    DEFAULT_DICTATION_TYPING_DELAY_MS
+}
+
+fn default_dictation_idle_action() -> String {
+    String::from(DEFAULT_DICTATION_IDLE_ACTION)
+}
+
+fn default_hud_click_action() -> String {
+    String::from(DEFAULT_HUD_CLICK_ACTION)
+}
+
+fn default_log_level() -> String {
+    String::from(DEFAULT_LOG_LEVEL)
+}
+
+fn default_hud_keep_open() -> bool {
+    DEFAULT_HUD_KEEP_OPEN
+}
+
+fn default_hud_hide_delay_ms() -> u32 {
+    DEFAULT_HUD_HIDE_DELAY_MS
+}
+
+fn default_dictation_show_window() -> bool {
+    DEFAULT_DICTATION_SHOW_WINDOW
+}
+
+fn default_dictation_normalize_audio() -> bool {
+    DEFAULT_DICTATION_NORMALIZE_AUDIO
+}
+
+fn default_dictation_history_audio_max_mb() -> u32 {
+    DEFAULT_DICTATION_HISTORY_AUDIO_MAX_MB
+}
+
+fn default_dictation_history_audio_max_days() -> u32 {
+    DEFAULT_DICTATION_HISTORY_AUDIO_MAX_DAYS
+}
+
+fn default_dictation_write_primary() -> bool {
+    DEFAULT_DICTATION_WRITE_PRIMARY
+}
+
+fn default_dictation_hold_threshold_ms() -> u32 {
+    DEFAULT_DICTATION_HOLD_THRESHOLD_MS
+}
+
+fn default_dictation_max_duration_secs() -> u32 {
+    DEFAULT_DICTATION_MAX_DURATION_SECS
+}
+
+/// Files written before onboarding existed belong to users already set
+/// up; only a fresh install starts with it pending.
+fn default_onboarding_completed() -> bool {
+    true
+}
+
+fn default_dictation_audio_format() -> String {
+    String::from(DEFAULT_DICTATION_AUDIO_FORMAT)
+}
+
+/// Provider and model a take can be sent to, as offered by the home
+/// picker.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DictationModelChoice {
+    pub provider: DictationProvider,
+    /// Empty for providers without a model setting.
+    pub model: String,
+}
+
+impl std::fmt::Display for DictationModelChoice {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.model.is_empty() {
+            f.write_str(self.provider.label())
+        } else {
+            write!(f, "{}: {}", self.provider.label(), self.model)
+        }
+    }
+}
+
+/// Named set of the dictation settings that change between uses (work,
+/// personal, code), switched as a whole.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DictationProfile {
+    pub name: String,
+    #[serde(default)]
+    pub provider: String,
+    /// Empty keeps the provider's current model.
+    #[serde(default)]
+    pub model: String,
+    /// Template wrapped around the transcription; empty = none.
+    #[serde(default)]
+    pub template: String,
+    #[serde(default)]
+    pub language: String,
+    #[serde(default)]
+    pub output_mode: String,
+    /// Dictation shortcut; empty keeps the current one.
+    #[serde(default)]
+    pub shortcut: String,
+}
+
+/// Tuning applied whenever `model` is the dictation model, so switching
+/// models doesn't mean re-tuning by hand.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ModelOverride {
+    pub model: String,
+    /// Extra instructions appended to the transcription prompt.
+    #[serde(default)]
+    pub prompt: String,
+    #[serde(default)]
+    pub temperature: Option<f32>,
+    /// Empty keeps the dictation language.
+    #[serde(default)]
+    pub language: String,
+}
+
+/// A `ModelOverride` as edited in the settings form.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ModelOverrideForm {
+    pub model: String,
+    pub prompt: String,
+    pub temperature: String,
+    pub language: String,
+}
+
+/// What a new user has to get through before global shortcuts are bound.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OnboardingStep {
+    ApiKey,
+    Microphone,
+    /// The user saw which shortcut starts a take and confirmed it; this
+    /// finishes the onboarding.
+    Shortcut,
+}
+
+impl OnboardingStep {
+    pub const ALL: [Self; 3] = [Self::ApiKey, Self::Microphone, Self::Shortcut];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::ApiKey => "Cadastrar a API key do ditado",
+            Self::Microphone => "Conectar um microfone",
+            Self::Shortcut => "Confirmar o atalho do ditado",
+        }
+    }
+}
+
+/// Steps of the first-run setup still to do, in order.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OnboardingState {
+    pub pending: Vec<OnboardingStep>,
+}
+
+impl OnboardingState {
+    pub fn is_complete(&self) -> bool {
+        self.pending.is_empty()
+    }
+
+    pub fn is_pending(&self, step: OnboardingStep) -> bool {
+        self.pending.contains(&step)
+    }
+}
+
+/// Which part of the settings `AppSettings::reset` restores.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResetScope {
+    All,
+    Shortcuts,
+    Audio,
+    Providers,
+}
+
+impl ResetScope {
+    pub const ALL: [Self; 4] = [Self::All, Self::Shortcuts, Self::Audio, Self::Providers];
+
+    pub fn code(self) -> &'static str {
+        match self {
+            Self::All => "all",
+            Self::Shortcuts => "shortcuts",
+            Self::Audio => "audio",
+            Self::Providers => "providers",
+        }
+    }
+
+    pub fn from_code(value: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|scope| scope.code() == value.trim().to_ascii_lowercase())
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::All => "Tudo",
+            Self::Shortcuts => "Atalhos",
+            Self::Audio => "Audio",
+            Self::Providers => "Provedores",
+        }
+    }
+}
+
+/// Settings fields that changed between two snapshots, named as in
+/// settings.json. Carried by the `config-changed` message.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ConfigDiff {
+    pub fields: Vec<String>,
+}
+
+impl ConfigDiff {
+    pub fn is_empty(&self) -> bool {
+        self.fields.is_empty()
+    }
+
+    pub fn contains(&self, field: &str) -> bool {
+        self.fields.iter().any(|changed| changed == field)
+    }
+}
+
+/// What kind of problem `SettingsForm::validate` found.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConfigProblemKind {
+    MissingKey,
+    UnknownModel,
+    InvalidShortcut,
+    MissingDevice,
+    InvalidValue,
+}
+
+impl ConfigProblemKind {
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::MissingKey => "API key",
+            Self::UnknownModel => "Modelo",
+            Self::InvalidShortcut => "Atalho",
+            Self::MissingDevice => "Microfone",
+            Self::InvalidValue => "Valor",
+        }
+    }
+}
+
+/// One thing that would break saving or recording with a configuration.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigProblem {
+    pub kind: ConfigProblemKind,
+    pub message: String,
+}
+
+impl ConfigProblem {
+    fn new(kind: ConfigProblemKind, message: impl Into<String>) -> Self {
+        Self {
+            kind,
+            message: message.into(),
+        }
+    }
+}
+
+/// What clicking the HUD status pill does.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HudClickAction {
+    ToggleDictation,
+    OpenSettings,
+    OpenHistory,
+}
+
+impl HudClickAction {
+    pub fn code(self) -> &'static str {
+        match self {
+            Self::ToggleDictation => "toggle",
+            Self::OpenSettings => "settings",
+            Self::OpenHistory => "history",
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::ToggleDictation => "Iniciar ou parar o ditado",
+            Self::OpenSettings => "Abrir as configuracoes",
+            Self::OpenHistory => "Abrir o historico",
+        }
+    }
+
+    pub fn from_code(value: &str) -> Self {
+        match value.trim() {
+            "toggle" => Self::ToggleDictation,
+            "history" => Self::OpenHistory,
+            _ => Self::OpenSettings,
+        }
+    }
+}
+
+impl std::fmt::Display for HudClickAction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.label())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct AppSettings {
     #[serde(default)]
     pub openrouter_api_key: String,
+    /// Keep the API keys in the OS keyring rather than in settings.json.
+    #[serde(default = "default_api_keys_in_keyring")]
+    pub api_keys_in_keyring: bool,
+    /// Further OpenRouter keys (e.g. a team one), tried in order when the
+    /// previous key is refused or out of credit.
+    #[serde(default)]
+    pub openrouter_fallback_api_keys: Vec<String>,
     #[serde(default)]
     pub openai_realtime_api_key: String,
     #[serde(default = "default_openrouter_model")]
     pub openrouter_model: String,
+    /// Tried in order when the main model fails or returns no text.
+    #[serde(default)]
+    pub openrouter_fallback_models: Vec<String>,
+    /// Second model the compare tool runs the last take through.
+    #[serde(default)]
+    pub openrouter_compare_model: String,
+    /// Sampling overrides for the OpenRouter request; `None` keeps the
+    /// model's default.
+    #[serde(default)]
+    pub openrouter_temperature: Option<f32>,
+    #[serde(default)]
+    pub openrouter_top_p: Option<f32>,
+    #[serde(default)]
+    pub openrouter_max_tokens: Option<u32>,
+    /// Attribution headers sent to OpenRouter; an empty value omits it.
+    #[serde(default = "default_openrouter_referer")]
+    pub openrouter_referer: String,
+    #[serde(default = "default_openrouter_app_title")]
+    pub openrouter_app_title: String,
+    /// Root of the chat completions and models endpoints, without the
+    /// trailing slash.
+    #[serde(default = "default_openrouter_base_url")]
+    pub openrouter_base_url: String,
+    /// Drops the attribution headers and asks OpenRouter to route only to
+    /// providers that don't keep or train on the request.
+    #[serde(default)]
+    pub openrouter_privacy_mode: bool,
+    #[serde(default = "default_dictation_provider")]
+    pub dictation_provider: String,
+    /// Language tag such as `pt-BR` or `en`; empty lets the provider detect it.
+    #[serde(default)]
+    pub dictation_language: String,
+    #[serde(default)]
+    pub deepgram_api_key: String,
+    #[serde(default = "default_deepgram_model")]
+    pub deepgram_model: String,
+    /// Transcribe while recording when the provider can stream.
+    #[serde(default = "default_dictation_streaming", alias = "deepgram_streaming")]
+    pub dictation_streaming: bool,
+    /// Return timed segments along with the text, for subtitles.
+    #[serde(default)]
+    pub dictation_timestamps: bool,
+    /// Names, acronyms and jargon the transcription should spell as given.
+    #[serde(default)]
+    pub dictation_vocabulary: Vec<String>,
+    #[serde(default)]
+    pub assemblyai_api_key: String,
     #[serde(default = "default_openai_realtime_model")]
     pub openai_realtime_model: String,
     #[serde(default)]
@@ -67,14 +559,196 @@ pub struct AppSettings {
     pub copilot_auto_include_transcript: bool,
     #[serde(default = "default_copilot_save_history")]
     pub copilot_save_history: bool,
+    #[serde(default)]
+    pub dictation_vad_enabled: bool,
+    #[serde(default = "default_dictation_silence_threshold_ms")]
+    pub dictation_silence_threshold_ms: u32,
+    #[serde(default = "default_dictation_shortcut")]
+    pub dictation_shortcut: String,
+    #[serde(default = "default_dictation_shortcut_mode")]
+    pub dictation_shortcut_mode: String,
+    /// Shortcut that throws the current take away and records again;
+    /// empty disables it.
+    #[serde(default)]
+    pub dictation_restart_shortcut: String,
+    /// Shortcut that copies the last transcription again; empty disables it.
+    #[serde(default)]
+    pub dictation_copy_last_shortcut: String,
+    /// Shortcut that undoes the last delivered dictation; empty disables it.
+    #[serde(default)]
+    pub dictation_undo_shortcut: String,
+    /// Shortcut that discards the take being recorded (or the transcription
+    /// in flight) without sending it; empty disables it.
+    #[serde(default)]
+    pub dictation_cancel_shortcut: String,
+    /// Key that, with the app focused, abandons the transcription in flight,
+    /// closes the home or quits (with Ctrl, discards the take being
+    /// recorded); empty leaves every key to the app being used.
+    #[serde(default = "default_dictation_stop_key")]
+    pub dictation_stop_key: String,
+    /// Shortcut that sends the last take again with the re-transcription
+    /// model; empty disables it.
+    #[serde(default)]
+    pub dictation_retranscribe_shortcut: String,
+    /// Shortcut that opens the history tab with its search focused; empty
+    /// disables it.
+    #[serde(default)]
+    pub dictation_history_shortcut: String,
+    /// Every global shortcut is left unbound, e.g. while gaming or sharing
+    /// the screen; kept across restarts until turned off.
+    #[serde(default)]
+    pub shortcuts_paused: bool,
+    /// How long the shortcut has to be held, in tap-or-hold mode, for its
+    /// release to end the take.
+    #[serde(default = "default_dictation_hold_threshold_ms")]
+    pub dictation_hold_threshold_ms: u32,
+    #[serde(default = "default_dictation_resample_quality")]
+    pub dictation_resample_quality: String,
+    #[serde(default = "default_dictation_target_sample_rate")]
+    pub dictation_target_sample_rate: String,
+    #[serde(default = "default_dictation_normalize_audio")]
+    pub dictation_normalize_audio: bool,
+    #[serde(default = "default_dictation_audio_format")]
+    pub dictation_audio_format: String,
+    #[serde(default)]
+    pub dictation_pre_roll_enabled: bool,
+    #[serde(default = "default_dictation_max_duration_secs")]
+    pub dictation_max_duration_secs: u32,
+    #[serde(default)]
+    pub dictation_recordings_dir: String,
+    /// One-based input channel; 0 averages all channels.
+    #[serde(default)]
+    pub dictation_input_channel: u16,
+    /// Microphone to record from; empty follows the system default.
+    #[serde(default)]
+    pub dictation_input_device: String,
+    /// Capture rate override in Hz; 0 keeps the device default.
+    #[serde(default)]
+    pub dictation_capture_sample_rate: u32,
+    /// Capture buffer override in frames; 0 keeps the device default.
+    #[serde(default)]
+    pub dictation_capture_buffer_frames: u32,
+    #[serde(default)]
+    pub dictation_sound_feedback: bool,
+    #[serde(default = "default_dictation_capture_mode")]
+    pub dictation_capture_mode: String,
+    /// Continuous silence, in seconds, after which a take is stopped even
+    /// without VAD; 0 disables it.
+    #[serde(default = "default_dictation_idle_timeout_secs")]
+    pub dictation_idle_timeout_secs: u32,
+    #[serde(default = "default_dictation_idle_action")]
+    pub dictation_idle_action: String,
+    /// Keep each take, compressed, next to its history entry.
+    #[serde(default)]
+    pub dictation_history_audio: bool,
+    #[serde(default = "default_dictation_history_audio_max_mb")]
+    pub dictation_history_audio_max_mb: u32,
+    /// 0 keeps takes until the size cap pushes them out.
+    #[serde(default = "default_dictation_history_audio_max_days")]
+    pub dictation_history_audio_max_days: u32,
+    /// Model used to re-transcribe history entries; empty uses the current
+    /// one.
+    #[serde(default)]
+    pub dictation_retranscribe_model: String,
+    #[serde(default)]
+    pub dictation_templates: Vec<TranscriptTemplate>,
+    /// Name of the template applied before delivering; empty = none.
+    #[serde(default)]
+    pub dictation_template: String,
+    /// Also fill the primary selection, for middle-click paste.
+    #[serde(default = "default_dictation_write_primary")]
+    pub dictation_write_primary: bool,
+    /// Desktop notification when a take finishes or fails.
+    #[serde(default)]
+    pub dictation_notifications: bool,
+    /// The user accepted that the `submit` output mode sends messages
+    /// without review; the mode can't be saved without it.
+    #[serde(default)]
+    pub dictation_auto_submit_confirmed: bool,
+    /// URI opened by the `uri` output mode; `{text}` is replaced with the
+    /// percent-encoded transcription.
+    #[serde(default = "default_dictation_uri_template")]
+    pub dictation_uri_template: String,
+    /// Seconds before the clipboard gets back what it held before the
+    /// transcription was copied; 0 leaves the transcription there.
+    #[serde(default)]
+    pub dictation_clipboard_restore_secs: u32,
+    /// Shell command that receives each transcription on stdin; empty = off.
+    #[serde(default)]
+    pub dictation_hook_command: String,
+    #[serde(default = "default_dictation_hook_timeout_secs")]
+    pub dictation_hook_timeout_secs: u32,
+    /// POST each transcription to `dictation_webhook_url`.
+    #[serde(default)]
+    pub dictation_webhook_enabled: bool,
+    #[serde(default)]
+    pub dictation_webhook_url: String,
+    #[serde(default = "default_dictation_output_mode")]
+    pub dictation_output_mode: String,
+    /// Pause between synthetic keystrokes in the typing output mode.
+    #[serde(default = "default_dictation_typing_delay_ms")]
+    pub dictation_typing_delay_ms: u32,
+    /// Raise the HUD when a take starts; off keeps focus in the app being
+    /// dictated into.
+    #[serde(default = "default_dictation_show_window")]
+    pub dictation_show_window: bool,
+    /// What clicking the HUD status pill does.
+    #[serde(default = "default_hud_click_action")]
+    pub hud_click_action: String,
+    /// Leave the HUD up after a take until it is dismissed; off hides it
+    /// `hud_hide_delay_ms` after the transcript is delivered.
+    #[serde(default = "default_hud_keep_open")]
+    pub hud_keep_open: bool,
+    #[serde(default = "default_hud_hide_delay_ms")]
+    pub hud_hide_delay_ms: u32,
+    /// Least severe log line kept, on stderr and in the log file.
+    #[serde(default = "default_log_level")]
+    pub log_level: String,
+    /// First-run setup is done; until then no global shortcut is bound.
+    #[serde(default = "default_onboarding_completed")]
+    pub onboarding_completed: bool,
+    /// Start OpenVoice with the desktop session, through an XDG autostart
+    /// entry.
+    #[serde(default)]
+    pub launch_at_login: bool,
+    /// Monthly dictation spend, in USD, above which a warning is shown;
+    /// 0 disables it.
+    #[serde(default)]
+    pub dictation_monthly_budget_usd: f64,
+    #[serde(default)]
+    pub dictation_model_overrides: Vec<ModelOverride>,
+    #[serde(default)]
+    pub dictation_profiles: Vec<DictationProfile>,
+    /// Name of the profile last activated; empty = none.
+    #[serde(default)]
+    pub active_profile: String,
 }
 
 impl Default for AppSettings {
     fn default() -> Self {
         Self {
             openrouter_api_key: String::new(),
+            api_keys_in_keyring: DEFAULT_API_KEYS_IN_KEYRING,
+            openrouter_fallback_api_keys: Vec::new(),
             openai_realtime_api_key: String::new(),
             openrouter_model: String::from(DEFAULT_OPENROUTER_MODEL),
+            openrouter_fallback_models: Vec::new(),
+            openrouter_compare_model: String::new(),
+            openrouter_temperature: None,
+            openrouter_top_p: None,
+            openrouter_max_tokens: None,
+            openrouter_referer: String::from(DEFAULT_OPENROUTER_REFERER),
+            openrouter_app_title: String::from(DEFAULT_OPENROUTER_APP_TITLE),
+            openrouter_base_url: String::from(DEFAULT_OPENROUTER_BASE_URL),
+            openrouter_privacy_mode: false,
+            dictation_provider: String::from(DEFAULT_DICTATION_PROVIDER),
+            dictation_language: String::new(),
+            deepgram_api_key: String::new(),
+            deepgram_model: String::from(DEFAULT_DEEPGRAM_MODEL),
+            dictation_streaming: DEFAULT_DICTATION_STREAMING,
+            dictation_timestamps: false,
+            dictation_vocabulary: Vec::new(),
+            assemblyai_api_key: String::new(),
             openai_realtime_model: String::from(DEFAULT_OPENAI_REALTIME_MODEL),
             openai_realtime_language: String::from(DEFAULT_OPENAI_REALTIME_LANGUAGE),
             openai_realtime_profile: String::from(DEFAULT_OPENAI_REALTIME_PROFILE),
@@ -82,51 +756,383 @@ impl Default for AppSettings {
             copilot_default_mode: String::from(DEFAULT_COPILOT_MODE),
             copilot_auto_include_transcript: DEFAULT_COPILOT_AUTO_INCLUDE_TRANSCRIPT,
             copilot_save_history: DEFAULT_COPILOT_SAVE_HISTORY,
+            dictation_vad_enabled: DEFAULT_DICTATION_VAD_ENABLED,
+            dictation_silence_threshold_ms: DEFAULT_DICTATION_SILENCE_THRESHOLD_MS,
+            dictation_shortcut: String::from(DEFAULT_DICTATION_SHORTCUT),
+            dictation_shortcut_mode: String::from(DEFAULT_DICTATION_SHORTCUT_MODE),
+            dictation_restart_shortcut: String::new(),
+            dictation_copy_last_shortcut: String::new(),
+            dictation_undo_shortcut: String::new(),
+            dictation_cancel_shortcut: String::new(),
+            dictation_stop_key: String::from(DEFAULT_DICTATION_STOP_KEY),
+            dictation_retranscribe_shortcut: String::new(),
+            dictation_history_shortcut: String::new(),
+            shortcuts_paused: false,
+            dictation_hold_threshold_ms: DEFAULT_DICTATION_HOLD_THRESHOLD_MS,
+            dictation_resample_quality: String::from(DEFAULT_DICTATION_RESAMPLE_QUALITY),
+            dictation_target_sample_rate: String::from(DEFAULT_DICTATION_TARGET_SAMPLE_RATE),
+            dictation_normalize_audio: DEFAULT_DICTATION_NORMALIZE_AUDIO,
+            dictation_audio_format: String::from(DEFAULT_DICTATION_AUDIO_FORMAT),
+            dictation_pre_roll_enabled: DEFAULT_DICTATION_PRE_ROLL_ENABLED,
+            dictation_max_duration_secs: DEFAULT_DICTATION_MAX_DURATION_SECS,
+            dictation_recordings_dir: String::new(),
+            dictation_input_channel: 0,
+            dictation_input_device: String::new(),
+            dictation_capture_sample_rate: 0,
+            dictation_capture_buffer_frames: 0,
+            dictation_sound_feedback: DEFAULT_DICTATION_SOUND_FEEDBACK,
+            dictation_capture_mode: String::from(DEFAULT_DICTATION_CAPTURE_MODE),
+            dictation_idle_timeout_secs: DEFAULT_DICTATION_IDLE_TIMEOUT_SECS,
+            dictation_idle_action: String::from(DEFAULT_DICTATION_IDLE_ACTION),
+            dictation_output_mode: String::from(DEFAULT_DICTATION_OUTPUT_MODE),
+            dictation_history_audio: false,
+            dictation_history_audio_max_mb: DEFAULT_DICTATION_HISTORY_AUDIO_MAX_MB,
+            dictation_history_audio_max_days: DEFAULT_DICTATION_HISTORY_AUDIO_MAX_DAYS,
+            dictation_retranscribe_model: String::new(),
+            dictation_templates: Vec::new(),
+            dictation_template: String::new(),
+            dictation_write_primary: DEFAULT_DICTATION_WRITE_PRIMARY,
+            dictation_notifications: false,
+            dictation_auto_submit_confirmed: false,
+            dictation_uri_template: String::from(DEFAULT_DICTATION_URI_TEMPLATE),
+            dictation_clipboard_restore_secs: 0,
+            dictation_hook_command: String::new(),
+            dictation_hook_timeout_secs: DEFAULT_DICTATION_HOOK_TIMEOUT_SECS,
+            dictation_webhook_enabled: false,
+            dictation_webhook_url: String::new(),
+            dictation_typing_delay_ms: DEFAULT_DICTATION_TYPING_DELAY_MS,
+            dictation_show_window: DEFAULT_DICTATION_SHOW_WINDOW,
+            hud_click_action: String::from(DEFAULT_HUD_CLICK_ACTION),
+            hud_keep_open: DEFAULT_HUD_KEEP_OPEN,
+            hud_hide_delay_ms: DEFAULT_HUD_HIDE_DELAY_MS,
+            log_level: String::from(DEFAULT_LOG_LEVEL),
+            onboarding_completed: false,
+            launch_at_login: false,
+            dictation_monthly_budget_usd: 0.0,
+            dictation_model_overrides: Vec::new(),
+            dictation_profiles: Vec::new(),
+            active_profile: String::new(),
         }
     }
 }
 
 impl AppSettings {
-    pub fn new(
-        openrouter_api_key: String,
-        openai_realtime_api_key: String,
-        openrouter_model: String,
-        openai_realtime_model: String,
-        openai_realtime_language: String,
-        openai_realtime_profile: String,
-        copilot_model: String,
-        copilot_default_mode: String,
-        copilot_auto_include_transcript: bool,
-        copilot_save_history: bool,
-    ) -> Result<Self, String> {
-        if openrouter_api_key.trim().is_empty() {
-            return Err(String::from("A OpenRouter API key nao pode ficar vazia."));
-        }
-
-        let openrouter_model = if openrouter_model.trim().is_empty() {
+    pub fn from_form(form: &SettingsForm) -> Result<Self, String> {
+        let dictation_provider = DictationProvider::from_code(&form.dictation_provider);
+        match dictation_provider {
+            DictationProvider::OpenRouter if form.openrouter_api_key.trim().is_empty() => {
+                return Err(String::from("A OpenRouter API key nao pode ficar vazia."));
+            }
+            DictationProvider::Deepgram if form.deepgram_api_key.trim().is_empty() => {
+                return Err(String::from(
+                    "A Deepgram API key nao pode ficar vazia quando o Deepgram transcreve o ditado.",
+                ));
+            }
+            DictationProvider::AssemblyAi if form.assemblyai_api_key.trim().is_empty() => {
+                return Err(String::from(
+                    "A AssemblyAI API key nao pode ficar vazia quando a AssemblyAI transcreve o ditado.",
+                ));
+            }
+            DictationProvider::OpenAi if form.openai_realtime_api_key.trim().is_empty() => {
+                return Err(String::from(
+                    "A OpenAI API key nao pode ficar vazia quando a OpenAI transcreve o ditado.",
+                ));
+            }
+            _ => {}
+        }
+
+        let openrouter_model = if form.openrouter_model.trim().is_empty() {
             String::from(DEFAULT_OPENROUTER_MODEL)
         } else {
-            openrouter_model.trim().to_owned()
+            form.openrouter_model.trim().to_owned()
         };
 
-        let openai_realtime_model = normalize_openai_realtime_model(&openai_realtime_model);
+        let openai_realtime_model = normalize_openai_realtime_model(&form.openai_realtime_model);
         let openai_realtime_language =
-            normalize_openai_realtime_language(&openai_realtime_language);
-        let openai_realtime_profile = normalize_openai_realtime_profile(&openai_realtime_profile);
-        let copilot_model = normalize_copilot_model(&copilot_model);
-        let copilot_default_mode = normalize_copilot_mode(&copilot_default_mode);
+            normalize_openai_realtime_language(&form.openai_realtime_language);
+        let openai_realtime_profile =
+            normalize_openai_realtime_profile(&form.openai_realtime_profile);
+        let copilot_model = normalize_copilot_model(&form.copilot_model);
+        let copilot_default_mode = normalize_copilot_mode(&form.copilot_default_mode);
+        let dictation_silence_threshold_ms =
+            parse_dictation_silence_threshold_ms(&form.dictation_silence_threshold_ms)?;
+        let dictation_max_duration_secs =
+            parse_dictation_max_duration_secs(&form.dictation_max_duration_secs)?;
+        let dictation_hold_threshold_ms =
+            parse_dictation_hold_threshold_ms(&form.dictation_hold_threshold_ms)?;
+        let dictation_idle_timeout_secs =
+            parse_dictation_idle_timeout_secs(&form.dictation_idle_timeout_secs)?;
+        let dictation_typing_delay_ms =
+            parse_dictation_typing_delay_ms(&form.dictation_typing_delay_ms)?;
+        let hud_hide_delay_ms = parse_hud_hide_delay_ms(&form.hud_hide_delay_ms)?;
+        let openrouter_base_url = parse_base_url(&form.openrouter_base_url)?;
+        let dictation_hook_timeout_secs =
+            parse_dictation_hook_timeout_secs(&form.dictation_hook_timeout_secs)?;
+        let dictation_uri_template = form.dictation_uri_template.trim().to_owned();
+        if DictationOutputMode::from_code(&form.dictation_output_mode) == DictationOutputMode::Uri
+            && !(dictation_uri_template.contains(':')
+                && dictation_uri_template.contains(TEMPLATE_PLACEHOLDER))
+        {
+            return Err(format!(
+                "A URI de saida precisa de um esquema (ex.: obsidian://) e de {TEMPLATE_PLACEHOLDER}."
+            ));
+        }
+        if DictationOutputMode::from_code(&form.dictation_output_mode)
+            == DictationOutputMode::Submit
+            && !form.dictation_auto_submit_confirmed
+        {
+            return Err(String::from(
+                "Confirme que o ditado pode enviar mensagens com Enter antes de usar o modo colar e enviar.",
+            ));
+        }
+        let dictation_templates = parse_templates(&form.dictation_templates)?;
+        let dictation_template = form.dictation_template.trim().to_owned();
+        if !dictation_template.is_empty()
+            && !dictation_templates
+                .iter()
+                .any(|template| template.name == dictation_template)
+        {
+            return Err(format!(
+                "O template \"{dictation_template}\" nao existe mais."
+            ));
+        }
+        let dictation_history_audio_max_mb =
+            parse_dictation_history_audio_max_mb(&form.dictation_history_audio_max_mb)?;
+        let dictation_history_audio_max_days =
+            parse_dictation_history_audio_max_days(&form.dictation_history_audio_max_days)?;
+        let dictation_clipboard_restore_secs =
+            parse_dictation_clipboard_restore_secs(&form.dictation_clipboard_restore_secs)?;
+        let dictation_webhook_url = form.dictation_webhook_url.trim().to_owned();
+        if form.dictation_webhook_enabled
+            && !(dictation_webhook_url.starts_with("http://")
+                || dictation_webhook_url.starts_with("https://"))
+        {
+            return Err(String::from(
+                "A URL do webhook precisa comecar com http:// ou https://.",
+            ));
+        }
+        let dictation_monthly_budget_usd =
+            parse_dictation_monthly_budget_usd(&form.dictation_monthly_budget_usd)?;
+        let openrouter_temperature = parse_sampling_param(
+            &form.openrouter_temperature,
+            OPENROUTER_TEMPERATURE_RANGE,
+            "A temperatura",
+        )?;
+        let openrouter_top_p =
+            parse_sampling_param(&form.openrouter_top_p, OPENROUTER_TOP_P_RANGE, "O top_p")?;
+        let openrouter_max_tokens = parse_openrouter_max_tokens(&form.openrouter_max_tokens)?;
+        let dictation_model_overrides = parse_model_overrides(&form.dictation_model_overrides)?;
+        let dictation_input_channel = parse_dictation_input_channel(&form.dictation_input_channel)?;
+        let dictation_capture_sample_rate = parse_capture_override(
+            &form.dictation_capture_sample_rate,
+            MIN_DICTATION_CAPTURE_SAMPLE_RATE..=MAX_DICTATION_CAPTURE_SAMPLE_RATE,
+            "A taxa de amostragem da captura",
+            "Hz",
+        )?;
+        let dictation_capture_buffer_frames = parse_capture_override(
+            &form.dictation_capture_buffer_frames,
+            MIN_DICTATION_CAPTURE_BUFFER_FRAMES..=MAX_DICTATION_CAPTURE_BUFFER_FRAMES,
+            "O buffer da captura",
+            "frames",
+        )?;
+        let dictation_shortcut = parse_dictation_shortcut(&form.dictation_shortcut)?;
+        let dictation_shortcut_mode = normalize_shortcut_mode(&form.dictation_shortcut_mode);
+        if Shortcut::parse(&dictation_shortcut)
+            .is_ok_and(|shortcut| shortcut.double_tap || shortcut.then.is_some())
+            && ShortcutMode::from_code(&dictation_shortcut_mode) != ShortcutMode::Toggle
+        {
+            return Err(String::from(
+                "Toque duplo e sequencia nao tem o que segurar; use o modo alternar.",
+            ));
+        }
+        let dictation_restart_shortcut =
+            parse_dictation_shortcut(&form.dictation_restart_shortcut)?;
+        if !dictation_restart_shortcut.is_empty()
+            && dictation_restart_shortcut == dictation_shortcut
+        {
+            return Err(String::from(
+                "O atalho de regravar precisa ser diferente do atalho de ditado.",
+            ));
+        }
+        let dictation_copy_last_shortcut =
+            parse_dictation_shortcut(&form.dictation_copy_last_shortcut)?;
+        if !dictation_copy_last_shortcut.is_empty()
+            && (dictation_copy_last_shortcut == dictation_shortcut
+                || dictation_copy_last_shortcut == dictation_restart_shortcut)
+        {
+            return Err(String::from(
+                "O atalho de copiar o ultimo ditado precisa ser diferente dos outros atalhos.",
+            ));
+        }
+        let dictation_undo_shortcut = parse_dictation_shortcut(&form.dictation_undo_shortcut)?;
+        if !dictation_undo_shortcut.is_empty()
+            && (dictation_undo_shortcut == dictation_shortcut
+                || dictation_undo_shortcut == dictation_restart_shortcut
+                || dictation_undo_shortcut == dictation_copy_last_shortcut)
+        {
+            return Err(String::from(
+                "O atalho de desfazer o ultimo ditado precisa ser diferente dos outros atalhos.",
+            ));
+        }
+        let dictation_cancel_shortcut = parse_dictation_shortcut(&form.dictation_cancel_shortcut)?;
+        if Shortcut::parse(&dictation_cancel_shortcut).is_ok_and(|shortcut| shortcut.then.is_some())
+        {
+            return Err(String::from(
+                "O atalho de cancelar precisa ser uma combinacao so, sem sequencia.",
+            ));
+        }
+        if !dictation_cancel_shortcut.is_empty()
+            && (dictation_cancel_shortcut == dictation_shortcut
+                || dictation_cancel_shortcut == dictation_restart_shortcut
+                || dictation_cancel_shortcut == dictation_copy_last_shortcut
+                || dictation_cancel_shortcut == dictation_undo_shortcut)
+        {
+            return Err(String::from(
+                "O atalho de cancelar a gravacao precisa ser diferente dos outros atalhos.",
+            ));
+        }
+        let dictation_stop_key = parse_stop_key(&form.dictation_stop_key)?;
+        let dictation_retranscribe_shortcut =
+            parse_dictation_shortcut(&form.dictation_retranscribe_shortcut)?;
+        if !dictation_retranscribe_shortcut.is_empty()
+            && (dictation_retranscribe_shortcut == dictation_shortcut
+                || dictation_retranscribe_shortcut == dictation_restart_shortcut
+                || dictation_retranscribe_shortcut == dictation_copy_last_shortcut
+                || dictation_retranscribe_shortcut == dictation_undo_shortcut
+                || dictation_retranscribe_shortcut == dictation_cancel_shortcut)
+        {
+            return Err(String::from(
+                "O atalho de refazer o ultimo ditado precisa ser diferente dos outros atalhos.",
+            ));
+        }
+        let mut taken = vec![
+            dictation_shortcut.as_str(),
+            dictation_restart_shortcut.as_str(),
+            dictation_copy_last_shortcut.as_str(),
+            dictation_undo_shortcut.as_str(),
+            dictation_cancel_shortcut.as_str(),
+            dictation_retranscribe_shortcut.as_str(),
+        ];
+        let dictation_history_shortcut =
+            parse_dictation_shortcut(&form.dictation_history_shortcut)?;
+        if !dictation_history_shortcut.is_empty()
+            && taken.contains(&dictation_history_shortcut.as_str())
+        {
+            return Err(String::from(
+                "O atalho do historico precisa ser diferente dos outros atalhos.",
+            ));
+        }
+        taken.push(&dictation_history_shortcut);
+        for template in &dictation_templates {
+            if template.shortcut.is_empty() {
+                continue;
+            }
+            if taken.contains(&template.shortcut.as_str()) {
+                return Err(format!(
+                    "O atalho do template \"{}\" precisa ser diferente dos outros atalhos.",
+                    template.name
+                ));
+            }
+            taken.push(&template.shortcut);
+        }
+        check_sequence_leaders(&taken)?;
+        let dictation_resample_quality =
+            normalize_resample_quality(&form.dictation_resample_quality);
+        let dictation_target_sample_rate =
+            normalize_target_sample_rate(&form.dictation_target_sample_rate);
 
         Ok(Self {
-            openrouter_api_key: openrouter_api_key.trim().to_owned(),
-            openai_realtime_api_key: openai_realtime_api_key.trim().to_owned(),
+            openrouter_api_key: form.openrouter_api_key.trim().to_owned(),
+            api_keys_in_keyring: form.api_keys_in_keyring,
+            openrouter_fallback_api_keys: parse_list(
+                &form.openrouter_fallback_api_keys,
+                form.openrouter_api_key.trim(),
+            ),
+            openai_realtime_api_key: form.openai_realtime_api_key.trim().to_owned(),
+            openrouter_fallback_models: parse_list(
+                &form.openrouter_fallback_models,
+                &openrouter_model,
+            ),
+            openrouter_compare_model: form.openrouter_compare_model.trim().to_owned(),
+            openrouter_temperature,
+            openrouter_top_p,
+            openrouter_max_tokens,
+            openrouter_referer: form.openrouter_referer.trim().to_owned(),
+            openrouter_app_title: form.openrouter_app_title.trim().to_owned(),
+            openrouter_base_url,
+            openrouter_privacy_mode: form.openrouter_privacy_mode,
             openrouter_model,
+            dictation_provider: dictation_provider.code().to_owned(),
+            dictation_language: form.dictation_language.trim().to_owned(),
+            deepgram_api_key: form.deepgram_api_key.trim().to_owned(),
+            deepgram_model: normalize_deepgram_model(&form.deepgram_model),
+            dictation_streaming: form.dictation_streaming,
+            dictation_timestamps: form.dictation_timestamps,
+            dictation_vocabulary: parse_list(&form.dictation_vocabulary, ""),
+            assemblyai_api_key: form.assemblyai_api_key.trim().to_owned(),
             openai_realtime_model,
             openai_realtime_language,
             openai_realtime_profile,
             copilot_model,
             copilot_default_mode,
-            copilot_auto_include_transcript,
-            copilot_save_history,
+            copilot_auto_include_transcript: form.copilot_auto_include_transcript,
+            copilot_save_history: form.copilot_save_history,
+            dictation_vad_enabled: form.dictation_vad_enabled,
+            dictation_silence_threshold_ms,
+            dictation_shortcut,
+            dictation_shortcut_mode,
+            dictation_restart_shortcut,
+            dictation_copy_last_shortcut,
+            dictation_undo_shortcut,
+            dictation_cancel_shortcut,
+            dictation_stop_key,
+            dictation_retranscribe_shortcut,
+            dictation_history_shortcut,
+            shortcuts_paused: form.shortcuts_paused,
+            dictation_hold_threshold_ms,
+            dictation_resample_quality,
+            dictation_target_sample_rate,
+            dictation_normalize_audio: form.dictation_normalize_audio,
+            dictation_audio_format: normalize_audio_format(&form.dictation_audio_format),
+            dictation_pre_roll_enabled: form.dictation_pre_roll_enabled,
+            dictation_max_duration_secs,
+            dictation_recordings_dir: form.dictation_recordings_dir.trim().to_owned(),
+            dictation_input_channel,
+            dictation_input_device: form.dictation_input_device.trim().to_owned(),
+            dictation_capture_sample_rate,
+            dictation_capture_buffer_frames,
+            dictation_sound_feedback: form.dictation_sound_feedback,
+            dictation_capture_mode: normalize_capture_mode(&form.dictation_capture_mode),
+            dictation_idle_timeout_secs,
+            dictation_idle_action: normalize_idle_action(&form.dictation_idle_action),
+            dictation_output_mode: normalize_output_mode(&form.dictation_output_mode),
+            dictation_history_audio: form.dictation_history_audio,
+            dictation_history_audio_max_mb,
+            dictation_history_audio_max_days,
+            dictation_retranscribe_model: form.dictation_retranscribe_model.trim().to_owned(),
+            dictation_templates,
+            dictation_template,
+            dictation_write_primary: form.dictation_write_primary,
+            dictation_notifications: form.dictation_notifications,
+            dictation_auto_submit_confirmed: form.dictation_auto_submit_confirmed,
+            dictation_uri_template,
+            dictation_clipboard_restore_secs,
+            dictation_hook_command: form.dictation_hook_command.trim().to_owned(),
+            dictation_hook_timeout_secs,
+            dictation_webhook_enabled: form.dictation_webhook_enabled,
+            dictation_webhook_url,
+            dictation_typing_delay_ms,
+            dictation_show_window: form.dictation_show_window,
+            hud_click_action: normalize_hud_click_action(&form.hud_click_action),
+            hud_keep_open: form.hud_keep_open,
+            hud_hide_delay_ms,
+            log_level: LogLevel::from_code(&form.log_level).code().to_owned(),
+            onboarding_completed: form.onboarding_completed,
+            launch_at_login: form.launch_at_login,
+            dictation_monthly_budget_usd,
+            dictation_model_overrides,
+            dictation_profiles: form.dictation_profiles.clone(),
+            active_profile: form.active_profile.clone(),
         })
     }
 
@@ -134,63 +1140,1056 @@ impl AppSettings {
         !self.openrouter_api_key.trim().is_empty()
     }
 
-    pub fn has_openai_realtime_api_key(&self) -> bool {
-        !self.openai_realtime_api_key.trim().is_empty()
+    pub fn has_deepgram_api_key(&self) -> bool {
+        !self.deepgram_api_key.trim().is_empty()
     }
 
-    pub fn normalized(mut self) -> Self {
-        self.openai_realtime_model = normalize_openai_realtime_model(&self.openai_realtime_model);
-        self.openai_realtime_language =
-            normalize_openai_realtime_language(&self.openai_realtime_language);
-        self.openai_realtime_profile =
-            normalize_openai_realtime_profile(&self.openai_realtime_profile);
-        self.copilot_model = normalize_copilot_model(&self.copilot_model);
-        self.copilot_default_mode = normalize_copilot_mode(&self.copilot_default_mode);
-        self
+    pub fn has_assemblyai_api_key(&self) -> bool {
+        !self.assemblyai_api_key.trim().is_empty()
     }
 
-    pub fn copilot_default_mode(&self) -> CopilotMode {
-        CopilotMode::from_code(&self.copilot_default_mode)
+    /// Whether the selected dictation provider has its key.
+    pub fn has_dictation_credentials(&self) -> bool {
+        self.has_credentials_for(self.dictation_provider())
     }
-}
-
-#[derive(Debug, Clone)]
-pub struct SettingsForm {
-    pub openrouter_api_key: String,
-    pub openai_realtime_api_key: String,
-    pub openrouter_model: String,
-    pub openai_realtime_model: String,
-    pub openai_realtime_language: String,
-    pub openai_realtime_profile: String,
-    pub copilot_model: String,
-    pub copilot_default_mode: String,
-    pub copilot_auto_include_transcript: bool,
-    pub copilot_save_history: bool,
-}
 
-impl From<&AppSettings> for SettingsForm {
-    fn from(settings: &AppSettings) -> Self {
-        Self {
-            openrouter_api_key: settings.openrouter_api_key.clone(),
-            openai_realtime_api_key: settings.openai_realtime_api_key.clone(),
-            openrouter_model: settings.openrouter_model.clone(),
-            openai_realtime_model: settings.openai_realtime_model.clone(),
-            openai_realtime_language: settings.openai_realtime_language.clone(),
-            openai_realtime_profile: settings.openai_realtime_profile.clone(),
-            copilot_model: settings.copilot_model.clone(),
-            copilot_default_mode: settings.copilot_default_mode.clone(),
-            copilot_auto_include_transcript: settings.copilot_auto_include_transcript,
-            copilot_save_history: settings.copilot_save_history,
+    pub fn has_credentials_for(&self, provider: DictationProvider) -> bool {
+        match provider {
+            DictationProvider::OpenRouter => self.has_api_key(),
+            DictationProvider::Deepgram => self.has_deepgram_api_key(),
+            DictationProvider::AssemblyAi => self.has_assemblyai_api_key(),
+            DictationProvider::OpenAi => self.has_openai_realtime_api_key(),
         }
     }
-}
 
-fn default_openai_realtime_profile() -> String {
-    String::from(DEFAULT_OPENAI_REALTIME_PROFILE)
-}
+    pub fn dictation_provider(&self) -> DictationProvider {
+        DictationProvider::from_code(&self.dictation_provider)
+    }
 
-fn normalize_openai_realtime_model(value: &str) -> String {
-    let trimmed = value.trim();
+    /// Model the next take goes to.
+    pub fn active_dictation_model(&self) -> DictationModelChoice {
+        let provider = self.dictation_provider();
+        let model = match provider {
+            DictationProvider::OpenRouter => self.openrouter_model.clone(),
+            DictationProvider::Deepgram => self.deepgram_model.clone(),
+            DictationProvider::AssemblyAi => String::new(),
+            DictationProvider::OpenAi => self.openai_realtime_model.clone(),
+        };
+
+        DictationModelChoice { provider, model }
+    }
+
+    /// Every configured model of the providers that have a key: the
+    /// OpenRouter main, fallback and compare models, and the one model of
+    /// each other provider.
+    pub fn dictation_model_choices(&self) -> Vec<DictationModelChoice> {
+        let openrouter = std::iter::once(&self.openrouter_model)
+            .chain(&self.openrouter_fallback_models)
+            .chain(std::iter::once(&self.openrouter_compare_model))
+            .filter(|model| !model.trim().is_empty())
+            .map(|model| DictationModelChoice {
+                provider: DictationProvider::OpenRouter,
+                model: model.clone(),
+            });
+        let others = [
+            (DictationProvider::Deepgram, self.deepgram_model.clone()),
+            (DictationProvider::AssemblyAi, String::new()),
+            (
+                DictationProvider::OpenAi,
+                self.openai_realtime_model.clone(),
+            ),
+        ]
+        .into_iter()
+        .map(|(provider, model)| DictationModelChoice { provider, model });
+
+        let mut choices: Vec<DictationModelChoice> = Vec::new();
+        for choice in openrouter.chain(others) {
+            if self.has_credentials_for(choice.provider) && !choices.contains(&choice) {
+                choices.push(choice);
+            }
+        }
+
+        choices
+    }
+
+    /// Makes `choice` the model of the next takes. An OpenRouter model
+    /// swaps places with the current main one, which becomes the first
+    /// fallback.
+    pub fn select_dictation_model(&mut self, choice: &DictationModelChoice) {
+        self.dictation_provider = choice.provider.code().to_owned();
+        if choice.provider != DictationProvider::OpenRouter || choice.model == self.openrouter_model
+        {
+            return;
+        }
+
+        let previous = std::mem::replace(&mut self.openrouter_model, choice.model.clone());
+        self.openrouter_fallback_models
+            .retain(|model| *model != choice.model);
+        self.openrouter_fallback_models.insert(0, previous);
+    }
+
+    /// Language forced on the transcription, if the user picked one, either
+    /// for every model or for the active one.
+    pub fn dictation_language(&self) -> Option<String> {
+        let language = self
+            .model_override()
+            .map(|tuning| tuning.language.trim())
+            .filter(|language| !language.is_empty())
+            .unwrap_or(self.dictation_language.trim());
+        (!language.is_empty()).then(|| language.to_owned())
+    }
+
+    /// Overrides stored for the model the next take goes to.
+    pub fn model_override(&self) -> Option<&ModelOverride> {
+        let active = self.active_dictation_model();
+        self.dictation_model_overrides
+            .iter()
+            .find(|tuning| !active.model.is_empty() && tuning.model == active.model)
+    }
+
+    /// OpenRouter temperature, with the active model's override first.
+    pub fn dictation_temperature(&self) -> Option<f32> {
+        self.model_override()
+            .and_then(|tuning| tuning.temperature)
+            .or(self.openrouter_temperature)
+    }
+
+    /// Instructions the active model's override adds to the prompt.
+    pub fn dictation_prompt_extra(&self) -> Option<&str> {
+        self.model_override()
+            .map(|tuning| tuning.prompt.trim())
+            .filter(|prompt| !prompt.is_empty())
+    }
+
+    pub fn has_openai_realtime_api_key(&self) -> bool {
+        !self.openai_realtime_api_key.trim().is_empty()
+    }
+
+    /// Applies the values `var` finds for `OPENVOICE_API_KEY` and
+    /// `OPENVOICE_MODEL`, both for the dictation provider, and for
+    /// `OPENVOICE_SHORTCUT`. An invalid shortcut is left out and reported.
+    pub fn apply_env_overrides(
+        &mut self,
+        var: impl Fn(&str) -> Option<String>,
+    ) -> Result<(), String> {
+        let provider = self.dictation_provider();
+        if let Some(key) = var(ENV_API_KEY) {
+            match provider {
+                DictationProvider::OpenRouter => self.openrouter_api_key = key,
+                DictationProvider::Deepgram => self.deepgram_api_key = key,
+                DictationProvider::AssemblyAi => self.assemblyai_api_key = key,
+                DictationProvider::OpenAi => self.openai_realtime_api_key = key,
+            }
+        }
+        if let Some(model) = var(ENV_MODEL) {
+            match provider {
+                DictationProvider::OpenRouter => self.openrouter_model = model,
+                DictationProvider::Deepgram => self.deepgram_model = model,
+                DictationProvider::AssemblyAi => {}
+                DictationProvider::OpenAi => self.openai_realtime_model = model,
+            }
+        }
+        if let Some(shortcut) = var(ENV_SHORTCUT) {
+            self.dictation_shortcut = parse_dictation_shortcut(&shortcut)
+                .ok()
+                .filter(|label| !label.is_empty())
+                .ok_or_else(|| format!("{ENV_SHORTCUT} invalido: {shortcut}"))?;
+        }
+
+        Ok(())
+    }
+
+    /// Copy safe to share: every API key is left empty.
+    pub fn without_api_keys(mut self) -> Self {
+        for key in self.api_keys_mut() {
+            key.clear();
+        }
+        self.openrouter_fallback_api_keys.clear();
+        self
+    }
+
+    /// Fills the API keys `self` leaves empty with those of `current`, so an
+    /// export without secrets doesn't sign the user out.
+    pub fn keep_api_keys_from(&mut self, current: &Self) {
+        let current_fallbacks = current.openrouter_fallback_api_keys.clone();
+        let current = [
+            &current.openrouter_api_key,
+            &current.openai_realtime_api_key,
+            &current.deepgram_api_key,
+            &current.assemblyai_api_key,
+        ];
+        for (key, current) in self.api_keys_mut().into_iter().zip(current) {
+            if key.trim().is_empty() {
+                key.clone_from(current);
+            }
+        }
+        if self.openrouter_fallback_api_keys.is_empty() {
+            self.openrouter_fallback_api_keys
+                .clone_from(&current_fallbacks);
+        }
+    }
+
+    /// Which fields `next` changes, compared through their JSON form so a
+    /// new setting is covered without touching this.
+    pub fn diff(&self, next: &Self) -> ConfigDiff {
+        let (Ok(serde_json::Value::Object(before)), Ok(serde_json::Value::Object(after))) =
+            (serde_json::to_value(self), serde_json::to_value(next))
+        else {
+            return ConfigDiff::default();
+        };
+
+        ConfigDiff {
+            fields: after
+                .into_iter()
+                .filter(|(field, value)| before.get(field) != Some(value))
+                .map(|(field, _)| field)
+                .collect(),
+        }
+    }
+
+    /// Restores the defaults of `scope`. API keys and a finished onboarding
+    /// survive even a full reset, so recovering doesn't sign the user out.
+    pub fn reset(&self, scope: ResetScope) -> Self {
+        let defaults = Self::default();
+        let mut next = self.clone();
+
+        match scope {
+            ResetScope::All => {
+                next = defaults;
+                next.api_keys_in_keyring = self.api_keys_in_keyring;
+                next.onboarding_completed = self.onboarding_completed;
+                next.keep_api_keys_from(self);
+            }
+            ResetScope::Shortcuts => {
+                next.dictation_shortcut = defaults.dictation_shortcut;
+                next.dictation_shortcut_mode = defaults.dictation_shortcut_mode;
+                next.dictation_restart_shortcut = defaults.dictation_restart_shortcut;
+                next.dictation_copy_last_shortcut = defaults.dictation_copy_last_shortcut;
+                next.dictation_undo_shortcut = defaults.dictation_undo_shortcut;
+                next.dictation_cancel_shortcut = defaults.dictation_cancel_shortcut;
+                next.dictation_stop_key = defaults.dictation_stop_key;
+                next.dictation_retranscribe_shortcut = defaults.dictation_retranscribe_shortcut;
+                next.dictation_history_shortcut = defaults.dictation_history_shortcut;
+                next.dictation_hold_threshold_ms = defaults.dictation_hold_threshold_ms;
+                next.shortcuts_paused = defaults.shortcuts_paused;
+                for template in &mut next.dictation_templates {
+                    template.shortcut.clear();
+                }
+                for profile in &mut next.dictation_profiles {
+                    profile.shortcut.clear();
+                }
+            }
+            ResetScope::Audio => {
+                next.dictation_resample_quality = defaults.dictation_resample_quality;
+                next.dictation_target_sample_rate = defaults.dictation_target_sample_rate;
+                next.dictation_normalize_audio = defaults.dictation_normalize_audio;
+                next.dictation_audio_format = defaults.dictation_audio_format;
+                next.dictation_pre_roll_enabled = defaults.dictation_pre_roll_enabled;
+                next.dictation_max_duration_secs = defaults.dictation_max_duration_secs;
+                next.dictation_input_channel = defaults.dictation_input_channel;
+                next.dictation_input_device = defaults.dictation_input_device;
+                next.dictation_capture_sample_rate = defaults.dictation_capture_sample_rate;
+                next.dictation_capture_buffer_frames = defaults.dictation_capture_buffer_frames;
+                next.dictation_capture_mode = defaults.dictation_capture_mode;
+                next.dictation_vad_enabled = defaults.dictation_vad_enabled;
+                next.dictation_silence_threshold_ms = defaults.dictation_silence_threshold_ms;
+                next.dictation_idle_timeout_secs = defaults.dictation_idle_timeout_secs;
+                next.dictation_idle_action = defaults.dictation_idle_action;
+                next.dictation_sound_feedback = defaults.dictation_sound_feedback;
+            }
+            ResetScope::Providers => {
+                next.dictation_provider = defaults.dictation_provider;
+                next.dictation_language = defaults.dictation_language;
+                next.openrouter_model = defaults.openrouter_model;
+                next.openrouter_fallback_models = defaults.openrouter_fallback_models;
+                next.openrouter_compare_model = defaults.openrouter_compare_model;
+                next.openrouter_temperature = defaults.openrouter_temperature;
+                next.openrouter_top_p = defaults.openrouter_top_p;
+                next.openrouter_max_tokens = defaults.openrouter_max_tokens;
+                next.openrouter_referer = defaults.openrouter_referer;
+                next.openrouter_app_title = defaults.openrouter_app_title;
+                next.openrouter_base_url = defaults.openrouter_base_url;
+                next.openrouter_privacy_mode = defaults.openrouter_privacy_mode;
+                next.deepgram_model = defaults.deepgram_model;
+                next.openai_realtime_model = defaults.openai_realtime_model;
+                next.openai_realtime_language = defaults.openai_realtime_language;
+                next.openai_realtime_profile = defaults.openai_realtime_profile;
+                next.dictation_retranscribe_model = defaults.dictation_retranscribe_model;
+                next.dictation_model_overrides = defaults.dictation_model_overrides;
+                next.active_profile = defaults.active_profile;
+            }
+        }
+
+        next.normalized()
+    }
+
+    fn api_keys_mut(&mut self) -> [&mut String; 4] {
+        [
+            &mut self.openrouter_api_key,
+            &mut self.openai_realtime_api_key,
+            &mut self.deepgram_api_key,
+            &mut self.assemblyai_api_key,
+        ]
+    }
+
+    pub fn normalized(mut self) -> Self {
+        self.openai_realtime_model = normalize_openai_realtime_model(&self.openai_realtime_model);
+        self.openai_realtime_language =
+            normalize_openai_realtime_language(&self.openai_realtime_language);
+        self.openai_realtime_profile =
+            normalize_openai_realtime_profile(&self.openai_realtime_profile);
+        self.copilot_model = normalize_copilot_model(&self.copilot_model);
+        self.dictation_provider = DictationProvider::from_code(&self.dictation_provider)
+            .code()
+            .to_owned();
+        self.deepgram_model = normalize_deepgram_model(&self.deepgram_model);
+        self.copilot_default_mode = normalize_copilot_mode(&self.copilot_default_mode);
+        self.dictation_silence_threshold_ms =
+            clamp_dictation_silence_threshold_ms(self.dictation_silence_threshold_ms);
+        self.dictation_max_duration_secs =
+            clamp_dictation_max_duration_secs(self.dictation_max_duration_secs);
+        self.dictation_hold_threshold_ms =
+            clamp_dictation_hold_threshold_ms(self.dictation_hold_threshold_ms);
+        self.dictation_shortcut = parse_dictation_shortcut(&self.dictation_shortcut)
+            .unwrap_or_else(|_| String::from(DEFAULT_DICTATION_SHORTCUT));
+        self.dictation_shortcut_mode = normalize_shortcut_mode(&self.dictation_shortcut_mode);
+        self.dictation_restart_shortcut =
+            parse_dictation_shortcut(&self.dictation_restart_shortcut).unwrap_or_default();
+        self.dictation_copy_last_shortcut =
+            parse_dictation_shortcut(&self.dictation_copy_last_shortcut).unwrap_or_default();
+        self.dictation_undo_shortcut =
+            parse_dictation_shortcut(&self.dictation_undo_shortcut).unwrap_or_default();
+        self.dictation_cancel_shortcut =
+            parse_dictation_shortcut(&self.dictation_cancel_shortcut).unwrap_or_default();
+        self.dictation_stop_key = parse_stop_key(&self.dictation_stop_key).unwrap_or_default();
+        self.dictation_retranscribe_shortcut =
+            parse_dictation_shortcut(&self.dictation_retranscribe_shortcut).unwrap_or_default();
+        self.dictation_history_shortcut =
+            parse_dictation_shortcut(&self.dictation_history_shortcut).unwrap_or_default();
+        self.dictation_resample_quality =
+            normalize_resample_quality(&self.dictation_resample_quality);
+        self.dictation_target_sample_rate =
+            normalize_target_sample_rate(&self.dictation_target_sample_rate);
+        self.dictation_audio_format = normalize_audio_format(&self.dictation_audio_format);
+        self.dictation_capture_mode = normalize_capture_mode(&self.dictation_capture_mode);
+        self.dictation_idle_timeout_secs =
+            clamp_dictation_idle_timeout_secs(self.dictation_idle_timeout_secs);
+        self.dictation_idle_action = normalize_idle_action(&self.dictation_idle_action);
+        self.hud_click_action = normalize_hud_click_action(&self.hud_click_action);
+        self.dictation_output_mode = normalize_output_mode(&self.dictation_output_mode);
+        self.dictation_typing_delay_ms = self
+            .dictation_typing_delay_ms
+            .min(MAX_DICTATION_TYPING_DELAY_MS);
+        self.hud_hide_delay_ms = self.hud_hide_delay_ms.min(MAX_HUD_HIDE_DELAY_MS);
+        self.openrouter_base_url = parse_base_url(&self.openrouter_base_url)
+            .unwrap_or_else(|_| String::from(DEFAULT_OPENROUTER_BASE_URL));
+        self.log_level = self.log_level().code().to_owned();
+        self.dictation_templates = parse_templates(&self.dictation_templates).unwrap_or_default();
+        if self.dictation_template().is_none() {
+            self.dictation_template.clear();
+        }
+        self.dictation_history_audio_max_mb = self
+            .dictation_history_audio_max_mb
+            .clamp(1, MAX_DICTATION_HISTORY_AUDIO_MAX_MB);
+        self.dictation_clipboard_restore_secs = self
+            .dictation_clipboard_restore_secs
+            .min(MAX_DICTATION_CLIPBOARD_RESTORE_SECS);
+        self.dictation_hook_timeout_secs = self
+            .dictation_hook_timeout_secs
+            .clamp(1, MAX_DICTATION_HOOK_TIMEOUT_SECS);
+        if !(MIN_DICTATION_CAPTURE_SAMPLE_RATE..=MAX_DICTATION_CAPTURE_SAMPLE_RATE)
+            .contains(&self.dictation_capture_sample_rate)
+        {
+            self.dictation_capture_sample_rate = 0;
+        }
+        if !(MIN_DICTATION_CAPTURE_BUFFER_FRAMES..=MAX_DICTATION_CAPTURE_BUFFER_FRAMES)
+            .contains(&self.dictation_capture_buffer_frames)
+        {
+            self.dictation_capture_buffer_frames = 0;
+        }
+        self.openrouter_temperature = self
+            .openrouter_temperature
+            .filter(|value| OPENROUTER_TEMPERATURE_RANGE.contains(value));
+        self.openrouter_top_p = self
+            .openrouter_top_p
+            .filter(|value| OPENROUTER_TOP_P_RANGE.contains(value));
+        self.openrouter_max_tokens = self.openrouter_max_tokens.filter(|value| *value > 0);
+        let mut models = std::collections::HashSet::new();
+        self.dictation_model_overrides.retain_mut(|tuning| {
+            tuning.temperature = tuning
+                .temperature
+                .filter(|value| OPENROUTER_TEMPERATURE_RANGE.contains(value));
+            !tuning.model.trim().is_empty() && models.insert(tuning.model.clone())
+        });
+        let mut names = std::collections::HashSet::new();
+        self.dictation_profiles.retain(|profile| {
+            !profile.name.trim().is_empty() && names.insert(profile.name.clone())
+        });
+        if self.active_profile().is_none() {
+            self.active_profile.clear();
+        }
+        self
+    }
+
+    pub fn copilot_default_mode(&self) -> CopilotMode {
+        CopilotMode::from_code(&self.copilot_default_mode)
+    }
+
+    /// Parsed dictation shortcut, or `None` when the user cleared it.
+    pub fn dictation_shortcut(&self) -> Option<Shortcut> {
+        Shortcut::parse(&self.dictation_shortcut).ok()
+    }
+
+    pub fn dictation_restart_shortcut(&self) -> Option<Shortcut> {
+        Shortcut::parse(&self.dictation_restart_shortcut).ok()
+    }
+
+    pub fn dictation_copy_last_shortcut(&self) -> Option<Shortcut> {
+        Shortcut::parse(&self.dictation_copy_last_shortcut).ok()
+    }
+
+    pub fn dictation_undo_shortcut(&self) -> Option<Shortcut> {
+        Shortcut::parse(&self.dictation_undo_shortcut).ok()
+    }
+
+    pub fn dictation_cancel_shortcut(&self) -> Option<Shortcut> {
+        Shortcut::parse(&self.dictation_cancel_shortcut).ok()
+    }
+
+    pub fn dictation_stop_key(&self) -> Option<Shortcut> {
+        Shortcut::parse(&self.dictation_stop_key).ok()
+    }
+
+    pub fn dictation_retranscribe_shortcut(&self) -> Option<Shortcut> {
+        Shortcut::parse(&self.dictation_retranscribe_shortcut).ok()
+    }
+
+    pub fn dictation_history_shortcut(&self) -> Option<Shortcut> {
+        Shortcut::parse(&self.dictation_history_shortcut).ok()
+    }
+
+    /// Every configured global shortcut and the action it triggers, the
+    /// dictation one first.
+    pub fn shortcut_binds(&self) -> Vec<(Shortcut, ShortcutAction)> {
+        let fixed = [
+            (self.dictation_shortcut(), ShortcutAction::Dictation),
+            (self.dictation_restart_shortcut(), ShortcutAction::Restart),
+            (
+                self.dictation_copy_last_shortcut(),
+                ShortcutAction::CopyLast,
+            ),
+            (self.dictation_undo_shortcut(), ShortcutAction::Undo),
+            (self.dictation_cancel_shortcut(), ShortcutAction::Cancel),
+            (
+                self.dictation_retranscribe_shortcut(),
+                ShortcutAction::Retranscribe,
+            ),
+            (self.dictation_history_shortcut(), ShortcutAction::History),
+        ];
+        let templates = self
+            .dictation_templates
+            .iter()
+            .enumerate()
+            .map(|(index, template)| {
+                (
+                    Shortcut::parse(&template.shortcut).ok(),
+                    ShortcutAction::Template(index),
+                )
+            });
+
+        fixed
+            .into_iter()
+            .chain(templates)
+            .filter_map(|(shortcut, action)| Some((shortcut?, action)))
+            .collect()
+    }
+
+    pub fn dictation_hold_threshold(&self) -> std::time::Duration {
+        std::time::Duration::from_millis(u64::from(self.dictation_hold_threshold_ms))
+    }
+
+    pub fn dictation_shortcut_mode(&self) -> ShortcutMode {
+        ShortcutMode::from_code(&self.dictation_shortcut_mode)
+    }
+
+    pub fn dictation_resample_quality(&self) -> ResampleQuality {
+        ResampleQuality::from_code(&self.dictation_resample_quality)
+    }
+
+    /// Zero-based channel to transcribe, or `None` to average all of them.
+    pub fn dictation_input_channel(&self) -> Option<u16> {
+        self.dictation_input_channel.checked_sub(1)
+    }
+
+    pub fn dictation_capture_overrides(&self) -> CaptureOverrides {
+        let device = self.dictation_input_device.trim();
+
+        CaptureOverrides {
+            device: (!device.is_empty()).then(|| device.to_owned()),
+            sample_rate: (self.dictation_capture_sample_rate > 0)
+                .then_some(self.dictation_capture_sample_rate),
+            buffer_frames: (self.dictation_capture_buffer_frames > 0)
+                .then_some(self.dictation_capture_buffer_frames),
+        }
+    }
+
+    pub fn dictation_idle_timeout(&self) -> Option<std::time::Duration> {
+        (self.dictation_idle_timeout_secs > 0)
+            .then(|| std::time::Duration::from_secs(u64::from(self.dictation_idle_timeout_secs)))
+    }
+
+    /// Limits for the audio kept in the history, when keeping it is on.
+    pub fn dictation_history_audio_retention(&self) -> Option<HistoryAudioRetention> {
+        self.dictation_history_audio.then(|| HistoryAudioRetention {
+            max_bytes: u64::from(self.dictation_history_audio_max_mb) * 1_024 * 1_024,
+            max_age: (self.dictation_history_audio_max_days > 0).then(|| {
+                std::time::Duration::from_secs(
+                    u64::from(self.dictation_history_audio_max_days) * 24 * 60 * 60,
+                )
+            }),
+        })
+    }
+
+    /// Template applied to transcriptions before they are delivered.
+    pub fn dictation_template(&self) -> Option<&TranscriptTemplate> {
+        self.dictation_templates
+            .iter()
+            .find(|template| template.name == self.dictation_template)
+    }
+
+    /// Setup steps still pending; `input_devices` is the last microphone
+    /// list.
+    pub fn onboarding_state(&self, input_devices: &[String]) -> OnboardingState {
+        if self.onboarding_completed {
+            return OnboardingState {
+                pending: Vec::new(),
+            };
+        }
+
+        let pending = OnboardingStep::ALL
+            .into_iter()
+            .filter(|step| match step {
+                OnboardingStep::ApiKey => !self.has_dictation_credentials(),
+                OnboardingStep::Microphone => input_devices.is_empty(),
+                OnboardingStep::Shortcut => true,
+            })
+            .collect();
+
+        OnboardingState { pending }
+    }
+
+    /// Marks `step` done. Detected steps can't be skipped, and the shortcut
+    /// is only confirmed once the others are through, which finishes it.
+    pub fn complete_onboarding_step(
+        &mut self,
+        step: OnboardingStep,
+        input_devices: &[String],
+    ) -> Result<(), String> {
+        let state = self.onboarding_state(input_devices);
+        let blocking = state
+            .pending
+            .iter()
+            .find(|pending| **pending == step || step == OnboardingStep::Shortcut);
+
+        match blocking {
+            Some(OnboardingStep::Shortcut) | None => {
+                if step == OnboardingStep::Shortcut {
+                    self.onboarding_completed = true;
+                }
+                Ok(())
+            }
+            Some(pending) => Err(format!("Falta: {}.", pending.label().to_lowercase())),
+        }
+    }
+
+    /// Profile last activated, if it still exists.
+    pub fn active_profile(&self) -> Option<&DictationProfile> {
+        self.dictation_profiles
+            .iter()
+            .find(|profile| profile.name == self.active_profile)
+    }
+
+    /// Current provider, model, template, language, output mode and
+    /// shortcut under `name`.
+    pub fn snapshot_profile(&self, name: &str) -> DictationProfile {
+        let model = self.active_dictation_model();
+        DictationProfile {
+            name: name.trim().to_owned(),
+            provider: model.provider.code().to_owned(),
+            model: model.model,
+            template: self.dictation_template.clone(),
+            language: self.dictation_language.clone(),
+            output_mode: self.dictation_output_mode.clone(),
+            shortcut: self.dictation_shortcut.clone(),
+        }
+    }
+
+    /// Stores the current setup as the profile `name`, replacing one of the
+    /// same name, and marks it active.
+    pub fn save_profile(&mut self, name: &str) -> Result<(), String> {
+        let profile = self.snapshot_profile(name);
+        if profile.name.is_empty() {
+            return Err(String::from("O perfil precisa de um nome."));
+        }
+
+        self.active_profile = profile.name.clone();
+        match self
+            .dictation_profiles
+            .iter_mut()
+            .find(|existing| existing.name == profile.name)
+        {
+            Some(existing) => *existing = profile,
+            None => self.dictation_profiles.push(profile),
+        }
+        Ok(())
+    }
+
+    /// Settings with the profile `name` applied, validated as if saved from
+    /// the form.
+    pub fn with_profile(&self, name: &str) -> Result<Self, String> {
+        let profile = self
+            .dictation_profiles
+            .iter()
+            .find(|profile| profile.name == name)
+            .ok_or_else(|| format!("Perfil \"{name}\" nao encontrado."))?
+            .clone();
+
+        let mut next = self.clone();
+        let provider = DictationProvider::from_code(&profile.provider);
+        next.dictation_provider = provider.code().to_owned();
+        if !profile.model.is_empty() {
+            match provider {
+                DictationProvider::OpenRouter => {
+                    next.select_dictation_model(&DictationModelChoice {
+                        provider,
+                        model: profile.model,
+                    })
+                }
+                DictationProvider::Deepgram => next.deepgram_model = profile.model,
+                DictationProvider::AssemblyAi => {}
+                DictationProvider::OpenAi => next.openai_realtime_model = profile.model,
+            }
+        }
+        next.dictation_template = profile.template;
+        if next.dictation_template().is_none() {
+            next.dictation_template.clear();
+        }
+        next.dictation_language = profile.language;
+        next.dictation_output_mode = profile.output_mode;
+        if !profile.shortcut.is_empty() {
+            next.dictation_shortcut = profile.shortcut;
+        }
+        next.active_profile = profile.name;
+
+        Self::from_form(&SettingsForm::from(&next))
+            .map_err(|error| format!("Perfil \"{name}\": {error}"))
+    }
+
+    /// Forgets the profile `name`; the settings it applied stay as they are.
+    pub fn delete_profile(&mut self, name: &str) {
+        self.dictation_profiles
+            .retain(|profile| profile.name != name);
+        if self.active_profile == name {
+            self.active_profile.clear();
+        }
+    }
+
+    pub fn dictation_retranscribe_model(&self) -> Option<&str> {
+        let model = self.dictation_retranscribe_model.trim();
+        (!model.is_empty()).then_some(model)
+    }
+
+    pub fn dictation_clipboard_restore_delay(&self) -> Option<std::time::Duration> {
+        (self.dictation_clipboard_restore_secs > 0).then(|| {
+            std::time::Duration::from_secs(u64::from(self.dictation_clipboard_restore_secs))
+        })
+    }
+
+    pub fn dictation_monthly_budget(&self) -> Option<f64> {
+        (self.dictation_monthly_budget_usd > 0.0).then_some(self.dictation_monthly_budget_usd)
+    }
+
+    pub fn dictation_idle_action(&self) -> IdleStopAction {
+        IdleStopAction::from_code(&self.dictation_idle_action)
+    }
+
+    pub fn hud_click_action(&self) -> HudClickAction {
+        HudClickAction::from_code(&self.hud_click_action)
+    }
+
+    pub fn log_level(&self) -> LogLevel {
+        LogLevel::from_code(&self.log_level)
+    }
+
+    /// How long the HUD stays up after a take, or `None` to keep it open
+    /// until dismissed.
+    pub fn hud_hide_delay(&self) -> Option<std::time::Duration> {
+        (!self.hud_keep_open)
+            .then(|| std::time::Duration::from_millis(u64::from(self.hud_hide_delay_ms)))
+    }
+
+    pub fn dictation_output_mode(&self) -> DictationOutputMode {
+        DictationOutputMode::from_code(&self.dictation_output_mode)
+    }
+
+    /// Post-transcription shell command, when one is configured.
+    pub fn dictation_hook_command(&self) -> Option<&str> {
+        let command = self.dictation_hook_command.trim();
+        (!command.is_empty()).then_some(command)
+    }
+
+    /// Where to POST finished transcriptions, when the webhook is on.
+    pub fn dictation_webhook_url(&self) -> Option<&str> {
+        let url = self.dictation_webhook_url.trim();
+        (self.dictation_webhook_enabled && !url.is_empty()).then_some(url)
+    }
+
+    pub fn dictation_capture_mode(&self) -> DictationCaptureMode {
+        DictationCaptureMode::from_code(&self.dictation_capture_mode)
+    }
+
+    pub fn dictation_target_sample_rate(&self) -> TargetSampleRate {
+        TargetSampleRate::from_code(&self.dictation_target_sample_rate)
+    }
+
+    pub fn dictation_audio_format(&self) -> AudioFormat {
+        AudioFormat::from_code(&self.dictation_audio_format)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct SettingsForm {
+    pub openrouter_api_key: String,
+    pub api_keys_in_keyring: bool,
+    pub openrouter_fallback_api_keys: String,
+    pub openai_realtime_api_key: String,
+    pub openrouter_model: String,
+    pub openrouter_fallback_models: String,
+    pub openrouter_compare_model: String,
+    pub openrouter_temperature: String,
+    pub openrouter_top_p: String,
+    pub openrouter_max_tokens: String,
+    pub openrouter_referer: String,
+    pub openrouter_app_title: String,
+    pub openrouter_base_url: String,
+    pub openrouter_privacy_mode: bool,
+    pub dictation_provider: String,
+    pub dictation_language: String,
+    pub deepgram_api_key: String,
+    pub deepgram_model: String,
+    pub dictation_streaming: bool,
+    pub dictation_timestamps: bool,
+    pub dictation_vocabulary: String,
+    pub assemblyai_api_key: String,
+    pub openai_realtime_model: String,
+    pub openai_realtime_language: String,
+    pub openai_realtime_profile: String,
+    pub copilot_model: String,
+    pub copilot_default_mode: String,
+    pub copilot_auto_include_transcript: bool,
+    pub copilot_save_history: bool,
+    pub dictation_vad_enabled: bool,
+    pub dictation_silence_threshold_ms: String,
+    pub dictation_shortcut: String,
+    pub dictation_shortcut_mode: String,
+    pub dictation_restart_shortcut: String,
+    pub dictation_copy_last_shortcut: String,
+    pub dictation_undo_shortcut: String,
+    pub dictation_cancel_shortcut: String,
+    pub dictation_stop_key: String,
+    pub dictation_retranscribe_shortcut: String,
+    pub dictation_history_shortcut: String,
+    pub shortcuts_paused: bool,
+    pub dictation_hold_threshold_ms: String,
+    pub dictation_resample_quality: String,
+    pub dictation_target_sample_rate: String,
+    pub dictation_normalize_audio: bool,
+    pub dictation_audio_format: String,
+    pub dictation_pre_roll_enabled: bool,
+    pub dictation_max_duration_secs: String,
+    pub dictation_recordings_dir: String,
+    pub dictation_input_channel: String,
+    pub dictation_input_device: String,
+    pub dictation_capture_sample_rate: String,
+    pub dictation_capture_buffer_frames: String,
+    pub dictation_sound_feedback: bool,
+    pub dictation_capture_mode: String,
+    pub dictation_idle_timeout_secs: String,
+    pub dictation_idle_action: String,
+    pub dictation_output_mode: String,
+    pub dictation_history_audio: bool,
+    pub dictation_history_audio_max_mb: String,
+    pub dictation_history_audio_max_days: String,
+    pub dictation_retranscribe_model: String,
+    pub dictation_templates: Vec<TranscriptTemplate>,
+    pub dictation_template: String,
+    pub dictation_write_primary: bool,
+    pub dictation_notifications: bool,
+    pub dictation_auto_submit_confirmed: bool,
+    pub dictation_uri_template: String,
+    pub dictation_clipboard_restore_secs: String,
+    pub dictation_hook_command: String,
+    pub dictation_hook_timeout_secs: String,
+    pub dictation_webhook_enabled: bool,
+    pub dictation_webhook_url: String,
+    pub dictation_typing_delay_ms: String,
+    pub dictation_show_window: bool,
+    pub hud_click_action: String,
+    pub hud_keep_open: bool,
+    pub hud_hide_delay_ms: String,
+    pub log_level: String,
+    pub dictation_model_overrides: Vec<ModelOverrideForm>,
+    pub dictation_profiles: Vec<DictationProfile>,
+    pub active_profile: String,
+    pub onboarding_completed: bool,
+    pub launch_at_login: bool,
+    pub dictation_monthly_budget_usd: String,
+}
+
+impl SettingsForm {
+    /// Text field holding the shortcut of `action`, for actions that have
+    /// one.
+    pub fn shortcut_field_mut(&mut self, action: ShortcutAction) -> Option<&mut String> {
+        match action {
+            ShortcutAction::Dictation => Some(&mut self.dictation_shortcut),
+            ShortcutAction::Restart => Some(&mut self.dictation_restart_shortcut),
+            ShortcutAction::Cancel => Some(&mut self.dictation_cancel_shortcut),
+            ShortcutAction::Retranscribe => Some(&mut self.dictation_retranscribe_shortcut),
+            ShortcutAction::CopyLast => Some(&mut self.dictation_copy_last_shortcut),
+            ShortcutAction::Undo => Some(&mut self.dictation_undo_shortcut),
+            ShortcutAction::History => Some(&mut self.dictation_history_shortcut),
+            ShortcutAction::Template(index) => self
+                .dictation_templates
+                .get_mut(index)
+                .map(|template| &mut template.shortcut),
+            ShortcutAction::Monitor
+            | ShortcutAction::FlushPending
+            | ShortcutAction::Session
+            | ShortcutAction::PauseShortcuts => None,
+        }
+    }
+}
+
+impl SettingsForm {
+    /// Every problem found in the form, so they can be shown before saving
+    /// instead of at record time. `input_devices` is the last microphone
+    /// list; when empty the device is not checked.
+    pub fn validate(&self, input_devices: &[String]) -> Vec<ConfigProblem> {
+        let mut problems = Vec::new();
+
+        let provider = DictationProvider::from_code(&self.dictation_provider);
+        let key = match provider {
+            DictationProvider::OpenRouter => &self.openrouter_api_key,
+            DictationProvider::Deepgram => &self.deepgram_api_key,
+            DictationProvider::AssemblyAi => &self.assemblyai_api_key,
+            DictationProvider::OpenAi => &self.openai_realtime_api_key,
+        };
+        if key.trim().is_empty() {
+            problems.push(ConfigProblem::new(
+                ConfigProblemKind::MissingKey,
+                format!("Falta a {} API key do ditado.", provider.label()),
+            ));
+        }
+
+        let openrouter_models = std::iter::once(self.openrouter_model.as_str())
+            .chain(self.openrouter_fallback_models.split(','))
+            .chain([
+                self.openrouter_compare_model.as_str(),
+                self.dictation_retranscribe_model.as_str(),
+            ])
+            .map(str::trim)
+            .filter(|model| !model.is_empty());
+        for model in openrouter_models {
+            if !model.contains('/') {
+                problems.push(ConfigProblem::new(
+                    ConfigProblemKind::UnknownModel,
+                    format!("Modelo OpenRouter desconhecido: {model} (use fornecedor/modelo)."),
+                ));
+            }
+        }
+        let openai_model = self.openai_realtime_model.trim();
+        if !openai_model.is_empty() && !SUPPORTED_OPENAI_REALTIME_MODELS.contains(&openai_model) {
+            problems.push(ConfigProblem::new(
+                ConfigProblemKind::UnknownModel,
+                format!("Modelo OpenAI desconhecido: {openai_model}."),
+            ));
+        }
+        if let Err(error) = parse_base_url(&self.openrouter_base_url) {
+            problems.push(ConfigProblem::new(ConfigProblemKind::InvalidValue, error));
+        }
+
+        let shortcuts = [
+            (ShortcutAction::Dictation, &self.dictation_shortcut),
+            (ShortcutAction::Restart, &self.dictation_restart_shortcut),
+            (ShortcutAction::Cancel, &self.dictation_cancel_shortcut),
+            (
+                ShortcutAction::Retranscribe,
+                &self.dictation_retranscribe_shortcut,
+            ),
+            (ShortcutAction::CopyLast, &self.dictation_copy_last_shortcut),
+            (ShortcutAction::Undo, &self.dictation_undo_shortcut),
+            (ShortcutAction::History, &self.dictation_history_shortcut),
+        ]
+        .into_iter()
+        .map(|(action, value)| (action.label().to_owned(), value))
+        .chain(
+            self.dictation_templates
+                .iter()
+                .map(|template| (format!("template {}", template.name), &template.shortcut)),
+        );
+        for (label, value) in shortcuts {
+            if let Err(error) = parse_dictation_shortcut(value) {
+                problems.push(ConfigProblem::new(
+                    ConfigProblemKind::InvalidShortcut,
+                    format!("Atalho de {label}: {error}"),
+                ));
+            }
+        }
+        if let Err(error) = parse_stop_key(&self.dictation_stop_key) {
+            problems.push(ConfigProblem::new(
+                ConfigProblemKind::InvalidShortcut,
+                format!("Tecla de parar: {error}"),
+            ));
+        }
+
+        let device = self.dictation_input_device.trim();
+        if !device.is_empty()
+            && !input_devices.is_empty()
+            && !input_devices.iter().any(|name| name == device)
+        {
+            problems.push(ConfigProblem::new(
+                ConfigProblemKind::MissingDevice,
+                format!("Microfone nao encontrado: {device}."),
+            ));
+        }
+
+        // Anything else saving would refuse, one at a time.
+        if problems.is_empty()
+            && let Err(error) = AppSettings::from_form(self)
+        {
+            problems.push(ConfigProblem::new(ConfigProblemKind::InvalidValue, error));
+        }
+
+        problems
+    }
+}
+
+impl From<&AppSettings> for SettingsForm {
+    fn from(settings: &AppSettings) -> Self {
+        Self {
+            openrouter_api_key: settings.openrouter_api_key.clone(),
+            api_keys_in_keyring: settings.api_keys_in_keyring,
+            openrouter_fallback_api_keys: settings.openrouter_fallback_api_keys.join(", "),
+            openai_realtime_api_key: settings.openai_realtime_api_key.clone(),
+            openrouter_model: settings.openrouter_model.clone(),
+            openrouter_fallback_models: settings.openrouter_fallback_models.join(", "),
+            openrouter_compare_model: settings.openrouter_compare_model.clone(),
+            openrouter_temperature: optional_value(settings.openrouter_temperature),
+            openrouter_top_p: optional_value(settings.openrouter_top_p),
+            openrouter_max_tokens: optional_value(settings.openrouter_max_tokens),
+            openrouter_referer: settings.openrouter_referer.clone(),
+            openrouter_app_title: settings.openrouter_app_title.clone(),
+            openrouter_base_url: settings.openrouter_base_url.clone(),
+            openrouter_privacy_mode: settings.openrouter_privacy_mode,
+            dictation_provider: settings.dictation_provider.clone(),
+            dictation_language: settings.dictation_language.clone(),
+            deepgram_api_key: settings.deepgram_api_key.clone(),
+            deepgram_model: settings.deepgram_model.clone(),
+            dictation_streaming: settings.dictation_streaming,
+            dictation_timestamps: settings.dictation_timestamps,
+            dictation_vocabulary: settings.dictation_vocabulary.join(", "),
+            assemblyai_api_key: settings.assemblyai_api_key.clone(),
+            openai_realtime_model: settings.openai_realtime_model.clone(),
+            openai_realtime_language: settings.openai_realtime_language.clone(),
+            openai_realtime_profile: settings.openai_realtime_profile.clone(),
+            copilot_model: settings.copilot_model.clone(),
+            copilot_default_mode: settings.copilot_default_mode.clone(),
+            copilot_auto_include_transcript: settings.copilot_auto_include_transcript,
+            copilot_save_history: settings.copilot_save_history,
+            dictation_vad_enabled: settings.dictation_vad_enabled,
+            dictation_silence_threshold_ms: settings.dictation_silence_threshold_ms.to_string(),
+            dictation_shortcut: settings.dictation_shortcut.clone(),
+            dictation_shortcut_mode: settings.dictation_shortcut_mode.clone(),
+            dictation_restart_shortcut: settings.dictation_restart_shortcut.clone(),
+            dictation_copy_last_shortcut: settings.dictation_copy_last_shortcut.clone(),
+            dictation_undo_shortcut: settings.dictation_undo_shortcut.clone(),
+            dictation_cancel_shortcut: settings.dictation_cancel_shortcut.clone(),
+            dictation_stop_key: settings.dictation_stop_key.clone(),
+            dictation_retranscribe_shortcut: settings.dictation_retranscribe_shortcut.clone(),
+            dictation_history_shortcut: settings.dictation_history_shortcut.clone(),
+            shortcuts_paused: settings.shortcuts_paused,
+            dictation_hold_threshold_ms: settings.dictation_hold_threshold_ms.to_string(),
+            dictation_resample_quality: settings.dictation_resample_quality.clone(),
+            dictation_target_sample_rate: settings.dictation_target_sample_rate.clone(),
+            dictation_normalize_audio: settings.dictation_normalize_audio,
+            dictation_audio_format: settings.dictation_audio_format.clone(),
+            dictation_pre_roll_enabled: settings.dictation_pre_roll_enabled,
+            dictation_max_duration_secs: settings.dictation_max_duration_secs.to_string(),
+            dictation_recordings_dir: settings.dictation_recordings_dir.clone(),
+            dictation_input_channel: optional_number(u32::from(settings.dictation_input_channel)),
+            dictation_input_device: settings.dictation_input_device.clone(),
+            dictation_capture_sample_rate: optional_number(settings.dictation_capture_sample_rate),
+            dictation_capture_buffer_frames: optional_number(
+                settings.dictation_capture_buffer_frames,
+            ),
+            dictation_sound_feedback: settings.dictation_sound_feedback,
+            dictation_capture_mode: settings.dictation_capture_mode.clone(),
+            dictation_idle_timeout_secs: optional_number(settings.dictation_idle_timeout_secs),
+            dictation_idle_action: settings.dictation_idle_action.clone(),
+            dictation_output_mode: settings.dictation_output_mode.clone(),
+            dictation_history_audio: settings.dictation_history_audio,
+            dictation_history_audio_max_mb: settings.dictation_history_audio_max_mb.to_string(),
+            dictation_history_audio_max_days: optional_number(
+                settings.dictation_history_audio_max_days,
+            ),
+            dictation_retranscribe_model: settings.dictation_retranscribe_model.clone(),
+            dictation_templates: settings.dictation_templates.clone(),
+            dictation_template: settings.dictation_template.clone(),
+            dictation_write_primary: settings.dictation_write_primary,
+            dictation_notifications: settings.dictation_notifications,
+            dictation_auto_submit_confirmed: settings.dictation_auto_submit_confirmed,
+            dictation_uri_template: settings.dictation_uri_template.clone(),
+            dictation_clipboard_restore_secs: optional_number(
+                settings.dictation_clipboard_restore_secs,
+            ),
+            dictation_hook_command: settings.dictation_hook_command.clone(),
+            dictation_hook_timeout_secs: settings.dictation_hook_timeout_secs.to_string(),
+            dictation_webhook_enabled: settings.dictation_webhook_enabled,
+            dictation_webhook_url: settings.dictation_webhook_url.clone(),
+            dictation_typing_delay_ms: settings.dictation_typing_delay_ms.to_string(),
+            dictation_show_window: settings.dictation_show_window,
+            hud_click_action: settings.hud_click_action.clone(),
+            hud_keep_open: settings.hud_keep_open,
+            hud_hide_delay_ms: settings.hud_hide_delay_ms.to_string(),
+            log_level: settings.log_level.clone(),
+            onboarding_completed: settings.onboarding_completed,
+            launch_at_login: settings.launch_at_login,
+            dictation_monthly_budget_usd: optional_amount(settings.dictation_monthly_budget_usd),
+            dictation_model_overrides: settings
+                .dictation_model_overrides
+                .iter()
+                .map(|tuning| ModelOverrideForm {
+                    model: tuning.model.clone(),
+                    prompt: tuning.prompt.clone(),
+                    temperature: tuning
+                        .temperature
+                        .map(|value| value.to_string())
+                        .unwrap_or_default(),
+                    language: tuning.language.clone(),
+                })
+                .collect(),
+            dictation_profiles: settings.dictation_profiles.clone(),
+            active_profile: settings.active_profile.clone(),
+        }
+    }
+}
+
+fn default_openai_realtime_profile() -> String {
+    String::from(DEFAULT_OPENAI_REALTIME_PROFILE)
+}
+
+fn normalize_openai_realtime_model(value: &str) -> String {
+    let trimmed = value.trim();
 
     if SUPPORTED_OPENAI_REALTIME_MODELS.contains(&trimmed) {
         trimmed.to_owned()
@@ -229,6 +2228,1352 @@ fn normalize_copilot_model(value: &str) -> String {
     }
 }
 
+fn normalize_deepgram_model(value: &str) -> String {
+    let trimmed = value.trim();
+
+    if trimmed.is_empty() {
+        String::from(DEFAULT_DEEPGRAM_MODEL)
+    } else {
+        trimmed.to_owned()
+    }
+}
+
 fn normalize_copilot_mode(value: &str) -> String {
     CopilotMode::from_code(value).code().to_owned()
 }
+
+fn parse_dictation_silence_threshold_ms(value: &str) -> Result<u32, String> {
+    let trimmed = value.trim();
+
+    if trimmed.is_empty() {
+        return Ok(DEFAULT_DICTATION_SILENCE_THRESHOLD_MS);
+    }
+
+    trimmed
+        .parse::<u32>()
+        .map(clamp_dictation_silence_threshold_ms)
+        .map_err(|_| {
+            String::from("O silencio do auto-stop precisa ser um numero inteiro em milissegundos.")
+        })
+}
+
+fn parse_dictation_hold_threshold_ms(value: &str) -> Result<u32, String> {
+    let trimmed = value.trim();
+
+    if trimmed.is_empty() {
+        return Ok(DEFAULT_DICTATION_HOLD_THRESHOLD_MS);
+    }
+
+    trimmed
+        .parse::<u32>()
+        .map(clamp_dictation_hold_threshold_ms)
+        .map_err(|_| {
+            String::from("O tempo para segurar o atalho precisa ser um numero em milissegundos.")
+        })
+}
+
+fn clamp_dictation_hold_threshold_ms(value: u32) -> u32 {
+    value.clamp(
+        MIN_DICTATION_HOLD_THRESHOLD_MS,
+        MAX_DICTATION_HOLD_THRESHOLD_MS,
+    )
+}
+
+fn parse_dictation_max_duration_secs(value: &str) -> Result<u32, String> {
+    let trimmed = value.trim();
+
+    if trimmed.is_empty() {
+        return Ok(DEFAULT_DICTATION_MAX_DURATION_SECS);
+    }
+
+    trimmed
+        .parse::<u32>()
+        .map(clamp_dictation_max_duration_secs)
+        .map_err(|_| String::from("A duracao maxima do ditado precisa ser um numero de segundos."))
+}
+
+fn clamp_dictation_max_duration_secs(value: u32) -> u32 {
+    value.clamp(
+        MIN_DICTATION_MAX_DURATION_SECS,
+        MAX_DICTATION_MAX_DURATION_SECS,
+    )
+}
+
+/// Empty or `0` turns the idle timeout off.
+fn parse_dictation_idle_timeout_secs(value: &str) -> Result<u32, String> {
+    let trimmed = value.trim();
+
+    if trimmed.is_empty() {
+        return Ok(0);
+    }
+
+    trimmed
+        .parse::<u32>()
+        .map(clamp_dictation_idle_timeout_secs)
+        .map_err(|_| String::from("O tempo sem fala precisa ser um numero de segundos."))
+}
+
+/// Empty means no pause between keystrokes.
+fn parse_dictation_typing_delay_ms(value: &str) -> Result<u32, String> {
+    let trimmed = value.trim();
+
+    if trimmed.is_empty() {
+        return Ok(0);
+    }
+
+    trimmed
+        .parse::<u32>()
+        .map(|value| value.min(MAX_DICTATION_TYPING_DELAY_MS))
+        .map_err(|_| String::from("O intervalo de digitacao precisa ser um numero de ms."))
+}
+
+/// Empty means OpenRouter itself. Anything else has to be an http(s) URL
+/// with a host; the trailing slash is dropped so endpoints can be appended.
+fn parse_base_url(value: &str) -> Result<String, String> {
+    let trimmed = value.trim().trim_end_matches('/');
+
+    if trimmed.is_empty() {
+        return Ok(String::from(DEFAULT_OPENROUTER_BASE_URL));
+    }
+
+    match reqwest::Url::parse(trimmed) {
+        Ok(url)
+            if matches!(url.scheme(), "http" | "https")
+                && url.host_str().is_some_and(|host| !host.is_empty()) =>
+        {
+            Ok(trimmed.to_owned())
+        }
+        _ => Err(format!(
+            "A URL base da API precisa ser http(s) com um host, ex.: {DEFAULT_OPENROUTER_BASE_URL}."
+        )),
+    }
+}
+
+/// Empty hides the HUD as soon as the take is delivered.
+fn parse_hud_hide_delay_ms(value: &str) -> Result<u32, String> {
+    let trimmed = value.trim();
+
+    if trimmed.is_empty() {
+        return Ok(0);
+    }
+
+    trimmed
+        .parse::<u32>()
+        .map(|value| value.min(MAX_HUD_HIDE_DELAY_MS))
+        .map_err(|_| String::from("O tempo para esconder o HUD precisa ser um numero de ms."))
+}
+
+/// Trims the templates, dropping rows left completely empty. Every kept
+/// template needs a unique name and the `{text}` placeholder.
+fn parse_templates(templates: &[TranscriptTemplate]) -> Result<Vec<TranscriptTemplate>, String> {
+    let mut parsed: Vec<TranscriptTemplate> = Vec::new();
+
+    for template in templates {
+        let name = template.name.trim();
+        let body = template.body.trim();
+        if name.is_empty() && body.is_empty() {
+            continue;
+        }
+        if name.is_empty() {
+            return Err(String::from("Todo template precisa de um nome."));
+        }
+        if !body.contains(TEMPLATE_PLACEHOLDER) {
+            return Err(format!(
+                "O template \"{name}\" precisa conter {TEMPLATE_PLACEHOLDER}."
+            ));
+        }
+        if parsed.iter().any(|template| template.name == name) {
+            return Err(format!("Ja existe um template chamado \"{name}\"."));
+        }
+        let shortcut = parse_dictation_shortcut(&template.shortcut)
+            .map_err(|error| format!("Template \"{name}\": {error}"))?;
+        parsed.push(TranscriptTemplate {
+            name: name.to_owned(),
+            body: body.to_owned(),
+            shortcut,
+        });
+    }
+
+    Ok(parsed)
+}
+
+fn parse_dictation_history_audio_max_mb(value: &str) -> Result<u32, String> {
+    let trimmed = value.trim();
+
+    if trimmed.is_empty() {
+        return Ok(DEFAULT_DICTATION_HISTORY_AUDIO_MAX_MB);
+    }
+
+    trimmed
+        .parse::<u32>()
+        .map(|value| value.clamp(1, MAX_DICTATION_HISTORY_AUDIO_MAX_MB))
+        .map_err(|_| String::from("O limite de audio do historico precisa ser um numero de MB."))
+}
+
+/// Empty means takes are only dropped by the size cap.
+fn parse_dictation_history_audio_max_days(value: &str) -> Result<u32, String> {
+    let trimmed = value.trim();
+
+    if trimmed.is_empty() {
+        return Ok(0);
+    }
+
+    trimmed
+        .parse::<u32>()
+        .map_err(|_| String::from("A idade maxima do audio precisa ser um numero de dias."))
+}
+
+/// Empty means the transcription stays on the clipboard.
+fn parse_dictation_clipboard_restore_secs(value: &str) -> Result<u32, String> {
+    let trimmed = value.trim();
+
+    if trimmed.is_empty() {
+        return Ok(0);
+    }
+
+    trimmed
+        .parse::<u32>()
+        .map(|value| value.min(MAX_DICTATION_CLIPBOARD_RESTORE_SECS))
+        .map_err(|_| {
+            String::from("O tempo para restaurar o clipboard precisa ser um numero de segundos.")
+        })
+}
+
+fn parse_dictation_hook_timeout_secs(value: &str) -> Result<u32, String> {
+    let trimmed = value.trim();
+
+    if trimmed.is_empty() {
+        return Ok(DEFAULT_DICTATION_HOOK_TIMEOUT_SECS);
+    }
+
+    trimmed
+        .parse::<u32>()
+        .map(|value| value.clamp(1, MAX_DICTATION_HOOK_TIMEOUT_SECS))
+        .map_err(|_| String::from("O tempo limite do comando precisa ser um numero de segundos."))
+}
+
+fn clamp_dictation_idle_timeout_secs(value: u32) -> u32 {
+    match value {
+        0 => 0,
+        value => value.clamp(
+            MIN_DICTATION_IDLE_TIMEOUT_SECS,
+            MAX_DICTATION_IDLE_TIMEOUT_SECS,
+        ),
+    }
+}
+
+fn parse_dictation_input_channel(value: &str) -> Result<u16, String> {
+    let trimmed = value.trim();
+
+    if trimmed.is_empty() {
+        return Ok(0);
+    }
+
+    trimmed
+        .parse::<u16>()
+        .map_err(|_| String::from("O canal de entrada precisa ser um numero (1 = primeiro canal)."))
+}
+
+/// Parses an optional numeric override where empty means "device default".
+fn parse_capture_override(
+    value: &str,
+    range: std::ops::RangeInclusive<u32>,
+    label: &str,
+    unit: &str,
+) -> Result<u32, String> {
+    let trimmed = value.trim();
+
+    if trimmed.is_empty() {
+        return Ok(0);
+    }
+
+    trimmed
+        .parse::<u32>()
+        .ok()
+        .filter(|value| range.contains(value))
+        .ok_or_else(|| {
+            format!(
+                "{label} precisa estar entre {} e {} {unit}, ou vazio para usar o padrao do dispositivo.",
+                range.start(),
+                range.end()
+            )
+        })
+}
+
+fn optional_number(value: u32) -> String {
+    match value {
+        0 => String::new(),
+        value => value.to_string(),
+    }
+}
+
+/// Comma- or line-separated entries, without blanks, repeats or `skip`.
+fn parse_list(value: &str, skip: &str) -> Vec<String> {
+    let mut entries: Vec<String> = Vec::new();
+
+    for entry in value.split([',', '\n']).map(str::trim) {
+        if !entry.is_empty() && entry != skip && !entries.iter().any(|seen| seen == entry) {
+            entries.push(entry.to_owned());
+        }
+    }
+
+    entries
+}
+
+fn optional_value<T: ToString>(value: Option<T>) -> String {
+    value.map(|value| value.to_string()).unwrap_or_default()
+}
+
+/// Empty keeps the model default; accepts `,` as decimal mark.
+fn parse_model_overrides(overrides: &[ModelOverrideForm]) -> Result<Vec<ModelOverride>, String> {
+    let mut parsed: Vec<ModelOverride> = Vec::new();
+
+    for tuning in overrides {
+        let model = tuning.model.trim();
+        if model.is_empty() {
+            if tuning.prompt.trim().is_empty()
+                && tuning.temperature.trim().is_empty()
+                && tuning.language.trim().is_empty()
+            {
+                continue;
+            }
+            return Err(String::from(
+                "Todo ajuste por modelo precisa do nome do modelo.",
+            ));
+        }
+        if parsed.iter().any(|existing| existing.model == model) {
+            return Err(format!("O modelo \"{model}\" ja tem ajustes."));
+        }
+        let temperature = parse_sampling_param(
+            &tuning.temperature,
+            OPENROUTER_TEMPERATURE_RANGE,
+            &format!("A temperatura de {model}"),
+        )?;
+        parsed.push(ModelOverride {
+            model: model.to_owned(),
+            prompt: tuning.prompt.trim().to_owned(),
+            temperature,
+            language: tuning.language.trim().to_owned(),
+        });
+    }
+
+    Ok(parsed)
+}
+
+fn parse_sampling_param(
+    value: &str,
+    range: std::ops::RangeInclusive<f32>,
+    label: &str,
+) -> Result<Option<f32>, String> {
+    let trimmed = value.trim();
+
+    if trimmed.is_empty() {
+        return Ok(None);
+    }
+
+    trimmed
+        .replace(',', ".")
+        .parse::<f32>()
+        .ok()
+        .filter(|value| range.contains(value))
+        .map(Some)
+        .ok_or_else(|| {
+            format!(
+                "{label} precisa estar entre {} e {}, ou vazio para usar o padrao do modelo.",
+                range.start(),
+                range.end()
+            )
+        })
+}
+
+fn parse_openrouter_max_tokens(value: &str) -> Result<Option<u32>, String> {
+    let trimmed = value.trim();
+
+    if trimmed.is_empty() {
+        return Ok(None);
+    }
+
+    trimmed
+        .parse::<u32>()
+        .ok()
+        .filter(|value| *value > 0)
+        .map(Some)
+        .ok_or_else(|| {
+            String::from(
+                "O limite de tokens precisa ser um numero positivo, ou vazio para usar o padrao do modelo.",
+            )
+        })
+}
+
+fn optional_amount(value: f64) -> String {
+    if value > 0.0 {
+        value.to_string()
+    } else {
+        String::new()
+    }
+}
+
+/// Empty or `0` turns the budget warning off; accepts `,` as decimal mark.
+fn parse_dictation_monthly_budget_usd(value: &str) -> Result<f64, String> {
+    let trimmed = value.trim();
+
+    if trimmed.is_empty() {
+        return Ok(0.0);
+    }
+
+    trimmed
+        .replace(',', ".")
+        .parse::<f64>()
+        .ok()
+        .filter(|amount| amount.is_finite() && *amount >= 0.0)
+        .ok_or_else(|| String::from("O orcamento mensal precisa ser um valor em dolares."))
+}
+
+fn parse_dictation_shortcut(value: &str) -> Result<String, String> {
+    if value.trim().is_empty() {
+        return Ok(String::new());
+    }
+
+    Shortcut::parse(value).map(|shortcut| shortcut.label())
+}
+
+/// The stop key is matched on its own, so it can't carry modifiers.
+fn parse_stop_key(value: &str) -> Result<String, String> {
+    let label = parse_dictation_shortcut(value)?;
+    match Shortcut::parse(&label) {
+        Ok(key) if !key.modifiers.is_empty() || key.then.is_some() => Err(String::from(
+            "A tecla de parar precisa ser uma tecla so, sem modificadores.",
+        )),
+        _ => Ok(label),
+    }
+}
+
+/// A plain shortcut on the leader of a sequence would swallow it.
+fn check_sequence_leaders(shortcuts: &[&str]) -> Result<(), String> {
+    let parsed = shortcuts
+        .iter()
+        .filter_map(|value| Shortcut::parse(value).ok())
+        .collect::<Vec<_>>();
+
+    for sequence in parsed.iter().filter(|shortcut| shortcut.then.is_some()) {
+        let leader = sequence.leader();
+        if parsed.contains(&leader) {
+            return Err(format!(
+                "{} ja inicia a sequencia {}; escolha outra combinacao.",
+                leader.label(),
+                sequence.label()
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+fn normalize_shortcut_mode(value: &str) -> String {
+    ShortcutMode::from_code(value).code().to_owned()
+}
+
+fn normalize_resample_quality(value: &str) -> String {
+    ResampleQuality::from_code(value).code().to_owned()
+}
+
+fn normalize_target_sample_rate(value: &str) -> String {
+    TargetSampleRate::from_code(value).code().to_owned()
+}
+
+fn normalize_idle_action(value: &str) -> String {
+    IdleStopAction::from_code(value).code().to_owned()
+}
+
+fn normalize_hud_click_action(value: &str) -> String {
+    HudClickAction::from_code(value).code().to_owned()
+}
+
+fn normalize_output_mode(value: &str) -> String {
+    DictationOutputMode::from_code(value).code().to_owned()
+}
+
+fn normalize_capture_mode(value: &str) -> String {
+    DictationCaptureMode::from_code(value).code().to_owned()
+}
+
+fn normalize_audio_format(value: &str) -> String {
+    AudioFormat::from_code(value).code().to_owned()
+}
+
+fn clamp_dictation_silence_threshold_ms(value: u32) -> u32 {
+    value.clamp(
+        MIN_DICTATION_SILENCE_THRESHOLD_MS,
+        MAX_DICTATION_SILENCE_THRESHOLD_MS,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        AppSettings, ConfigProblemKind, DEFAULT_DICTATION_HOOK_TIMEOUT_SECS,
+        DEFAULT_DICTATION_SHORTCUT, DEFAULT_DICTATION_SILENCE_THRESHOLD_MS,
+        DEFAULT_DICTATION_STOP_KEY, DEFAULT_HUD_CLICK_ACTION, DEFAULT_OPENROUTER_BASE_URL,
+        DEFAULT_OPENROUTER_MODEL, DictationModelChoice, HudClickAction,
+        MAX_DICTATION_MAX_DURATION_SECS, MAX_DICTATION_TYPING_DELAY_MS, MAX_HUD_HIDE_DELAY_MS,
+        MIN_DICTATION_HOLD_THRESHOLD_MS, MIN_DICTATION_IDLE_TIMEOUT_SECS,
+        MIN_DICTATION_SILENCE_THRESHOLD_MS, ModelOverrideForm, OnboardingStep, ResetScope,
+        SettingsForm,
+    };
+    use crate::modules::audio::domain::CaptureOverrides;
+    use crate::modules::dictation::domain::{
+        DictationOutputMode, DictationProvider, HistoryAudioRetention, TranscriptTemplate,
+    };
+    use crate::modules::shortcuts::domain::{Shortcut, ShortcutAction, ShortcutMode};
+
+    fn valid_form() -> SettingsForm {
+        let mut form = SettingsForm::from(&AppSettings::default());
+        form.openrouter_api_key = String::from("sk-or-test");
+        form
+    }
+
+    #[test]
+    fn builds_settings_from_form() {
+        let mut form = valid_form();
+        form.dictation_vad_enabled = true;
+        form.dictation_silence_threshold_ms = String::from(" 2000 ");
+
+        let settings = AppSettings::from_form(&form).expect("settings");
+
+        assert!(settings.dictation_vad_enabled);
+        assert_eq!(settings.dictation_silence_threshold_ms, 2_000);
+    }
+
+    #[test]
+    fn clamps_silence_threshold_and_defaults_when_empty() {
+        let mut form = valid_form();
+        form.dictation_silence_threshold_ms = String::from("10");
+        let clamped = AppSettings::from_form(&form).expect("settings");
+
+        form.dictation_silence_threshold_ms = String::new();
+        let defaulted = AppSettings::from_form(&form).expect("settings");
+
+        assert_eq!(
+            clamped.dictation_silence_threshold_ms,
+            MIN_DICTATION_SILENCE_THRESHOLD_MS
+        );
+        assert_eq!(
+            defaulted.dictation_silence_threshold_ms,
+            DEFAULT_DICTATION_SILENCE_THRESHOLD_MS
+        );
+    }
+
+    #[test]
+    fn normalizes_dictation_shortcut_and_mode() {
+        let mut form = valid_form();
+        form.dictation_shortcut = String::from("ctrl+alt+space");
+        form.dictation_shortcut_mode = String::from("hold");
+
+        let settings = AppSettings::from_form(&form).expect("settings");
+
+        assert_eq!(settings.dictation_shortcut, "Ctrl+Alt+Space");
+        assert_eq!(settings.dictation_shortcut_mode(), ShortcutMode::Hold);
+
+        form.dictation_shortcut = String::from("  ");
+        let disabled = AppSettings::from_form(&form).expect("settings");
+
+        assert!(disabled.dictation_shortcut().is_none());
+
+        form.dictation_shortcut = String::from("Super+Shift");
+        assert!(AppSettings::from_form(&form).is_err());
+
+        form.dictation_shortcut = String::from("double-tap right shift");
+        assert!(AppSettings::from_form(&form).is_err());
+
+        form.dictation_shortcut_mode = String::from("toggle");
+        let double_tap = AppSettings::from_form(&form).expect("settings");
+        assert_eq!(double_tap.dictation_shortcut, "DoubleTap+RightShift");
+    }
+
+    #[test]
+    fn rejects_a_shortcut_on_the_leader_of_a_sequence() {
+        let mut form = valid_form();
+        form.dictation_history_shortcut = String::from("ctrl+shift+v then h");
+        form.dictation_undo_shortcut = String::from("ctrl+shift+v then u");
+        let settings = AppSettings::from_form(&form).expect("settings");
+
+        assert_eq!(settings.dictation_history_shortcut, "Ctrl+Shift+V then H");
+
+        form.dictation_copy_last_shortcut = String::from("ctrl+shift+v");
+        assert!(AppSettings::from_form(&form).is_err());
+
+        form.dictation_copy_last_shortcut = String::new();
+        form.dictation_cancel_shortcut = String::from("ctrl+shift+v then c");
+        assert!(AppSettings::from_form(&form).is_err());
+    }
+
+    #[test]
+    fn parses_the_stop_key() {
+        let mut form = valid_form();
+        assert_eq!(form.dictation_stop_key, DEFAULT_DICTATION_STOP_KEY);
+
+        form.dictation_stop_key = String::from("f12");
+        let custom = AppSettings::from_form(&form).expect("settings");
+
+        form.dictation_stop_key = String::new();
+        let disabled = AppSettings::from_form(&form).expect("settings");
+
+        assert_eq!(custom.dictation_stop_key, "F12");
+        assert!(disabled.dictation_stop_key().is_none());
+
+        form.dictation_stop_key = String::from("ctrl+escape");
+        assert!(AppSettings::from_form(&form).is_err());
+    }
+
+    #[test]
+    fn rejects_cancel_shortcut_equal_to_another_shortcut() {
+        let mut form = valid_form();
+        form.dictation_cancel_shortcut = String::from("ctrl+escape");
+        let settings = AppSettings::from_form(&form).expect("settings");
+
+        assert_eq!(settings.dictation_cancel_shortcut, "Ctrl+Escape");
+
+        form.dictation_cancel_shortcut = form.dictation_shortcut.clone();
+        assert!(AppSettings::from_form(&form).is_err());
+    }
+
+    #[test]
+    fn binds_the_retranscribe_shortcut() {
+        let mut form = valid_form();
+        form.dictation_retranscribe_shortcut = String::from("super+shift+r");
+        let settings = AppSettings::from_form(&form).expect("settings");
+
+        assert!(settings.shortcut_binds().contains(&(
+            Shortcut::parse("Super+Shift+R").expect("shortcut"),
+            ShortcutAction::Retranscribe
+        )));
+
+        form.dictation_copy_last_shortcut = String::from("Super+Shift+R");
+        assert!(AppSettings::from_form(&form).is_err());
+    }
+
+    #[test]
+    fn rejects_history_shortcut_equal_to_another_shortcut() {
+        let mut form = valid_form();
+        form.dictation_history_shortcut = String::from("super+h");
+        let settings = AppSettings::from_form(&form).expect("settings");
+
+        assert_eq!(settings.dictation_history_shortcut, "Super+H");
+
+        form.dictation_history_shortcut = form.dictation_shortcut.clone();
+        assert!(AppSettings::from_form(&form).is_err());
+    }
+
+    #[test]
+    fn clamps_hold_threshold() {
+        let mut form = valid_form();
+        form.dictation_shortcut_mode = String::from("tap-or-hold");
+        form.dictation_hold_threshold_ms = String::from("20");
+        let settings = AppSettings::from_form(&form).expect("settings");
+
+        assert_eq!(settings.dictation_shortcut_mode(), ShortcutMode::TapOrHold);
+        assert_eq!(
+            settings.dictation_hold_threshold_ms,
+            MIN_DICTATION_HOLD_THRESHOLD_MS
+        );
+
+        form.dictation_hold_threshold_ms = String::from("meio segundo");
+        assert!(AppSettings::from_form(&form).is_err());
+    }
+
+    #[test]
+    fn rejects_restart_shortcut_equal_to_dictation_shortcut() {
+        let mut form = valid_form();
+        form.dictation_restart_shortcut = String::from("super+shift+r");
+        let settings = AppSettings::from_form(&form).expect("settings");
+
+        assert_eq!(settings.dictation_restart_shortcut, "Super+Shift+R");
+
+        form.dictation_restart_shortcut = form.dictation_shortcut.clone();
+        assert!(AppSettings::from_form(&form).is_err());
+    }
+
+    #[test]
+    fn rejects_copy_last_shortcut_equal_to_another_shortcut() {
+        let mut form = valid_form();
+        form.dictation_restart_shortcut = String::from("Super+Shift+R");
+        form.dictation_copy_last_shortcut = String::from("super+shift+v");
+        let settings = AppSettings::from_form(&form).expect("settings");
+
+        assert_eq!(settings.dictation_copy_last_shortcut, "Super+Shift+V");
+
+        form.dictation_copy_last_shortcut = form.dictation_restart_shortcut.clone();
+        assert!(AppSettings::from_form(&form).is_err());
+    }
+
+    #[test]
+    fn rejects_undo_shortcut_equal_to_another_shortcut() {
+        let mut form = valid_form();
+        form.dictation_copy_last_shortcut = String::from("Super+Shift+V");
+        form.dictation_undo_shortcut = String::from("super+shift+z");
+        let settings = AppSettings::from_form(&form).expect("settings");
+
+        assert_eq!(settings.dictation_undo_shortcut, "Super+Shift+Z");
+
+        form.dictation_undo_shortcut = form.dictation_copy_last_shortcut.clone();
+        assert!(AppSettings::from_form(&form).is_err());
+    }
+
+    #[test]
+    fn clamps_max_duration() {
+        let mut form = valid_form();
+        form.dictation_max_duration_secs = String::from("99999");
+        let clamped = AppSettings::from_form(&form).expect("settings");
+
+        form.dictation_max_duration_secs = String::from("1m");
+
+        assert_eq!(
+            clamped.dictation_max_duration_secs,
+            MAX_DICTATION_MAX_DURATION_SECS
+        );
+        assert!(AppSettings::from_form(&form).is_err());
+    }
+
+    #[test]
+    fn parses_idle_timeout() {
+        let mut form = valid_form();
+        form.dictation_idle_timeout_secs = String::from("3");
+        let clamped = AppSettings::from_form(&form).expect("settings");
+
+        form.dictation_idle_timeout_secs = String::new();
+        let disabled = AppSettings::from_form(&form).expect("settings");
+
+        assert_eq!(
+            clamped.dictation_idle_timeout_secs,
+            MIN_DICTATION_IDLE_TIMEOUT_SECS
+        );
+        assert_eq!(disabled.dictation_idle_timeout(), None);
+        assert_eq!(
+            SettingsForm::from(&disabled).dictation_idle_timeout_secs,
+            ""
+        );
+    }
+
+    #[test]
+    fn normalizes_hud_click_action() {
+        let mut form = valid_form();
+        form.hud_click_action = String::from(" toggle ");
+        let toggle = AppSettings::from_form(&form).expect("settings");
+
+        form.hud_click_action = String::from("tray");
+        let fallback = AppSettings::from_form(&form).expect("settings");
+
+        assert_eq!(toggle.hud_click_action, "toggle");
+        assert_eq!(toggle.hud_click_action(), HudClickAction::ToggleDictation);
+        assert_eq!(fallback.hud_click_action(), HudClickAction::OpenSettings);
+        assert_eq!(
+            AppSettings::default().hud_click_action,
+            DEFAULT_HUD_CLICK_ACTION
+        );
+    }
+
+    #[test]
+    fn parses_typing_output_mode() {
+        let mut form = valid_form();
+        form.dictation_output_mode = String::from("type");
+        form.dictation_typing_delay_ms = String::from("9000");
+        let settings = AppSettings::from_form(&form).expect("settings");
+
+        assert_eq!(settings.dictation_output_mode(), DictationOutputMode::Type);
+        assert_eq!(
+            settings.dictation_typing_delay_ms,
+            MAX_DICTATION_TYPING_DELAY_MS
+        );
+
+        form.dictation_typing_delay_ms = String::from("fast");
+        assert!(AppSettings::from_form(&form).is_err());
+    }
+
+    #[test]
+    fn resets_only_the_requested_scope() {
+        let mut settings = AppSettings::from_form(&valid_form()).expect("settings");
+        settings.dictation_shortcut = String::from("Ctrl+Alt+X");
+        settings.dictation_input_device = String::from("USB Mic");
+        settings.openrouter_model = String::from("vendor/other-model");
+        settings.onboarding_completed = true;
+
+        let shortcuts = settings.reset(ResetScope::Shortcuts);
+        assert_eq!(shortcuts.dictation_shortcut, DEFAULT_DICTATION_SHORTCUT);
+        assert_eq!(shortcuts.dictation_input_device, "USB Mic");
+
+        let audio = settings.reset(ResetScope::Audio);
+        assert!(audio.dictation_input_device.is_empty());
+        assert_eq!(audio.openrouter_model, "vendor/other-model");
+
+        let providers = settings.reset(ResetScope::Providers);
+        assert_eq!(providers.openrouter_model, DEFAULT_OPENROUTER_MODEL);
+        assert_eq!(providers.dictation_shortcut, "Ctrl+Alt+X");
+
+        let all = settings.reset(ResetScope::All);
+        assert_eq!(all.dictation_shortcut, DEFAULT_DICTATION_SHORTCUT);
+        assert_eq!(all.openrouter_api_key, "sk-or-test");
+        assert!(all.onboarding_completed);
+        assert_eq!(ResetScope::from_code(" Audio "), Some(ResetScope::Audio));
+        assert_eq!(ResetScope::from_code("tray"), None);
+    }
+
+    #[test]
+    fn hides_the_hud_only_when_not_kept_open() {
+        let mut form = valid_form();
+        assert_eq!(
+            AppSettings::from_form(&form)
+                .expect("settings")
+                .hud_hide_delay(),
+            None
+        );
+
+        form.hud_keep_open = false;
+        form.hud_hide_delay_ms = String::from("999999");
+        let settings = AppSettings::from_form(&form).expect("settings");
+        assert_eq!(
+            settings.hud_hide_delay(),
+            Some(std::time::Duration::from_millis(u64::from(
+                MAX_HUD_HIDE_DELAY_MS
+            )))
+        );
+
+        form.hud_hide_delay_ms = String::from("soon");
+        assert!(AppSettings::from_form(&form).is_err());
+    }
+
+    #[test]
+    fn validates_the_api_base_url() {
+        let mut form = valid_form();
+        form.openrouter_base_url = String::from("  ");
+        assert_eq!(
+            AppSettings::from_form(&form)
+                .expect("settings")
+                .openrouter_base_url,
+            DEFAULT_OPENROUTER_BASE_URL
+        );
+
+        form.openrouter_base_url = String::from("http://localhost:4000/v1/");
+        assert_eq!(
+            AppSettings::from_form(&form)
+                .expect("settings")
+                .openrouter_base_url,
+            "http://localhost:4000/v1"
+        );
+
+        for invalid in ["localhost:4000", "ftp://gateway/v1", "https://"] {
+            form.openrouter_base_url = String::from(invalid);
+            assert!(AppSettings::from_form(&form).is_err(), "{invalid}");
+            assert_eq!(
+                form.validate(&[])
+                    .iter()
+                    .map(|problem| problem.kind)
+                    .collect::<Vec<_>>(),
+                [ConfigProblemKind::InvalidValue]
+            );
+        }
+    }
+
+    #[test]
+    fn validates_transcript_templates() {
+        let template = |name: &str, body: &str| TranscriptTemplate {
+            name: name.to_owned(),
+            body: body.to_owned(),
+            shortcut: String::new(),
+        };
+        let mut form = valid_form();
+        form.dictation_templates = vec![
+            template(" Tarefa ", "- [ ] {text}"),
+            template("", " "),
+            template("Citacao", "> {text}"),
+        ];
+        form.dictation_template = String::from("Tarefa");
+        let settings = AppSettings::from_form(&form).expect("settings");
+
+        assert_eq!(settings.dictation_templates.len(), 2);
+        assert_eq!(
+            settings
+                .dictation_template()
+                .map(|template| template.apply("ligar")),
+            Some(String::from("- [ ] ligar"))
+        );
+
+        form.dictation_templates[2] = template("Tarefa", "{text}");
+        assert!(AppSettings::from_form(&form).is_err());
+
+        form.dictation_templates[2] = template("Sem texto", "assinatura");
+        assert!(AppSettings::from_form(&form).is_err());
+
+        form.dictation_templates.truncate(1);
+        form.dictation_template = String::from("Citacao");
+        assert!(AppSettings::from_form(&form).is_err());
+    }
+
+    #[test]
+    fn binds_template_shortcuts_after_the_fixed_ones() {
+        let mut form = valid_form();
+        form.dictation_shortcut = String::from("Ctrl+Shift+V");
+        form.dictation_templates = vec![TranscriptTemplate {
+            name: String::from("Email"),
+            body: String::from("{text}\\n\\n-- "),
+            shortcut: String::from("ctrl+shift+e"),
+        }];
+        let settings = AppSettings::from_form(&form).expect("settings");
+
+        assert_eq!(settings.dictation_templates[0].shortcut, "Ctrl+Shift+E");
+        assert_eq!(
+            settings
+                .shortcut_binds()
+                .into_iter()
+                .map(|(shortcut, action)| (shortcut.label(), action))
+                .collect::<Vec<_>>(),
+            vec![
+                (String::from("Ctrl+Shift+V"), ShortcutAction::Dictation),
+                (String::from("Ctrl+Shift+E"), ShortcutAction::Template(0)),
+            ]
+        );
+
+        form.dictation_templates[0].shortcut = String::from("Ctrl+Shift+V");
+        assert!(AppSettings::from_form(&form).is_err());
+
+        form.dictation_templates[0].shortcut = String::from("Ctrl+Shift");
+        assert!(AppSettings::from_form(&form).is_err());
+    }
+
+    #[test]
+    fn parses_history_audio_retention() {
+        let mut form = valid_form();
+        let settings = AppSettings::from_form(&form).expect("settings");
+        assert_eq!(settings.dictation_history_audio_retention(), None);
+
+        form.dictation_history_audio = true;
+        form.dictation_history_audio_max_mb = String::from("50");
+        form.dictation_history_audio_max_days = String::new();
+        let settings = AppSettings::from_form(&form).expect("settings");
+        assert_eq!(
+            settings.dictation_history_audio_retention(),
+            Some(HistoryAudioRetention {
+                max_bytes: 50 * 1_024 * 1_024,
+                max_age: None,
+            })
+        );
+
+        form.dictation_history_audio_max_days = String::from("a month");
+        assert!(AppSettings::from_form(&form).is_err());
+    }
+
+    #[test]
+    fn parses_clipboard_restore_delay() {
+        let mut form = valid_form();
+        let settings = AppSettings::from_form(&form).expect("settings");
+        assert_eq!(settings.dictation_clipboard_restore_delay(), None);
+
+        form.dictation_clipboard_restore_secs = String::from("8");
+        let settings = AppSettings::from_form(&form).expect("settings");
+        assert_eq!(
+            settings.dictation_clipboard_restore_delay(),
+            Some(std::time::Duration::from_secs(8))
+        );
+        assert_eq!(
+            SettingsForm::from(&settings).dictation_clipboard_restore_secs,
+            "8"
+        );
+
+        form.dictation_clipboard_restore_secs = String::from("later");
+        assert!(AppSettings::from_form(&form).is_err());
+    }
+
+    #[test]
+    fn parses_post_transcription_hook() {
+        let mut form = valid_form();
+        let settings = AppSettings::from_form(&form).expect("settings");
+        assert_eq!(settings.dictation_hook_command(), None);
+        assert_eq!(
+            settings.dictation_hook_timeout_secs,
+            DEFAULT_DICTATION_HOOK_TIMEOUT_SECS
+        );
+
+        form.dictation_hook_command = String::from(" task add \"$OPENVOICE_TEXT\" ");
+        form.dictation_hook_timeout_secs = String::from("0");
+        let settings = AppSettings::from_form(&form).expect("settings");
+        assert_eq!(
+            settings.dictation_hook_command(),
+            Some("task add \"$OPENVOICE_TEXT\"")
+        );
+        assert_eq!(settings.dictation_hook_timeout_secs, 1);
+
+        form.dictation_hook_timeout_secs = String::from("soon");
+        assert!(AppSettings::from_form(&form).is_err());
+    }
+
+    #[test]
+    fn validates_uri_template_only_in_uri_mode() {
+        let mut form = valid_form();
+        form.dictation_uri_template = String::from("no placeholder");
+        assert!(AppSettings::from_form(&form).is_ok());
+
+        form.dictation_output_mode = String::from("uri");
+        assert!(AppSettings::from_form(&form).is_err());
+
+        form.dictation_uri_template = String::from(" obsidian://new?content={text} ");
+        let settings = AppSettings::from_form(&form).expect("settings");
+        assert_eq!(settings.dictation_output_mode(), DictationOutputMode::Uri);
+        assert_eq!(
+            settings.dictation_uri_template,
+            "obsidian://new?content={text}"
+        );
+    }
+
+    #[test]
+    fn requires_confirmation_for_auto_submit() {
+        let mut form = valid_form();
+        form.dictation_output_mode = String::from("submit");
+        assert!(AppSettings::from_form(&form).is_err());
+
+        form.dictation_auto_submit_confirmed = true;
+        let settings = AppSettings::from_form(&form).expect("settings");
+        assert_eq!(
+            settings.dictation_output_mode(),
+            DictationOutputMode::Submit
+        );
+    }
+
+    #[test]
+    fn validates_webhook_url_only_when_enabled() {
+        let mut form = valid_form();
+        form.dictation_webhook_url = String::from("n8n.local/hook");
+        let disabled = AppSettings::from_form(&form).expect("settings");
+        assert_eq!(disabled.dictation_webhook_url(), None);
+
+        form.dictation_webhook_enabled = true;
+        assert!(AppSettings::from_form(&form).is_err());
+
+        form.dictation_webhook_url = String::from(" https://n8n.local/hook ");
+        let enabled = AppSettings::from_form(&form).expect("settings");
+        assert_eq!(
+            enabled.dictation_webhook_url(),
+            Some("https://n8n.local/hook")
+        );
+    }
+
+    #[test]
+    fn parses_fallback_model_chain() {
+        let mut form = valid_form();
+        form.openrouter_fallback_models = format!(
+            " openai/gpt-4o-mini-audio-preview,\n{DEFAULT_OPENROUTER_MODEL}, , openai/gpt-4o-mini-audio-preview"
+        );
+        let settings = AppSettings::from_form(&form).expect("settings");
+
+        assert_eq!(
+            settings.openrouter_fallback_models,
+            vec![String::from("openai/gpt-4o-mini-audio-preview")]
+        );
+        assert_eq!(
+            SettingsForm::from(&settings).openrouter_fallback_models,
+            "openai/gpt-4o-mini-audio-preview"
+        );
+    }
+
+    #[test]
+    fn switches_between_configured_models() {
+        let mut form = valid_form();
+        form.openrouter_fallback_models = String::from("openai/gpt-4o-mini-audio-preview");
+        let mut settings = AppSettings::from_form(&form).expect("settings");
+        let fallback = DictationModelChoice {
+            provider: DictationProvider::OpenRouter,
+            model: String::from("openai/gpt-4o-mini-audio-preview"),
+        };
+
+        // Only OpenRouter has a key in the valid form.
+        assert_eq!(settings.dictation_model_choices().len(), 2);
+        assert_eq!(
+            fallback.to_string(),
+            "OpenRouter: openai/gpt-4o-mini-audio-preview"
+        );
+
+        settings.select_dictation_model(&fallback);
+
+        assert_eq!(settings.active_dictation_model(), fallback);
+        assert_eq!(
+            settings.openrouter_fallback_models,
+            vec![String::from(DEFAULT_OPENROUTER_MODEL)]
+        );
+
+        settings.deepgram_api_key = String::from("dg-test");
+        let deepgram = settings
+            .dictation_model_choices()
+            .into_iter()
+            .find(|choice| choice.provider == DictationProvider::Deepgram)
+            .expect("deepgram choice");
+        settings.select_dictation_model(&deepgram);
+
+        assert_eq!(settings.dictation_provider(), DictationProvider::Deepgram);
+        assert_eq!(settings.openrouter_model, fallback.model);
+    }
+
+    #[test]
+    fn parses_optional_sampling_params() {
+        let mut form = valid_form();
+        form.openrouter_temperature = String::from("0");
+        form.openrouter_top_p = String::from("0,9");
+        form.openrouter_max_tokens = String::from("512");
+        let settings = AppSettings::from_form(&form).expect("settings");
+
+        assert_eq!(settings.openrouter_temperature, Some(0.0));
+        assert_eq!(settings.openrouter_top_p, Some(0.9));
+        assert_eq!(settings.openrouter_max_tokens, Some(512));
+        assert_eq!(SettingsForm::from(&settings).openrouter_top_p, "0.9");
+
+        form.openrouter_temperature = String::new();
+        assert_eq!(
+            AppSettings::from_form(&form)
+                .expect("settings")
+                .openrouter_temperature,
+            None
+        );
+
+        form.openrouter_temperature = String::from("3");
+        assert!(AppSettings::from_form(&form).is_err());
+        form.openrouter_temperature = String::new();
+        form.openrouter_max_tokens = String::from("0");
+        assert!(AppSettings::from_form(&form).is_err());
+    }
+
+    #[test]
+    fn parses_monthly_budget() {
+        let mut form = valid_form();
+        form.dictation_monthly_budget_usd = String::from("2,50");
+        let budget = AppSettings::from_form(&form).expect("settings");
+
+        assert_eq!(budget.dictation_monthly_budget(), Some(2.5));
+        assert_eq!(
+            SettingsForm::from(&budget).dictation_monthly_budget_usd,
+            "2.5"
+        );
+
+        form.dictation_monthly_budget_usd = String::from("-1");
+        assert!(AppSettings::from_form(&form).is_err());
+    }
+
+    #[test]
+    fn maps_input_channel_to_zero_based_index() {
+        let mut form = valid_form();
+        let averaged = AppSettings::from_form(&form).expect("settings");
+
+        form.dictation_input_channel = String::from("2");
+        let second = AppSettings::from_form(&form).expect("settings");
+
+        assert_eq!(averaged.dictation_input_channel(), None);
+        assert_eq!(second.dictation_input_channel(), Some(1));
+        assert_eq!(SettingsForm::from(&second).dictation_input_channel, "2");
+    }
+
+    #[test]
+    fn parses_capture_overrides() {
+        let mut form = valid_form();
+        let defaults = AppSettings::from_form(&form).expect("settings");
+
+        form.dictation_input_device = String::from(" USB Mic ");
+        form.dictation_capture_sample_rate = String::from("48000");
+        form.dictation_capture_buffer_frames = String::from("512");
+        let custom = AppSettings::from_form(&form).expect("settings");
+
+        form.dictation_capture_sample_rate = String::from("4000");
+        let rejected = AppSettings::from_form(&form);
+
+        assert_eq!(
+            defaults.dictation_capture_overrides(),
+            CaptureOverrides::default()
+        );
+        assert_eq!(
+            custom.dictation_capture_overrides(),
+            CaptureOverrides {
+                device: Some(String::from("USB Mic")),
+                sample_rate: Some(48_000),
+                buffer_frames: Some(512),
+            }
+        );
+        assert!(rejected.is_err());
+    }
+
+    #[test]
+    fn rejects_non_numeric_silence_threshold() {
+        let mut form = valid_form();
+        form.dictation_silence_threshold_ms = String::from("abc");
+
+        assert!(AppSettings::from_form(&form).is_err());
+    }
+
+    #[test]
+    fn switches_between_profiles() {
+        let mut settings = AppSettings::from_form(&valid_form()).expect("settings");
+        settings.deepgram_api_key = String::from("dg-test");
+        settings.dictation_language = String::from("pt-BR");
+        settings.save_profile(" Trabalho ").expect("saved");
+
+        settings.dictation_provider = String::from("deepgram");
+        settings.dictation_language = String::from("en");
+        settings.dictation_shortcut = String::from("Super+Shift+C");
+        settings.save_profile("Codigo").expect("saved");
+        assert_eq!(settings.active_profile, "Codigo");
+        assert!(settings.save_profile("  ").is_err());
+
+        let work = settings.with_profile("Trabalho").expect("activated");
+        assert_eq!(work.active_profile, "Trabalho");
+        assert_eq!(work.dictation_provider(), DictationProvider::OpenRouter);
+        assert_eq!(work.openrouter_model, DEFAULT_OPENROUTER_MODEL);
+        assert_eq!(work.dictation_language, "pt-BR");
+        assert_eq!(work.dictation_shortcut, "Super+Shift+D");
+        assert_eq!(work.dictation_profiles.len(), 2);
+        assert!(settings.with_profile("Pessoal").is_err());
+
+        let mut work = work;
+        work.delete_profile("Trabalho");
+        assert!(work.active_profile.is_empty());
+        assert_eq!(work.dictation_profiles.len(), 1);
+    }
+
+    #[test]
+    fn exports_without_api_keys_and_keeps_them_on_import() {
+        let mut current = AppSettings::from_form(&valid_form()).expect("settings");
+        current.deepgram_api_key = String::from("dg-test");
+        current.openrouter_fallback_api_keys = vec![String::from("sk-or-team")];
+
+        let mut exported = current.clone().without_api_keys();
+        assert!(exported.openrouter_api_key.is_empty());
+        assert!(exported.openrouter_fallback_api_keys.is_empty());
+        assert!(exported.deepgram_api_key.is_empty());
+
+        exported.deepgram_api_key = String::from("dg-other");
+        exported.keep_api_keys_from(&current);
+        assert_eq!(exported.openrouter_api_key, "sk-or-test");
+        assert_eq!(exported.openrouter_fallback_api_keys, ["sk-or-team"]);
+        assert_eq!(exported.deepgram_api_key, "dg-other");
+    }
+
+    #[test]
+    fn applies_env_overrides_to_the_dictation_provider() {
+        let mut settings = AppSettings {
+            dictation_provider: String::from("deepgram"),
+            ..AppSettings::default()
+        };
+        let env = |name: &str| match name {
+            "OPENVOICE_API_KEY" => Some(String::from("dg-env")),
+            "OPENVOICE_MODEL" => Some(String::from("nova-2")),
+            "OPENVOICE_SHORTCUT" => Some(String::from("super+alt+v")),
+            _ => None,
+        };
+        settings.apply_env_overrides(env).expect("applied");
+
+        assert_eq!(settings.deepgram_api_key, "dg-env");
+        assert_eq!(settings.deepgram_model, "nova-2");
+        assert_eq!(settings.dictation_shortcut, "Super+Alt+V");
+        assert!(settings.openrouter_api_key.is_empty());
+
+        let invalid = |name: &str| (name == "OPENVOICE_SHORTCUT").then(|| String::from("Super+"));
+        assert!(settings.apply_env_overrides(invalid).is_err());
+        assert_eq!(settings.dictation_shortcut, "Super+Alt+V");
+    }
+
+    #[test]
+    fn applies_the_overrides_of_the_active_model() {
+        let mut form = valid_form();
+        form.openrouter_temperature = String::from("0.2");
+        form.dictation_language = String::from("pt-BR");
+        form.dictation_model_overrides = vec![ModelOverrideForm {
+            model: String::from(DEFAULT_OPENROUTER_MODEL),
+            prompt: String::from(" Keep code identifiers in English. "),
+            temperature: String::from("0,7"),
+            language: String::from("en"),
+        }];
+        let settings = AppSettings::from_form(&form).expect("settings");
+
+        assert_eq!(settings.dictation_temperature(), Some(0.7));
+        assert_eq!(settings.dictation_language().as_deref(), Some("en"));
+        assert_eq!(
+            settings.dictation_prompt_extra(),
+            Some("Keep code identifiers in English.")
+        );
+
+        let other = AppSettings {
+            openrouter_model: String::from("openai/gpt-4o-audio-preview"),
+            ..settings
+        };
+        assert_eq!(other.dictation_temperature(), Some(0.2));
+        assert_eq!(other.dictation_language().as_deref(), Some("pt-BR"));
+        assert_eq!(other.dictation_prompt_extra(), None);
+
+        form.dictation_model_overrides[0].temperature = String::from("3");
+        assert!(AppSettings::from_form(&form).is_err());
+        form.dictation_model_overrides[0].model.clear();
+        assert!(AppSettings::from_form(&form).is_err());
+    }
+
+    #[test]
+    fn lists_every_config_problem() {
+        let mut form = SettingsForm::from(&AppSettings::default());
+        form.openrouter_fallback_models = String::from("google/gemini-2.5-flash, gemini");
+        form.openai_realtime_model = String::from("gpt-9-transcribe");
+        form.dictation_undo_shortcut = String::from("Ctrl+");
+        form.dictation_input_device = String::from("USB Mic");
+        let devices = [String::from("Built-in")];
+
+        let kinds: Vec<ConfigProblemKind> = form
+            .validate(&devices)
+            .into_iter()
+            .map(|problem| problem.kind)
+            .collect();
+        assert_eq!(
+            kinds,
+            [
+                ConfigProblemKind::MissingKey,
+                ConfigProblemKind::UnknownModel,
+                ConfigProblemKind::UnknownModel,
+                ConfigProblemKind::InvalidShortcut,
+                ConfigProblemKind::MissingDevice,
+            ]
+        );
+        assert!(form.validate(&[]).len() == 4);
+
+        let mut form = valid_form();
+        assert!(form.validate(&[]).is_empty());
+        form.dictation_hook_timeout_secs = String::from("abc");
+        assert_eq!(
+            form.validate(&[])
+                .iter()
+                .map(|problem| problem.kind)
+                .collect::<Vec<_>>(),
+            [ConfigProblemKind::InvalidValue]
+        );
+    }
+
+    #[test]
+    fn walks_through_onboarding() {
+        let devices = [String::from("Built-in")];
+        let mut settings = AppSettings::default();
+        assert_eq!(settings.onboarding_state(&[]).pending, OnboardingStep::ALL);
+        assert!(
+            settings
+                .complete_onboarding_step(OnboardingStep::Shortcut, &devices)
+                .is_err()
+        );
+
+        settings.openrouter_api_key = String::from("sk-or-test");
+        assert!(
+            settings
+                .complete_onboarding_step(OnboardingStep::ApiKey, &devices)
+                .is_ok()
+        );
+        assert!(
+            settings
+                .complete_onboarding_step(OnboardingStep::Shortcut, &[])
+                .is_err()
+        );
+        settings
+            .complete_onboarding_step(OnboardingStep::Shortcut, &devices)
+            .expect("done");
+        assert!(settings.onboarding_state(&[]).is_complete());
+
+        let upgraded: AppSettings = serde_json::from_str("{}").expect("settings");
+        assert!(upgraded.onboarding_completed);
+    }
+}
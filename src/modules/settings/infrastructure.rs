@@ -1,6 +1,11 @@
 use crate::modules::settings::domain::AppSettings;
+use keyring::Entry;
+use keyring::credential::CredentialPersistence;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+const KEYRING_SERVICE: &str = "openvoice";
 
 pub fn load_settings() -> Result<AppSettings, String> {
     let path = settings_path()?;
@@ -9,8 +14,89 @@ pub fn load_settings() -> Result<AppSettings, String> {
         return Ok(AppSettings::default());
     }
 
+    let mut settings = read_settings_file(&path)?;
+    let keys_in_keyring = settings.api_keys_in_keyring;
+
+    if keyring_persists() {
+        // A key on the wrong side of the flag, e.g. one written by an older
+        // version or left in the keyring after opting out, is moved over.
+        let mut misplaced = false;
+        let mut fallbacks = settings.openrouter_fallback_api_keys.join("\n");
+        for (account, value) in api_keys(&mut settings, &mut fallbacks) {
+            if !value.is_empty() {
+                misplaced |= keys_in_keyring;
+                continue;
+            }
+            match read_keyring(account) {
+                Ok(Some(key)) => {
+                    *value = key;
+                    misplaced |= !keys_in_keyring;
+                }
+                Ok(None) => {}
+                Err(error) => log_warn!("[openvoice][settings] {error}"),
+            }
+        }
+        settings.openrouter_fallback_api_keys = fallbacks.lines().map(str::to_owned).collect();
+
+        if misplaced {
+            match save_settings(&settings) {
+                Ok(()) => log_info!("[openvoice][settings] API keys moved to their store"),
+                Err(error) => log_error!("[openvoice][settings] API key migration failed: {error}"),
+            }
+        }
+    }
+
+    Ok(settings)
+}
+
+pub fn save_settings(settings: &AppSettings) -> Result<(), String> {
+    let path = settings_path()?;
+    let stored = if keyring_persists() {
+        stored_settings(settings, write_keyring)
+    } else {
+        settings.clone()
+    };
+
+    write_settings_file(&path, &stored)
+}
+
+/// `settings` as written to the file. With `api_keys_in_keyring` on, every
+/// key `store` accepts is left out; with it off, `store` clears the keyring
+/// copies and the keys stay in the file.
+fn stored_settings(
+    settings: &AppSettings,
+    mut store: impl FnMut(&str, &str) -> Result<(), String>,
+) -> AppSettings {
+    let mut stored = settings.clone();
+    let mut fallbacks = stored.openrouter_fallback_api_keys.join("\n");
+
+    if settings.api_keys_in_keyring {
+        for (account, value) in api_keys(&mut stored, &mut fallbacks) {
+            // A key the keyring refused stays in the file rather than lost.
+            match store(account, value) {
+                Ok(()) => value.clear(),
+                Err(error) => {
+                    log_warn!("[openvoice][settings] keeping {account} in the file: {error}")
+                }
+            }
+        }
+    } else {
+        for (account, _) in api_keys(&mut stored, &mut fallbacks) {
+            if let Err(error) = store(account, "") {
+                log_warn!("[openvoice][settings] {error}");
+            }
+        }
+    }
+    stored.openrouter_fallback_api_keys = fallbacks.lines().map(str::to_owned).collect();
+
+    stored
+}
+
+/// Settings stored at `path`, as they are in the file: keys kept in the
+/// keyring are not filled in.
+pub fn read_settings_file(path: &Path) -> Result<AppSettings, String> {
     let contents =
-        fs::read_to_string(&path).map_err(|error| format!("Falha ao ler settings: {error}"))?;
+        fs::read_to_string(path).map_err(|error| format!("Falha ao ler settings: {error}"))?;
 
     serde_json::from_str::<AppSettings>(&contents)
         .map(AppSettings::normalized)
@@ -22,9 +108,7 @@ pub fn load_settings() -> Result<AppSettings, String> {
         })
 }
 
-pub fn save_settings(settings: &AppSettings) -> Result<(), String> {
-    let path = settings_path()?;
-
+pub fn write_settings_file(path: &Path, settings: &AppSettings) -> Result<(), String> {
     if let Some(parent) = path.parent() {
         fs::create_dir_all(parent)
             .map_err(|error| format!("Falha ao criar pasta de settings: {error}"))?;
@@ -33,10 +117,23 @@ pub fn save_settings(settings: &AppSettings) -> Result<(), String> {
     let contents = serde_json::to_string_pretty(settings)
         .map_err(|error| format!("Falha ao serializar settings: {error}"))?;
 
-    fs::write(&path, contents)
+    fs::write(path, contents)
         .map_err(|error| format!("Falha ao salvar settings em {}: {error}", path.display()))
 }
 
+/// When settings.json was last written, by the app or by hand.
+pub fn settings_modified_at() -> Option<SystemTime> {
+    fs::metadata(settings_path().ok()?).ok()?.modified().ok()
+}
+
+/// Value of an `OPENVOICE_*` override; unset and empty are the same.
+pub fn env_override(name: &str) -> Option<String> {
+    std::env::var(name)
+        .ok()
+        .map(|value| value.trim().to_owned())
+        .filter(|value| !value.is_empty())
+}
+
 fn settings_path() -> Result<PathBuf, String> {
     let base = std::env::var_os("XDG_CONFIG_HOME")
         .map(PathBuf::from)
@@ -47,3 +144,116 @@ fn settings_path() -> Result<PathBuf, String> {
 
     Ok(base.join("openvoice").join("settings.json"))
 }
+
+/// API keys kept in the keyring when `api_keys_in_keyring` is on, by
+/// keyring account. The fallback OpenRouter keys share one entry, one key
+/// per line, passed in as `fallbacks`.
+fn api_keys<'a>(
+    settings: &'a mut AppSettings,
+    fallbacks: &'a mut String,
+) -> [(&'static str, &'a mut String); 5] {
+    [
+        ("openrouter-api-key", &mut settings.openrouter_api_key),
+        ("openrouter-fallback-api-keys", fallbacks),
+        ("openai-api-key", &mut settings.openai_realtime_api_key),
+        ("deepgram-api-key", &mut settings.deepgram_api_key),
+        ("assemblyai-api-key", &mut settings.assemblyai_api_key),
+    ]
+}
+
+/// False only on platforms without a native keyring backend, where keyring
+/// falls back to an in-memory store whose keys would be gone on the next
+/// launch.
+fn keyring_persists() -> bool {
+    matches!(
+        keyring::default::default_credential_builder().persistence(),
+        CredentialPersistence::UntilDelete
+    )
+}
+
+fn read_keyring(account: &str) -> Result<Option<String>, String> {
+    match keyring_entry(account)?.get_password() {
+        Ok(value) => Ok(Some(value)),
+        Err(keyring::Error::NoEntry) => Ok(None),
+        Err(error) => Err(format!("Falha ao ler {account} do keyring: {error}")),
+    }
+}
+
+/// Stores `value` under `account`; an empty value removes the entry.
+fn write_keyring(account: &str, value: &str) -> Result<(), String> {
+    let entry = keyring_entry(account)?;
+    let result = if value.is_empty() {
+        match entry.delete_credential() {
+            Err(keyring::Error::NoEntry) => Ok(()),
+            result => result,
+        }
+    } else {
+        entry.set_password(value)
+    };
+
+    result.map_err(|error| format!("Falha ao salvar {account} no keyring: {error}"))
+}
+
+fn keyring_entry(account: &str) -> Result<Entry, String> {
+    Entry::new(KEYRING_SERVICE, account)
+        .map_err(|error| format!("Falha ao preparar o keyring do OpenVoice: {error}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{keyring_persists, read_settings_file, stored_settings, write_settings_file};
+    use crate::modules::settings::domain::AppSettings;
+    use std::collections::HashMap;
+
+    #[test]
+    fn writes_the_file_without_keys_kept_in_the_keyring() {
+        assert!(
+            keyring_persists(),
+            "a persistent keyring backend is built in"
+        );
+
+        let settings = AppSettings {
+            api_keys_in_keyring: true,
+            openrouter_api_key: String::from("sk-or-main"),
+            openrouter_fallback_api_keys: vec![String::from("sk-or-a"), String::from("sk-or-b")],
+            deepgram_api_key: String::from("dg-key"),
+            ..AppSettings::default()
+        };
+        let mut keyring = HashMap::new();
+        let stored = stored_settings(&settings, |account, value| {
+            if account == "deepgram-api-key" {
+                return Err(String::from("refused"));
+            }
+            keyring.insert(account.to_owned(), value.to_owned());
+            Ok(())
+        });
+
+        let path = std::env::temp_dir()
+            .join(format!("openvoice-settings-{}", std::process::id()))
+            .join("settings.json");
+        write_settings_file(&path, &stored).expect("write");
+        let contents = std::fs::read_to_string(&path).expect("read");
+        let reloaded = read_settings_file(&path).expect("reload");
+        let _ = std::fs::remove_dir_all(path.parent().expect("dir"));
+
+        assert!(!contents.contains("sk-or-"));
+        assert!(reloaded.openrouter_api_key.is_empty());
+        assert!(contents.contains("dg-key"));
+        assert_eq!(keyring["openrouter-api-key"], "sk-or-main");
+        assert_eq!(keyring["openrouter-fallback-api-keys"], "sk-or-a\nsk-or-b");
+        assert!(stored.openrouter_fallback_api_keys.is_empty());
+
+        let off = AppSettings {
+            api_keys_in_keyring: false,
+            ..settings
+        };
+        let mut cleared = Vec::new();
+        let stored = stored_settings(&off, |account, value| {
+            cleared.push((account.to_owned(), value.to_owned()));
+            Ok(())
+        });
+        assert_eq!(stored.openrouter_api_key, "sk-or-main");
+        assert!(cleared.iter().all(|(_, value)| value.is_empty()));
+        assert_eq!(cleared.len(), 5);
+    }
+}
@@ -0,0 +1,302 @@
+use crate::modules::settings::domain::AppSettings;
+use crate::modules::shortcuts::domain::{
+    Shortcut, ShortcutAction, ShortcutError, ShortcutEvent, ShortcutMode, ShortcutTrigger,
+};
+use crate::modules::shortcuts::infrastructure::{self, SharedReceiver};
+use crate::platform::hyprland;
+
+const TRIGGER_FLAG: &str = "--trigger";
+
+/// Global shortcuts currently bound in the compositor.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ShortcutRegistration {
+    /// Mode of the dictation shortcut; the others always act on press.
+    pub mode: ShortcutMode,
+    /// Every bound shortcut and the action it triggers, the dictation one
+    /// first.
+    pub binds: Vec<(Shortcut, ShortcutAction)>,
+}
+
+impl ShortcutRegistration {
+    pub fn shortcut_for(&self, action: ShortcutAction) -> Option<&Shortcut> {
+        self.binds
+            .iter()
+            .find(|(_, bound)| *bound == action)
+            .map(|(shortcut, _)| shortcut)
+    }
+
+    /// Second keys of every sequence sharing the leader of `action`'s
+    /// shortcut, with the action each one triggers.
+    pub fn chord_follows(&self, action: ShortcutAction) -> Vec<(Shortcut, ShortcutAction)> {
+        let Some(leader) = self.shortcut_for(action).map(Shortcut::leader) else {
+            return Vec::new();
+        };
+
+        self.binds
+            .iter()
+            .filter(|(shortcut, _)| shortcut.leader() == leader)
+            .filter_map(|(shortcut, action)| {
+                shortcut
+                    .then
+                    .as_deref()
+                    .map(|follow| (follow.clone(), *action))
+            })
+            .collect()
+    }
+
+    /// Binds kept for the whole run; cancel would otherwise swallow a key
+    /// like Escape in every other app.
+    fn permanent_binds(&self) -> impl Iterator<Item = &(Shortcut, ShortcutAction)> {
+        self.binds
+            .iter()
+            .filter(|(_, action)| *action != ShortcutAction::Cancel)
+    }
+}
+
+/// Binds every shortcut configured in settings: dictation, the recovery
+/// actions and one per transcript template. The cancel shortcut is only
+/// checked for conflicts here; [`swap_cancel_shortcut`] binds it while a
+/// take is running. Returns `None` when none is
+/// set or the session has no supported compositor; the `--trigger` CLI
+/// keeps working in both cases.
+pub fn setup_global_shortcuts(
+    settings: &AppSettings,
+) -> Result<Option<ShortcutRegistration>, ShortcutError> {
+    if settings.shortcuts_paused {
+        log_info!("[openvoice][shortcuts] shortcuts paused; nothing bound");
+        return Ok(None);
+    }
+    if !settings.onboarding_completed {
+        log_info!("[openvoice][shortcuts] onboarding pending; nothing bound");
+        return Ok(None);
+    }
+
+    let binds = settings.shortcut_binds();
+    if binds.is_empty() {
+        return Ok(None);
+    }
+
+    if !hyprland::is_hyprland_session() {
+        log_warn!(
+            "[openvoice][shortcuts] global binds require Hyprland; use `{TRIGGER_FLAG} dictation` from your compositor instead"
+        );
+        return Ok(None);
+    }
+
+    // Check every combination before binding any, so a conflict never
+    // leaves half of the shortcuts registered. A sequence's second key is
+    // bound only briefly, but binding it would still replace the other bind.
+    for (shortcut, action) in &binds {
+        let steps = std::iter::once(shortcut).chain(shortcut.then.as_deref());
+        for step in steps {
+            if let Some(owner) = infrastructure::find_conflict(step) {
+                return Err(ShortcutError::Conflict {
+                    shortcut: shortcut.clone(),
+                    action: *action,
+                    owner,
+                });
+            }
+        }
+    }
+
+    let registration = ShortcutRegistration {
+        mode: settings.dictation_shortcut_mode(),
+        binds,
+    };
+    bind_all(&registration).map_err(ShortcutError::Failed)?;
+
+    Ok(Some(registration))
+}
+
+fn bind_all(registration: &ShortcutRegistration) -> Result<(), String> {
+    for (shortcut, action) in registration.permanent_binds() {
+        // Sequences sharing a leader bind it once per action; whichever
+        // wins, the app finds every follow-up key from it.
+        if shortcut.then.is_some() {
+            let leader = ShortcutTrigger::new(*action, ShortcutEvent::Leader);
+            infrastructure::bind_shortcut(shortcut, leader, None)?;
+            log_debug!(
+                "[openvoice][shortcuts] bound {} to {}",
+                shortcut.label(),
+                action.code()
+            );
+            continue;
+        }
+
+        let pressed = ShortcutTrigger::new(*action, ShortcutEvent::Pressed);
+        let released = (*action == ShortcutAction::Dictation && registration.mode.uses_release())
+            .then(|| ShortcutTrigger::new(*action, ShortcutEvent::Released));
+
+        infrastructure::bind_shortcut(shortcut, pressed, released)?;
+        log_debug!(
+            "[openvoice][shortcuts] bound {} to {}",
+            shortcut.label(),
+            action.code()
+        );
+    }
+
+    Ok(())
+}
+
+/// Replaces the current registration with the one described by settings.
+pub fn update_shortcut(
+    current: Option<ShortcutRegistration>,
+    settings: &AppSettings,
+) -> Result<Option<ShortcutRegistration>, ShortcutError> {
+    if let Some(current) = current {
+        release_shortcut(&current);
+    }
+
+    setup_global_shortcuts(settings)
+}
+
+pub fn release_shortcut(registration: &ShortcutRegistration) {
+    for (shortcut, _) in registration.permanent_binds() {
+        if let Err(error) = infrastructure::unbind_shortcut(shortcut) {
+            log_error!("[openvoice][shortcuts] failed to unbind: {error}");
+        }
+    }
+}
+
+/// Unbinds the cancel shortcut bound for the previous take, if any, and
+/// binds `next` in its place.
+pub fn swap_cancel_shortcut(
+    previous: Option<Shortcut>,
+    next: Option<Shortcut>,
+) -> Result<(), String> {
+    if let Some(previous) = &previous {
+        infrastructure::unbind_shortcut(previous)?;
+    }
+    if let Some(next) = &next {
+        let trigger = ShortcutTrigger::new(ShortcutAction::Cancel, ShortcutEvent::Pressed);
+        infrastructure::bind_shortcut(next, trigger, None)?;
+    }
+
+    Ok(())
+}
+
+/// Unbinds the second keys armed by the previous leader and binds `next`
+/// in their place; an empty `next` ends the chord.
+pub fn swap_chord(
+    previous: Vec<Shortcut>,
+    next: Vec<(Shortcut, ShortcutAction)>,
+) -> Result<(), String> {
+    for shortcut in &previous {
+        infrastructure::unbind_shortcut(shortcut)?;
+    }
+    for (shortcut, action) in &next {
+        let trigger = ShortcutTrigger::new(*action, ShortcutEvent::Pressed);
+        infrastructure::bind_shortcut(shortcut, trigger, None)?;
+    }
+
+    Ok(())
+}
+
+pub fn listen_for_triggers() -> Result<SharedReceiver, String> {
+    infrastructure::listen_for_triggers()
+}
+
+/// Blocks until the next trigger arrives; `None` once the listener closes.
+pub fn poll_next_trigger(receiver: SharedReceiver) -> Option<ShortcutTrigger> {
+    receiver.lock().ok()?.recv().ok()
+}
+
+/// Handles `openvoice --trigger <action> [pressed|released]` by forwarding
+/// the trigger to the running instance. Returns `None` for a normal launch.
+pub fn forward_cli_trigger(args: &[String]) -> Option<Result<(), String>> {
+    let trigger = parse_cli_trigger(args)?;
+
+    Some(trigger.and_then(infrastructure::send_trigger))
+}
+
+fn parse_cli_trigger(args: &[String]) -> Option<Result<ShortcutTrigger, String>> {
+    let position = args.iter().position(|arg| arg == TRIGGER_FLAG)?;
+    let value = args[position + 1..].join(" ");
+
+    Some(ShortcutTrigger::decode(&value).ok_or_else(|| {
+        format!(
+            "Uso: openvoice {TRIGGER_FLAG} <dictation|monitor|restart|cancel|retranscribe|copy-last|flush-pending|session|undo|history|pause-shortcuts|template-<n>> [pressed|released]"
+        )
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ShortcutRegistration, parse_cli_trigger, setup_global_shortcuts};
+    use crate::modules::settings::domain::AppSettings;
+    use crate::modules::shortcuts::domain::{Shortcut, ShortcutMode};
+    use crate::modules::shortcuts::domain::{ShortcutAction, ShortcutEvent, ShortcutTrigger};
+
+    fn args(values: &[&str]) -> Vec<String> {
+        values.iter().map(|value| (*value).to_owned()).collect()
+    }
+
+    #[test]
+    fn parses_cli_trigger() {
+        assert_eq!(
+            parse_cli_trigger(&args(&["--trigger", "dictation", "released"])),
+            Some(Ok(ShortcutTrigger::new(
+                ShortcutAction::Dictation,
+                ShortcutEvent::Released
+            )))
+        );
+        assert!(parse_cli_trigger(&args(&[])).is_none());
+        assert!(matches!(
+            parse_cli_trigger(&args(&["--trigger"])),
+            Some(Err(_))
+        ));
+    }
+
+    #[test]
+    fn finds_every_follow_up_of_a_leader() {
+        let shortcut = |value: &str| Shortcut::parse(value).expect("shortcut");
+        let registration = ShortcutRegistration {
+            mode: ShortcutMode::Toggle,
+            binds: vec![
+                (shortcut("Super+D"), ShortcutAction::Dictation),
+                (shortcut("Ctrl+Shift+V then T"), ShortcutAction::Template(0)),
+                (shortcut("Ctrl+Shift+V then H"), ShortcutAction::History),
+                (shortcut("Ctrl+Alt+V then U"), ShortcutAction::Undo),
+            ],
+        };
+
+        assert_eq!(
+            registration.chord_follows(ShortcutAction::History),
+            vec![
+                (shortcut("T"), ShortcutAction::Template(0)),
+                (shortcut("H"), ShortcutAction::History),
+            ]
+        );
+        assert!(
+            registration
+                .chord_follows(ShortcutAction::Dictation)
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn binds_nothing_while_paused() {
+        let settings = AppSettings {
+            shortcuts_paused: true,
+            onboarding_completed: true,
+            ..AppSettings::default()
+        };
+
+        assert_eq!(setup_global_shortcuts(&settings), Ok(None));
+        assert_eq!(
+            setup_global_shortcuts(&AppSettings {
+                shortcuts_paused: false,
+                onboarding_completed: false,
+                ..settings
+            }),
+            Ok(None)
+        );
+        assert_eq!(
+            parse_cli_trigger(&args(&["--trigger", "pause-shortcuts"])),
+            Some(Ok(ShortcutTrigger::new(
+                ShortcutAction::PauseShortcuts,
+                ShortcutEvent::Pressed
+            )))
+        );
+    }
+}
@@ -0,0 +1,867 @@
+use std::time::{Duration, Instant};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ShortcutMode {
+    Toggle,
+    Hold,
+    /// A quick tap toggles; holding past the threshold records until release.
+    TapOrHold,
+}
+
+impl ShortcutMode {
+    pub fn code(self) -> &'static str {
+        match self {
+            Self::Toggle => "toggle",
+            Self::Hold => "hold",
+            Self::TapOrHold => "tap-or-hold",
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Toggle => "Toggle",
+            Self::Hold => "Segurar para gravar",
+            Self::TapOrHold => "Tocar alterna, segurar grava",
+        }
+    }
+
+    pub fn from_code(value: &str) -> Self {
+        match value.trim() {
+            "hold" => Self::Hold,
+            "tap-or-hold" => Self::TapOrHold,
+            _ => Self::Toggle,
+        }
+    }
+
+    /// Whether the release edge has to be bound at all.
+    pub fn uses_release(self) -> bool {
+        matches!(self, Self::Hold | Self::TapOrHold)
+    }
+
+    /// Whether releasing the shortcut after holding it for `held` ends the
+    /// take; a tap shorter than `hold_threshold` leaves it recording.
+    pub fn stops_on_release(self, held: Duration, hold_threshold: Duration) -> bool {
+        match self {
+            Self::Toggle => false,
+            Self::Hold => true,
+            Self::TapOrHold => held >= hold_threshold,
+        }
+    }
+}
+
+impl std::fmt::Display for ShortcutMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.label())
+    }
+}
+
+const TEMPLATE_ACTION_PREFIX: &str = "template-";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ShortcutAction {
+    Dictation,
+    /// Toggles hearing the microphone through the output while recording.
+    Monitor,
+    /// Discards the current take and starts recording again.
+    Restart,
+    /// Discards the take being recorded, or abandons the transcription in
+    /// flight.
+    Cancel,
+    /// Sends the last take again with the re-transcription model.
+    Retranscribe,
+    /// Puts the last transcription back on the clipboard.
+    CopyLast,
+    /// Retries a transcription the clipboard refused earlier.
+    FlushPending,
+    /// Starts a dictation session, or ends it and copies the document.
+    Session,
+    /// Reverts what the last dictation wrote to the clipboard or typed.
+    Undo,
+    /// Opens the history tab with its search focused.
+    History,
+    /// Unbinds every global shortcut, or binds them again. Only reachable
+    /// through `--trigger`, since a paused shortcut could not resume itself.
+    PauseShortcuts,
+    /// Dictation delivered through the transcript template at this index.
+    Template(usize),
+}
+
+impl ShortcutAction {
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Dictation => "ditado",
+            Self::Monitor => "monitor",
+            Self::Restart => "regravar",
+            Self::Cancel => "cancelar",
+            Self::Retranscribe => "retranscrever",
+            Self::CopyLast => "copiar ultimo",
+            Self::FlushPending => "copiar pendente",
+            Self::Session => "sessao",
+            Self::Undo => "desfazer",
+            Self::History => "historico",
+            Self::PauseShortcuts => "pausar atalhos",
+            Self::Template(_) => "ditado com template",
+        }
+    }
+
+    pub fn code(self) -> String {
+        let code = match self {
+            Self::Template(index) => return format!("{TEMPLATE_ACTION_PREFIX}{index}"),
+            Self::Dictation => "dictation",
+            Self::Monitor => "monitor",
+            Self::Restart => "restart",
+            Self::Cancel => "cancel",
+            Self::Retranscribe => "retranscribe",
+            Self::CopyLast => "copy-last",
+            Self::FlushPending => "flush-pending",
+            Self::Session => "session",
+            Self::Undo => "undo",
+            Self::History => "history",
+            Self::PauseShortcuts => "pause-shortcuts",
+        };
+
+        code.to_owned()
+    }
+
+    pub fn from_code(value: &str) -> Option<Self> {
+        let value = value.trim();
+        if let Some(index) = value.strip_prefix(TEMPLATE_ACTION_PREFIX) {
+            return index.parse().ok().map(Self::Template);
+        }
+
+        match value {
+            "dictation" => Some(Self::Dictation),
+            "monitor" => Some(Self::Monitor),
+            "restart" => Some(Self::Restart),
+            "cancel" => Some(Self::Cancel),
+            "retranscribe" => Some(Self::Retranscribe),
+            "copy-last" => Some(Self::CopyLast),
+            "flush-pending" => Some(Self::FlushPending),
+            "session" => Some(Self::Session),
+            "undo" => Some(Self::Undo),
+            "history" => Some(Self::History),
+            "pause-shortcuts" => Some(Self::PauseShortcuts),
+            _ => None,
+        }
+    }
+}
+
+/// Why the global shortcuts could not be bound.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ShortcutError {
+    /// Another bind in the compositor already owns the combination.
+    Conflict {
+        shortcut: Shortcut,
+        action: ShortcutAction,
+        /// What the existing bind runs, as the compositor reports it.
+        owner: String,
+    },
+    Failed(String),
+}
+
+impl std::fmt::Display for ShortcutError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Conflict {
+                shortcut,
+                action,
+                owner,
+            } => write!(
+                f,
+                "{} ja esta em uso por outro bind ({owner}); escolha outra combinacao para {}.",
+                shortcut.label(),
+                action.label()
+            ),
+            Self::Failed(error) => f.write_str(error),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ShortcutEvent {
+    Pressed,
+    Released,
+    /// A modifier went up with no other key in between; two of them close
+    /// together make a double-tap.
+    Tap,
+    /// The first step of a sequence fired; its second key is bound for
+    /// [`CHORD_WINDOW`].
+    Leader,
+}
+
+impl ShortcutEvent {
+    pub fn code(self) -> &'static str {
+        match self {
+            Self::Pressed => "pressed",
+            Self::Released => "released",
+            Self::Tap => "tap",
+            Self::Leader => "leader",
+        }
+    }
+
+    pub fn from_code(value: &str) -> Option<Self> {
+        match value.trim() {
+            "pressed" => Some(Self::Pressed),
+            "released" => Some(Self::Released),
+            "tap" => Some(Self::Tap),
+            "leader" => Some(Self::Leader),
+            _ => None,
+        }
+    }
+}
+
+/// Turns modifier taps into a press when the same action is tapped twice
+/// within [`DOUBLE_TAP_WINDOW`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DoubleTapDetector {
+    last: Option<(ShortcutAction, Instant)>,
+}
+
+pub const DOUBLE_TAP_WINDOW: Duration = Duration::from_millis(400);
+
+impl DoubleTapDetector {
+    /// Records a tap; true when it completes a double-tap.
+    pub fn tap(&mut self, action: ShortcutAction, now: Instant) -> bool {
+        match self.last {
+            Some((previous, at))
+                if previous == action && now.duration_since(at) <= DOUBLE_TAP_WINDOW =>
+            {
+                self.last = None;
+                true
+            }
+            _ => {
+                self.last = Some((action, now));
+                false
+            }
+        }
+    }
+}
+
+/// How long the second key of a sequence stays bound after its leader.
+pub const CHORD_WINDOW: Duration = Duration::from_millis(1_500);
+
+/// A single shortcut activation forwarded to the running app, e.g. the
+/// dictation shortcut going down (`pressed`) or up (`released`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ShortcutTrigger {
+    pub action: ShortcutAction,
+    pub event: ShortcutEvent,
+}
+
+impl ShortcutTrigger {
+    pub fn new(action: ShortcutAction, event: ShortcutEvent) -> Self {
+        Self { action, event }
+    }
+
+    pub fn encode(self) -> String {
+        format!("{} {}", self.action.code(), self.event.code())
+    }
+
+    pub fn decode(value: &str) -> Option<Self> {
+        let mut parts = value.split_whitespace();
+        let action = ShortcutAction::from_code(parts.next()?)?;
+        let event = parts
+            .next()
+            .map_or(Some(ShortcutEvent::Pressed), ShortcutEvent::from_code)?;
+
+        if parts.next().is_some() {
+            return None;
+        }
+
+        Some(Self { action, event })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ShortcutModifier {
+    Super,
+    Ctrl,
+    Alt,
+    Shift,
+}
+
+impl ShortcutModifier {
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Super => "Super",
+            Self::Ctrl => "Ctrl",
+            Self::Alt => "Alt",
+            Self::Shift => "Shift",
+        }
+    }
+
+    fn from_label(value: &str) -> Option<Self> {
+        match value.to_ascii_lowercase().as_str() {
+            "super" | "meta" | "win" | "logo" | "mod4" => Some(Self::Super),
+            "ctrl" | "control" => Some(Self::Ctrl),
+            "cmd" | "command" => Some(Self::Super),
+            // Portable spelling: Cmd on macOS, Ctrl everywhere else.
+            "cmdorctrl" | "commandorcontrol" if cfg!(target_os = "macos") => Some(Self::Super),
+            "cmdorctrl" | "commandorcontrol" => Some(Self::Ctrl),
+            "alt" | "mod1" => Some(Self::Alt),
+            "shift" => Some(Self::Shift),
+            _ => None,
+        }
+    }
+}
+
+/// Accelerator written as `Super+Shift+D`: any number of modifiers followed
+/// by exactly one key. Keys are kept in their canonical label form.
+///
+/// `DoubleTap+Ctrl` (or `double-tap Right Shift`) is a double-tap of a lone
+/// modifier key; its `modifiers` hold that modifier, as compositors expect
+/// for binds on the modifier key itself.
+///
+/// `Ctrl+Shift+V then T` is a sequence: `modifiers` and `key` describe the
+/// leader, which is the only part bound for good, and `then` the key that
+/// has to follow it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Shortcut {
+    pub modifiers: Vec<ShortcutModifier>,
+    pub key: String,
+    pub double_tap: bool,
+    pub then: Option<Box<Shortcut>>,
+}
+
+impl Shortcut {
+    pub fn parse(value: &str) -> Result<Self, String> {
+        let lowercase = value.to_ascii_lowercase();
+        let Some(split) = lowercase.find(SEQUENCE_SEPARATOR) else {
+            return Self::parse_step(value);
+        };
+
+        let mut leader = Self::parse_step(&value[..split])?;
+        let follow = Self::parse_step(&value[split + SEQUENCE_SEPARATOR.len()..])?;
+        if leader.double_tap || follow.double_tap {
+            return Err(String::from(
+                "Sequencias de atalho nao aceitam toque duplo.",
+            ));
+        }
+
+        leader.then = Some(Box::new(follow));
+        Ok(leader)
+    }
+
+    fn parse_step(value: &str) -> Result<Self, String> {
+        if let Some(key) = strip_double_tap(value) {
+            let (label, _, modifier) = MODIFIER_KEYS
+                .iter()
+                .find(|(label, _, _)| label.eq_ignore_ascii_case(&key))
+                .ok_or_else(|| {
+                    format!("Toque duplo so funciona com Ctrl, Shift, Alt ou Super: {value}.")
+                })?;
+
+            return Ok(Self {
+                modifiers: vec![*modifier],
+                key: (*label).to_owned(),
+                double_tap: true,
+                then: None,
+            });
+        }
+
+        let parts = value
+            .split('+')
+            .map(str::trim)
+            .filter(|part| !part.is_empty())
+            .collect::<Vec<_>>();
+
+        let Some((key, modifier_parts)) = parts.split_last() else {
+            return Err(String::from("O atalho nao pode ficar vazio."));
+        };
+
+        let mut modifiers = Vec::with_capacity(modifier_parts.len());
+        for part in modifier_parts {
+            let modifier = ShortcutModifier::from_label(part)
+                .ok_or_else(|| format!("Modificador de atalho desconhecido: {part}."))?;
+
+            if !modifiers.contains(&modifier) {
+                modifiers.push(modifier);
+            }
+        }
+        modifiers.sort();
+
+        if ShortcutModifier::from_label(key).is_some() {
+            return Err(String::from(
+                "O atalho precisa terminar em uma tecla, nao em um modificador.",
+            ));
+        }
+
+        let key =
+            canonical_key(key).ok_or_else(|| format!("Tecla de atalho desconhecida: {key}."))?;
+
+        Ok(Self {
+            modifiers,
+            key,
+            double_tap: false,
+            then: None,
+        })
+    }
+
+    pub fn label(&self) -> String {
+        if self.double_tap {
+            return format!("{DOUBLE_TAP_PREFIX}+{}", self.key);
+        }
+
+        let step = self
+            .modifiers
+            .iter()
+            .map(|modifier| modifier.label())
+            .chain(std::iter::once(self.key.as_str()))
+            .collect::<Vec<_>>()
+            .join("+");
+
+        match &self.then {
+            Some(follow) => format!("{step}{SEQUENCE_SEPARATOR}{}", follow.label()),
+            None => step,
+        }
+    }
+
+    /// The first step alone; the shortcut itself unless it is a sequence.
+    pub fn leader(&self) -> Self {
+        Self {
+            then: None,
+            ..self.clone()
+        }
+    }
+
+    /// XKB keysym name for the key, as understood by compositors.
+    pub fn keysym(&self) -> &str {
+        if self.double_tap {
+            return MODIFIER_KEYS
+                .iter()
+                .find(|(label, _, _)| *label == self.key)
+                .map_or(self.key.as_str(), |(_, keysym, _)| keysym);
+        }
+
+        NAMED_KEYS
+            .iter()
+            .find(|(label, _)| *label == self.key)
+            .map_or(self.key.as_str(), |(_, keysym)| keysym)
+    }
+}
+
+const DOUBLE_TAP_PREFIX: &str = "DoubleTap";
+
+const SEQUENCE_SEPARATOR: &str = " then ";
+
+/// Modifier keys that can be double-tapped: label, XKB keysym and the
+/// modifier they set.
+const MODIFIER_KEYS: &[(&str, &str, ShortcutModifier)] = &[
+    ("Ctrl", "Control_L", ShortcutModifier::Ctrl),
+    ("RightCtrl", "Control_R", ShortcutModifier::Ctrl),
+    ("Shift", "Shift_L", ShortcutModifier::Shift),
+    ("RightShift", "Shift_R", ShortcutModifier::Shift),
+    ("Alt", "Alt_L", ShortcutModifier::Alt),
+    ("RightAlt", "Alt_R", ShortcutModifier::Alt),
+    ("Super", "Super_L", ShortcutModifier::Super),
+    ("RightSuper", "Super_R", ShortcutModifier::Super),
+];
+
+/// The modifier key of `DoubleTap+Right Shift` or `double-tap ctrl`, with
+/// spaces and `Left` dropped and `Control` shortened (`RightShift`, `Ctrl`).
+fn strip_double_tap(value: &str) -> Option<String> {
+    let compact = value
+        .chars()
+        .filter(|ch| !ch.is_whitespace() && *ch != '-' && *ch != '_')
+        .collect::<String>()
+        .to_ascii_lowercase();
+    let key = compact
+        .strip_prefix("doubletap")?
+        .trim_start_matches('+')
+        .replace("control", "ctrl")
+        .replace("left", "");
+
+    Some(key)
+}
+
+/// Canonical label and XKB keysym for named keys.
+const NAMED_KEYS: &[(&str, &str)] = &[
+    ("Space", "space"),
+    ("Enter", "Return"),
+    ("Tab", "Tab"),
+    ("Escape", "Escape"),
+    ("Backspace", "BackSpace"),
+    ("Delete", "Delete"),
+    ("Insert", "Insert"),
+    ("Home", "Home"),
+    ("End", "End"),
+    ("PageUp", "Prior"),
+    ("PageDown", "Next"),
+    ("Up", "Up"),
+    ("Down", "Down"),
+    ("Left", "Left"),
+    ("Right", "Right"),
+    ("PrintScreen", "Print"),
+    ("ScrollLock", "Scroll_Lock"),
+    ("Pause", "Pause"),
+    ("CapsLock", "Caps_Lock"),
+    ("Menu", "Menu"),
+    ("PlayPause", "XF86AudioPlay"),
+    ("Stop", "XF86AudioStop"),
+    ("PrevTrack", "XF86AudioPrev"),
+    ("NextTrack", "XF86AudioNext"),
+    ("Mute", "XF86AudioMute"),
+    ("MicMute", "XF86AudioMicMute"),
+    ("VolumeUp", "XF86AudioRaiseVolume"),
+    ("VolumeDown", "XF86AudioLowerVolume"),
+    ("Numpad0", "KP_0"),
+    ("Numpad1", "KP_1"),
+    ("Numpad2", "KP_2"),
+    ("Numpad3", "KP_3"),
+    ("Numpad4", "KP_4"),
+    ("Numpad5", "KP_5"),
+    ("Numpad6", "KP_6"),
+    ("Numpad7", "KP_7"),
+    ("Numpad8", "KP_8"),
+    ("Numpad9", "KP_9"),
+    ("NumpadEnter", "KP_Enter"),
+    ("NumpadAdd", "KP_Add"),
+    ("NumpadSubtract", "KP_Subtract"),
+    ("NumpadMultiply", "KP_Multiply"),
+    ("NumpadDivide", "KP_Divide"),
+    ("NumpadDecimal", "KP_Decimal"),
+    // Hyprland names mouse buttons by their evdev code.
+    ("MouseMiddle", "mouse:274"),
+    ("Mouse4", "mouse:275"),
+    ("Mouse5", "mouse:276"),
+    (";", "semicolon"),
+    (",", "comma"),
+    (".", "period"),
+    ("/", "slash"),
+    ("\\", "backslash"),
+    ("[", "bracketleft"),
+    ("]", "bracketright"),
+    ("-", "minus"),
+    ("=", "equal"),
+    ("`", "grave"),
+    ("'", "apostrophe"),
+];
+
+const KEY_ALIASES: &[(&str, &str)] = &[
+    ("return", "Enter"),
+    ("esc", "Escape"),
+    ("del", "Delete"),
+    ("ins", "Insert"),
+    ("pgup", "PageUp"),
+    ("prior", "PageUp"),
+    ("pgdown", "PageDown"),
+    ("pgdn", "PageDown"),
+    ("next", "PageDown"),
+    ("print", "PrintScreen"),
+    ("prtsc", "PrintScreen"),
+    ("scroll_lock", "ScrollLock"),
+    ("break", "Pause"),
+    ("caps_lock", "CapsLock"),
+    ("apps", "Menu"),
+    ("contextmenu", "Menu"),
+    ("mediaplaypause", "PlayPause"),
+    ("play", "PlayPause"),
+    ("mediastop", "Stop"),
+    ("mediaprevioustrack", "PrevTrack"),
+    ("mediaprev", "PrevTrack"),
+    ("medianexttrack", "NextTrack"),
+    ("medianext", "NextTrack"),
+    ("audiomute", "Mute"),
+    ("volumemute", "Mute"),
+    ("audiomicmute", "MicMute"),
+    ("audioraisevolume", "VolumeUp"),
+    ("audiolowervolume", "VolumeDown"),
+    ("kpenter", "NumpadEnter"),
+    ("arrowup", "Up"),
+    ("arrowdown", "Down"),
+    ("arrowleft", "Left"),
+    ("arrowright", "Right"),
+    ("mediatrackprevious", "PrevTrack"),
+    ("mediatracknext", "NextTrack"),
+    ("audiovolumemute", "Mute"),
+    ("audiovolumeup", "VolumeUp"),
+    ("audiovolumedown", "VolumeDown"),
+    ("microphonevolumemute", "MicMute"),
+    ("mouse3", "MouseMiddle"),
+    ("middleclick", "MouseMiddle"),
+    ("xbutton1", "Mouse4"),
+    ("mouseback", "Mouse4"),
+    ("xbutton2", "Mouse5"),
+    ("mouseforward", "Mouse5"),
+    ("semicolon", ";"),
+    ("comma", ","),
+    ("period", "."),
+    ("dot", "."),
+    ("slash", "/"),
+    ("backslash", "\\"),
+    ("bracketleft", "["),
+    ("bracketright", "]"),
+    ("minus", "-"),
+    ("equal", "="),
+    ("grave", "`"),
+    ("backtick", "`"),
+    ("apostrophe", "'"),
+];
+
+fn canonical_key(value: &str) -> Option<String> {
+    let mut chars = value.chars();
+    if let (Some(ch), None) = (chars.next(), chars.next()) {
+        if ch.is_ascii_alphanumeric() {
+            return Some(ch.to_ascii_uppercase().to_string());
+        }
+
+        return NAMED_KEYS
+            .iter()
+            .find(|(label, _)| *label == value)
+            .map(|(label, _)| (*label).to_owned());
+    }
+
+    let lowered = value.to_ascii_lowercase();
+
+    // `kp5` is the keysym-style spelling of `Numpad5`.
+    if let Some(digit) = lowered.strip_prefix("kp")
+        && let [digit @ b'0'..=b'9'] = digit.as_bytes()
+    {
+        return Some(format!("Numpad{}", *digit as char));
+    }
+
+    if let Some(number) = lowered.strip_prefix('f')
+        && let Ok(number) = number.parse::<u8>()
+        && (1..=24).contains(&number)
+    {
+        return Some(format!("F{number}"));
+    }
+
+    if let Some((_, label)) = KEY_ALIASES.iter().find(|(alias, _)| *alias == lowered) {
+        return Some((*label).to_owned());
+    }
+
+    NAMED_KEYS
+        .iter()
+        .find(|(label, _)| label.eq_ignore_ascii_case(&lowered))
+        .map(|(label, _)| (*label).to_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        DOUBLE_TAP_WINDOW, DoubleTapDetector, NAMED_KEYS, Shortcut, ShortcutAction, ShortcutEvent,
+        ShortcutMode, ShortcutModifier, ShortcutTrigger,
+    };
+    use std::time::{Duration, Instant};
+
+    #[test]
+    fn parses_double_tap_modifiers() {
+        let right_shift = Shortcut::parse("double-tap Right Shift").expect("shortcut");
+        let ctrl = Shortcut::parse("DoubleTap+control").expect("shortcut");
+
+        assert_eq!(right_shift.label(), "DoubleTap+RightShift");
+        assert_eq!(right_shift.keysym(), "Shift_R");
+        assert_eq!(right_shift.modifiers, vec![ShortcutModifier::Shift]);
+        assert_eq!(Shortcut::parse(&ctrl.label()), Ok(ctrl.clone()));
+        assert_eq!(ctrl.keysym(), "Control_L");
+        assert!(Shortcut::parse("DoubleTap+D").is_err());
+    }
+
+    #[test]
+    fn parses_leader_sequences() {
+        let sequence = Shortcut::parse("ctrl+shift+v THEN t").expect("shortcut");
+        let follow = sequence.then.as_deref().expect("second step");
+
+        assert_eq!(sequence.label(), "Ctrl+Shift+V then T");
+        assert_eq!(sequence.keysym(), "V");
+        assert_eq!(follow.label(), "T");
+        assert_eq!(
+            sequence.leader(),
+            Shortcut::parse("Ctrl+Shift+V").expect("leader")
+        );
+        assert_eq!(Shortcut::parse(&sequence.label()), Ok(sequence.clone()));
+        assert!(Shortcut::parse("Ctrl+V then DoubleTap+Shift").is_err());
+        assert!(Shortcut::parse("Ctrl+V then").is_err());
+        assert_eq!(
+            ShortcutTrigger::decode("template-0 leader"),
+            Some(ShortcutTrigger::new(
+                ShortcutAction::Template(0),
+                ShortcutEvent::Leader
+            ))
+        );
+    }
+
+    #[test]
+    fn detects_two_taps_within_the_window() {
+        let start = Instant::now();
+        let mut detector = DoubleTapDetector::default();
+
+        assert!(!detector.tap(ShortcutAction::Dictation, start));
+        assert!(detector.tap(
+            ShortcutAction::Dictation,
+            start + Duration::from_millis(200)
+        ));
+        assert!(!detector.tap(ShortcutAction::Dictation, start + DOUBLE_TAP_WINDOW * 2));
+        assert!(!detector.tap(ShortcutAction::Undo, start + DOUBLE_TAP_WINDOW * 2));
+    }
+
+    #[test]
+    fn tap_or_hold_stops_only_after_a_long_press() {
+        let threshold = Duration::from_millis(400);
+
+        assert!(!ShortcutMode::TapOrHold.stops_on_release(Duration::from_millis(150), threshold));
+        assert!(ShortcutMode::TapOrHold.stops_on_release(Duration::from_millis(900), threshold));
+        assert!(ShortcutMode::Hold.stops_on_release(Duration::ZERO, threshold));
+        assert!(!ShortcutMode::Toggle.uses_release());
+    }
+
+    #[test]
+    fn parses_and_normalizes_shortcut() {
+        let shortcut = Shortcut::parse(" shift + super + d ").expect("shortcut");
+
+        assert_eq!(
+            shortcut.modifiers,
+            vec![ShortcutModifier::Super, ShortcutModifier::Shift]
+        );
+        assert_eq!(shortcut.key, "D");
+        assert_eq!(shortcut.label(), "Super+Shift+D");
+    }
+
+    #[test]
+    fn treats_cmd_or_ctrl_as_the_platform_modifier() {
+        let shortcut = Shortcut::parse("CmdOrCtrl+Shift+V").expect("shortcut");
+        let expected = if cfg!(target_os = "macos") {
+            "Super+Shift+V"
+        } else {
+            "Ctrl+Shift+V"
+        };
+
+        assert_eq!(shortcut.label(), expected);
+        assert_eq!(
+            Shortcut::parse("cmd+k").expect("shortcut").label(),
+            "Super+K"
+        );
+    }
+
+    #[test]
+    fn maps_named_keys_to_keysyms() {
+        let shortcut = Shortcut::parse("Ctrl+Alt+pgdn").expect("shortcut");
+        let function_key = Shortcut::parse("f9").expect("shortcut");
+
+        assert_eq!(shortcut.label(), "Ctrl+Alt+PageDown");
+        assert_eq!(shortcut.keysym(), "Next");
+        assert_eq!(function_key.keysym(), "F9");
+    }
+
+    #[test]
+    fn maps_numpad_and_punctuation_keys() {
+        let period = Shortcut::parse("Ctrl+Alt+.").expect("shortcut");
+        let numpad = Shortcut::parse("super+kp7").expect("shortcut");
+
+        assert_eq!(period.label(), "Ctrl+Alt+.");
+        assert_eq!(period.keysym(), "period");
+        assert_eq!(numpad.label(), "Super+Numpad7");
+        assert_eq!(numpad.keysym(), "KP_7");
+        assert_eq!(
+            Shortcut::parse("Ctrl+backtick").expect("shortcut").keysym(),
+            "grave"
+        );
+        assert!(Shortcut::parse("Ctrl+~").is_err());
+    }
+
+    #[test]
+    fn named_key_labels_round_trip() {
+        for (label, keysym) in NAMED_KEYS {
+            let shortcut = Shortcut::parse(&format!("Ctrl+{label}")).expect("shortcut");
+
+            assert_eq!(shortcut.label(), format!("Ctrl+{label}"));
+            assert_eq!(shortcut.keysym(), *keysym);
+        }
+    }
+
+    #[test]
+    fn maps_mouse_buttons_to_hyprland_codes() {
+        let back = Shortcut::parse("mouse4").expect("shortcut");
+        let forward = Shortcut::parse("Super+XButton2").expect("shortcut");
+
+        assert_eq!(back.label(), "Mouse4");
+        assert_eq!(back.keysym(), "mouse:275");
+        assert_eq!(forward.label(), "Super+Mouse5");
+        assert_eq!(forward.keysym(), "mouse:276");
+    }
+
+    #[test]
+    fn maps_media_and_extended_keys() {
+        let mic = Shortcut::parse("micmute").expect("shortcut");
+        let play = Shortcut::parse("Super+MediaPlayPause").expect("shortcut");
+        let print = Shortcut::parse("prtsc").expect("shortcut");
+
+        assert_eq!(mic.keysym(), "XF86AudioMicMute");
+        assert_eq!(play.label(), "Super+PlayPause");
+        assert_eq!(play.keysym(), "XF86AudioPlay");
+        assert_eq!(print.label(), "PrintScreen");
+        assert_eq!(print.keysym(), "Print");
+        assert_eq!(
+            Shortcut::parse("capslock").expect("shortcut").keysym(),
+            "Caps_Lock"
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_shortcuts() {
+        assert!(Shortcut::parse("").is_err());
+        assert!(Shortcut::parse("Super+Shift").is_err());
+        assert!(Shortcut::parse("Hyper+D").is_err());
+        assert!(Shortcut::parse("Super+F25").is_err());
+    }
+
+    #[test]
+    fn round_trips_triggers() {
+        let trigger = ShortcutTrigger::new(ShortcutAction::Dictation, ShortcutEvent::Released);
+
+        assert_eq!(ShortcutTrigger::decode(&trigger.encode()), Some(trigger));
+        assert_eq!(
+            ShortcutTrigger::decode("dictation"),
+            Some(ShortcutTrigger::new(
+                ShortcutAction::Dictation,
+                ShortcutEvent::Pressed
+            ))
+        );
+        assert_eq!(ShortcutTrigger::decode("dictation down"), None);
+        assert_eq!(
+            ShortcutTrigger::decode("template-2 pressed"),
+            Some(ShortcutTrigger::new(
+                ShortcutAction::Template(2),
+                ShortcutEvent::Pressed
+            ))
+        );
+        assert_eq!(ShortcutAction::Template(2).code(), "template-2");
+        assert_eq!(ShortcutTrigger::decode("template-x"), None);
+        assert_eq!(
+            ShortcutTrigger::decode("monitor"),
+            Some(ShortcutTrigger::new(
+                ShortcutAction::Monitor,
+                ShortcutEvent::Pressed
+            ))
+        );
+        assert_eq!(
+            ShortcutTrigger::decode("cancel"),
+            Some(ShortcutTrigger::new(
+                ShortcutAction::Cancel,
+                ShortcutEvent::Pressed
+            ))
+        );
+        assert_eq!(
+            ShortcutTrigger::decode("copy-last released"),
+            Some(ShortcutTrigger::new(
+                ShortcutAction::CopyLast,
+                ShortcutEvent::Released
+            ))
+        );
+        assert_eq!(
+            ShortcutTrigger::decode("retranscribe"),
+            Some(ShortcutTrigger::new(
+                ShortcutAction::Retranscribe,
+                ShortcutEvent::Pressed
+            ))
+        );
+        assert_eq!(
+            ShortcutTrigger::decode("flush-pending"),
+            Some(ShortcutTrigger::new(
+                ShortcutAction::FlushPending,
+                ShortcutEvent::Pressed
+            ))
+        );
+    }
+}
@@ -0,0 +1,251 @@
+use crate::modules::shortcuts::domain::{
+    Shortcut, ShortcutEvent, ShortcutModifier, ShortcutTrigger,
+};
+use crate::platform::hyprland::{self, HyprlandBind};
+use std::env;
+use std::fs;
+use std::io::{BufRead, BufReader, Write};
+use std::os::unix::net::{UnixListener, UnixStream};
+use std::path::PathBuf;
+use std::sync::mpsc::{self, Receiver};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+pub type SharedReceiver = Arc<Mutex<Receiver<ShortcutTrigger>>>;
+
+const TRIGGER_SOCKET_NAME: &str = "openvoice.sock";
+const TRIGGER_READ_TIMEOUT_MS: u64 = 500;
+const TRIGGER_ARG: &str = "--trigger";
+
+/// Opens the trigger socket and forwards every decoded trigger to the
+/// returned receiver. Fails when another instance already owns the socket.
+pub fn listen_for_triggers() -> Result<SharedReceiver, String> {
+    let path = trigger_socket_path();
+
+    if path.exists() {
+        if UnixStream::connect(&path).is_ok() {
+            return Err(String::from(
+                "Outra instancia do OpenVoice ja esta recebendo os atalhos globais.",
+            ));
+        }
+
+        fs::remove_file(&path).map_err(|error| {
+            format!(
+                "Falha ao remover socket antigo de atalhos em {}: {error}",
+                path.display()
+            )
+        })?;
+    }
+
+    let listener = UnixListener::bind(&path).map_err(|error| {
+        format!(
+            "Nao consegui abrir o socket de atalhos em {}: {error}",
+            path.display()
+        )
+    })?;
+
+    let (trigger_tx, trigger_rx) = mpsc::channel();
+
+    thread::spawn(move || {
+        for stream in listener.incoming() {
+            let stream = match stream {
+                Ok(stream) => stream,
+                Err(error) => {
+                    log_error!("[openvoice][shortcuts] failed to accept trigger: {error}");
+                    continue;
+                }
+            };
+
+            for trigger in read_triggers(stream) {
+                if trigger_tx.send(trigger).is_err() {
+                    return;
+                }
+            }
+        }
+    });
+
+    Ok(Arc::new(Mutex::new(trigger_rx)))
+}
+
+pub fn send_trigger(trigger: ShortcutTrigger) -> Result<(), String> {
+    let path = trigger_socket_path();
+    let mut stream = UnixStream::connect(&path).map_err(|error| {
+        format!(
+            "Nao encontrei o OpenVoice rodando em {}: {error}",
+            path.display()
+        )
+    })?;
+
+    writeln!(stream, "{}", trigger.encode())
+        .map_err(|error| format!("Falha ao enviar atalho para o OpenVoice: {error}"))
+}
+
+/// Describes a bind from another app on the same combination, if any.
+/// Leftover binds from a previous OpenVoice run don't count.
+pub fn find_conflict(shortcut: &Shortcut) -> Option<String> {
+    let binds = hyprland::list_binds()?;
+
+    conflicting_bind(&binds, shortcut).map(|bind| {
+        if bind.arg.is_empty() {
+            bind.dispatcher.clone()
+        } else {
+            format!("{} {}", bind.dispatcher, bind.arg)
+        }
+    })
+}
+
+fn conflicting_bind<'a>(
+    binds: &'a [HyprlandBind],
+    shortcut: &Shortcut,
+) -> Option<&'a HyprlandBind> {
+    let modmask = hyprland_modmask(shortcut);
+    let key = shortcut.keysym();
+
+    binds.iter().find(|bind| {
+        bind.modmask == modmask
+            && bind.key.eq_ignore_ascii_case(key)
+            && !bind.arg.contains(TRIGGER_ARG)
+    })
+}
+
+/// Binds the shortcut in Hyprland so it runs this executable with
+/// `--trigger`. Any existing bind on the same combination is replaced, so
+/// callers check [`find_conflict`] first.
+pub fn bind_shortcut(
+    shortcut: &Shortcut,
+    pressed: ShortcutTrigger,
+    released: Option<ShortcutTrigger>,
+) -> Result<(), String> {
+    let mods = hyprland_mods(shortcut);
+    let key = shortcut.keysym();
+
+    // Binds outlive the app, so clear leftovers from a previous run first.
+    let _ = hyprland::unbind(&mods, key);
+
+    // A lone modifier only reports its release; the app pairs the taps.
+    if shortcut.double_tap {
+        let tap = ShortcutTrigger::new(pressed.action, ShortcutEvent::Tap);
+        return hyprland::bind_exec(&mods, key, &trigger_command(tap)?, true);
+    }
+
+    hyprland::bind_exec(&mods, key, &trigger_command(pressed)?, false)?;
+
+    if let Some(released) = released {
+        hyprland::bind_exec(&mods, key, &trigger_command(released)?, true)?;
+    }
+
+    Ok(())
+}
+
+pub fn unbind_shortcut(shortcut: &Shortcut) -> Result<(), String> {
+    hyprland::unbind(&hyprland_mods(shortcut), shortcut.keysym())
+}
+
+fn read_triggers(stream: UnixStream) -> Vec<ShortcutTrigger> {
+    let _ = stream.set_read_timeout(Some(Duration::from_millis(TRIGGER_READ_TIMEOUT_MS)));
+
+    BufReader::new(stream)
+        .lines()
+        .map_while(Result::ok)
+        .filter_map(|line| {
+            let trigger = ShortcutTrigger::decode(&line);
+            if trigger.is_none() {
+                log_warn!("[openvoice][shortcuts] ignoring unknown trigger: {line}");
+            }
+            trigger
+        })
+        .collect()
+}
+
+fn trigger_command(trigger: ShortcutTrigger) -> Result<String, String> {
+    let executable = env::current_exe()
+        .map_err(|error| format!("Nao consegui descobrir o executavel do OpenVoice: {error}"))?;
+
+    Ok(format!(
+        "{} {TRIGGER_ARG} {}",
+        shell_quote(&executable.to_string_lossy()),
+        trigger.encode()
+    ))
+}
+
+fn hyprland_mods(shortcut: &Shortcut) -> String {
+    shortcut
+        .modifiers
+        .iter()
+        .map(|modifier| match modifier {
+            ShortcutModifier::Super => "SUPER",
+            ShortcutModifier::Ctrl => "CTRL",
+            ShortcutModifier::Alt => "ALT",
+            ShortcutModifier::Shift => "SHIFT",
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn hyprland_modmask(shortcut: &Shortcut) -> u32 {
+    shortcut
+        .modifiers
+        .iter()
+        .map(|modifier| match modifier {
+            ShortcutModifier::Shift => 1,
+            ShortcutModifier::Ctrl => 4,
+            ShortcutModifier::Alt => 8,
+            ShortcutModifier::Super => 64,
+        })
+        .sum()
+}
+
+fn shell_quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', r"'\''"))
+}
+
+fn trigger_socket_path() -> PathBuf {
+    env::var_os("XDG_RUNTIME_DIR")
+        .map(PathBuf::from)
+        .unwrap_or_else(env::temp_dir)
+        .join(TRIGGER_SOCKET_NAME)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{conflicting_bind, hyprland_mods, shell_quote};
+    use crate::modules::shortcuts::domain::Shortcut;
+    use crate::platform::hyprland::HyprlandBind;
+
+    #[test]
+    fn finds_foreign_binds_on_the_same_combination() {
+        let shortcut = Shortcut::parse("Super+Shift+D").expect("shortcut");
+        let bind = |modmask: u32, key: &str, arg: &str| HyprlandBind {
+            modmask,
+            key: key.to_owned(),
+            dispatcher: String::from("exec"),
+            arg: arg.to_owned(),
+        };
+        let binds = [
+            bind(64, "D", "rofi -show drun"),
+            bind(65, "d", "'/usr/bin/openvoice' --trigger dictation pressed"),
+        ];
+
+        assert_eq!(conflicting_bind(&binds, &shortcut), None);
+
+        let binds = [bind(65, "d", "discord")];
+        assert_eq!(
+            conflicting_bind(&binds, &shortcut).map(|bind| bind.arg.as_str()),
+            Some("discord")
+        );
+    }
+
+    #[test]
+    fn formats_hyprland_modifiers() {
+        let shortcut = Shortcut::parse("Shift+Super+D").expect("shortcut");
+
+        assert_eq!(hyprland_mods(&shortcut), "SUPER SHIFT");
+    }
+
+    #[test]
+    fn quotes_executable_for_exec_binds() {
+        assert_eq!(shell_quote("/opt/open voice"), "'/opt/open voice'");
+        assert_eq!(shell_quote("/tmp/it's"), r"'/tmp/it'\''s'");
+    }
+}
@@ -0,0 +1,109 @@
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Shows a desktop notification through `notify-send`.
+pub fn notify(summary: &str, body: &str) -> Result<(), String> {
+    match Command::new("notify-send")
+        .args(["--app-name=OpenVoice", summary, body])
+        .status()
+    {
+        Ok(status) if status.success() => Ok(()),
+        Ok(status) => Err(format!("notify-send terminou com {status}.")),
+        Err(error) if error.kind() == ErrorKind::NotFound => Err(String::from(
+            "notify-send nao encontrado; instale libnotify para ver notificacoes.",
+        )),
+        Err(error) => Err(format!("Falha ao executar notify-send: {error}")),
+    }
+}
+
+/// Hands `uri` to the desktop's handler for its scheme through `xdg-open`.
+pub fn open_uri(uri: &str) -> Result<(), String> {
+    match Command::new("xdg-open").arg(uri).status() {
+        Ok(status) if status.success() => Ok(()),
+        Ok(status) => Err(format!(
+            "xdg-open terminou com {status}; confira se ha um app para esse tipo de link."
+        )),
+        Err(error) if error.kind() == ErrorKind::NotFound => Err(String::from(
+            "xdg-open nao encontrado; instale xdg-utils para abrir URIs.",
+        )),
+        Err(error) => Err(format!("Falha ao executar xdg-open: {error}")),
+    }
+}
+
+/// Whether the XDG autostart entry that launches OpenVoice at login exists.
+pub fn autostart_enabled() -> bool {
+    autostart_path().is_ok_and(|path| path.exists())
+}
+
+/// Writes or removes the XDG autostart entry. The entry points at the
+/// running binary, so enabling it again after a move fixes the path.
+pub fn set_autostart(enabled: bool) -> Result<(), String> {
+    let path = autostart_path()?;
+
+    if !enabled {
+        return match fs::remove_file(&path) {
+            Ok(()) => Ok(()),
+            Err(error) if error.kind() == ErrorKind::NotFound => Ok(()),
+            Err(error) => Err(format!(
+                "Falha ao remover {} do inicio automatico: {error}",
+                path.display()
+            )),
+        };
+    }
+
+    let exec = std::env::current_exe()
+        .map_err(|error| format!("Nao consegui descobrir o executavel do OpenVoice: {error}"))?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|error| format!("Falha ao criar pasta de autostart: {error}"))?;
+    }
+
+    fs::write(&path, autostart_entry(&exec))
+        .map_err(|error| format!("Falha ao salvar {}: {error}", path.display()))
+}
+
+fn autostart_path() -> Result<PathBuf, String> {
+    let base = std::env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))
+        .ok_or_else(|| {
+            String::from("Nao consegui descobrir a pasta de configuracao do usuario.")
+        })?;
+
+    Ok(base.join("autostart").join("openvoice.desktop"))
+}
+
+fn autostart_entry(exec: &Path) -> String {
+    // Desktop entries take a quoted Exec argument with these escaped.
+    let quoted = exec
+        .display()
+        .to_string()
+        .chars()
+        .fold(String::new(), |mut quoted, ch| {
+            if matches!(ch, '"' | '`' | '$' | '\\') {
+                quoted.push('\\');
+            }
+            quoted.push(ch);
+            quoted
+        });
+
+    format!(
+        "[Desktop Entry]\nType=Application\nName=OpenVoice\nComment=Ditado por voz\nExec=\"{quoted}\"\nTerminal=false\nX-GNOME-Autostart-enabled=true\n"
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::autostart_entry;
+    use std::path::Path;
+
+    #[test]
+    fn quotes_autostart_exec() {
+        let entry = autostart_entry(Path::new("/opt/Open Voice/$bin"));
+
+        assert!(entry.starts_with("[Desktop Entry]\n"));
+        assert!(entry.contains("Exec=\"/opt/Open Voice/\\$bin\"\n"));
+    }
+}
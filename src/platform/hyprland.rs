@@ -19,6 +19,17 @@ struct HyprlandMonitor {
     focused: bool,
 }
 
+/// One entry of `hyprctl binds -j`. `modmask` uses Hyprland's bits:
+/// Shift 1, Ctrl 4, Alt 8, Super 64.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct HyprlandBind {
+    pub modmask: u32,
+    pub key: String,
+    pub dispatcher: String,
+    #[serde(default)]
+    pub arg: String,
+}
+
 pub fn is_hyprland_session() -> bool {
     env::var_os("HYPRLAND_INSTANCE_SIGNATURE").is_some()
         || env::var("XDG_CURRENT_DESKTOP")
@@ -51,6 +62,28 @@ pub fn apply_no_screen_share(app_id: &str) -> Result<(), String> {
     Ok(())
 }
 
+/// Registers a global `exec` bind. Release binds (`bindr`) fire when the key
+/// combination goes up instead of down.
+pub fn bind_exec(mods: &str, key: &str, command: &str, on_release: bool) -> Result<(), String> {
+    let keyword = if on_release { "bindr" } else { "bind" };
+    let bind = format!("{mods}, {key}, exec, {command}");
+
+    run_hyprctl_command(&["keyword", keyword, &bind])
+}
+
+/// Binds currently active in the compositor; `None` when hyprctl can't
+/// report them.
+pub fn list_binds() -> Option<Vec<HyprlandBind>> {
+    let stdout = run_hyprctl(&["binds", "-j"])?;
+    serde_json::from_str(&stdout).ok()
+}
+
+pub fn unbind(mods: &str, key: &str) -> Result<(), String> {
+    let bind = format!("{mods}, {key}");
+
+    run_hyprctl_command(&["keyword", "unbind", &bind])
+}
+
 fn run_hyprctl(args: &[&str]) -> Option<String> {
     let output = Command::new("hyprctl").args(args).output().ok()?;
 
@@ -0,0 +1,182 @@
+use std::env;
+use std::io::ErrorKind;
+use std::process::Command;
+
+/// Types `text` into the focused window as synthetic keystrokes, pausing
+/// `delay_ms` between keys. Tries the injectors for the current session in
+/// order and returns the name of the one that worked.
+pub fn type_text(text: &str, delay_ms: u32) -> Result<&'static str, String> {
+    let wayland = env::var_os("WAYLAND_DISPLAY").is_some();
+
+    run_first_available(typing_commands(text, delay_ms, wayland))
+}
+
+/// Presses Ctrl+V and then Enter in the focused window, pasting the
+/// clipboard and sending it in chat apps.
+pub fn paste_and_submit() -> Result<&'static str, String> {
+    let wayland = env::var_os("WAYLAND_DISPLAY").is_some();
+
+    run_first_available(submit_commands(wayland))
+}
+
+/// Presses Backspace `count` times in the focused window, erasing text
+/// that was just typed.
+pub fn erase_text(count: usize) -> Result<&'static str, String> {
+    let wayland = env::var_os("WAYLAND_DISPLAY").is_some();
+
+    run_first_available(erase_commands(count, wayland))
+}
+
+fn run_first_available(commands: Vec<(&'static str, Vec<String>)>) -> Result<&'static str, String> {
+    let mut missing = Vec::new();
+
+    for (program, args) in commands {
+        match Command::new(program).args(&args).status() {
+            Ok(status) if status.success() => return Ok(program),
+            Ok(status) => return Err(format!("{program} terminou com {status}.")),
+            Err(error) if error.kind() == ErrorKind::NotFound => missing.push(program),
+            Err(error) => return Err(format!("Falha ao executar {program}: {error}")),
+        }
+    }
+
+    Err(format!(
+        "Nenhuma ferramenta de digitacao encontrada; instale {}.",
+        missing.join(" ou ")
+    ))
+}
+
+/// Candidate commands, best first: `wtype` speaks the Wayland virtual
+/// keyboard protocol, `ydotool` goes through uinput and `xdotool` covers X11.
+fn typing_commands(text: &str, delay_ms: u32, wayland: bool) -> Vec<(&'static str, Vec<String>)> {
+    let delay = delay_ms.to_string();
+    let wtype = (
+        "wtype",
+        vec![
+            String::from("-d"),
+            delay.clone(),
+            String::from("--"),
+            text.to_owned(),
+        ],
+    );
+    let ydotool = (
+        "ydotool",
+        vec![
+            String::from("type"),
+            String::from("--key-delay"),
+            delay.clone(),
+            String::from("--"),
+            text.to_owned(),
+        ],
+    );
+    let xdotool = (
+        "xdotool",
+        vec![
+            String::from("type"),
+            String::from("--delay"),
+            delay,
+            String::from("--"),
+            text.to_owned(),
+        ],
+    );
+
+    if wayland {
+        vec![wtype, ydotool, xdotool]
+    } else {
+        vec![xdotool, ydotool]
+    }
+}
+
+/// Ctrl+V then Enter for each injector. `ydotool` takes raw evdev codes:
+/// 29 is left Ctrl, 47 is V and 28 is Enter.
+fn submit_commands(wayland: bool) -> Vec<(&'static str, Vec<String>)> {
+    let args = |values: &[&str]| values.iter().map(|value| (*value).to_owned()).collect();
+    let wtype = (
+        "wtype",
+        args(&["-M", "ctrl", "v", "-m", "ctrl", "-k", "Return"]),
+    );
+    let ydotool = (
+        "ydotool",
+        args(&["key", "29:1", "47:1", "47:0", "29:0", "28:1", "28:0"]),
+    );
+    let xdotool = ("xdotool", args(&["key", "ctrl+v", "Return"]));
+
+    if wayland {
+        vec![wtype, ydotool, xdotool]
+    } else {
+        vec![xdotool, ydotool]
+    }
+}
+
+/// Backspace `count` times for each injector; 14 is Backspace in evdev.
+fn erase_commands(count: usize, wayland: bool) -> Vec<(&'static str, Vec<String>)> {
+    let wtype = (
+        "wtype",
+        (0..count)
+            .flat_map(|_| [String::from("-k"), String::from("BackSpace")])
+            .collect(),
+    );
+    let ydotool = (
+        "ydotool",
+        std::iter::once(String::from("key"))
+            .chain((0..count).flat_map(|_| [String::from("14:1"), String::from("14:0")]))
+            .collect(),
+    );
+    let xdotool = (
+        "xdotool",
+        vec![
+            String::from("key"),
+            String::from("--repeat"),
+            count.to_string(),
+            String::from("BackSpace"),
+        ],
+    );
+
+    if wayland {
+        vec![wtype, ydotool, xdotool]
+    } else {
+        vec![xdotool, ydotool]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{erase_commands, submit_commands, typing_commands};
+
+    #[test]
+    fn prefers_wtype_on_wayland() {
+        let commands = typing_commands("-ola", 8, true);
+
+        assert_eq!(commands[0].0, "wtype");
+        assert_eq!(commands[0].1, ["-d", "8", "--", "-ola"]);
+        assert_eq!(
+            typing_commands("ola", 8, false)
+                .iter()
+                .map(|(program, _)| *program)
+                .collect::<Vec<_>>(),
+            ["xdotool", "ydotool"]
+        );
+    }
+
+    #[test]
+    fn erases_one_backspace_per_character() {
+        let commands = erase_commands(2, true);
+
+        assert_eq!(commands[0].1, ["-k", "BackSpace", "-k", "BackSpace"]);
+        assert_eq!(commands[1].1, ["key", "14:1", "14:0", "14:1", "14:0"]);
+        assert_eq!(
+            erase_commands(2, false)[0].1,
+            ["key", "--repeat", "2", "BackSpace"]
+        );
+    }
+
+    #[test]
+    fn pastes_before_pressing_enter() {
+        let commands = submit_commands(true);
+
+        assert_eq!(
+            commands[0].1,
+            ["-M", "ctrl", "v", "-m", "ctrl", "-k", "Return"]
+        );
+        assert_eq!(submit_commands(false)[0].1, ["key", "ctrl+v", "Return"]);
+    }
+}
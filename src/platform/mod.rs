@@ -1,4 +1,6 @@
+pub mod desktop;
 pub mod hyprland;
+pub mod keyboard;
 pub mod monitors;
 pub mod screenshot;
 pub mod window;
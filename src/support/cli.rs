@@ -0,0 +1,68 @@
+//! Argument handling shared by the one-shot `--flag [path]` commands that
+//! run instead of the app.
+
+use std::path::PathBuf;
+
+/// The first of `flags` present in `args`, with the path given right after
+/// it; the path is empty when the next argument is another flag or none.
+pub fn path_command<'a>(args: &'a [String], flags: &[&'a str]) -> Option<(&'a str, &'a str)> {
+    let (position, flag) = args.iter().enumerate().find_map(|(position, arg)| {
+        flags
+            .iter()
+            .find(|flag| **flag == arg)
+            .map(|flag| (position, *flag))
+    })?;
+    let path = args
+        .get(position + 1)
+        .filter(|arg| !arg.starts_with("--"))
+        .map_or("", String::as_str);
+
+    Some((flag, path))
+}
+
+pub fn has_flag(args: &[String], flag: &str) -> bool {
+    args.iter().any(|arg| arg == flag)
+}
+
+/// `path`, or `default_file` in the home folder when it is blank; `missing`
+/// is the error when there is no home folder either.
+pub fn path_or_home(path: &str, default_file: &str, missing: &str) -> Result<PathBuf, String> {
+    let path = path.trim();
+    if !path.is_empty() {
+        return Ok(PathBuf::from(path));
+    }
+
+    std::env::var_os("HOME")
+        .map(|home| PathBuf::from(home).join(default_file))
+        .ok_or_else(|| missing.to_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{has_flag, path_command};
+
+    #[test]
+    fn finds_the_flag_and_its_optional_path() {
+        let args = |values: &[&str]| {
+            values
+                .iter()
+                .map(|value| (*value).to_owned())
+                .collect::<Vec<_>>()
+        };
+        let flags = ["--create-backup", "--restore-backup"];
+
+        let given = args(&["openvoice", "--restore-backup", "/tmp/a.tar.gz"]);
+        assert_eq!(
+            path_command(&given, &flags),
+            Some(("--restore-backup", "/tmp/a.tar.gz"))
+        );
+
+        let omitted = args(&["openvoice", "--create-backup", "--with-audio"]);
+        assert_eq!(
+            path_command(&omitted, &flags),
+            Some(("--create-backup", ""))
+        );
+        assert!(has_flag(&omitted, "--with-audio"));
+        assert_eq!(path_command(&args(&["openvoice"]), &flags), None);
+    }
+}
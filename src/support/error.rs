@@ -0,0 +1,135 @@
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+/// Failure classes the UI and logs react to. Messages stay in the app's
+/// language; the variant says what kind of failure it was.
+#[derive(Debug, Clone, PartialEq, Eq, Error, Serialize, Deserialize)]
+#[serde(tag = "kind", content = "detail", rename_all = "snake_case")]
+pub enum OpenVoiceError {
+    /// The provider rejected the credentials (401/403).
+    #[error("{provider} recusou a API key: {message}")]
+    ApiAuth { provider: String, message: String },
+    /// Too many requests or the account ran out of quota (429).
+    #[error("{provider} limitou as requisicoes: {message}")]
+    RateLimited { provider: String, message: String },
+    /// Any other non-2xx answer.
+    #[error("{provider} retornou {status}: {message}")]
+    Api {
+        provider: String,
+        status: u16,
+        message: String,
+    },
+    /// The request never got an answer (DNS, TLS, connection, timeout).
+    #[error("{0}")]
+    Network(String),
+    /// The answer arrived but could not be read, or carried no text.
+    #[error("{0}")]
+    InvalidResponse(String),
+    #[error("Nenhum audio foi capturado.")]
+    NoAudio,
+    /// The microphone could not be opened or stopped delivering audio.
+    #[error("{0}")]
+    DeviceUnavailable(String),
+    #[error("Transcricao cancelada; o resultado sera descartado.")]
+    Cancelled,
+    #[error("{0}")]
+    Other(String),
+}
+
+impl OpenVoiceError {
+    /// Classifies a non-2xx HTTP answer from `provider`.
+    pub fn from_status(provider: impl ToString, status: u16, message: impl Into<String>) -> Self {
+        let provider = provider.to_string();
+        let message = message.into();
+
+        match status {
+            401 | 403 => Self::ApiAuth { provider, message },
+            429 => Self::RateLimited { provider, message },
+            status => Self::Api {
+                provider,
+                status,
+                message,
+            },
+        }
+    }
+
+    /// Whether trying again (or with another model) could succeed; a bad
+    /// key, a missing take or a cancel will fail the same way every time.
+    pub fn is_retryable(&self) -> bool {
+        !matches!(
+            self,
+            Self::ApiAuth { .. } | Self::NoAudio | Self::DeviceUnavailable(_) | Self::Cancelled
+        )
+    }
+
+    /// Whether the key itself was the problem (refused, or out of credit
+    /// with a 402), so another key could get through.
+    pub fn is_key_refused(&self) -> bool {
+        matches!(self, Self::ApiAuth { .. } | Self::Api { status: 402, .. })
+    }
+
+    /// What the user can do about it, for the HUD hint.
+    pub fn advice(&self) -> Option<&'static str> {
+        match self {
+            Self::ApiAuth { .. } => Some("Confira a API key nas configuracoes."),
+            Self::RateLimited { .. } => Some("Aguarde um pouco e tente de novo."),
+            Self::Network(_) => Some("Verifique a conexao com a internet."),
+            Self::NoAudio | Self::DeviceUnavailable(_) => {
+                Some("Verifique se o microfone esta conectado e liberado.")
+            }
+            _ => None,
+        }
+    }
+}
+
+impl From<String> for OpenVoiceError {
+    fn from(message: String) -> Self {
+        Self::Other(message)
+    }
+}
+
+/// Lets code that still reports plain strings use `?` on typed errors.
+impl From<OpenVoiceError> for String {
+    fn from(error: OpenVoiceError) -> Self {
+        error.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::OpenVoiceError;
+
+    #[test]
+    fn classifies_http_status() {
+        assert!(matches!(
+            OpenVoiceError::from_status("Deepgram", 401, "bad key"),
+            OpenVoiceError::ApiAuth { .. }
+        ));
+        assert!(matches!(
+            OpenVoiceError::from_status("Deepgram", 429, "slow down"),
+            OpenVoiceError::RateLimited { .. }
+        ));
+        assert_eq!(
+            OpenVoiceError::from_status("Deepgram", 500, "oops").to_string(),
+            "Deepgram retornou 500: oops"
+        );
+        assert!(!OpenVoiceError::Cancelled.is_retryable());
+        assert!(OpenVoiceError::from_status("OpenRouter", 402, "no credits").is_key_refused());
+        assert!(!OpenVoiceError::from_status("OpenRouter", 500, "oops").is_key_refused());
+    }
+
+    #[test]
+    fn serializes_with_a_kind_tag() {
+        let json = serde_json::to_string(&OpenVoiceError::from_status("OpenRouter", 429, "quota"))
+            .expect("json");
+
+        assert_eq!(
+            json,
+            r#"{"kind":"rate_limited","detail":{"provider":"OpenRouter","message":"quota"}}"#
+        );
+        assert_eq!(
+            serde_json::to_string(&OpenVoiceError::NoAudio).expect("json"),
+            r#"{"kind":"no_audio"}"#
+        );
+    }
+}
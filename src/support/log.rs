@@ -0,0 +1,184 @@
+//! Leveled logging to stderr and to a size-rotated file in the data dir,
+//! so diagnostics survive a launch from the desktop and can be attached to
+//! bug reports from the settings window.
+
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicU8, Ordering};
+
+use crate::modules::audio::infrastructure::storage;
+use crate::modules::live_transcription::infrastructure::db;
+
+pub const LOG_FILE: &str = "openvoice.log";
+/// The file is moved to `openvoice.log.1` past this size, replacing the
+/// previous rotation.
+const MAX_LOG_BYTES: u64 = 1024 * 1024;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum LogLevel {
+    Error,
+    Warn,
+    Info,
+    Debug,
+}
+
+impl LogLevel {
+    pub const ALL: [Self; 4] = [Self::Error, Self::Warn, Self::Info, Self::Debug];
+
+    pub fn code(self) -> &'static str {
+        match self {
+            Self::Error => "error",
+            Self::Warn => "warn",
+            Self::Info => "info",
+            Self::Debug => "debug",
+        }
+    }
+
+    pub fn from_code(value: &str) -> Self {
+        Self::ALL
+            .into_iter()
+            .find(|level| level.code() == value.trim().to_ascii_lowercase())
+            .unwrap_or(Self::Info)
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Error => "Somente erros",
+            Self::Warn => "Avisos",
+            Self::Info => "Informativo",
+            Self::Debug => "Depuracao",
+        }
+    }
+}
+
+impl std::fmt::Display for LogLevel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.label())
+    }
+}
+
+static LEVEL: AtomicU8 = AtomicU8::new(LogLevel::Info as u8);
+static FILE: Mutex<()> = Mutex::new(());
+
+/// Takes effect for every later line, from any thread.
+pub fn set_level(level: LogLevel) {
+    LEVEL.store(level as u8, Ordering::Relaxed);
+}
+
+pub fn level() -> LogLevel {
+    LogLevel::ALL[usize::from(LEVEL.load(Ordering::Relaxed))]
+}
+
+pub fn enabled(level: LogLevel) -> bool {
+    level <= self::level()
+}
+
+/// Backs the `log_*!` macros. Logging never fails the caller: a file that
+/// can't be written only loses the line there.
+pub fn write(level: LogLevel, message: std::fmt::Arguments) {
+    if !enabled(level) {
+        return;
+    }
+
+    eprintln!("{message}");
+    if cfg!(test) {
+        return;
+    }
+    let line = format!("{} {} {message}", db::now_iso(), level.code());
+    let _guard = FILE.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+    let _ = log_path().and_then(|path| append(&path, &line));
+}
+
+pub fn log_path() -> Result<PathBuf, String> {
+    Ok(storage::data_dir()?.join("logs").join(LOG_FILE))
+}
+
+fn rotated_path(path: &std::path::Path) -> PathBuf {
+    path.with_extension("log.1")
+}
+
+fn append(path: &std::path::Path, line: &str) -> Result<(), String> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(|error| error.to_string())?;
+    }
+    if fs::metadata(path).is_ok_and(|metadata| metadata.len() >= MAX_LOG_BYTES) {
+        fs::rename(path, rotated_path(path)).map_err(|error| error.to_string())?;
+    }
+
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .map_err(|error| error.to_string())?;
+    writeln!(file, "{line}").map_err(|error| error.to_string())
+}
+
+/// The last `count` lines logged, oldest first, reaching into the rotated
+/// file when the current one is shorter.
+pub fn recent_logs(count: usize) -> Result<Vec<String>, String> {
+    let path = log_path()?;
+    let _guard = FILE.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+    Ok(read_recent(&path, count))
+}
+
+fn read_recent(path: &std::path::Path, count: usize) -> Vec<String> {
+    let contents = [rotated_path(path), path.to_path_buf()]
+        .iter()
+        .filter_map(|path| fs::read_to_string(path).ok())
+        .collect::<String>();
+    let lines = contents.lines().collect::<Vec<_>>();
+
+    lines[lines.len().saturating_sub(count)..]
+        .iter()
+        .map(|line| (*line).to_owned())
+        .collect()
+}
+
+macro_rules! log_error {
+    ($($arg:tt)*) => {
+        $crate::support::log::write($crate::support::log::LogLevel::Error, format_args!($($arg)*))
+    };
+}
+
+macro_rules! log_warn {
+    ($($arg:tt)*) => {
+        $crate::support::log::write($crate::support::log::LogLevel::Warn, format_args!($($arg)*))
+    };
+}
+
+macro_rules! log_info {
+    ($($arg:tt)*) => {
+        $crate::support::log::write($crate::support::log::LogLevel::Info, format_args!($($arg)*))
+    };
+}
+
+macro_rules! log_debug {
+    ($($arg:tt)*) => {
+        $crate::support::log::write($crate::support::log::LogLevel::Debug, format_args!($($arg)*))
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{LogLevel, append, read_recent, rotated_path};
+
+    #[test]
+    fn keeps_the_tail_across_the_rotated_file() {
+        let dir = std::env::temp_dir().join(format!("openvoice-log-{}", std::process::id()));
+        let path = dir.join("openvoice.log");
+        let _ = std::fs::remove_dir_all(&dir);
+
+        append(&path, "first").expect("append");
+        std::fs::rename(&path, rotated_path(&path)).expect("rotate");
+        append(&path, "second").expect("append");
+        append(&path, "third").expect("append");
+
+        assert_eq!(read_recent(&path, 2), vec!["second", "third"]);
+        assert_eq!(read_recent(&path, 10), vec!["first", "second", "third"]);
+        assert_eq!(LogLevel::from_code(" DEBUG "), LogLevel::Debug);
+        assert_eq!(LogLevel::from_code("verbose"), LogLevel::Info);
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
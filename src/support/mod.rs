@@ -1 +1,5 @@
+pub mod cli;
+pub mod error;
+#[macro_use]
+pub mod log;
 pub mod openai;
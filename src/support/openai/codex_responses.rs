@@ -75,7 +75,7 @@ impl CodexResponsesClient {
         if !status.is_success() {
             let body_text = response.text().unwrap_or_default();
             let log_body = truncate_for_log(&body_text, MAX_ERROR_BODY_CHARS);
-            eprintln!("[openvoice][codex] status={status} body={log_body}");
+            log_warn!("[openvoice][codex] status={status} body={log_body}");
             return Err(format!("Codex Responses retornou status {status}"));
         }
 
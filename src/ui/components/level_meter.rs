@@ -0,0 +1,54 @@
+use crate::app::Message;
+use crate::modules::audio::domain::AudioLevel;
+use iced::widget::{container, row};
+use iced::{Background, Border, Color, Element, Length};
+
+const METER_HEIGHT: f32 = 3.0;
+const METER_STEPS: u16 = 1_000;
+
+pub fn view<'a>(level: AudioLevel, accent: Color) -> Element<'a, Message> {
+    fraction(level.meter_fraction(), accent)
+}
+
+/// The same thin bar for any 0..=1 value, e.g. transcription progress.
+pub fn fraction<'a>(value: f32, accent: Color) -> Element<'a, Message> {
+    let filled = (value.clamp(0.0, 1.0) * f32::from(METER_STEPS)).round() as u16;
+    let empty = METER_STEPS - filled;
+
+    let mut bar = row![];
+
+    if filled > 0 {
+        bar = bar.push(segment(
+            Length::FillPortion(filled),
+            accent.scale_alpha(0.85),
+        ));
+    }
+
+    if empty > 0 {
+        bar = bar.push(segment(
+            Length::FillPortion(empty),
+            Color::from_rgba(1.0, 1.0, 1.0, 0.08),
+        ));
+    }
+
+    container(bar)
+        .width(Length::Fill)
+        .height(METER_HEIGHT)
+        .into()
+}
+
+fn segment<'a>(width: Length, color: Color) -> Element<'a, Message> {
+    container("")
+        .width(width)
+        .height(Length::Fill)
+        .style(move |_| {
+            container::Style::default()
+                .background(Background::Color(color))
+                .border(Border {
+                    color: Color::TRANSPARENT,
+                    width: 0.0,
+                    radius: 999.0.into(),
+                })
+        })
+        .into()
+}
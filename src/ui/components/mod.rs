@@ -1,3 +1,5 @@
 pub mod chrome_button;
 pub mod drag_handle;
+pub mod level_meter;
 pub mod status_indicator;
+pub mod waveform;
@@ -2,7 +2,7 @@ use crate::app::Message;
 use iced::widget::{container, row, text};
 use iced::{Alignment, Background, Border, Color, Element};
 
-pub fn view<'a>(label: &'a str, accent: Color) -> Element<'a, Message> {
+pub fn view<'a>(label: impl text::IntoFragment<'a>, accent: Color) -> Element<'a, Message> {
     let dot = container("").width(8).height(8).style(move |_| {
         container::Style::default()
             .background(Background::Color(accent))
@@ -0,0 +1,49 @@
+use crate::app::Message;
+use crate::modules::audio::domain::db_fraction;
+use iced::widget::{Space, container, row};
+use iced::{Alignment, Background, Border, Color, Element, Length};
+use std::collections::VecDeque;
+
+const WAVEFORM_HEIGHT: f32 = 14.0;
+const MIN_BAR_HEIGHT: f32 = 1.0;
+const VISIBLE_BARS: usize = 120;
+
+/// Scrolling waveform: newest bar on the right, empty slots on the left
+/// until enough audio has been captured to fill the width.
+pub fn view<'a>(peaks: &VecDeque<f32>, accent: Color) -> Element<'a, Message> {
+    let visible = peaks.len().min(VISIBLE_BARS);
+    let padding = VISIBLE_BARS - visible;
+
+    let mut bars = row![].spacing(1).align_y(Alignment::Center);
+
+    for _ in 0..padding {
+        bars = bars.push(bar(MIN_BAR_HEIGHT, Color::from_rgba(1.0, 1.0, 1.0, 0.08)));
+    }
+
+    for peak in peaks.iter().skip(peaks.len() - visible) {
+        let height = (db_fraction(*peak) * WAVEFORM_HEIGHT).max(MIN_BAR_HEIGHT);
+        bars = bars.push(bar(height, accent.scale_alpha(0.85)));
+    }
+
+    container(bars)
+        .width(Length::Fill)
+        .height(WAVEFORM_HEIGHT)
+        .align_y(Alignment::Center)
+        .into()
+}
+
+fn bar<'a>(height: f32, color: Color) -> Element<'a, Message> {
+    container(Space::new())
+        .width(Length::Fill)
+        .height(height)
+        .style(move |_| {
+            container::Style::default()
+                .background(Background::Color(color))
+                .border(Border {
+                    color: Color::TRANSPARENT,
+                    width: 0.0,
+                    radius: 999.0.into(),
+                })
+        })
+        .into()
+}
@@ -0,0 +1,221 @@
+use crate::app::{Message, Overlay};
+use crate::modules::dictation::domain::{DictationProvider, HistoryEntry};
+use crate::modules::live_transcription::infrastructure::db::format_iso_for_display;
+use iced::widget::{Id, Space, button, column, container, row, scrollable, text, text_input};
+use iced::{Alignment, Background, Border, Color, Element, Length, Shadow};
+
+/// Search box focused by the history shortcut.
+pub const SEARCH_INPUT: Id = Id::new("history-search");
+
+pub fn tab_content(state: &Overlay) -> Element<'_, Message> {
+    let search_bar = text_input("Buscar no historico...", &state.history_search_query)
+        .id(SEARCH_INPUT)
+        .on_input(Message::HistorySearchChanged)
+        .padding([10, 14])
+        .size(13);
+
+    let clear_label = if state.history_confirm_clear {
+        "Confirmar limpeza"
+    } else {
+        "Limpar historico"
+    };
+    let header = row![
+        text(format!("{} transcricoes", state.history_entries.len()))
+            .size(13)
+            .color(muted()),
+        Space::new().width(Length::Fill),
+        button(
+            text(clear_label)
+                .size(12)
+                .color(Color::from_rgba8(248, 113, 113, 0.85)),
+        )
+        .on_press_maybe((!state.history_entries.is_empty()).then_some(Message::ClearHistory))
+        .style(|_, _| action_btn_style())
+        .padding([6, 14]),
+    ]
+    .align_y(Alignment::Center);
+
+    let body: Element<'_, Message> = if state.history_loading {
+        text("Carregando historico...")
+            .size(13)
+            .color(muted())
+            .into()
+    } else if let Some(error) = state
+        .history_error
+        .as_ref()
+        .filter(|_| state.history_entries.is_empty())
+    {
+        error_text(error)
+    } else if state.history_entries.is_empty() {
+        text("Nenhuma transcricao ainda. Cada ditado concluido aparece aqui.")
+            .size(13)
+            .color(muted())
+            .into()
+    } else {
+        let entries = state
+            .history_entries
+            .iter()
+            .filter(|entry| entry.matches(&state.history_search_query))
+            .map(|entry| entry_card(state, entry));
+        let list = scrollable(column(entries).spacing(8)).height(Length::Fill);
+        match &state.history_error {
+            // An action on one entry failed; keep the list on screen.
+            Some(error) => column![error_text(error), list].spacing(8).into(),
+            None => list.into(),
+        }
+    };
+
+    column![header, search_bar, body].spacing(16).into()
+}
+
+fn entry_card<'a>(state: &Overlay, entry: &'a HistoryEntry) -> Element<'a, Message> {
+    let mut details = vec![
+        DictationProvider::from_code(&entry.provider)
+            .label()
+            .to_owned(),
+        format!("{:.1}s", entry.duration_seconds),
+    ];
+    if let Some(model) = &entry.model {
+        details.insert(1, model.clone());
+    }
+    let stats = entry.stats();
+    details.push(format!("{} palavra(s)", stats.words));
+    if let Some(latency_ms) = stats.latency_ms {
+        details.push(format!("{:.1}s de espera", latency_ms as f64 / 1000.0));
+    }
+    if let Some(cost) = entry.cost_usd {
+        details.push(format!("US$ {cost:.4}"));
+    }
+    if let Some(api_key) = &entry.api_key {
+        details.push(format!("chave {api_key}"));
+    }
+
+    let summary = row![
+        column![
+            text(format_iso_for_display(&entry.created_at))
+                .size(11)
+                .color(muted()),
+            text(details.join(" · ")).size(11).color(muted()),
+        ]
+        .spacing(3),
+        Space::new().width(Length::Fill),
+        audio_actions(state, entry),
+        ghost_btn("Copiar", Color::WHITE, Message::CopyHistoryEntry(entry.id)),
+        ghost_btn(
+            "\u{2715}",
+            Color::from_rgba8(248, 113, 113, 0.65),
+            Message::DeleteHistoryEntry(entry.id)
+        ),
+    ]
+    .align_y(Alignment::Center)
+    .spacing(8);
+
+    container(
+        column![
+            summary,
+            text(entry.text.as_str())
+                .size(13)
+                .color(Color::from_rgba(1.0, 1.0, 1.0, 0.85)),
+        ]
+        .spacing(8),
+    )
+    .width(Length::Fill)
+    .padding([12, 16])
+    .style(|_| card_style())
+    .into()
+}
+
+/// Replay and re-transcribe, for entries whose audio was kept.
+fn audio_actions<'a>(state: &Overlay, entry: &HistoryEntry) -> Element<'a, Message> {
+    if entry.audio_path.is_none() {
+        return Space::new().into();
+    }
+
+    let playing = state.history_playing == Some(entry.id);
+    let retranscribing = state.history_retranscribing == Some(entry.id);
+    let play = button(
+        text(if playing { "Tocando..." } else { "Ouvir" })
+            .size(11)
+            .color(Color::WHITE),
+    )
+    .on_press_maybe(
+        state
+            .history_playing
+            .is_none()
+            .then_some(Message::PlayHistoryEntry(entry.id)),
+    )
+    .style(|_, _| ghost_btn_style())
+    .padding([4, 8]);
+    let retranscribe = button(
+        text(if retranscribing {
+            "Retranscrevendo..."
+        } else {
+            "Retranscrever"
+        })
+        .size(11)
+        .color(Color::WHITE),
+    )
+    .on_press_maybe(
+        state
+            .history_retranscribing
+            .is_none()
+            .then_some(Message::RetranscribeHistoryEntry(entry.id)),
+    )
+    .style(|_, _| ghost_btn_style())
+    .padding([4, 8]);
+
+    row![play, retranscribe].spacing(8).into()
+}
+
+fn error_text<'a>(error: &str) -> Element<'a, Message> {
+    text(format!("Erro: {error}"))
+        .size(13)
+        .color(Color::from_rgb8(249, 115, 22))
+        .into()
+}
+
+fn ghost_btn<'a>(label: &'static str, color: Color, message: Message) -> Element<'a, Message> {
+    button(text(label).size(11).color(color))
+        .on_press(message)
+        .style(|_, _| ghost_btn_style())
+        .padding([4, 8])
+        .into()
+}
+
+fn muted() -> Color {
+    Color::from_rgba(1.0, 1.0, 1.0, 0.38)
+}
+
+fn card_style() -> container::Style {
+    container::Style::default()
+        .background(Background::Color(Color::from_rgba(1.0, 1.0, 1.0, 0.06)))
+        .border(Border {
+            color: Color::from_rgba(1.0, 1.0, 1.0, 0.07),
+            width: 1.0,
+            radius: 10.0.into(),
+        })
+}
+
+fn ghost_btn_style() -> button::Style {
+    button::Style {
+        background: None,
+        border: Border::default(),
+        shadow: Shadow::default(),
+        text_color: Color::WHITE,
+        snap: false,
+    }
+}
+
+fn action_btn_style() -> button::Style {
+    button::Style {
+        background: Some(Background::Color(Color::from_rgba(1.0, 1.0, 1.0, 0.06))),
+        border: Border {
+            color: Color::from_rgba(1.0, 1.0, 1.0, 0.12),
+            width: 1.0,
+            radius: 6.0.into(),
+        },
+        shadow: Shadow::default(),
+        text_color: Color::WHITE,
+        snap: false,
+    }
+}
@@ -1,9 +1,17 @@
 use crate::app::{HomeTab, Message, Overlay};
 use crate::modules::live_transcription::infrastructure::db::format_iso_for_display;
-use crate::ui::{copilot, sessions, settings};
-use iced::widget::{Space, button, column, container, row, scrollable, text};
+use crate::modules::settings::application as settings_application;
+use crate::modules::settings::domain::{OnboardingState, OnboardingStep};
+use crate::ui::components::level_meter;
+use crate::ui::{copilot, history, sessions, settings};
+use iced::widget::{
+    Space, button, column, container, pick_list, row, scrollable, text, text_input,
+};
 use iced::{Alignment, Background, Border, Color, Element, Length, Shadow};
 
+const NO_TEMPLATE: &str = "Sem template";
+const DEFAULT_INPUT_DEVICE: &str = "Microfone padrao do sistema";
+
 pub fn view(state: &Overlay) -> Element<'_, Message> {
     let header = row![
         column![
@@ -25,6 +33,7 @@ pub fn view(state: &Overlay) -> Element<'_, Message> {
         HomeTab::Home => scrollable(home_content(state)).height(Length::Fill).into(),
         HomeTab::Copilot => copilot::session_tab_content(state),
         HomeTab::Sessions => sessions::tab_content(state),
+        HomeTab::History => history::tab_content(state),
         HomeTab::Settings => settings::tab_content(state),
     };
 
@@ -95,7 +104,28 @@ fn home_content(state: &Overlay) -> Element<'_, Message> {
     ]
     .spacing(12);
 
-    let mut content = column![cards].spacing(16);
+    let mut content = column![].spacing(16);
+
+    let onboarding = state.settings.onboarding_state(&state.input_devices);
+    if !onboarding.is_complete() {
+        content = content.push(onboarding_card(state, &onboarding));
+    }
+
+    content = content.push(cards);
+
+    if !state.settings.dictation_templates.is_empty() {
+        content = content.push(template_picker(state));
+    }
+
+    content = content.push(profile_picker(state));
+    content = content.push(model_picker(state));
+    content = content.push(input_device_picker(state));
+    content = content.push(shortcuts_toggle(state));
+    content = content.push(session_card(state));
+
+    if let Some(pending) = &state.pending_output {
+        content = content.push(pending_output_card(pending));
+    }
 
     // Status hints
     let status = status_hints(state);
@@ -123,6 +153,291 @@ fn home_content(state: &Overlay) -> Element<'_, Message> {
     content.into()
 }
 
+/// Quick switch for the template wrapped around each dictation.
+fn template_picker(state: &Overlay) -> Element<'_, Message> {
+    let options: Vec<String> = std::iter::once(String::from(NO_TEMPLATE))
+        .chain(
+            state
+                .settings
+                .dictation_templates
+                .iter()
+                .map(|template| template.name.clone()),
+        )
+        .collect();
+    let selected = state.settings.dictation_template().map_or_else(
+        || String::from(NO_TEMPLATE),
+        |template| template.name.clone(),
+    );
+
+    row![
+        text("Template do ditado")
+            .size(13)
+            .color(Color::from_rgba8(148, 163, 184, 0.9))
+            .width(Length::Fill),
+        pick_list(options, Some(selected), |name| {
+            Message::SelectDictationTemplate(if name == NO_TEMPLATE {
+                String::new()
+            } else {
+                name
+            })
+        })
+        .text_size(13),
+    ]
+    .spacing(12)
+    .align_y(Alignment::Center)
+    .into()
+}
+
+/// Switches between saved setups (model, template, language, output mode
+/// and shortcut) and saves the current one under a name.
+fn profile_picker(state: &Overlay) -> Element<'_, Message> {
+    let profiles = settings_application::list_profiles(&state.settings);
+    let selected = state
+        .settings
+        .active_profile()
+        .map(|profile| profile.name.clone());
+
+    let mut controls = row![
+        text("Perfil")
+            .size(13)
+            .color(Color::from_rgba8(148, 163, 184, 0.9))
+            .width(Length::Fill),
+    ]
+    .spacing(8)
+    .align_y(Alignment::Center);
+
+    if !profiles.is_empty() {
+        controls = controls.push(
+            pick_list(profiles, selected.clone(), Message::ActivateProfile)
+                .placeholder("Nenhum")
+                .text_size(13),
+        );
+    }
+    if selected.is_some() {
+        controls = controls.push(session_button("Remover", Message::DeleteActiveProfile));
+    }
+
+    controls
+        .push(
+            text_input("Nome do perfil", &state.profile_name)
+                .on_input(Message::ProfileNameChanged)
+                .on_submit(Message::SaveProfile)
+                .padding([8, 12])
+                .size(13)
+                .width(Length::Fixed(140.0)),
+        )
+        .push(session_button("Salvar perfil", Message::SaveProfile))
+        .into()
+}
+
+/// Quick switch between the models configured in settings.
+fn model_picker(state: &Overlay) -> Element<'_, Message> {
+    row![
+        text("Modelo do ditado")
+            .size(13)
+            .color(Color::from_rgba8(148, 163, 184, 0.9))
+            .width(Length::Fill),
+        pick_list(
+            state.settings.dictation_model_choices(),
+            Some(state.settings.active_dictation_model()),
+            Message::SelectDictationModel,
+        )
+        .text_size(13),
+    ]
+    .spacing(12)
+    .align_y(Alignment::Center)
+    .into()
+}
+
+/// Quick switch for the microphone, e.g. after docking a laptop. The list
+/// is read again every time it opens.
+fn input_device_picker(state: &Overlay) -> Element<'_, Message> {
+    let options: Vec<String> = std::iter::once(String::from(DEFAULT_INPUT_DEVICE))
+        .chain(state.input_devices.iter().cloned())
+        .collect();
+    let selected = match state.settings.dictation_input_device.as_str() {
+        "" => String::from(DEFAULT_INPUT_DEVICE),
+        name => name.to_owned(),
+    };
+
+    row![
+        text("Microfone")
+            .size(13)
+            .color(Color::from_rgba8(148, 163, 184, 0.9))
+            .width(Length::Fill),
+        pick_list(options, Some(selected), |name| {
+            Message::SelectInputDevice(if name == DEFAULT_INPUT_DEVICE {
+                String::new()
+            } else {
+                name
+            })
+        })
+        .on_open(Message::RefreshInputDevices)
+        .text_size(13),
+    ]
+    .spacing(12)
+    .align_y(Alignment::Center)
+    .into()
+}
+
+/// Pauses every global shortcut, e.g. while gaming or sharing the screen.
+fn shortcuts_toggle(state: &Overlay) -> Element<'_, Message> {
+    let (status, action) = if state.settings.shortcuts_paused {
+        ("Atalhos globais pausados", "Retomar")
+    } else {
+        ("Atalhos globais ativos", "Pausar")
+    };
+
+    row![
+        text(status)
+            .size(13)
+            .color(Color::from_rgba8(148, 163, 184, 0.9))
+            .width(Length::Fill),
+        session_button(action, Message::ToggleShortcutsPaused),
+    ]
+    .spacing(12)
+    .align_y(Alignment::Center)
+    .into()
+}
+
+/// Session controls and, while one is open, the document so far.
+fn session_card(state: &Overlay) -> Element<'_, Message> {
+    let Some(session) = &state.dictation_session else {
+        return row![
+            text("Sessao de ditado: junta varios ditados em um documento")
+                .size(13)
+                .color(Color::from_rgba8(148, 163, 184, 0.9))
+                .width(Length::Fill),
+            session_button("Iniciar sessao", Message::StartDictationSession),
+        ]
+        .spacing(12)
+        .align_y(Alignment::Center)
+        .into();
+    };
+
+    let document = if session.is_empty() {
+        String::from("Dite para comecar o documento.")
+    } else {
+        session.text()
+    };
+
+    container(
+        column![
+            row![
+                text(format!("Sessao de ditado: {} trecho(s)", session.len()))
+                    .size(13)
+                    .color(Color::from_rgba8(148, 163, 184, 0.9))
+                    .width(Length::Fill),
+                session_button("Encerrar e copiar", Message::EndDictationSession),
+                session_button("Descartar", Message::DiscardDictationSession),
+            ]
+            .spacing(12)
+            .align_y(Alignment::Center),
+            scrollable(text(document).size(14).color(Color::WHITE)).height(Length::Shrink),
+        ]
+        .spacing(10),
+    )
+    .padding(14)
+    .style(|_| recent_card_style())
+    .into()
+}
+
+fn session_button(label: &str, message: Message) -> Element<'_, Message> {
+    button(text(label).size(13))
+        .on_press(message)
+        .style(|_, _| ghost_btn_style())
+        .padding([8, 12])
+        .into()
+}
+
+/// First-run checklist; global shortcuts stay unbound until it is done.
+fn onboarding_card<'a>(state: &'a Overlay, onboarding: &OnboardingState) -> Element<'a, Message> {
+    let steps = OnboardingStep::ALL.into_iter().map(|step| {
+        let pending = onboarding.is_pending(step);
+        let action: Option<Element<'a, Message>> = match step {
+            _ if !pending => None,
+            OnboardingStep::ApiKey => Some(session_button(
+                "Abrir settings",
+                Message::SwitchHomeTab(HomeTab::Settings),
+            )),
+            OnboardingStep::Microphone => Some(session_button(
+                "Procurar de novo",
+                Message::RefreshInputDevices,
+            )),
+            // Confirmed last, once nothing else is missing.
+            OnboardingStep::Shortcut => {
+                (onboarding.pending == [OnboardingStep::Shortcut]).then(|| {
+                    button(text(format!("Usar {}", state.settings.dictation_shortcut)).size(13))
+                        .on_press(Message::CompleteOnboardingStep(OnboardingStep::Shortcut))
+                        .style(|_, _| ghost_btn_style())
+                        .padding([8, 12])
+                        .into()
+                })
+            }
+        };
+
+        row![
+            text(if pending { "[ ]" } else { "[x]" })
+                .size(13)
+                .color(Color::from_rgba8(148, 163, 184, 0.9)),
+            text(step.label()).size(13).width(Length::Fill),
+        ]
+        .push(action)
+        .spacing(12)
+        .align_y(Alignment::Center)
+        .into()
+    });
+
+    container(
+        column![
+            text("Primeiros passos")
+                .size(13)
+                .color(Color::from_rgb8(251, 146, 60)),
+            text("Os atalhos globais so sao registrados depois de concluir estes passos.")
+                .size(12)
+                .color(Color::from_rgba8(148, 163, 184, 0.9)),
+        ]
+        .extend(steps)
+        .spacing(10),
+    )
+    .padding(14)
+    .style(|_| recent_card_style())
+    .into()
+}
+
+/// Transcription the clipboard refused, with a button to try again.
+fn pending_output_card(pending: &str) -> Element<'_, Message> {
+    let preview = pending.split_whitespace().collect::<Vec<_>>().join(" ");
+
+    container(
+        row![
+            column![
+                text("Transcricao pendente")
+                    .size(13)
+                    .color(Color::from_rgb8(251, 146, 60)),
+                text(preview)
+                    .size(13)
+                    .color(Color::from_rgba8(226, 232, 240, 0.8)),
+            ]
+            .spacing(4)
+            .width(Length::Fill),
+            button(text("Copiar pendente").size(13))
+                .on_press(Message::FlushPendingOutput)
+                .style(|_, _| ghost_btn_style())
+                .padding([8, 12]),
+            button(text("Descartar").size(13))
+                .on_press(Message::DiscardPendingOutput)
+                .style(|_, _| ghost_btn_style())
+                .padding([8, 12]),
+        ]
+        .spacing(12)
+        .align_y(Alignment::Center),
+    )
+    .padding(14)
+    .style(|_| error_card_style())
+    .into()
+}
+
 fn status_hints(state: &Overlay) -> Element<'_, Message> {
     let mut items: Vec<Element<'_, Message>> = Vec::new();
 
@@ -144,16 +459,37 @@ fn status_hints(state: &Overlay) -> Element<'_, Message> {
             Color::from_rgb8(251, 146, 60),
         ));
     } else if state.is_processing() {
+        let accent = Color::from_rgb8(251, 146, 60);
+        let label = match state.dictation_stage {
+            Some(stage) => stage.label(),
+            None => String::from("Processando ditado..."),
+        };
+        items.push(status_pill(&label, accent));
+
+        if let Some(fraction) = state.dictation_progress_fraction() {
+            items.push(level_meter::fraction(fraction, accent));
+        }
+    } else if let Some(stats) = &state.last_dictation_stats {
         items.push(status_pill(
-            "Processando ditado...",
-            Color::from_rgb8(251, 146, 60),
+            &format!("Ultimo ditado: {}", stats.label()),
+            Color::from_rgb8(74, 222, 128),
+        ));
+    }
+
+    if state.settings.shortcuts_paused {
+        items.push(status_pill(
+            "Atalhos globais pausados",
+            Color::from_rgb8(250, 204, 21),
         ));
     }
 
     // Provider status
-    if !state.settings.has_api_key() {
+    if !state.settings.has_dictation_credentials() {
         items.push(status_pill(
-            "OpenRouter API key nao configurada",
+            &format!(
+                "{} API key nao configurada",
+                state.settings.dictation_provider()
+            ),
             Color::from_rgb8(248, 113, 113),
         ));
     }
@@ -309,6 +645,7 @@ fn tab_bar(active: HomeTab) -> Element<'static, Message> {
         tab_button("Inicio", HomeTab::Home, active),
         tab_button("Copilot", HomeTab::Copilot, active),
         tab_button("Sessoes", HomeTab::Sessions, active),
+        tab_button("Historico", HomeTab::History, active),
         tab_button("Configuracoes", HomeTab::Settings, active),
     ]
     .spacing(4)
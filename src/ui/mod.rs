@@ -1,6 +1,7 @@
 pub mod components;
 pub mod copilot;
 pub mod copilot_response;
+pub mod history;
 pub mod home;
 pub mod overlay;
 pub mod sessions;
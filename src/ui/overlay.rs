@@ -1,9 +1,11 @@
 use crate::app::{HomeTab, Message, Overlay, OverlayPhase};
 use crate::ui::components::chrome_button::{self, ButtonKind};
 use crate::ui::components::drag_handle;
+use crate::ui::components::level_meter;
 use crate::ui::components::status_indicator;
-use iced::widget::{Space, column, container, row, text};
-use iced::{Alignment, Background, Border, Color, Element, Length, Shadow};
+use crate::ui::components::waveform;
+use iced::widget::{Space, column, container, mouse_area, row, text};
+use iced::{Alignment, Background, Border, Color, Element, Length, Shadow, mouse};
 
 pub fn view(state: &Overlay) -> Element<'_, Message> {
     let accent = phase_color(state.phase);
@@ -24,12 +26,59 @@ pub fn view(state: &Overlay) -> Element<'_, Message> {
         None
     };
 
+    // Only while recording or transcribing: the idle row has no room left.
+    let cancel_button: Element<'_, Message> = if state.dictation_cancel.is_some() {
+        chrome_button::view(
+            "ESC",
+            Some(Message::CancelTranscription),
+            ButtonKind::Caption(accent),
+        )
+    } else if state.is_dictation_recording() {
+        chrome_button::view(
+            "ESC",
+            Some(Message::CancelRecording),
+            ButtonKind::Caption(accent),
+        )
+    } else {
+        Space::new().width(0).into()
+    };
+
+    let session_button: Element<'_, Message> = if state.is_dictation_recording() {
+        let monitor_color = if state.dictation_monitor_enabled {
+            accent
+        } else {
+            Color::from_rgba(1.0, 1.0, 1.0, 0.4)
+        };
+
+        chrome_button::view(
+            "MON",
+            Some(Message::ToggleDictationMonitor),
+            ButtonKind::Caption(monitor_color),
+        )
+    } else {
+        Space::new().width(0).into()
+    };
+
+    // Transcriptions the clipboard refused; a click copies them again.
+    let pending_button: Element<'_, Message> = if state.pending_count() > 0 {
+        chrome_button::view(
+            "PEND",
+            Some(Message::FlushPendingOutput),
+            ButtonKind::Caption(Color::from_rgb8(251, 146, 60)),
+        )
+    } else {
+        Space::new().width(0).into()
+    };
+
     let status_label = match state.phase {
-        OverlayPhase::Idle => "READY",
-        OverlayPhase::Recording => "REC",
-        OverlayPhase::Processing => "WAIT",
-        OverlayPhase::Success => "COPIED",
-        OverlayPhase::Error => "ERROR",
+        OverlayPhase::Idle => String::from("READY"),
+        OverlayPhase::Recording => String::from("REC"),
+        OverlayPhase::Processing if state.is_uploading() => {
+            format!("{} SEND", state.processing_spinner())
+        }
+        OverlayPhase::Processing => format!("{} WAIT", state.processing_spinner()),
+        OverlayPhase::Success => String::from("COPIED"),
+        OverlayPhase::Error => String::from("ERROR"),
     };
 
     let info_text = state
@@ -47,8 +96,13 @@ pub fn view(state: &Overlay) -> Element<'_, Message> {
     let mut content = column![
         row![
             drag_handle::view(),
-            status_indicator::view(status_label, accent),
+            mouse_area(status_indicator::view(status_label, accent))
+                .on_press(Message::HudStatusClicked)
+                .interaction(mouse::Interaction::Pointer),
             Space::new().width(Length::Fill),
+            pending_button,
+            cancel_button,
+            session_button,
             chrome_button::view("CC", realtime_action, ButtonKind::Caption(accent)),
             chrome_button::view("", mic_action, ButtonKind::Mic(accent)),
             chrome_button::view("AI", Some(Message::OpenCopilotView), ButtonKind::Ghost),
@@ -66,7 +120,18 @@ pub fn view(state: &Overlay) -> Element<'_, Message> {
     ]
     .spacing(8);
 
-    if has_info {
+    let show_waveform =
+        state.is_dictation_recording() && state.error.is_none() && !state.dictation_clipping;
+
+    if state.is_dictation_recording() {
+        content = content.push(level_meter::view(state.dictation_level, accent));
+    } else if let Some(fraction) = state.dictation_progress_fraction() {
+        content = content.push(level_meter::fraction(fraction, accent));
+    }
+
+    if show_waveform {
+        content = content.push(waveform::view(&state.dictation_waveform, accent));
+    } else if has_info {
         content = content.push(text(info_text).size(11).color(info_color));
     }
 
@@ -1,13 +1,26 @@
 use crate::app::{Message, Overlay};
+use crate::modules::backup::application::DEFAULT_BACKUP_FILE;
 use crate::modules::copilot::domain::CopilotMode;
+use crate::modules::dictation::application as dictation_application;
+use crate::modules::dictation::domain::{
+    AudioFormat, DictationCaptureMode, DictationOutputMode, DictationProvider, IdleStopAction,
+    ModelComparison, ResampleQuality, TargetSampleRate,
+};
+use crate::modules::settings::application::DEFAULT_CONFIG_EXPORT_FILE;
 use crate::modules::settings::domain::{
-    SUPPORTED_OPENAI_REALTIME_LANGUAGES, SUPPORTED_OPENAI_REALTIME_PROFILES,
+    DEFAULT_DICTATION_SHORTCUT, HudClickAction, ResetScope, SUPPORTED_OPENAI_REALTIME_LANGUAGES,
+    SUPPORTED_OPENAI_REALTIME_PROFILES,
 };
+use crate::modules::shortcuts::domain::{ShortcutAction, ShortcutMode};
+use crate::support::log::{self, LogLevel};
 use iced::widget::{
     Space, button, checkbox, column, container, pick_list, row, scrollable, text, text_input,
 };
 use iced::{Alignment, Background, Border, Color, Element, Length, Shadow};
 
+/// Segments of the last take listed under the recordings settings.
+const LAST_DICTATION_SEGMENTS: usize = 8;
+
 pub fn tab_content(state: &Overlay) -> Element<'_, Message> {
     let save_settings = action_button(
         if state.is_saving_settings {
@@ -77,6 +90,23 @@ pub fn tab_content(state: &Overlay) -> Element<'_, Message> {
         .style(|_| card_style())
     });
 
+    let usage_summary = match &state.dictation_usage {
+        Some(usage) => format!(
+            "Uso do ditado - hoje: {}. Mes: {}.",
+            usage.today.summary(),
+            usage.month.summary()
+        ),
+        None => String::from("Uso do ditado ainda nao carregado."),
+    };
+
+    let streaming_label = if dictation_application::provider_supports_streaming(
+        DictationProvider::from_code(&state.settings_form.dictation_provider),
+    ) {
+        "Transcrever em streaming durante a gravacao"
+    } else {
+        "Transcrever em streaming (o provedor atual envia o audio ao final)"
+    };
+
     let content = column![
         container(
             column![
@@ -93,19 +123,657 @@ pub fn tab_content(state: &Overlay) -> Element<'_, Message> {
                 .on_input(Message::SettingsApiKeyChanged)
                 .secure(true)
                 .padding([12, 14]),
-                text_input("Modelo", &state.settings_form.openrouter_model)
-                    .on_input(Message::SettingsModelChanged)
+                text_input(
+                    "Chaves extras, usadas se a anterior for recusada ou ficar sem credito (separadas por virgula)",
+                    &state.settings_form.openrouter_fallback_api_keys
+                )
+                .on_input(Message::SettingsFallbackApiKeysChanged)
+                .secure(true)
+                .padding([12, 14]),
+                row![
+                    text_input("Modelo", &state.settings_form.openrouter_model)
+                        .on_input(Message::SettingsModelChanged)
+                        .padding([12, 14]),
+                    pick_list(
+                        state.openrouter_models.as_slice(),
+                        state
+                            .openrouter_models
+                            .iter()
+                            .find(|model| **model == state.settings_form.openrouter_model),
+                        Message::SettingsModelChanged
+                    )
+                    .placeholder("Modelos com audio")
+                    .padding([12, 14]),
+                    action_button(
+                        if state.is_loading_openrouter_models {
+                            "Buscando..."
+                        } else {
+                            "Atualizar"
+                        },
+                        (!state.is_loading_openrouter_models)
+                            .then_some(Message::RefreshOpenRouterModels)
+                    ),
+                ]
+                .spacing(12)
+                .align_y(Alignment::Center),
+                text_input(
+                    "Modelos de fallback, em ordem (separados por virgula)",
+                    &state.settings_form.openrouter_fallback_models
+                )
+                .on_input(Message::SettingsFallbackModelsChanged)
+                .padding([12, 14]),
+                row![
+                    text_input(
+                        "Modelo para comparar com a ultima captura",
+                        &state.settings_form.openrouter_compare_model
+                    )
+                    .on_input(Message::SettingsCompareModelChanged)
+                    .padding([12, 14]),
+                    action_button(
+                        if state.is_comparing_models {
+                            "Comparando..."
+                        } else {
+                            "Comparar modelos"
+                        },
+                        (!state.is_comparing_models && state.last_capture.is_some())
+                            .then_some(Message::CompareModels)
+                    ),
+                ]
+                .spacing(12)
+                .align_y(Alignment::Center),
+                state
+                    .model_comparison
+                    .as_ref()
+                    .map(model_comparison_view)
+                    .unwrap_or_else(|| Element::from(Space::new().height(0))),
+                row![
+                    text_input(
+                        "Temperatura (vazio = padrao)",
+                        &state.settings_form.openrouter_temperature
+                    )
+                    .on_input(Message::SettingsTemperatureChanged)
+                    .padding([12, 14]),
+                    text_input("top_p (vazio = padrao)", &state.settings_form.openrouter_top_p)
+                        .on_input(Message::SettingsTopPChanged)
+                        .padding([12, 14]),
+                    text_input(
+                        "Max tokens (vazio = padrao)",
+                        &state.settings_form.openrouter_max_tokens
+                    )
+                    .on_input(Message::SettingsMaxTokensChanged)
+                    .padding([12, 14]),
+                ]
+                .spacing(12),
+                text(
+                    "Ajustes por modelo: prompt extra, temperatura e idioma usados quando o modelo e o do ditado."
+                )
+                .size(12)
+                .color(Color::from_rgba8(148, 163, 184, 0.88)),
+                model_overrides_editor(state),
+                row![
+                    text_input(
+                        "HTTP-Referer (vazio = nao enviar)",
+                        &state.settings_form.openrouter_referer
+                    )
+                    .on_input(Message::SettingsRefererChanged)
+                    .padding([12, 14]),
+                    text_input(
+                        "X-Title (vazio = nao enviar)",
+                        &state.settings_form.openrouter_app_title
+                    )
+                    .on_input(Message::SettingsAppTitleChanged)
+                    .padding([12, 14]),
+                ]
+                .spacing(12),
+                text_input(
+                    "URL base da API (vazio = OpenRouter; LiteLLM ou proxy compativel)",
+                    &state.settings_form.openrouter_base_url
+                )
+                .on_input(Message::SettingsBaseUrlChanged)
+                .padding([12, 14]),
+                checkbox(state.settings_form.openrouter_privacy_mode)
+                    .label("Modo privado: nao identificar o app e recusar provedores que guardam os pedidos")
+                    .on_toggle(Message::SettingsPrivacyModeChanged)
+                    .text_size(13),
+                checkbox(state.settings_form.api_keys_in_keyring)
+                    .label("Guardar as API keys no keyring do sistema em vez do settings.json")
+                    .on_toggle(Message::SettingsApiKeysInKeyringChanged)
+                    .text_size(13),
+            ]
+            .spacing(14),
+        )
+        .padding(18)
+        .style(|_| card_style()),
+        container(
+            column![
+                section_title("Deepgram"),
+                text(
+                    "O Deepgram aceita streaming: com ele como provedor, o texto parcial aparece no HUD enquanto voce fala."
+                )
+                .size(12)
+                .color(Color::from_rgba8(148, 163, 184, 0.88)),
+                text_input(
+                    "Deepgram API key",
+                    &state.settings_form.deepgram_api_key
+                )
+                .on_input(Message::SettingsDeepgramApiKeyChanged)
+                .secure(true)
+                .padding([12, 14]),
+                text_input("Modelo Deepgram", &state.settings_form.deepgram_model)
+                    .on_input(Message::SettingsDeepgramModelChanged)
                     .padding([12, 14]),
             ]
             .spacing(14),
         )
         .padding(18)
         .style(|_| card_style()),
+        container(
+            column![
+                section_title("AssemblyAI"),
+                text(
+                    "A AssemblyAI recebe o audio ao final da gravacao e a transcricao chega quando o processamento termina."
+                )
+                .size(12)
+                .color(Color::from_rgba8(148, 163, 184, 0.88)),
+                text_input(
+                    "AssemblyAI API key",
+                    &state.settings_form.assemblyai_api_key
+                )
+                .on_input(Message::SettingsAssemblyAiApiKeyChanged)
+                .secure(true)
+                .padding([12, 14]),
+            ]
+            .spacing(14),
+        )
+        .padding(18)
+        .style(|_| card_style()),
+        container(
+            column![
+                section_title("Ditado"),
+                text(
+                    "Com o auto-stop ligado, o ditado termina sozinho depois de um periodo de silencio e segue direto para a transcricao."
+                )
+                .size(12)
+                .color(Color::from_rgba8(148, 163, 184, 0.88)),
+                pick_list(
+                    dictation_application::list_providers(),
+                    Some(DictationProvider::from_code(
+                        &state.settings_form.dictation_provider
+                    )),
+                    |provider| Message::SettingsDictationProviderChanged(
+                        provider.code().to_owned()
+                    )
+                )
+                .placeholder("Provedor do ditado"),
+                text_input(
+                    "Idioma do ditado (ex.: pt-BR, en; vazio detecta)",
+                    &state.settings_form.dictation_language
+                )
+                .on_input(Message::SettingsDictationLanguageChanged)
+                .padding([12, 14]),
+                text_input(
+                    "Orcamento mensal em US$ (vazio = sem aviso)",
+                    &state.settings_form.dictation_monthly_budget_usd
+                )
+                .on_input(Message::SettingsDictationMonthlyBudgetChanged)
+                .padding([12, 14]),
+                text(usage_summary)
+                    .size(12)
+                    .color(Color::from_rgba8(148, 163, 184, 0.88)),
+                checkbox(state.settings_form.dictation_streaming)
+                    .label(streaming_label)
+                    .on_toggle(Message::SettingsDictationStreamingChanged)
+                    .text_size(13),
+                text_input(
+                    "Vocabulario: nomes, siglas e jargoes (separados por virgula)",
+                    &state.settings_form.dictation_vocabulary
+                )
+                .on_input(Message::SettingsDictationVocabularyChanged)
+                .padding([12, 14]),
+                checkbox(state.settings_form.dictation_timestamps)
+                    .label("Incluir timestamps por trecho (permite exportar SRT)")
+                    .on_toggle(Message::SettingsDictationTimestampsChanged)
+                    .text_size(13),
+                checkbox(state.settings_form.dictation_vad_enabled)
+                    .label("Parar automaticamente ao detectar silencio")
+                    .on_toggle(Message::SettingsDictationVadEnabledChanged)
+                    .text_size(13),
+                text_input(
+                    "Silencio para auto-stop (ms)",
+                    &state.settings_form.dictation_silence_threshold_ms
+                )
+                .on_input(Message::SettingsDictationSilenceThresholdChanged)
+                .padding([12, 14]),
+                text(
+                    "O atalho global chama o OpenVoice via Hyprland. Em outros compositors, associe `openvoice --trigger dictation` (ou `restart` para regravar, `cancel` para abandonar a transcricao) manualmente. Use DoubleTap+Ctrl ou DoubleTap+RightShift para um toque duplo, e Mouse4/Mouse5 para os botoes laterais do mouse. Deixe vazio para desativar."
+                )
+                .size(12)
+                .color(Color::from_rgba8(148, 163, 184, 0.88)),
+                state
+                    .shortcut_conflict
+                    .as_ref()
+                    .map(|conflict| {
+                        text(format!("Conflito de atalho: {conflict} Salve para tentar de novo."))
+                            .size(13)
+                            .color(Color::from_rgb8(251, 146, 60))
+                            .into()
+                    })
+                    .unwrap_or_else(|| Element::from(Space::new().height(0))),
+                row![
+                    shortcut_input(
+                        state,
+                        "Atalho global (ex.: Super+Shift+D ou CmdOrCtrl+Shift+V)",
+                        &state.settings_form.dictation_shortcut,
+                        Message::SettingsDictationShortcutChanged,
+                        ShortcutAction::Dictation,
+                    ),
+                    action_button(
+                        "Usar padrao",
+                        Some(Message::SettingsDictationShortcutChanged(String::from(
+                            DEFAULT_DICTATION_SHORTCUT
+                        ))),
+                    ),
+                ]
+                .spacing(12)
+                .align_y(Alignment::Center),
+                shortcut_input(
+                    state,
+                    "Atalho para descartar e regravar (vazio = desativado)",
+                    &state.settings_form.dictation_restart_shortcut,
+                    Message::SettingsDictationRestartShortcutChanged,
+                    ShortcutAction::Restart,
+                ),
+                shortcut_input(
+                    state,
+                    "Atalho para copiar o ultimo ditado de novo (vazio = desativado)",
+                    &state.settings_form.dictation_copy_last_shortcut,
+                    Message::SettingsDictationCopyLastShortcutChanged,
+                    ShortcutAction::CopyLast,
+                ),
+                shortcut_input(
+                    state,
+                    "Atalho para refazer o ultimo ditado com o modelo de retranscricao (vazio = desativado)",
+                    &state.settings_form.dictation_retranscribe_shortcut,
+                    Message::SettingsDictationRetranscribeShortcutChanged,
+                    ShortcutAction::Retranscribe,
+                ),
+                shortcut_input(
+                    state,
+                    "Atalho para abrir o historico com a busca (vazio = desativado)",
+                    &state.settings_form.dictation_history_shortcut,
+                    Message::SettingsDictationHistoryShortcutChanged,
+                    ShortcutAction::History,
+                ),
+                shortcut_input(
+                    state,
+                    "Atalho para desfazer o ultimo ditado (vazio = desativado)",
+                    &state.settings_form.dictation_undo_shortcut,
+                    Message::SettingsDictationUndoShortcutChanged,
+                    ShortcutAction::Undo,
+                ),
+                shortcut_input(
+                    state,
+                    "Atalho para cancelar a gravacao sem enviar, ativo so durante o ditado (ex.: Escape; vazio = desativado)",
+                    &state.settings_form.dictation_cancel_shortcut,
+                    Message::SettingsDictationCancelShortcutChanged,
+                    ShortcutAction::Cancel,
+                ),
+                text_input(
+                    "Tecla com o app em foco para parar, fechar ou sair (padrao: Escape; vazio = nenhuma)",
+                    &state.settings_form.dictation_stop_key
+                )
+                .on_input(Message::SettingsDictationStopKeyChanged)
+                .padding([12, 14]),
+                pick_list(
+                    SUPPORTED_SHORTCUT_MODES,
+                    Some(ShortcutMode::from_code(
+                        &state.settings_form.dictation_shortcut_mode
+                    )),
+                    |mode| Message::SettingsDictationShortcutModeChanged(mode.code().to_owned())
+                )
+                .placeholder("Modo do atalho"),
+                text_input(
+                    "Segurar por quantos ms conta como push-to-talk (tocar alterna)",
+                    &state.settings_form.dictation_hold_threshold_ms
+                )
+                .on_input(Message::SettingsDictationHoldThresholdChanged)
+                .padding([12, 14]),
+                pick_list(
+                    SUPPORTED_CAPTURE_MODES,
+                    Some(DictationCaptureMode::from_code(
+                        &state.settings_form.dictation_capture_mode
+                    )),
+                    |mode| Message::SettingsDictationCaptureModeChanged(mode.code().to_owned())
+                )
+                .placeholder("Fontes de audio do ditado"),
+                pick_list(
+                    SUPPORTED_RESAMPLE_QUALITIES,
+                    Some(ResampleQuality::from_code(
+                        &state.settings_form.dictation_resample_quality
+                    )),
+                    |quality| Message::SettingsDictationResampleQualityChanged(
+                        quality.code().to_owned()
+                    )
+                )
+                .placeholder("Qualidade da conversao de audio"),
+                pick_list(
+                    SUPPORTED_TARGET_SAMPLE_RATES,
+                    Some(TargetSampleRate::from_code(
+                        &state.settings_form.dictation_target_sample_rate
+                    )),
+                    |rate| Message::SettingsDictationTargetSampleRateChanged(
+                        rate.code().to_owned()
+                    )
+                )
+                .placeholder("Taxa de amostragem do envio"),
+                pick_list(
+                    SUPPORTED_AUDIO_FORMATS,
+                    Some(AudioFormat::from_code(&state.settings_form.dictation_audio_format)),
+                    |format| Message::SettingsDictationAudioFormatChanged(format.code().to_owned())
+                )
+                .placeholder("Formato de envio"),
+                text_input(
+                    "Duracao maxima do ditado (s)",
+                    &state.settings_form.dictation_max_duration_secs
+                )
+                .on_input(Message::SettingsDictationMaxDurationChanged)
+                .padding([12, 14]),
+                row![
+                    text_input(
+                        "Parar apos segundos sem fala (vazio = nunca)",
+                        &state.settings_form.dictation_idle_timeout_secs
+                    )
+                    .on_input(Message::SettingsDictationIdleTimeoutChanged)
+                    .padding([12, 14]),
+                    pick_list(
+                        SUPPORTED_IDLE_ACTIONS,
+                        Some(IdleStopAction::from_code(
+                            &state.settings_form.dictation_idle_action
+                        )),
+                        |action| Message::SettingsDictationIdleActionChanged(
+                            action.code().to_owned()
+                        )
+                    )
+                    .placeholder("Ao parar por inatividade"),
+                ]
+                .spacing(12),
+                row![
+                    pick_list(
+                        SUPPORTED_OUTPUT_MODES,
+                        Some(DictationOutputMode::from_code(
+                            &state.settings_form.dictation_output_mode
+                        )),
+                        |mode| Message::SettingsDictationOutputModeChanged(
+                            mode.code().to_owned()
+                        )
+                    )
+                    .placeholder("Destino da transcricao"),
+                    text_input(
+                        "Intervalo entre teclas (ms)",
+                        &state.settings_form.dictation_typing_delay_ms
+                    )
+                    .on_input(Message::SettingsDictationTypingDelayChanged)
+                    .padding([12, 14]),
+                    text_input(
+                        "Restaurar clipboard apos (s, vazio = nunca)",
+                        &state.settings_form.dictation_clipboard_restore_secs
+                    )
+                    .on_input(Message::SettingsDictationClipboardRestoreChanged)
+                    .padding([12, 14]),
+                ]
+                .spacing(12)
+                .align_y(Alignment::Center),
+                text_input(
+                    "URI de saida (ex.: obsidian://new?vault=Notas&content={text})",
+                    &state.settings_form.dictation_uri_template
+                )
+                .on_input(Message::SettingsDictationUriTemplateChanged)
+                .padding([12, 14]),
+                checkbox(state.settings_form.dictation_auto_submit_confirmed)
+                    .label("Entendo que \"Colar e enviar\" aperta Enter e envia a mensagem sem revisao")
+                    .on_toggle(Message::SettingsDictationAutoSubmitConfirmedChanged)
+                    .text_size(13),
+                row![
+                    checkbox(state.settings_form.dictation_history_audio)
+                        .label("Guardar o audio no historico")
+                        .on_toggle(Message::SettingsDictationHistoryAudioChanged)
+                        .text_size(13),
+                    text_input(
+                        "Limite (MB)",
+                        &state.settings_form.dictation_history_audio_max_mb
+                    )
+                    .on_input(Message::SettingsDictationHistoryAudioMaxMbChanged)
+                    .padding([12, 14]),
+                    text_input(
+                        "Apagar apos dias (vazio = nunca)",
+                        &state.settings_form.dictation_history_audio_max_days
+                    )
+                    .on_input(Message::SettingsDictationHistoryAudioMaxDaysChanged)
+                    .padding([12, 14]),
+                ]
+                .spacing(12)
+                .align_y(Alignment::Center),
+                text_input(
+                    "Modelo para retranscrever (vazio = modelo atual)",
+                    &state.settings_form.dictation_retranscribe_model
+                )
+                .on_input(Message::SettingsDictationRetranscribeModelChanged)
+                .padding([12, 14]),
+                templates_editor(state),
+                checkbox(state.settings_form.dictation_write_primary)
+                    .label("Copiar tambem para a selecao primaria (colar com o botao do meio)")
+                    .on_toggle(Message::SettingsDictationWritePrimaryChanged)
+                    .text_size(13),
+                checkbox(state.settings_form.dictation_notifications)
+                    .label("Notificar no desktop ao terminar ou falhar (notify-send)")
+                    .on_toggle(Message::SettingsDictationNotificationsChanged)
+                    .text_size(13),
+                text_input(
+                    "Canal de entrada (vazio = media de todos os canais)",
+                    &state.settings_form.dictation_input_channel
+                )
+                .on_input(Message::SettingsDictationInputChannelChanged)
+                .padding([12, 14]),
+                row![
+                    text_input(
+                        "Taxa da captura em Hz (vazio = padrao do microfone)",
+                        &state.settings_form.dictation_capture_sample_rate
+                    )
+                    .on_input(Message::SettingsDictationCaptureSampleRateChanged)
+                    .padding([12, 14]),
+                    text_input(
+                        "Buffer da captura em frames (vazio = padrao)",
+                        &state.settings_form.dictation_capture_buffer_frames
+                    )
+                    .on_input(Message::SettingsDictationCaptureBufferFramesChanged)
+                    .padding([12, 14]),
+                ]
+                .spacing(12),
+                row![
+                    text_input(
+                        "Comando apos transcrever (texto no stdin e em $OPENVOICE_TEXT)",
+                        &state.settings_form.dictation_hook_command
+                    )
+                    .on_input(Message::SettingsDictationHookCommandChanged)
+                    .padding([12, 14]),
+                    text_input(
+                        "Tempo limite (s)",
+                        &state.settings_form.dictation_hook_timeout_secs
+                    )
+                    .on_input(Message::SettingsDictationHookTimeoutChanged)
+                    .padding([12, 14])
+                    .width(Length::Fixed(140.0)),
+                ]
+                .spacing(12)
+                .align_y(Alignment::Center),
+                row![
+                    checkbox(state.settings_form.dictation_webhook_enabled)
+                        .label("Webhook")
+                        .on_toggle(Message::SettingsDictationWebhookEnabledChanged)
+                        .text_size(13),
+                    text_input(
+                        "URL que recebe cada transcricao via POST (JSON)",
+                        &state.settings_form.dictation_webhook_url
+                    )
+                    .on_input(Message::SettingsDictationWebhookUrlChanged)
+                    .padding([12, 14]),
+                ]
+                .spacing(12)
+                .align_y(Alignment::Center),
+                text_input(
+                    "Pasta para salvar as gravacoes (vazio = nao salvar)",
+                    &state.settings_form.dictation_recordings_dir
+                )
+                .on_input(Message::SettingsDictationRecordingsDirChanged)
+                .padding([12, 14]),
+                state
+                    .last_recording_path
+                    .as_ref()
+                    .map(|path| {
+                        row![
+                            text(format!("Ultima gravacao: {}", path.display()))
+                                .size(12)
+                                .color(Color::from_rgba8(148, 163, 184, 0.88))
+                                .width(Length::Fill),
+                            action_button("Copiar caminho", Some(Message::CopyLastRecordingPath)),
+                        ]
+                        .spacing(12)
+                        .align_y(Alignment::Center)
+                        .into()
+                    })
+                    .unwrap_or_else(|| Element::from(Space::new().height(0))),
+                state
+                    .last_capture
+                    .as_ref()
+                    .map(|audio| {
+                        row![
+                            text(format!(
+                                "Ultima captura: {:.1}s de audio",
+                                audio.duration_seconds()
+                            ))
+                            .size(12)
+                            .color(Color::from_rgba8(148, 163, 184, 0.88))
+                            .width(Length::Fill),
+                            action_button(
+                                if state.is_playing_last_capture {
+                                    "Tocando..."
+                                } else {
+                                    "Ouvir"
+                                },
+                                (!state.is_playing_last_capture)
+                                    .then_some(Message::PlayLastRecording)
+                            ),
+                            action_button(
+                                "Copiar texto",
+                                state
+                                    .last_dictation
+                                    .is_some()
+                                    .then_some(Message::CopyLastDictation)
+                            ),
+                            action_button(
+                                "Retranscrever",
+                                (!state.is_dictation_recording() && !state.is_processing())
+                                    .then_some(Message::RetranscribeLast)
+                            ),
+                            action_button(
+                                "Desfazer saida",
+                                state
+                                    .last_output
+                                    .is_some()
+                                    .then_some(Message::UndoLastOutput)
+                            ),
+                        ]
+                        .spacing(12)
+                        .align_y(Alignment::Center)
+                        .into()
+                    })
+                    .unwrap_or_else(|| Element::from(Space::new().height(0))),
+                state
+                    .last_dictation
+                    .as_ref()
+                    .filter(|result| !result.segments.is_empty())
+                    .map(|result| {
+                        column![
+                            row![
+                                text(format!(
+                                    "Ultimo ditado: {} trecho(s)",
+                                    result.segments.len()
+                                ))
+                                .size(12)
+                                .color(Color::from_rgba8(148, 163, 184, 0.88))
+                                .width(Length::Fill),
+                                action_button(
+                                    "Exportar SRT",
+                                    Some(Message::ExportLastDictationSrt)
+                                ),
+                            ]
+                            .spacing(12)
+                            .align_y(Alignment::Center),
+                            column(result.segments.iter().take(LAST_DICTATION_SEGMENTS).map(
+                                |segment| {
+                                    text(segment.label())
+                                        .size(12)
+                                        .color(Color::from_rgba8(226, 232, 240, 0.9))
+                                        .into()
+                                }
+                            ))
+                            .spacing(4),
+                        ]
+                        .spacing(8)
+                        .into()
+                    })
+                    .unwrap_or_else(|| Element::from(Space::new().height(0))),
+                checkbox(state.settings_form.dictation_pre_roll_enabled)
+                    .label("Manter o microfone aberto para nao perder o inicio da fala")
+                    .on_toggle(Message::SettingsDictationPreRollEnabledChanged)
+                    .text_size(13),
+                checkbox(state.settings_form.dictation_show_window)
+                    .label("Mostrar o HUD ao iniciar o ditado")
+                    .on_toggle(Message::SettingsDictationShowWindowChanged)
+                    .text_size(13),
+                pick_list(
+                    SUPPORTED_HUD_CLICK_ACTIONS,
+                    Some(HudClickAction::from_code(
+                        &state.settings_form.hud_click_action
+                    )),
+                    |action| Message::SettingsHudClickActionChanged(action.code().to_owned())
+                )
+                .placeholder("Ao clicar no status do HUD"),
+                checkbox(state.settings_form.hud_keep_open)
+                    .label("Manter o HUD aberto apos o ditado ate ser fechado")
+                    .on_toggle(Message::SettingsHudKeepOpenChanged)
+                    .text_size(13),
+                text_input(
+                    "Esconder o HUD apos (ms)",
+                    &state.settings_form.hud_hide_delay_ms
+                )
+                .on_input_maybe(
+                    (!state.settings_form.hud_keep_open)
+                        .then_some(Message::SettingsHudHideDelayChanged)
+                )
+                .padding([12, 14]),
+                checkbox(state.settings_form.launch_at_login)
+                    .label("Abrir o OpenVoice ao entrar na sessao")
+                    .on_toggle(Message::SettingsLaunchAtLoginChanged)
+                    .text_size(13),
+                checkbox(state.settings_form.dictation_sound_feedback)
+                    .label("Tocar um bipe ao iniciar e parar o ditado")
+                    .on_toggle(Message::SettingsDictationSoundFeedbackChanged)
+                    .text_size(13),
+                checkbox(state.settings_form.dictation_normalize_audio)
+                    .label("Normalizar volume antes de enviar")
+                    .on_toggle(Message::SettingsDictationNormalizeAudioChanged)
+                    .text_size(13),
+            ]
+            .spacing(14),
+        )
+        .padding(18)
+        .style(|_| card_style()),
         container(
             column![
                 section_title("OpenAI Realtime"),
                 text(
-                    "OpenAI Realtime e usado na transcricao ao vivo do audio do sistema. Aqui entram a API key da Platform e o modelo de transcricao."
+                    "OpenAI Realtime e usado na transcricao ao vivo do audio do sistema. Com a OpenAI como provedor do ditado, a mesma API key e o mesmo modelo transcrevem a gravacao, enviada como multipart sem base64."
                 )
                 .size(12)
                 .color(Color::from_rgba8(148, 163, 184, 0.88)),
@@ -191,12 +859,116 @@ pub fn tab_content(state: &Overlay) -> Element<'_, Message> {
         )
         .padding(18)
         .style(|_| card_style()),
+        container(
+            column![
+                section_title("Transferir settings"),
+                text(format!(
+                    "Exporta todas as settings para um JSON, para levar a outra maquina. Sem caminho, usa ~/{DEFAULT_CONFIG_EXPORT_FILE}. Ao importar, API keys ausentes no arquivo sao mantidas."
+                ))
+                .size(12)
+                .color(Color::from_rgba8(148, 163, 184, 0.88)),
+                text_input("Arquivo das settings", &state.config_transfer_path)
+                    .on_input(Message::ConfigTransferPathChanged)
+                    .padding([12, 14]),
+                checkbox(state.config_export_secrets)
+                    .label("Incluir as API keys no arquivo exportado")
+                    .on_toggle(Message::ConfigExportSecretsChanged)
+                    .text_size(13),
+                row![
+                    action_button("Exportar", Some(Message::ExportConfig)),
+                    action_button("Importar", Some(Message::ImportConfig)),
+                ]
+                .spacing(12),
+                text("Restaurar os padroes, para sair de uma configuracao quebrada. API keys sao mantidas.")
+                    .size(12)
+                    .color(Color::from_rgba8(148, 163, 184, 0.88)),
+                row(ResetScope::ALL.map(|scope| {
+                    action_button(scope.label(), Some(Message::ResetConfig(scope)))
+                }))
+                .spacing(12),
+            ]
+            .spacing(14),
+        )
+        .padding(18)
+        .style(|_| card_style()),
+        container(
+            column![
+                section_title("Backup"),
+                text(format!(
+                    "Um arquivo com as settings, o historico e, se marcado, o audio guardado. Sem caminho, usa ~/{DEFAULT_BACKUP_FILE}. Restaurar substitui o historico atual."
+                ))
+                .size(12)
+                .color(Color::from_rgba8(148, 163, 184, 0.88)),
+                text_input("Arquivo do backup", &state.backup_path)
+                    .on_input(Message::BackupPathChanged)
+                    .padding([12, 14]),
+                checkbox(state.backup_include_secrets)
+                    .label("Incluir as API keys no backup")
+                    .on_toggle(Message::BackupIncludeSecretsChanged)
+                    .text_size(13),
+                checkbox(state.backup_include_audio)
+                    .label("Incluir o audio guardado no historico")
+                    .on_toggle(Message::BackupIncludeAudioChanged)
+                    .text_size(13),
+                row![
+                    action_button("Criar backup", Some(Message::CreateBackup)),
+                    action_button("Restaurar backup", Some(Message::RestoreBackup)),
+                ]
+                .spacing(12),
+            ]
+            .spacing(14),
+        )
+        .padding(18)
+        .style(|_| card_style()),
+        container(
+            column![
+                section_title("Diagnostico"),
+                text(format!(
+                    "Os logs ficam em {}. Carregue as ultimas linhas para anexar a um bug report.",
+                    log::log_path()
+                        .map(|path| path.display().to_string())
+                        .unwrap_or_else(|error| error)
+                ))
+                .size(12)
+                .color(Color::from_rgba8(148, 163, 184, 0.88)),
+                pick_list(
+                    LogLevel::ALL,
+                    Some(state.settings.log_level()),
+                    Message::SelectLogLevel
+                )
+                .placeholder("Nivel de log"),
+                row![
+                    action_button("Carregar logs", Some(Message::LoadRecentLogs)),
+                    action_button(
+                        "Copiar logs",
+                        (!state.recent_logs.is_empty()).then_some(Message::CopyRecentLogs)
+                    ),
+                ]
+                .spacing(12),
+                recent_logs(state),
+            ]
+            .spacing(14),
+        )
+        .padding(18)
+        .style(|_| card_style()),
         container(
             column![
                 section_title("Runtime"),
                 status_row("Clipboard", "transcript copiado apos processamento"),
                 status_row("Armazenamento", "settings em ~/.config/openvoice; captura do sistema disponivel para funcionalidades futuras"),
                 status_row("Audio", "ditado usa apenas o microfone no fluxo HUD atual"),
+                status_row(
+                    "Auto-stop do ditado",
+                    if state.settings.dictation_vad_enabled {
+                        format!(
+                            "apos {} ms de silencio",
+                            state.settings.dictation_silence_threshold_ms
+                        )
+                    } else {
+                        String::from("desligado")
+                    },
+                ),
+                status_row("Atalho global", shortcut_status(state)),
                 status_row(
                     "Auth realtime",
                     if state.settings.has_openai_realtime_api_key() {
@@ -232,6 +1004,7 @@ pub fn tab_content(state: &Overlay) -> Element<'_, Message> {
         )
         .padding(18)
         .style(|_| card_style()),
+        config_problems(state),
         // Save button at the bottom, outside all cards
         row![
             save_settings,
@@ -279,6 +1052,210 @@ pub fn tab_content(state: &Overlay) -> Element<'_, Message> {
     scrollable(content).height(Length::Fill).into()
 }
 
+fn shortcut_status(state: &Overlay) -> String {
+    if let Some(error) = &state.shortcut_error {
+        return format!("indisponivel: {error}");
+    }
+    if state.settings.shortcuts_paused {
+        return String::from("pausados");
+    }
+
+    match &state.shortcut_registration {
+        Some(registration) => registration
+            .binds
+            .iter()
+            .map(|(shortcut, action)| match action {
+                ShortcutAction::Dictation => format!(
+                    "{} ({})",
+                    shortcut.label(),
+                    registration.mode.label().to_lowercase()
+                ),
+                ShortcutAction::Template(index) => format!(
+                    "{} {}",
+                    state
+                        .settings
+                        .dictation_templates
+                        .get(*index)
+                        .map_or("template", |template| template.name.as_str()),
+                    shortcut.label()
+                ),
+                action => format!("{} {}", action.label(), shortcut.label()),
+            })
+            .collect::<Vec<_>>()
+            .join(", "),
+        None if state.settings.dictation_shortcut.is_empty() => String::from("desativado"),
+        None => String::from("somente via --trigger"),
+    }
+}
+
+/// Each model's transcript of the last take, then the word diff between
+/// the first two.
+fn model_comparison_view(comparison: &ModelComparison) -> Element<'_, Message> {
+    let mut lines: Vec<Element<'_, Message>> = comparison
+        .transcripts
+        .iter()
+        .map(|transcript| {
+            let body = match &transcript.result {
+                Ok(text) => text.clone(),
+                Err(error) => format!("erro: {error}"),
+            };
+
+            text(format!("{}: {body}", transcript.model))
+                .size(12)
+                .color(Color::from_rgba8(226, 232, 240, 0.9))
+                .into()
+        })
+        .collect();
+
+    if let Some(diff) = comparison.diff() {
+        lines.push(
+            text(format!("Diferencas: {diff}"))
+                .size(12)
+                .color(Color::from_rgba8(148, 163, 184, 0.88))
+                .into(),
+        );
+    }
+
+    column(lines).spacing(6).into()
+}
+
+/// Shortcut field with a button that records the next combination pressed
+/// in the window instead of typing it.
+fn shortcut_input<'a>(
+    state: &Overlay,
+    placeholder: &'a str,
+    value: &'a str,
+    on_input: fn(String) -> Message,
+    action: ShortcutAction,
+) -> Element<'a, Message> {
+    let capture_label = if state.shortcut_capture == Some(action) {
+        "Pressione a combinacao..."
+    } else {
+        "Gravar"
+    };
+
+    row![
+        text_input(placeholder, value)
+            .on_input(on_input)
+            .padding([12, 14]),
+        action_button(capture_label, Some(Message::CaptureShortcut(action))),
+    ]
+    .spacing(12)
+    .align_y(Alignment::Center)
+    .into()
+}
+
+/// One row per transcript template, plus a button to add another. Which
+/// one is active is picked on the home tab.
+fn templates_editor(state: &Overlay) -> Element<'_, Message> {
+    let rows = state
+        .settings_form
+        .dictation_templates
+        .iter()
+        .enumerate()
+        .map(|(index, template)| {
+            row![
+                text_input("Nome do template", &template.name)
+                    .on_input(move |value| Message::SettingsTemplateNameChanged(index, value))
+                    .padding([12, 14])
+                    .width(Length::Fixed(180.0)),
+                text_input("Texto com {text} (\\n quebra a linha)", &template.body)
+                    .on_input(move |value| Message::SettingsTemplateBodyChanged(index, value))
+                    .padding([12, 14]),
+                text_input("Atalho (opcional)", &template.shortcut)
+                    .on_input(move |value| Message::SettingsTemplateShortcutChanged(index, value))
+                    .padding([12, 14])
+                    .width(Length::Fixed(180.0)),
+                action_button("Remover", Some(Message::RemoveSettingsTemplate(index))),
+            ]
+            .spacing(12)
+            .align_y(Alignment::Center)
+            .into()
+        });
+
+    column(rows)
+        .push(action_button(
+            "Adicionar template",
+            Some(Message::AddSettingsTemplate),
+        ))
+        .spacing(8)
+        .into()
+}
+
+/// Problems the form has right now, listed above the save button.
+fn config_problems(state: &Overlay) -> Element<'_, Message> {
+    let problems = state.settings_form.validate(&state.input_devices);
+    if problems.is_empty() {
+        return Space::new().height(0).into();
+    }
+
+    container(
+        column(problems.into_iter().map(|problem| {
+            row![
+                text(problem.kind.label())
+                    .size(12)
+                    .color(Color::from_rgb8(251, 146, 60))
+                    .width(Length::Fixed(80.0)),
+                text(problem.message)
+                    .size(13)
+                    .color(Color::from_rgb8(255, 207, 164)),
+            ]
+            .spacing(12)
+            .into()
+        }))
+        .spacing(8),
+    )
+    .padding(18)
+    .style(|_| error_style())
+    .into()
+}
+
+/// One row per model with its own prompt, temperature and language.
+fn model_overrides_editor(state: &Overlay) -> Element<'_, Message> {
+    let rows = state
+        .settings_form
+        .dictation_model_overrides
+        .iter()
+        .enumerate()
+        .map(|(index, tuning)| {
+            row![
+                text_input("Modelo", &tuning.model)
+                    .on_input(move |value| Message::SettingsModelOverrideModelChanged(index, value))
+                    .padding([12, 14])
+                    .width(Length::Fixed(200.0)),
+                text_input("Instrucoes extras no prompt", &tuning.prompt)
+                    .on_input(move |value| Message::SettingsModelOverridePromptChanged(
+                        index, value
+                    ))
+                    .padding([12, 14]),
+                text_input("Temperatura", &tuning.temperature)
+                    .on_input(
+                        move |value| Message::SettingsModelOverrideTemperatureChanged(index, value)
+                    )
+                    .padding([12, 14])
+                    .width(Length::Fixed(110.0)),
+                text_input("Idioma", &tuning.language)
+                    .on_input(move |value| Message::SettingsModelOverrideLanguageChanged(
+                        index, value
+                    ))
+                    .padding([12, 14])
+                    .width(Length::Fixed(90.0)),
+                action_button("Remover", Some(Message::RemoveSettingsModelOverride(index))),
+            ]
+            .spacing(12)
+            .align_y(Alignment::Center)
+            .into()
+        });
+
+    column(rows)
+        .push(action_button(
+            "Adicionar ajuste por modelo",
+            Some(Message::AddSettingsModelOverride),
+        ))
+        .spacing(8)
+        .into()
+}
+
 fn section_title(label: &'static str) -> Element<'static, Message> {
     text(label)
         .size(13)
@@ -299,6 +1276,16 @@ fn status_row(label: &'static str, value: impl Into<String>) -> Element<'static,
     .into()
 }
 
+fn recent_logs(state: &Overlay) -> Element<'_, Message> {
+    if state.recent_logs.is_empty() {
+        return Space::new().height(0).into();
+    }
+
+    scrollable(text(state.recent_logs.join("\n")).size(11))
+        .height(Length::Fixed(220.0))
+        .into()
+}
+
 fn action_button<'a>(label: impl Into<String>, on_press: Option<Message>) -> Element<'a, Message> {
     button(text(label.into()).size(14))
         .padding([12, 16])
@@ -433,6 +1420,49 @@ const SUPPORTED_OPENAI_REALTIME_PROFILE_OPTIONS: [ProfileOption; 3] = [
     ProfileOption::new("Accuracy", "accuracy"),
 ];
 
+const SUPPORTED_SHORTCUT_MODES: [ShortcutMode; 3] = [
+    ShortcutMode::Toggle,
+    ShortcutMode::Hold,
+    ShortcutMode::TapOrHold,
+];
+
+const SUPPORTED_CAPTURE_MODES: [DictationCaptureMode; 3] = [
+    DictationCaptureMode::Microphone,
+    DictationCaptureMode::Mixed,
+    DictationCaptureMode::Split,
+];
+
+const SUPPORTED_OUTPUT_MODES: [DictationOutputMode; 4] = [
+    DictationOutputMode::Clipboard,
+    DictationOutputMode::Type,
+    DictationOutputMode::Uri,
+    DictationOutputMode::Submit,
+];
+
+const SUPPORTED_IDLE_ACTIONS: [IdleStopAction; 2] =
+    [IdleStopAction::Discard, IdleStopAction::Transcribe];
+
+const SUPPORTED_HUD_CLICK_ACTIONS: [HudClickAction; 3] = [
+    HudClickAction::OpenSettings,
+    HudClickAction::ToggleDictation,
+    HudClickAction::OpenHistory,
+];
+
+const SUPPORTED_RESAMPLE_QUALITIES: [ResampleQuality; 3] = [
+    ResampleQuality::Fast,
+    ResampleQuality::Balanced,
+    ResampleQuality::High,
+];
+
+const SUPPORTED_TARGET_SAMPLE_RATES: [TargetSampleRate; 4] = [
+    TargetSampleRate::Hz16000,
+    TargetSampleRate::Hz24000,
+    TargetSampleRate::Hz48000,
+    TargetSampleRate::Original,
+];
+
+const SUPPORTED_AUDIO_FORMATS: [AudioFormat; 2] = [AudioFormat::Wav, AudioFormat::Ogg];
+
 const SUPPORTED_COPILOT_MODE_OPTIONS: [CopilotModeOption; 3] = [
     CopilotModeOption::new("General", "general"),
     CopilotModeOption::new("Interview", "interview"),